@@ -1,4 +1,4 @@
-use starknet::ContractAddress;
+use starknet::{ClassHash, ContractAddress, SyscallResult};
 #[allow(unused_imports)]
 use core::array::ArrayTrait;
 #[allow(unused_imports)]
@@ -117,6 +117,80 @@ pub fn set_block_hash(block_number: u64, value: felt252) {
     cheatcode::<'set_block_hash'>([block_number.into(), value].span());
 }
 
+/// Take a snapshot of the whole Starknet state.
+///
+/// The snapshot includes the storage, the deployed contracts, the unpopped logs, the execution info
+/// and the mocks. Returns the id of the snapshot, to be used with `revert_to_snapshot`.
+pub fn snapshot_state() -> felt252 {
+    let mut snapshot_id = cheatcode::<'snapshot_state'>([].span());
+    *snapshot_id.pop_front().unwrap()
+}
+
+/// Revert the whole Starknet state to a snapshot taken by `snapshot_state`.
+///
+/// The snapshot remains valid after the call, so the state may be reverted to it multiple times.
+/// Panics if there is no snapshot with the given id.
+pub fn revert_to_snapshot(snapshot_id: felt252) {
+    let res = cheatcode::<'revert_to_snapshot'>([snapshot_id].span());
+    assert(res.is_empty(), 'Invalid snapshot id');
+}
+
+/// Mock the result of calls to an entry point of a contract.
+///
+/// After a call to `mock_call`, `starknet::syscalls::call_contract_syscall` to the entry point
+/// `selector` of the contract at `address` will return `result` without executing the contract,
+/// which does not need to be deployed.
+/// A mocked `Err` is treated as a panic of the entry point with the given revert reason, so
+/// `'ENTRYPOINT_FAILED'` is appended to it.
+pub fn mock_call(
+    address: ContractAddress, selector: felt252, result: SyscallResult<Span<felt252>>
+) {
+    let mut input = array![address.into(), selector];
+    result.serialize(ref input);
+    cheatcode::<'mock_call'>(input.span());
+}
+
+/// Stop mocking the result of calls to an entry point of a contract, mocked by `mock_call`.
+pub fn clear_mock_call(address: ContractAddress, selector: felt252) {
+    cheatcode::<'clear_mock_call'>([address.into(), selector].span());
+}
+
+/// Mock the result of library calls to an entry point of a class.
+///
+/// After a call to `mock_library_call`, `starknet::syscalls::library_call_syscall` to the entry
+/// point `selector` of the class `class_hash` will return `result` without executing the class,
+/// which does not need to be declared.
+/// A mocked `Err` is treated as a panic of the entry point with the given revert reason, so
+/// `'ENTRYPOINT_FAILED'` is appended to it.
+pub fn mock_library_call(
+    class_hash: ClassHash, selector: felt252, result: SyscallResult<Span<felt252>>
+) {
+    let mut input = array![class_hash.into(), selector];
+    result.serialize(ref input);
+    cheatcode::<'mock_library_call'>(input.span());
+}
+
+/// Stop mocking the result of library calls to an entry point of a class, mocked by
+/// `mock_library_call`.
+pub fn clear_mock_library_call(class_hash: ClassHash, selector: felt252) {
+    cheatcode::<'clear_mock_library_call'>([class_hash.into(), selector].span());
+}
+
+/// Force a syscall to fail.
+///
+/// After a call to `fail_syscall`, every call of the syscall with the selector `syscall` (for
+/// example `'StorageRead'` or `'Deploy'`) fails with `revert_reason`, without having any effect.
+pub fn fail_syscall(syscall: felt252, revert_reason: Span<felt252>) {
+    let mut input = array![syscall];
+    input.append_span(revert_reason);
+    cheatcode::<'fail_syscall'>(input.span());
+}
+
+/// Stop forcing a syscall to fail, forced by `fail_syscall`.
+pub fn clear_failing_syscall(syscall: felt252) {
+    cheatcode::<'clear_failing_syscall'>([syscall].span());
+}
+
 /// Pop the earliest unpopped logged event for the contract.
 ///
 /// The value is returned as a tuple of two spans, the first for the keys and the second for the
@@ -169,6 +243,76 @@ pub fn pop_log<T, +starknet::Event<T>>(address: ContractAddress) -> Option<T> {
     starknet::Event::deserialize(ref keys, ref data)
 }
 
+/// Get all the unpopped logged events for the contract, without popping them.
+///
+/// Each event is returned as a tuple of two spans, the first for the keys and the second for the
+/// data, in the order of emission.
+pub fn get_logs_raw(address: ContractAddress) -> Array<(Span<felt252>, Span<felt252>)> {
+    let mut logs = cheatcode::<'get_logs'>([address.into()].span());
+    Serde::deserialize(ref logs).unwrap()
+}
+
+/// Get all the unpopped logged events for the contract having `key` as one of their keys, without
+/// popping them.
+///
+/// Since the first key of an event is its selector, this may be used for filtering events by their
+/// selector, e.g. `get_logs_with_key_raw(address, selector!("Transfer"))`.
+pub fn get_logs_with_key_raw(
+    address: ContractAddress, key: felt252
+) -> Array<(Span<felt252>, Span<felt252>)> {
+    let mut logs = cheatcode::<'get_logs'>([address.into(), key].span());
+    Serde::deserialize(ref logs).unwrap()
+}
+
+/// Get all the unpopped logged events for the contract as the requested type, without popping
+/// them.
+///
+/// Panics if any of the events can't be deserialized as the requested type.
+pub fn get_logs<T, +starknet::Event<T>, +Drop<T>>(address: ContractAddress) -> Array<T> {
+    deserialize_logs(get_logs_raw(address))
+}
+
+/// Get all the unpopped logged events for the contract having `key` as one of their keys as the
+/// requested type, without popping them.
+///
+/// Panics if any of the events can't be deserialized as the requested type.
+pub fn get_logs_with_key<T, +starknet::Event<T>, +Drop<T>>(
+    address: ContractAddress, key: felt252
+) -> Array<T> {
+    deserialize_logs(get_logs_with_key_raw(address, key))
+}
+
+/// Deserializes raw events as the requested type.
+fn deserialize_logs<T, +starknet::Event<T>, +Drop<T>>(
+    raw_logs: Array<(Span<felt252>, Span<felt252>)>
+) -> Array<T> {
+    let mut logs = array![];
+    for (mut keys, mut data) in raw_logs {
+        logs.append(starknet::Event::deserialize(ref keys, ref data).expect('Invalid event'));
+    };
+    logs
+}
+
+/// Get the number of unpopped logged events for the contract.
+pub fn count_logs(address: ContractAddress) -> usize {
+    get_logs_raw(address).len()
+}
+
+/// Get all the unpopped logged events of all the contracts, without popping them.
+///
+/// Each event is returned as a tuple of the address of the emitting contract, and two spans, the
+/// first for the keys and the second for the data, in the order of emission.
+/// Useful for asserting the ordering of events across contracts.
+pub fn get_all_logs_raw() -> Array<(ContractAddress, Span<felt252>, Span<felt252>)> {
+    let mut logs = cheatcode::<'get_all_logs'>([].span());
+    Serde::deserialize(ref logs).unwrap()
+}
+
+/// Pop all the unpopped logged events for the contract.
+pub fn clear_logs(address: ContractAddress) {
+    cheatcode::<'clear_logs'>([address.into()].span());
+}
+
 // TODO(Ilya): Decide if we limit the type of `to_address`.
 /// Pop the earliest unpopped l2 to l1 message for the contract.
 ///
@@ -182,3 +326,17 @@ pub fn pop_l2_to_l1_message(address: ContractAddress) -> Option<(felt252, Span<f
         (Serde::deserialize(ref l2_to_l1_message)?, Serde::deserialize(ref l2_to_l1_message)?,)
     )
 }
+
+/// Get all the unpopped l2 to l1 messages for the contract, without popping them.
+///
+/// Each message is returned as a tuple of the l1 address the message was sent to as a felt252, and
+/// the message data as a span, in the order of sending.
+pub fn get_l2_to_l1_messages(address: ContractAddress) -> Array<(felt252, Span<felt252>)> {
+    let mut messages = cheatcode::<'get_l2_to_l1_messages'>([address.into()].span());
+    Serde::deserialize(ref messages).unwrap()
+}
+
+/// Pop all the unpopped l2 to l1 messages for the contract.
+pub fn clear_l2_to_l1_messages(address: ContractAddress) {
+    cheatcode::<'clear_l2_to_l1_messages'>([address.into()].span());
+}
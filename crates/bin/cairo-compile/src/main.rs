@@ -4,6 +4,12 @@ use std::path::PathBuf;
 use anyhow::Context;
 use cairo_lang_compiler::project::check_compiler_path;
 use cairo_lang_compiler::{CompilerConfig, compile_cairo_project_at_path};
+use cairo_lang_sierra::program::Program;
+use cairo_lang_sierra_to_casm::compiler::{SierraToCasmConfig, compile};
+use cairo_lang_sierra_to_casm::cost_report::CostReport;
+use cairo_lang_sierra_to_casm::metadata::{
+    MetadataComputationConfig, calc_metadata, calc_metadata_ap_change_only,
+};
 use cairo_lang_utils::logging::init_logging;
 use clap::Parser;
 
@@ -44,6 +50,10 @@ struct Args {
     /// Overrides inlining behavior.
     #[arg(short, long, default_value = "default")]
     inlining_strategy: InliningStrategy,
+    /// A file to write a JSON report of the static costs of the functions of the program to.
+    /// Function names are readable only with `--replace-ids`.
+    #[arg(long)]
+    cost_report: Option<PathBuf>,
 }
 
 fn main() -> anyhow::Result<()> {
@@ -61,6 +71,11 @@ fn main() -> anyhow::Result<()> {
         ..CompilerConfig::default()
     })?;
 
+    if let Some(path) = args.cost_report {
+        let report = serde_json::to_string_pretty(&cost_report(&sierra_program)?)?;
+        fs::write(path, report + "\n").context("Failed to write cost report.")?;
+    }
+
     match args.output {
         Some(path) => {
             fs::write(path, format!("{sierra_program}")).context("Failed to write output.")?
@@ -70,3 +85,24 @@ fn main() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+/// Computes the static costs of the functions of the program.
+fn cost_report(program: &Program) -> anyhow::Result<CostReport> {
+    let gas_usage_check = program.requires_gas_counter();
+    let metadata = if gas_usage_check {
+        calc_metadata(program, MetadataComputationConfig {
+            compute_runtime_costs: true,
+            ..Default::default()
+        })
+    } else {
+        calc_metadata_ap_change_only(program)
+    }
+    .context("Failed calculating Sierra variables.")?;
+    let cairo_program = compile(program, &metadata, SierraToCasmConfig {
+        gas_usage_check,
+        max_bytecode_size: usize::MAX,
+        peephole_optimizations: false,
+    })
+    .context("Failed compiling to CASM.")?;
+    Ok(CostReport::new(program, &metadata, &cairo_program))
+}
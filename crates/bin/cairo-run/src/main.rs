@@ -1,5 +1,6 @@
 //! Compiles and runs a Cairo program.
 
+use std::fs;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
@@ -8,15 +9,23 @@ use cairo_lang_compiler::db::RootDatabase;
 use cairo_lang_compiler::diagnostics::DiagnosticsReporter;
 use cairo_lang_compiler::project::{check_compiler_path, setup_project};
 use cairo_lang_diagnostics::ToOption;
+use cairo_lang_runner::args::UserArgs;
 use cairo_lang_runner::casm_run::format_next_item;
-use cairo_lang_runner::profiling::ProfilingInfoProcessor;
-use cairo_lang_runner::{ProfilingInfoCollectionConfig, SierraCasmRunner, StarknetState};
+use cairo_lang_runner::json_output::{panic_data_to_json, resources_to_json};
+use cairo_lang_runner::profiling::{ProfilerOutputFormat, ProfilingInfoProcessor};
+use cairo_lang_runner::{
+    ProfilingInfoCollectionConfig, RunResultStarknet, RunResultValue, SierraCasmRunner,
+    StarknetState,
+};
+use cairo_lang_sierra::program::Function;
 use cairo_lang_sierra_generator::db::SierraGenGroup;
 use cairo_lang_sierra_generator::program_generator::SierraProgramWithDebug;
 use cairo_lang_sierra_generator::replace_ids::{DebugReplacer, SierraIdReplacer};
 use cairo_lang_starknet::contract::{find_contracts, get_contracts_info};
 use cairo_lang_utils::Upcast;
 use clap::Parser;
+use num_traits::ToPrimitive;
+use serde_json::Value;
 
 /// Compiles a Cairo project and runs the function `main`.
 /// Exits with 1 if the compilation or run fails, otherwise 0.
@@ -40,6 +49,59 @@ struct Args {
     /// Whether to run the profiler.
     #[arg(long, default_value_t = false)]
     run_profiler: bool,
+    /// The format of the profiler results. Formats other than text are written to
+    /// `--profiler-output`.
+    #[arg(long, default_value_t, value_enum, requires = "run_profiler")]
+    profiler_format: ProfilerFormat,
+    /// The file the profiler results are written to, for formats other than text. Defaults to
+    /// `profile.<extension of the format>`.
+    #[arg(long, requires = "run_profiler")]
+    profiler_output: Option<PathBuf>,
+    /// The arguments to pass to `main`, either as a JSON array with a value per parameter, or as
+    /// a list of felts holding the serialization of all the parameters.
+    #[arg(long, conflicts_with = "args_file")]
+    args: Option<String>,
+    /// A file containing the arguments to pass to `main`, in the same format as `--args`.
+    #[arg(long)]
+    args_file: Option<PathBuf>,
+    /// The format in which the result of the run is printed.
+    #[arg(long, default_value_t, value_enum)]
+    output_format: OutputFormat,
+}
+
+/// Options for the `output-format` argument.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, clap::ValueEnum)]
+enum OutputFormat {
+    /// Human readable text.
+    #[default]
+    Text,
+    /// A JSON document holding the decoded return value or panic data, the remaining gas and the
+    /// used resources.
+    Json,
+}
+
+/// Options for the `profiler-format` argument.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, clap::ValueEnum)]
+enum ProfilerFormat {
+    /// Human readable text tables.
+    #[default]
+    Text,
+    /// Folded stacks, for flamegraph tools such as `flamegraph.pl` and `inferno`.
+    Folded,
+    /// Chrome trace-event JSON, for `chrome://tracing` and Perfetto.
+    ChromeTrace,
+    /// pprof protobuf, for `go tool pprof`.
+    Pprof,
+}
+impl From<ProfilerFormat> for ProfilerOutputFormat {
+    fn from(val: ProfilerFormat) -> Self {
+        match val {
+            ProfilerFormat::Text => ProfilerOutputFormat::Text,
+            ProfilerFormat::Folded => ProfilerOutputFormat::Folded,
+            ProfilerFormat::ChromeTrace => ProfilerOutputFormat::ChromeTrace,
+            ProfilerFormat::Pprof => ProfilerOutputFormat::Pprof,
+        }
+    }
 }
 
 fn main() -> anyhow::Result<()> {
@@ -87,15 +149,26 @@ fn main() -> anyhow::Result<()> {
         if args.run_profiler { Some(ProfilingInfoCollectionConfig::default()) } else { None },
     )
     .with_context(|| "Failed setting up runner.")?;
+    let func = runner.find_function("::main")?;
+    let user_args: UserArgs = match (&args.args, &args.args_file) {
+        (Some(user_args), _) => user_args.parse()?,
+        (None, Some(path)) => fs::read_to_string(path)
+            .with_context(|| format!("Failed reading arguments file: {}", path.display()))?
+            .parse()?,
+        (None, None) => UserArgs::Felts(vec![]),
+    };
+    let func_args =
+        runner.decode_args(func, &user_args).with_context(|| "Failed decoding the arguments.")?;
     let result = runner
         .run_function_with_starknet_context(
-            runner.find_function("::main")?,
-            &[],
+            func,
+            &func_args,
             args.available_gas,
             StarknetState::default(),
         )
         .with_context(|| "Failed to run the function.")?;
 
+    let is_json_output = args.output_format == OutputFormat::Json;
     if args.run_profiler {
         let profiling_info_processor = ProfilingInfoProcessor::new(
             Some(db),
@@ -103,20 +176,47 @@ fn main() -> anyhow::Result<()> {
             debug_info.statements_locations.get_statements_functions_map_for_tests(db),
             Default::default(),
         );
-        match result.profiling_info {
+        let profiler_format = ProfilerOutputFormat::from(args.profiler_format);
+        // In JSON mode, stdout is reserved for the JSON document.
+        let profiling_output = match &result.profiling_info {
             Some(raw_profiling_info) => {
-                let profiling_info = profiling_info_processor.process(&raw_profiling_info);
-                println!("Profiling info:\n{}", profiling_info);
+                let profiling_info = profiling_info_processor.process(raw_profiling_info);
+                if profiler_format == ProfilerOutputFormat::Text {
+                    format!("Profiling info:\n{}", profiling_info)
+                } else {
+                    let path = args.profiler_output.clone().unwrap_or_else(|| {
+                        PathBuf::from(format!("profile.{}", profiler_format.extension()))
+                    });
+                    fs::write(&path, profiling_info.export(profiler_format)).with_context(
+                        || format!("Failed writing the profile to `{}`.", path.display()),
+                    )?;
+                    format!("Profiling info written to `{}`.", path.display())
+                }
             }
-            None => println!("Warning: Profiling info not found."),
+            None => "Warning: Profiling info not found.".into(),
+        };
+        if is_json_output {
+            eprintln!("{profiling_output}");
+        } else {
+            println!("{profiling_output}");
         }
     }
 
+    if is_json_output {
+        print_json_output(&runner, func, &result, args.print_full_memory)?;
+    } else {
+        print_text_output(result, args.print_full_memory);
+    }
+    Ok(())
+}
+
+/// Prints the result of the run in a human readable format.
+fn print_text_output(result: RunResultStarknet, print_full_memory: bool) {
     match result.value {
-        cairo_lang_runner::RunResultValue::Success(values) => {
+        RunResultValue::Success(values) => {
             println!("Run completed successfully, returning {values:?}")
         }
-        cairo_lang_runner::RunResultValue::Panic(values) => {
+        RunResultValue::Panic(values) => {
             print!("Run panicked with [");
             let mut felts = values.into_iter();
             let mut first = true;
@@ -133,7 +233,7 @@ fn main() -> anyhow::Result<()> {
     if let Some(gas) = result.gas_counter {
         println!("Remaining gas: {gas}");
     }
-    if args.print_full_memory {
+    if print_full_memory {
         print!("Full memory: [");
         for cell in &result.memory {
             match cell {
@@ -143,5 +243,44 @@ fn main() -> anyhow::Result<()> {
         }
         println!("]");
     }
+}
+
+/// Prints the result of the run as a JSON document, see [cairo_lang_runner::json_output].
+fn print_json_output(
+    runner: &SierraCasmRunner,
+    func: &Function,
+    result: &RunResultStarknet,
+    print_full_memory: bool,
+) -> anyhow::Result<()> {
+    let mut output = serde_json::Map::new();
+    match &result.value {
+        RunResultValue::Success(values) => {
+            output.insert("status".into(), "success".into());
+            output.insert(
+                "return_value".into(),
+                runner.return_value_to_json(func, values, &result.memory)?,
+            );
+        }
+        RunResultValue::Panic(values) => {
+            output.insert("status".into(), "panic".into());
+            output.insert("panic_data".into(), panic_data_to_json(values));
+        }
+    }
+    output.insert(
+        "gas_counter".into(),
+        result.gas_counter.and_then(|gas| gas.to_u64()).map_or(Value::Null, Value::from),
+    );
+    output.insert("resources".into(), resources_to_json(&result.used_resources));
+    if print_full_memory {
+        output.insert(
+            "memory".into(),
+            result
+                .memory
+                .iter()
+                .map(|cell| cell.map_or(Value::Null, |value| value.to_string().into()))
+                .collect(),
+        );
+    }
+    println!("{}", serde_json::to_string_pretty(&output)?);
     Ok(())
 }
@@ -4,6 +4,8 @@ use std::path::PathBuf;
 
 use anyhow::Ok;
 use cairo_lang_compiler::project::check_compiler_path;
+use cairo_lang_runner::profiling::ProfilerOutputFormat;
+use cairo_lang_test_runner::reporter::TestOutputFormat;
 use cairo_lang_test_runner::{RunProfilerConfig, TestRunConfig, TestRunner};
 use clap::{Parser, ValueEnum};
 use serde::Serialize;
@@ -27,6 +29,46 @@ impl From<RunProfilerConfigArg> for RunProfilerConfig {
     }
 }
 
+/// The clap-arg equivalent of [ProfilerOutputFormat].
+#[derive(ValueEnum, Clone, Default, Debug, Serialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "kebab-case")]
+enum ProfilerOutputFormatArg {
+    #[default]
+    Text,
+    Folded,
+    ChromeTrace,
+    Pprof,
+}
+impl From<ProfilerOutputFormatArg> for ProfilerOutputFormat {
+    fn from(val: ProfilerOutputFormatArg) -> Self {
+        match val {
+            ProfilerOutputFormatArg::Text => ProfilerOutputFormat::Text,
+            ProfilerOutputFormatArg::Folded => ProfilerOutputFormat::Folded,
+            ProfilerOutputFormatArg::ChromeTrace => ProfilerOutputFormat::ChromeTrace,
+            ProfilerOutputFormatArg::Pprof => ProfilerOutputFormat::Pprof,
+        }
+    }
+}
+
+/// The clap-arg equivalent of [TestOutputFormat].
+#[derive(ValueEnum, Clone, Default, Debug, Serialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "kebab-case")]
+enum TestOutputFormatArg {
+    #[default]
+    Pretty,
+    Json,
+    Junit,
+}
+impl From<TestOutputFormatArg> for TestOutputFormat {
+    fn from(val: TestOutputFormatArg) -> Self {
+        match val {
+            TestOutputFormatArg::Pretty => TestOutputFormat::Pretty,
+            TestOutputFormatArg::Json => TestOutputFormat::Json,
+            TestOutputFormatArg::Junit => TestOutputFormat::Junit,
+        }
+    }
+}
+
 /// Compiles a Cairo project and runs all the functions marked as `#[test]`.
 /// Exits with 1 if the compilation or run fails, otherwise 0.
 #[derive(Parser, Debug)]
@@ -56,12 +98,40 @@ struct Args {
     /// [cairo_lang_test_runner::RunProfilerConfig]
     #[clap(short, long, default_value_t, value_enum)]
     run_profiler: RunProfilerConfigArg,
+    /// The format of the profiling info of each test. See
+    /// [cairo_lang_runner::profiling::ProfilerOutputFormat]
+    #[arg(long, default_value_t, value_enum)]
+    profiler_format: ProfilerOutputFormatArg,
+    /// The directory into which the profiling info of each test is written, for formats other
+    /// than text.
+    #[arg(long, default_value = "profiles")]
+    profiler_output: PathBuf,
     /// Should disable gas calculation.
     #[arg(long)]
     gas_disabled: bool,
     /// Whether to print resource usage after each test.
     #[arg(long, default_value_t = false)]
     print_resource_usage: bool,
+    /// The number of tests to run in parallel. Defaults to the number of available CPUs.
+    #[arg(short, long)]
+    jobs: Option<usize>,
+    /// The format in which the test results are printed. See
+    /// [cairo_lang_test_runner::reporter::TestOutputFormat]
+    #[arg(long, default_value_t, value_enum)]
+    format: TestOutputFormatArg,
+    /// The number of runs of fuzz tests that don't set it.
+    #[arg(long, default_value_t = 256, value_parser = clap::value_parser!(u32).range(1..))]
+    fuzzer_runs: u32,
+    /// The seed for generating the arguments of fuzz tests that don't set it. Random by default.
+    #[arg(long)]
+    fuzzer_seed: Option<u64>,
+    /// A JSON fixture file of the Starknet state every test starts from.
+    #[arg(long)]
+    starknet_state: Option<PathBuf>,
+    /// Collects line coverage and writes it into the given directory, as an LCOV report
+    /// (`lcov.info`) and a JSON summary (`coverage.json`).
+    #[arg(long, value_name = "DIR", num_args = 0..=1, default_missing_value = "coverage")]
+    coverage: Option<PathBuf>,
 }
 
 fn main() -> anyhow::Result<()> {
@@ -70,13 +140,24 @@ fn main() -> anyhow::Result<()> {
     // Check if args.path is a file or a directory.
     check_compiler_path(args.single_file, &args.path)?;
 
+    let format: TestOutputFormat = args.format.into();
     let config = TestRunConfig {
         filter: args.filter,
         ignored: args.ignored,
         include_ignored: args.include_ignored,
         run_profiler: args.run_profiler.into(),
+        profiler_format: args.profiler_format.into(),
+        profiler_output_dir: args.profiler_output,
         gas_enabled: !args.gas_disabled,
         print_resource_usage: args.print_resource_usage,
+        // Debug prints would corrupt the machine readable formats, so they are reported instead.
+        capture_output: format != TestOutputFormat::Pretty,
+        jobs: args.jobs,
+        format,
+        fuzzer_runs: args.fuzzer_runs,
+        fuzzer_seed: args.fuzzer_seed,
+        starknet_state_fixture: args.starknet_state,
+        coverage: args.coverage,
     };
 
     let runner = TestRunner::new(&args.path, args.starknet, args.allow_warnings, config)?;
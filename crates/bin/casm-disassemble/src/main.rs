@@ -0,0 +1,133 @@
+use std::fmt::Write;
+use std::fs;
+
+use anyhow::Context;
+use cairo_lang_casm::decoder::decode_bytecode;
+use cairo_lang_starknet_classes::NestedIntList;
+use cairo_lang_starknet_classes::casm_contract_class::CasmContractClass;
+use clap::Parser;
+use num_bigint::BigInt;
+
+/// Prints the annotated assembly of a compiled contract class, with its entry points, hints and
+/// bytecode segmentation.
+/// Exits with 0/1 if the disassembly succeeds/fails.
+#[derive(Parser, Debug)]
+#[clap(version, verbatim_doc_comment)]
+struct Args {
+    /// The path of the file with the compiled contract class.
+    file: String,
+    /// The output file name (default: stdout).
+    output: Option<String>,
+}
+
+fn main() -> anyhow::Result<()> {
+    let args = Args::parse();
+    let casm_contract_class: CasmContractClass = serde_json::from_str(
+        &fs::read_to_string(&args.file)
+            .with_context(|| format!("Failed to read {}.", &args.file))?,
+    )
+    .with_context(|| "deserialization Failed.")?;
+    let res = disassemble(&casm_contract_class)?;
+    match args.output {
+        Some(path) => fs::write(path, res).with_context(|| "Failed to write disassembly.")?,
+        None => print!("{res}"),
+    }
+    Ok(())
+}
+
+/// Returns the annotated assembly of the given compiled contract class.
+fn disassemble(casm_contract_class: &CasmContractClass) -> anyhow::Result<String> {
+    let prime = BigInt::from(casm_contract_class.prime.clone());
+    let half_prime = &prime >> 1;
+    // Immediates are encoded modulo the prime, so large values are displayed as negative.
+    let bytecode: Vec<BigInt> = casm_contract_class
+        .bytecode
+        .iter()
+        .map(|value| {
+            let value = BigInt::from(value.value.clone());
+            if value > half_prime { value - &prime } else { value }
+        })
+        .collect();
+
+    let entry_points_by_type = &casm_contract_class.entry_points_by_type;
+    let mut entry_points: Vec<_> = [
+        ("EXTERNAL", &entry_points_by_type.external),
+        ("L1_HANDLER", &entry_points_by_type.l1_handler),
+        ("CONSTRUCTOR", &entry_points_by_type.constructor),
+    ]
+    .into_iter()
+    .flat_map(|(kind, entry_points)| {
+        entry_points.iter().map(move |entry_point| (entry_point.offset, kind, entry_point))
+    })
+    .collect();
+    entry_points.sort_by_key(|(offset, ..)| *offset);
+    let mut entry_points = entry_points.into_iter().peekable();
+
+    let mut segments = vec![];
+    collect_segments(
+        &casm_contract_class.get_bytecode_segment_lengths(),
+        &mut vec![],
+        &mut 0,
+        &mut segments,
+    );
+    let mut segments = segments.into_iter().peekable();
+
+    let mut res = String::new();
+    writeln!(res, "// Compiler version: {}", casm_contract_class.compiler_version)?;
+    for (pc, instruction) in decode_bytecode(&bytecode, &casm_contract_class.hints) {
+        while let Some((path, start, length)) = segments.next_if(|(_, start, _)| *start <= pc) {
+            writeln!(res, "\n// Segment {path}: offset {start}, length {length}.")?;
+        }
+        while let Some((offset, kind, entry_point)) =
+            entry_points.next_if(|(offset, ..)| *offset <= pc)
+        {
+            writeln!(
+                res,
+                "// {kind} entry point {:#x} at offset {offset}, builtins: [{}].",
+                entry_point.selector,
+                entry_point.builtins.join(", ")
+            )?;
+        }
+        match instruction {
+            Ok(instruction) => {
+                let text = instruction.to_string();
+                let mut lines: Vec<&str> = text.lines().collect();
+                let body = lines.pop().unwrap_or_default();
+                for line in lines {
+                    writeln!(res, "        {line}")?;
+                }
+                writeln!(res, "{pc:>6}: {body};")?;
+            }
+            Err(err) => writeln!(res, "{pc:>6}: dw {}; // {err}.", bytecode[pc])?,
+        }
+    }
+    Ok(res)
+}
+
+/// Collects the leaf segments of the bytecode segmentation as `(path, start, length)`, where the
+/// path is the dot separated index of the leaf in the segmentation tree.
+fn collect_segments(
+    segment: &NestedIntList,
+    path: &mut Vec<usize>,
+    offset: &mut usize,
+    segments: &mut Vec<(String, usize, usize)>,
+) {
+    match segment {
+        NestedIntList::Leaf(length) => {
+            let path = if path.is_empty() {
+                "0".to_string()
+            } else {
+                path.iter().map(|index| index.to_string()).collect::<Vec<_>>().join(".")
+            };
+            segments.push((path, *offset, *length));
+            *offset += length;
+        }
+        NestedIntList::Node(children) => {
+            for (index, child) in children.iter().enumerate() {
+                path.push(index);
+                collect_segments(child, path, offset, segments);
+                path.pop();
+            }
+        }
+    }
+}
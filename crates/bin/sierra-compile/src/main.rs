@@ -16,6 +16,9 @@ struct Args {
     /// The path of the file to compile.
     file: String,
     output: String,
+    /// Whether to run peephole optimizations on the generated CASM.
+    #[arg(long, default_value_t = false)]
+    optimize_casm: bool,
 }
 
 fn main() -> anyhow::Result<()> {
@@ -24,7 +27,7 @@ fn main() -> anyhow::Result<()> {
 
     let args = Args::parse();
 
-    let sierra_code = fs::read_to_string(args.file).with_context(|| "Could not read file!")?;
+    let sierra_code = fs::read_to_string(&args.file).with_context(|| "Could not read file!")?;
     let Ok(program) = ProgramParser::new().parse(&sierra_code) else {
         anyhow::bail!(indoc! {"
             Failed to parse sierra program.
@@ -36,7 +39,7 @@ fn main() -> anyhow::Result<()> {
         &program,
         &calc_metadata(&program, Default::default())
             .with_context(|| "Failed calculating Sierra variables.")?,
-        SierraToCasmConfig { gas_usage_check: true, max_bytecode_size: usize::MAX },
+        SierraToCasmConfig { peephole_optimizations: args.optimize_casm, ..Default::default() },
     )
     .with_context(|| "Compilation failed.")?;
 
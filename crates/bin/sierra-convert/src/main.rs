@@ -0,0 +1,62 @@
+use std::fs;
+
+use anyhow::Context;
+use cairo_lang_starknet_classes::sierra_convert::{SierraFormat, format_sierra, parse_sierra};
+use clap::{Parser, ValueEnum};
+
+/// The forms of a Sierra program.
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum Format {
+    /// The Sierra text format, with the debug info and numeric ids in a trailing comment block.
+    Text,
+    /// A versioned program JSON, as produced by `sierra-compile` and `cairo-compile`.
+    Json,
+    /// The compressed felt252 form of contract classes. Any contract class file may be used as
+    /// input.
+    Felts,
+}
+impl From<Format> for SierraFormat {
+    fn from(format: Format) -> Self {
+        match format {
+            Format::Text => SierraFormat::Text,
+            Format::Json => SierraFormat::Json,
+            Format::Felts => SierraFormat::Felts,
+        }
+    }
+}
+
+/// Converts a Sierra program between its text, JSON and felt252 forms, keeping its debug info.
+/// Exits with 0/1 if the conversion succeeds/fails.
+#[derive(Parser, Debug)]
+#[clap(version, verbatim_doc_comment)]
+struct Args {
+    /// The path of the file with the Sierra program.
+    file: String,
+    /// The form of the input (default: detected from the content).
+    #[arg(long, value_enum)]
+    from: Option<Format>,
+    /// The form of the output.
+    #[arg(long, value_enum)]
+    to: Format,
+    /// The output file name (default: stdout).
+    output: Option<String>,
+}
+
+fn main() -> anyhow::Result<()> {
+    let args = Args::parse();
+    let content = fs::read_to_string(&args.file)
+        .with_context(|| format!("Failed to read {}.", &args.file))?;
+    let from = args.from.map_or_else(|| SierraFormat::detect(&content), SierraFormat::from);
+    let artifact = parse_sierra(&content, from)
+        .with_context(|| format!("Failed to parse {} as {from:?} Sierra.", &args.file))?;
+    let mut converted = format_sierra(&artifact, args.to.into())
+        .with_context(|| format!("Failed to convert Sierra to {:?}.", args.to))?;
+    if !converted.ends_with('\n') {
+        converted.push('\n');
+    }
+    match args.output {
+        Some(path) => fs::write(path, converted).with_context(|| "Failed to write output.")?,
+        None => print!("{converted}"),
+    }
+    Ok(())
+}
@@ -5,8 +5,9 @@ use anyhow::Context;
 use cairo_lang_compiler::CompilerConfig;
 use cairo_lang_compiler::diagnostics::DiagnosticsReporter;
 use cairo_lang_compiler::project::check_compiler_path;
-use cairo_lang_starknet::compile::starknet_compile;
+use cairo_lang_starknet::compile::compile_path;
 use cairo_lang_starknet_classes::allowed_libfuncs::ListSelector;
+use cairo_lang_starknet_classes::contract_cost_report::ContractCostReport;
 use clap::Parser;
 
 /// Compiles the specified contract from a Cairo project, into a contract class file.
@@ -36,6 +37,10 @@ struct Args {
     /// A file of the allowed libfuncs list to use.
     #[arg(long)]
     allowed_libfuncs_list_file: Option<String>,
+    /// A file to write a JSON report of the static costs of the functions and entry points of the
+    /// contract to. Function names are readable only with `--replace-ids`.
+    #[arg(long)]
+    cost_report: Option<PathBuf>,
 }
 
 fn main() -> anyhow::Result<()> {
@@ -51,16 +56,17 @@ fn main() -> anyhow::Result<()> {
     if args.allow_warnings {
         diagnostics_reporter = diagnostics_reporter.allow_warnings();
     }
-    let res = starknet_compile(
-        args.path,
-        args.contract_path,
-        Some(CompilerConfig {
-            replace_ids: args.replace_ids,
-            diagnostics_reporter,
-            ..CompilerConfig::default()
-        }),
-        Some(list_selector),
-    )?;
+    let contract = compile_path(&args.path, args.contract_path.as_deref(), CompilerConfig {
+        replace_ids: args.replace_ids,
+        diagnostics_reporter,
+        ..CompilerConfig::default()
+    })?;
+    contract.validate_version_compatible(list_selector)?;
+    if let Some(path) = args.cost_report {
+        let report = serde_json::to_string_pretty(&ContractCostReport::new(&contract)?)?;
+        fs::write(path, report + "\n").with_context(|| "Failed to write cost report.")?;
+    }
+    let res = serde_json::to_string_pretty(&contract).with_context(|| "Serialization failed.")?;
     match args.output {
         Some(path) => fs::write(path, res).with_context(|| "Failed to write output.")?,
         None => println!("{res}"),
@@ -29,6 +29,9 @@ struct Args {
     /// The max bytecode size.
     #[arg(long, default_value_t = 180000)]
     max_bytecode_size: usize,
+    /// Whether to run peephole optimizations on the generated CASM.
+    #[arg(long, default_value_t = false)]
+    optimize_casm: bool,
 }
 
 /// Same as `ContractClass` - but ignores `abi` in deserialization.
@@ -70,6 +73,7 @@ fn main() -> anyhow::Result<()> {
         contract_class,
         args.add_pythonic_hints,
         args.max_bytecode_size,
+        args.optimize_casm,
     )
     .with_context(|| "Compilation failed.")?;
 
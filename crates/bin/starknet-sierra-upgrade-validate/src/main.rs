@@ -358,6 +358,7 @@ fn run_single(mut sierra_class: ContractClassInfo, config: &RunConfig) -> RunRes
         contract_class,
         false,
         config.max_bytecode_size,
+        false,
     ) {
         Ok(compiled_contract_class) => compiled_contract_class,
         Err(err) => {
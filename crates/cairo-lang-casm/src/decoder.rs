@@ -0,0 +1,256 @@
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+use core::fmt::Display;
+
+use num_bigint::BigInt;
+use num_traits::ToPrimitive;
+
+use crate::assembler::{ApUpdate, FpUpdate, InstructionRepr, Op1Addr, Opcode, PcUpdate, Res};
+use crate::encoder::{
+    AP_ADD_BIT, AP_ADD1_BIT, DST_REG_BIT, OFFSET_BITS, OP0_REG_BIT, OP1_AP_BIT, OP1_FP_BIT,
+    OP1_IMM_BIT, OPCODE_ASSERT_EQ_BIT, OPCODE_CALL_BIT, OPCODE_RET_BIT, PC_JNZ_BIT,
+    PC_JUMP_ABS_BIT, PC_JUMP_REL_BIT, RES_ADD_BIT, RES_MUL_BIT,
+};
+use crate::hints::Hint;
+use crate::instructions::{
+    AddApInstruction, AssertEqInstruction, CallInstruction, Instruction, InstructionBody,
+    JnzInstruction, JumpInstruction, RetInstruction,
+};
+use crate::operand::{BinOpOperand, CellRef, DerefOrImmediate, Operation, Register, ResOperand};
+
+#[cfg(test)]
+#[path = "decoder_test.rs"]
+mod test;
+
+/// The number of flag bits in an encoded instruction.
+const FLAGS_BITS: u32 = 15;
+
+#[derive(Debug, Eq, PartialEq)]
+pub enum DecodeError {
+    /// The encoded instruction is missing, or is missing its immediate.
+    UnexpectedEnd,
+    /// The value is not a valid instruction encoding.
+    InvalidEncoding(BigInt),
+    /// The instruction flags are inconsistent.
+    InvalidFlags(u64),
+    /// The instruction is valid, but has no matching `Instruction` representation.
+    UnsupportedInstruction,
+}
+impl Display for DecodeError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            DecodeError::UnexpectedEnd => write!(f, "Unexpected end of bytecode"),
+            DecodeError::InvalidEncoding(value) => {
+                write!(f, "Invalid instruction encoding {value}")
+            }
+            DecodeError::InvalidFlags(flags) => write!(f, "Invalid instruction flags {flags:#x}"),
+            DecodeError::UnsupportedInstruction => write!(f, "Unsupported instruction"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DecodeError {}
+
+impl InstructionRepr {
+    /// Decodes the instruction at the start of `encoded`, including its immediate if it has one.
+    pub fn decode(encoded: &[BigInt]) -> Result<Self, DecodeError> {
+        let value = encoded.first().ok_or(DecodeError::UnexpectedEnd)?;
+        let encoding = value
+            .to_u64()
+            .filter(|encoding| encoding >> (3 * OFFSET_BITS + FLAGS_BITS) == 0)
+            .ok_or_else(|| DecodeError::InvalidEncoding(value.clone()))?;
+        // Convert the offsets from positive numbers in the range [0, 2^16) centered around 2^15
+        // back to numbers in the range [-2^15, 2^15).
+        let decode_offset = |index: u32| {
+            let offset_enc = (encoding >> (index * OFFSET_BITS)) & ((1 << OFFSET_BITS) - 1);
+            (offset_enc as i32 - (1 << (OFFSET_BITS - 1))) as i16
+        };
+        let flags = encoding >> (3 * OFFSET_BITS);
+        let flag = |bit: i32| flags & (1 << bit) != 0;
+        let invalid_flags = || DecodeError::InvalidFlags(flags);
+        // Returns the single set flag out of `bits`, or `None` if none are set.
+        let exclusive_flag = |bits: &[i32]| -> Result<Option<i32>, DecodeError> {
+            let mut set_bits = bits.iter().filter(|bit| flag(**bit));
+            let set_bit = set_bits.next().copied();
+            if set_bits.next().is_some() { Err(invalid_flags()) } else { Ok(set_bit) }
+        };
+        let register = |bit: i32| if flag(bit) { Register::FP } else { Register::AP };
+
+        let op1_addr = match exclusive_flag(&[OP1_IMM_BIT, OP1_AP_BIT, OP1_FP_BIT])? {
+            Some(OP1_IMM_BIT) => Op1Addr::Imm,
+            Some(OP1_AP_BIT) => Op1Addr::AP,
+            Some(OP1_FP_BIT) => Op1Addr::FP,
+            _ => Op1Addr::Op0,
+        };
+        let pc_update = match exclusive_flag(&[PC_JUMP_ABS_BIT, PC_JUMP_REL_BIT, PC_JNZ_BIT])? {
+            Some(PC_JUMP_ABS_BIT) => PcUpdate::Jump,
+            Some(PC_JUMP_REL_BIT) => PcUpdate::JumpRel,
+            Some(PC_JNZ_BIT) => PcUpdate::Jnz,
+            _ => PcUpdate::Regular,
+        };
+        let res = match (exclusive_flag(&[RES_ADD_BIT, RES_MUL_BIT])?, &pc_update) {
+            (None, PcUpdate::Jnz) => Res::Unconstrained,
+            (_, PcUpdate::Jnz) => return Err(invalid_flags()),
+            (Some(RES_ADD_BIT), _) => Res::Add,
+            (Some(RES_MUL_BIT), _) => Res::Mul,
+            _ => Res::Op1,
+        };
+        let opcode = match exclusive_flag(&[OPCODE_CALL_BIT, OPCODE_RET_BIT, OPCODE_ASSERT_EQ_BIT])?
+        {
+            Some(OPCODE_CALL_BIT) => Opcode::Call,
+            Some(OPCODE_RET_BIT) => Opcode::Ret,
+            Some(OPCODE_ASSERT_EQ_BIT) => Opcode::AssertEq,
+            _ => Opcode::Nop,
+        };
+        let ap_update = match (exclusive_flag(&[AP_ADD_BIT, AP_ADD1_BIT])?, &opcode) {
+            (None, Opcode::Call) => ApUpdate::Add2,
+            (_, Opcode::Call) => return Err(invalid_flags()),
+            (Some(AP_ADD_BIT), _) => ApUpdate::Add,
+            (Some(AP_ADD1_BIT), _) => ApUpdate::Add1,
+            _ => ApUpdate::Regular,
+        };
+        let fp_update = match opcode {
+            Opcode::Nop | Opcode::AssertEq => FpUpdate::Regular,
+            Opcode::Call => FpUpdate::ApPlus2,
+            Opcode::Ret => FpUpdate::Dst,
+        };
+        let imm = if op1_addr == Op1Addr::Imm {
+            Some(encoded.get(1).ok_or(DecodeError::UnexpectedEnd)?.clone())
+        } else {
+            None
+        };
+
+        Ok(InstructionRepr {
+            off0: decode_offset(0),
+            off1: decode_offset(1),
+            off2: decode_offset(2),
+            imm,
+            dst_register: register(DST_REG_BIT),
+            op0_register: register(OP0_REG_BIT),
+            op1_addr,
+            res,
+            pc_update,
+            ap_update,
+            fp_update,
+            opcode,
+        })
+    }
+
+    /// Converts the low level representation back into an instruction, without hints.
+    /// Offsets that the instruction ignores are not validated.
+    pub fn disassemble(&self) -> Result<Instruction, DecodeError> {
+        let unsupported = || DecodeError::UnsupportedInstruction;
+        let (body, inc_ap) = match self.opcode {
+            Opcode::Ret => (InstructionBody::Ret(RetInstruction {}), false),
+            Opcode::Call => {
+                let relative = match self.pc_update {
+                    PcUpdate::Jump => false,
+                    PcUpdate::JumpRel => true,
+                    _ => return Err(unsupported()),
+                };
+                let target = self.op1_operand().ok_or_else(unsupported)?;
+                (InstructionBody::Call(CallInstruction { target, relative }), false)
+            }
+            Opcode::AssertEq => {
+                if self.pc_update != PcUpdate::Regular {
+                    return Err(unsupported());
+                }
+                let a = CellRef { register: self.dst_register, offset: self.off0 };
+                let b = self.res_operand().ok_or_else(unsupported)?;
+                (InstructionBody::AssertEq(AssertEqInstruction { a, b }), self.inc_ap()?)
+            }
+            Opcode::Nop => match self.pc_update {
+                PcUpdate::Jump | PcUpdate::JumpRel => {
+                    let target = self.op1_operand().ok_or_else(unsupported)?;
+                    let relative = self.pc_update == PcUpdate::JumpRel;
+                    (InstructionBody::Jump(JumpInstruction { target, relative }), self.inc_ap()?)
+                }
+                PcUpdate::Jnz => {
+                    let jump_offset = self.op1_operand().ok_or_else(unsupported)?;
+                    let condition = CellRef { register: self.dst_register, offset: self.off0 };
+                    (
+                        InstructionBody::Jnz(JnzInstruction { jump_offset, condition }),
+                        self.inc_ap()?,
+                    )
+                }
+                PcUpdate::Regular if self.ap_update == ApUpdate::Add => {
+                    let operand = self.res_operand().ok_or_else(unsupported)?;
+                    (InstructionBody::AddAp(AddApInstruction { operand }), false)
+                }
+                PcUpdate::Regular => return Err(unsupported()),
+            },
+        };
+        Ok(Instruction::new(body, inc_ap))
+    }
+
+    /// Returns whether the instruction has an `ap++`, or an error if it has a different ap update.
+    fn inc_ap(&self) -> Result<bool, DecodeError> {
+        match self.ap_update {
+            ApUpdate::Regular => Ok(false),
+            ApUpdate::Add1 => Ok(true),
+            ApUpdate::Add | ApUpdate::Add2 => Err(DecodeError::UnsupportedInstruction),
+        }
+    }
+
+    /// Returns the op1 operand, if it is a dereference or an immediate.
+    fn op1_operand(&self) -> Option<DerefOrImmediate> {
+        match self.op1_addr {
+            Op1Addr::Imm => Some(DerefOrImmediate::Immediate(self.imm.clone()?.into())),
+            Op1Addr::AP => {
+                Some(DerefOrImmediate::Deref(CellRef { register: Register::AP, offset: self.off2 }))
+            }
+            Op1Addr::FP => {
+                Some(DerefOrImmediate::Deref(CellRef { register: Register::FP, offset: self.off2 }))
+            }
+            Op1Addr::Op0 => None,
+        }
+    }
+
+    /// Returns the res operand, computed from op0, op1 and the res flags.
+    fn res_operand(&self) -> Option<ResOperand> {
+        let op0 = CellRef { register: self.op0_register, offset: self.off1 };
+        let op = match self.res {
+            Res::Op1 => {
+                return Some(match self.op1_operand() {
+                    Some(op1) => op1.into(),
+                    None => ResOperand::DoubleDeref(op0, self.off2),
+                });
+            }
+            Res::Add => Operation::Add,
+            Res::Mul => Operation::Mul,
+            Res::Unconstrained => return None,
+        };
+        Some(ResOperand::BinOp(BinOpOperand { op, a: op0, b: self.op1_operand()? }))
+    }
+}
+
+/// Decodes the instruction at the start of `encoded`. The size of the instruction is given by
+/// `instruction.body.op_size()`.
+pub fn decode_instruction(encoded: &[BigInt]) -> Result<Instruction, DecodeError> {
+    InstructionRepr::decode(encoded)?.disassemble()
+}
+
+/// Decodes the given bytecode, attaching the given hints by their bytecode offset.
+/// Returns the offset of each decoded instruction. A value that fails to decode is returned as an
+/// error at its offset, and decoding resumes at the next value.
+pub fn decode_bytecode(
+    bytecode: &[BigInt],
+    hints: &[(usize, Vec<Hint>)],
+) -> Vec<(usize, Result<Instruction, DecodeError>)> {
+    let mut hints = hints.iter().peekable();
+    let mut decoded = Vec::new();
+    let mut pc = 0;
+    while pc < bytecode.len() {
+        let mut instruction = decode_instruction(&bytecode[pc..]);
+        let size = instruction.as_ref().map_or(1, |instruction| instruction.body.op_size());
+        while let Some((_, instruction_hints)) = hints.next_if(|(offset, _)| *offset <= pc) {
+            if let Ok(instruction) = &mut instruction {
+                instruction.hints.extend(instruction_hints.iter().cloned());
+            }
+        }
+        decoded.push((pc, instruction));
+        pc += size;
+    }
+    decoded
+}
@@ -0,0 +1,81 @@
+#[cfg(not(feature = "std"))]
+use alloc::{vec, vec::Vec};
+
+use num_bigint::BigInt;
+use test_case::test_case;
+
+use super::{DecodeError, decode_bytecode, decode_instruction};
+use crate::assembler::InstructionRepr;
+use crate::casm;
+use crate::hints::{CoreHint, Hint};
+use crate::inline::CasmContext;
+use crate::operand::{CellRef, Register};
+
+#[test_case(casm!(jmp abs 3;); "jmp abs 3;")]
+#[test_case(casm!(jmp rel -5, ap++;); "jmp rel -5, ap++;")]
+#[test_case(casm!(jmp abs [ap + 2];); "jmp abs [ap + 2];")]
+#[test_case(casm!(call abs 3;); "call abs 3;")]
+#[test_case(casm!(call rel (-5);); "call rel (-5);")]
+#[test_case(casm!(call rel [fp - 3];); "call rel [fp - 3];")]
+#[test_case(casm!(jmp rel 205 if [ap + 5] != 0;); "jmp rel 205 if [ap + 5] != 0;")]
+#[test_case(casm!(jmp rel [fp + 1] if [fp - 1] != 0, ap++;); "jmp rel [fp + 1] if [fp - 1] != 0;")]
+#[test_case(casm!([ap + 5] = 205;); "[ap + 5] = 205;")]
+#[test_case(casm!([fp + 5] = [[ap - 2] + 3], ap++;); "[fp + 5] = [[ap - 2] + 3], ap++;")]
+#[test_case(casm!([ap] = [fp - 5] * [ap - 1];); "[ap] = [fp - 5] * [ap - 1];")]
+#[test_case(casm!([fp - 3] = [ap] + 1, ap++;); "[fp - 3] = [ap] + 1, ap++;")]
+#[test_case(casm!(ret;); "ret;")]
+#[test_case(casm!(ap += 205;); "ap += 205;")]
+#[test_case(casm!(ap += [fp + 7];); "ap += [fp + 7];")]
+fn test_decode(mut casm: CasmContext) {
+    let instruction = casm.instructions.remove(0);
+    let repr = instruction.assemble();
+    let encoded = repr.encode();
+    assert_eq!(InstructionRepr::decode(&encoded), Ok(repr));
+    let decoded = decode_instruction(&encoded).unwrap();
+    assert_eq!(decoded.body.op_size(), encoded.len());
+    assert_eq!(decoded, instruction);
+}
+
+#[test_case(&[] => DecodeError::UnexpectedEnd; "empty")]
+#[test_case(&[BigInt::from(0x400680017fff8005u64)] => DecodeError::UnexpectedEnd;
+            "missing immediate")]
+#[test_case(&[BigInt::from(-1)] => DecodeError::InvalidEncoding(BigInt::from(-1)); "negative")]
+#[test_case(&[BigInt::from(1u64 << 63)] => DecodeError::InvalidEncoding(BigInt::from(1u64 << 63));
+            "too large")]
+#[test_case(&[BigInt::from(0x18u64 << 48)] => DecodeError::InvalidFlags(0x18);
+            "multiple op1 sources")]
+#[test_case(&[BigInt::from(0x220u64 << 48)] => DecodeError::InvalidFlags(0x220);
+            "jnz with res")]
+#[test_case(&[BigInt::from(0x1808u64 << 48)] => DecodeError::InvalidFlags(0x1808);
+            "call with ap update")]
+#[test_case(&[BigInt::from(0x8u64 << 48)] => DecodeError::UnsupportedInstruction; "nop")]
+fn test_decode_error(encoded: &[BigInt]) -> DecodeError {
+    decode_instruction(encoded).unwrap_err()
+}
+
+#[test]
+fn test_decode_bytecode() {
+    let casm = casm! {
+        [ap + 0] = 1, ap++;
+        jmp rel 5 if [fp + -3] != 0;
+        ret;
+    };
+    let mut bytecode: Vec<BigInt> =
+        casm.instructions.iter().flat_map(|inst| inst.assemble().encode()).collect();
+    bytecode.insert(4, BigInt::from(-7));
+    let hint: Hint =
+        CoreHint::AllocSegment { dst: CellRef { register: Register::AP, offset: 0 } }.into();
+    let decoded = decode_bytecode(&bytecode, &[(2, vec![hint.clone()])]);
+
+    let mut expected = casm.instructions;
+    expected[1].hints.push(hint);
+    assert_eq!(
+        decoded,
+        vec![
+            (0, Ok(expected[0].clone())),
+            (2, Ok(expected[1].clone())),
+            (4, Err(DecodeError::InvalidEncoding(BigInt::from(-7)))),
+            (5, Ok(expected[2].clone())),
+        ]
+    );
+}
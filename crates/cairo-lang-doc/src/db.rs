@@ -242,9 +242,17 @@ fn extract_item_outer_documentation(
     db: &dyn DocGroup,
     item_id: DocumentableItemId,
 ) -> Option<String> {
-    // Get the text of the item (trivia + definition)
-    let raw_text =
-        item_id.stable_location(db.upcast())?.syntax_node(db.upcast()).get_text(db.upcast());
+    let syntax_node = item_id.stable_location(db.upcast())?.syntax_node(db.upcast());
+    get_node_outer_documentation(db.upcast(), &syntax_node)
+}
+
+/// Gets the doc comments above a syntax node, in a final form usable for display.
+///
+/// Useful for documenting nodes which are not items, e.g. user code that plugins generate items
+/// from.
+pub fn get_node_outer_documentation(db: &dyn SyntaxGroup, node: &SyntaxNode) -> Option<String> {
+    // Get the text of the node (trivia + definition)
+    let raw_text = node.get_text(db);
     let lines = raw_text
         .lines()
         .filter(|line| !line.trim().is_empty())
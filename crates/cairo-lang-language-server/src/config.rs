@@ -38,6 +38,40 @@ pub struct Config {
     /// The property is set by the user under the `cairo1.traceMacroDiagnostics` key in client
     /// configuration.
     pub trace_macro_diagnostics: bool,
+    /// Which kinds of inlay hints to show.
+    pub inlay_hints: InlayHintsConfig,
+}
+
+/// Toggles of the kinds of inlay hints, all enabled by default.
+#[derive(Debug, Clone)]
+pub struct InlayHintsConfig {
+    /// Whether to show the inferred types of `let` bindings without type annotations.
+    ///
+    /// The property is set by the user under the `cairo1.inlayHints.typeHints` key in client
+    /// configuration.
+    pub type_hints: bool,
+    /// Whether to show the names of parameters at function call arguments.
+    ///
+    /// The property is set by the user under the `cairo1.inlayHints.parameterHints` key in client
+    /// configuration.
+    pub parameter_hints: bool,
+    /// Whether to show the impls chosen for calls of trait functions.
+    ///
+    /// The property is set by the user under the `cairo1.inlayHints.implHints` key in client
+    /// configuration.
+    pub impl_hints: bool,
+    /// Whether to show the snapshots implicitly taken of (or desnapped from) method receivers and
+    /// operands.
+    ///
+    /// The property is set by the user under the `cairo1.inlayHints.snapshotHints` key in client
+    /// configuration.
+    pub snapshot_hints: bool,
+}
+
+impl Default for InlayHintsConfig {
+    fn default() -> Self {
+        Self { type_hints: true, parameter_hints: true, impl_hints: true, snapshot_hints: true }
+    }
 }
 
 impl Config {
@@ -62,6 +96,22 @@ impl Config {
                 scope_uri: None,
                 section: Some("cairo1.traceMacroDiagnostics".to_owned()),
             },
+            ConfigurationItem {
+                scope_uri: None,
+                section: Some("cairo1.inlayHints.typeHints".to_owned()),
+            },
+            ConfigurationItem {
+                scope_uri: None,
+                section: Some("cairo1.inlayHints.parameterHints".to_owned()),
+            },
+            ConfigurationItem {
+                scope_uri: None,
+                section: Some("cairo1.inlayHints.implHints".to_owned()),
+            },
+            ConfigurationItem {
+                scope_uri: None,
+                section: Some("cairo1.inlayHints.snapshotHints".to_owned()),
+            },
         ];
         let expected_len = items.len();
 
@@ -87,6 +137,14 @@ impl Config {
                     .map(Into::into);
                 state.config.trace_macro_diagnostics =
                     response.pop_front().as_ref().and_then(Value::as_bool).unwrap_or_default();
+                let mut toggle =
+                    || response.pop_front().as_ref().and_then(Value::as_bool).unwrap_or(true);
+                state.config.inlay_hints = InlayHintsConfig {
+                    type_hints: toggle(),
+                    parameter_hints: toggle(),
+                    impl_hints: toggle(),
+                    snapshot_hints: toggle(),
+                };
 
                 debug!("reloaded configuration: {:#?}", state.config);
             })
@@ -12,11 +12,30 @@ use std::time::Duration;
 
 use tracing::debug;
 
+pub const CAIRO_LS_CACHE_DIR: &'_ str = "CAIRO_LS_CACHE_DIR";
 pub const CAIRO_LS_DB_REPLACE_INTERVAL: &'_ str = "CAIRO_LS_DB_REPLACE_INTERVAL";
 pub const CAIRO_LS_LOG: &'_ str = "CAIRO_LS_LOG";
 pub const CAIRO_LS_PROFILE: &'_ str = "CAIRO_LS_PROFILE";
 pub const SCARB: &'_ str = "SCARB";
 
+/// Directory where the LS persists diagnostics of immutable crates between runs.
+///
+/// Defaults to `cairo-language-server` in the platform cache directory. In tests, caching is
+/// disabled unless this variable is set.
+pub fn cache_dir() -> Option<PathBuf> {
+    if let Some(path) = env::var_os(CAIRO_LS_CACHE_DIR) {
+        return Some(PathBuf::from(path));
+    }
+    if cfg!(feature = "testing") {
+        return None;
+    }
+    let platform_cache_dir = env::var_os("XDG_CACHE_HOME")
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".cache")))
+        .or_else(|| env::var_os("LOCALAPPDATA").map(PathBuf::from))?;
+    Some(platform_cache_dir.join("cairo-language-server"))
+}
+
 /// Interval between compiler database regenerations (to free unused memory).
 pub fn db_replace_interval() -> Duration {
     const DEFAULT: u64 = 300;
@@ -45,6 +64,10 @@ pub fn scarb_path() -> Option<PathBuf> {
 
 /// Print all environment variables values (or defaults) as debug messages in logs.
 pub fn report_to_logs() {
+    debug!(
+        "{CAIRO_LS_CACHE_DIR}={}",
+        cache_dir().map(|p| p.display().to_string()).unwrap_or_default()
+    );
     debug!("{CAIRO_LS_DB_REPLACE_INTERVAL}={:?}", db_replace_interval());
     debug!("{CAIRO_LS_LOG}={}", log_env_filter());
     debug!("{CAIRO_LS_PROFILE}={}", tracing_profile());
@@ -0,0 +1,83 @@
+use cairo_lang_defs::ids::{LanguageElementId, NamedLanguageElementId};
+use cairo_lang_semantic::{ConcreteTypeId, TypeLongId};
+use cairo_lang_syntax::node::{SyntaxNode, TypedStablePtr, TypedSyntaxNode, ast};
+use cairo_lang_utils::{LookupIntern, Upcast};
+use lsp_types::{CodeAction, Range, TextEdit};
+
+use crate::ide::code_actions::{column, identifier_of, quick_fix};
+use crate::ide::utils::identifier_type;
+use crate::lang::db::{AnalysisDatabase, LsSemanticGroup};
+use crate::lang::lsp::{LsProtoGroup, ToLsp};
+
+/// Create a Quick Fix code action deriving `Drop` for the type of a variable, given a
+/// `VariableNotDropped` diagnostic.
+#[tracing::instrument(level = "trace", skip_all)]
+pub fn add_drop_derive(db: &AnalysisDatabase, node: &SyntaxNode) -> Option<CodeAction> {
+    let syntax_db = db.upcast();
+    let identifier = identifier_of(db, node)?;
+    let ty = identifier_type(db, &identifier)?;
+    // Only types defined in the user's crate can be changed.
+    let user_crate_id = db.find_module_containing_node(node)?.owning_crate(db.upcast());
+    let (name, module_id, item, attributes) = match ty.lookup_intern(db) {
+        TypeLongId::Concrete(ConcreteTypeId::Struct(concrete_struct)) => {
+            let struct_id = concrete_struct.struct_id(db);
+            let item = struct_id.stable_ptr(db.upcast()).lookup(syntax_db);
+            let attributes = item.attributes(syntax_db);
+            (
+                struct_id.name(db.upcast()),
+                struct_id.parent_module(db.upcast()),
+                item.as_syntax_node(),
+                attributes,
+            )
+        }
+        TypeLongId::Concrete(ConcreteTypeId::Enum(concrete_enum)) => {
+            let enum_id = concrete_enum.enum_id(db);
+            let item = enum_id.stable_ptr(db.upcast()).lookup(syntax_db);
+            let attributes = item.attributes(syntax_db);
+            (
+                enum_id.name(db.upcast()),
+                enum_id.parent_module(db.upcast()),
+                item.as_syntax_node(),
+                attributes,
+            )
+        }
+        _ => return None,
+    };
+    if module_id.owning_crate(db.upcast()) != user_crate_id {
+        return None;
+    }
+
+    let file_id = item.stable_ptr().file_id(syntax_db);
+    let derive = attributes.elements(syntax_db).into_iter().find(|attribute| {
+        attribute.attr(syntax_db).as_syntax_node().get_text_without_trivia(syntax_db) == "derive"
+    });
+    // Extend an existing `derive` attribute, or add a new one before the item.
+    let (offset, new_text) = match derive.map(|derive| derive.arguments(syntax_db)) {
+        Some(ast::OptionArgListParenthesized::ArgListParenthesized(arguments)) => {
+            let derived = arguments.arguments(syntax_db).elements(syntax_db);
+            if derived
+                .iter()
+                .any(|arg| arg.as_syntax_node().get_text_without_trivia(syntax_db) == "Drop")
+            {
+                return None;
+            }
+            let separator = if derived.is_empty() { "" } else { ", " };
+            (
+                arguments.rparen(syntax_db).as_syntax_node().span_start_without_trivia(syntax_db),
+                format!("{separator}Drop"),
+            )
+        }
+        Some(ast::OptionArgListParenthesized::Empty(_)) => return None,
+        None => {
+            let start = item.span_start_without_trivia(syntax_db);
+            let indent = " ".repeat(column(db, file_id, start)?);
+            (start, format!("#[derive(Drop)]\n{indent}"))
+        }
+    };
+    let position = offset.position_in_file(db.upcast(), file_id)?.to_lsp();
+    Some(quick_fix(
+        format!("Derive `Drop` for `{name}`"),
+        db.url_for_file(file_id),
+        vec![TextEdit { range: Range::new(position, position), new_text }],
+    ))
+}
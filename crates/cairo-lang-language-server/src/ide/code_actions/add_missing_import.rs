@@ -0,0 +1,186 @@
+use cairo_lang_defs::db::DefsGroup;
+use cairo_lang_defs::ids::{LanguageElementId, ModuleId, ModuleItemId, NamedLanguageElementId};
+use cairo_lang_filesystem::db::FilesGroup;
+use cairo_lang_semantic::db::SemanticGroup;
+use cairo_lang_semantic::items::visibility::peek_visible_in;
+use cairo_lang_syntax::node::kind::SyntaxKind;
+use cairo_lang_syntax::node::{SyntaxNode, Terminal, TypedSyntaxNode, ast};
+use cairo_lang_utils::Upcast;
+use cairo_lang_utils::ordered_hash_map::{Entry, OrderedHashMap};
+use cairo_lang_utils::unordered_hash_set::UnorderedHashSet;
+use lsp_types::{CodeAction, Range, TextEdit, Url};
+use smol_str::SmolStr;
+
+use crate::ide::code_actions::{identifier_of, module_start_position, quick_fix};
+use crate::lang::db::{AnalysisDatabase, LsSemanticGroup};
+use crate::lang::lsp::LsProtoGroup;
+
+/// Create Quick Fix code actions importing the items a `PathNotFound` diagnostic may refer to.
+#[tracing::instrument(level = "trace", skip_all)]
+pub fn add_missing_import(db: &AnalysisDatabase, node: &SyntaxNode, uri: Url) -> Vec<CodeAction> {
+    missing_import_actions(db, node, uri).unwrap_or_default()
+}
+
+/// Returns a list of code actions adding a `use` of an item named like the unresolved path.
+fn missing_import_actions(
+    db: &AnalysisDatabase,
+    node: &SyntaxNode,
+    uri: Url,
+) -> Option<Vec<CodeAction>> {
+    let syntax_db = db.upcast();
+    let file_id = db.file_for_url(&uri)?;
+    let identifier = identifier_of(db, node)?;
+    // Only the first segment of a path can be brought into scope by an import.
+    let segment = identifier.as_syntax_node().parent()?;
+    let path = segment.parent()?;
+    if path.kind(syntax_db) != SyntaxKind::ExprPath
+        || ast::ExprPath::from_syntax_node(syntax_db, path)
+            .elements(syntax_db)
+            .first()?
+            .as_syntax_node()
+            != segment
+    {
+        return None;
+    }
+
+    let user_module_id = db.find_module_containing_node(node)?;
+    let module_start_position = module_start_position(db, file_id, node)?;
+    let mut paths = visible_items_paths(db, identifier.text(syntax_db), user_module_id);
+    paths.sort_by_key(|path| (path.split("::").count(), path.clone()));
+    Some(
+        paths
+            .into_iter()
+            .map(|path| {
+                quick_fix(
+                    format!("Import {path}"),
+                    uri.clone(),
+                    vec![TextEdit {
+                        range: Range::new(module_start_position, module_start_position),
+                        new_text: format!("use {path};\n"),
+                    }],
+                )
+            })
+            .collect(),
+    )
+}
+
+/// Returns the shortest paths of the items with the given name visible from the user module,
+/// relative to it for items of its own crate.
+fn visible_items_paths(
+    db: &AnalysisDatabase,
+    name: SmolStr,
+    user_module_id: ModuleId,
+) -> Vec<String> {
+    let mut top_module_id = user_module_id;
+    while let ModuleId::Submodule(submodule_id) = top_module_id {
+        top_module_id = submodule_id.parent_module(db.upcast());
+    }
+    let ModuleId::CrateRoot(user_crate_id) = top_module_id else {
+        unreachable!("current module is not a top-level module")
+    };
+
+    let mut items = visible_items_in_module(
+        db,
+        user_module_id,
+        user_module_id,
+        &name,
+        true,
+        &mut UnorderedHashSet::default(),
+    );
+    for crate_id in db.crates() {
+        if crate_id == user_crate_id {
+            continue;
+        }
+        let crate_name = crate_id.name(db.upcast());
+        items.extend(
+            visible_items_in_module(
+                db,
+                ModuleId::CrateRoot(crate_id),
+                user_module_id,
+                &name,
+                false,
+                &mut UnorderedHashSet::default(),
+            )
+            .into_iter()
+            .map(|(item_id, path)| (item_id, format!("{crate_name}::{path}"))),
+        );
+    }
+
+    let mut result: OrderedHashMap<ModuleItemId, String> = OrderedHashMap::default();
+    // Items of the user module itself are already in scope.
+    for (item_id, path) in items.into_iter().filter(|(_, path)| path.contains("::")) {
+        match result.entry(item_id) {
+            Entry::Occupied(existing_path) => {
+                if path.split("::").count() < existing_path.get().split("::").count() {
+                    *existing_path.into_mut() = path;
+                }
+            }
+            Entry::Vacant(entry) => {
+                entry.insert(path);
+            }
+        }
+    }
+    result.into_iter().map(|(_, path)| path).collect()
+}
+
+/// Returns the items with the given name defined in a module or its visible submodules, with
+/// their paths relative to the module. Includes the items of the parent modules if needed.
+fn visible_items_in_module(
+    db: &AnalysisDatabase,
+    module_id: ModuleId,
+    user_module_id: ModuleId,
+    name: &SmolStr,
+    include_parent: bool,
+    visited_modules: &mut UnorderedHashSet<ModuleId>,
+) -> Vec<(ModuleItemId, String)> {
+    let mut result = vec![];
+    if !visited_modules.insert(module_id) {
+        return result;
+    }
+    // Check if an item in the current module is visible from the user module.
+    let is_visible = |item_name: SmolStr| {
+        let Ok(Some(item_info)) = db.module_item_info_by_name(module_id, item_name) else {
+            return false;
+        };
+        peek_visible_in(db.upcast(), item_info.visibility, module_id, user_module_id)
+    };
+
+    if let Ok(Some(item_id)) = db.module_item_by_name(module_id, name.clone()) {
+        if !matches!(item_id, ModuleItemId::Use(_)) && is_visible(name.clone()) {
+            result.push((item_id, name.to_string()));
+        }
+    }
+    let submodules =
+        db.module_submodules_ids(module_id).map(|ids| ids.to_vec()).unwrap_or_default();
+    for submodule_id in submodules {
+        let submodule_name = submodule_id.name(db.upcast());
+        if !is_visible(submodule_name.clone()) {
+            continue;
+        }
+        for (item_id, path) in visible_items_in_module(
+            db,
+            ModuleId::Submodule(submodule_id),
+            user_module_id,
+            name,
+            false,
+            visited_modules,
+        ) {
+            result.push((item_id, format!("{submodule_name}::{path}")));
+        }
+    }
+    if include_parent {
+        if let ModuleId::Submodule(submodule_id) = module_id {
+            for (item_id, path) in visible_items_in_module(
+                db,
+                submodule_id.parent_module(db.upcast()),
+                user_module_id,
+                name,
+                true,
+                visited_modules,
+            ) {
+                result.push((item_id, format!("super::{path}")));
+            }
+        }
+    }
+    result
+}
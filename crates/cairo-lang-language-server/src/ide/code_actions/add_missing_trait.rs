@@ -1,8 +1,5 @@
-use std::collections::HashMap;
-
-use cairo_lang_defs::ids::{LookupItemId, ModuleId, NamedLanguageElementId};
+use cairo_lang_defs::ids::{LookupItemId, NamedLanguageElementId};
 use cairo_lang_filesystem::ids::FileId;
-use cairo_lang_filesystem::span::TextOffset;
 use cairo_lang_semantic::db::SemanticGroup;
 use cairo_lang_semantic::expr::inference::InferenceId;
 use cairo_lang_semantic::items::function_with_body::SemanticExprLookup;
@@ -11,12 +8,13 @@ use cairo_lang_semantic::resolve::Resolver;
 use cairo_lang_syntax::node::kind::SyntaxKind;
 use cairo_lang_syntax::node::{SyntaxNode, TypedStablePtr, TypedSyntaxNode, ast};
 use cairo_lang_utils::Upcast;
-use lsp_types::{CodeAction, CodeActionKind, Range, TextEdit, Url, WorkspaceEdit};
+use lsp_types::{CodeAction, Range, TextEdit, Url};
 use tracing::debug;
 
+use crate::ide::code_actions::{module_start_position, quick_fix};
 use crate::ide::utils::find_methods_for_type;
 use crate::lang::db::{AnalysisDatabase, LsSemanticGroup};
-use crate::lang::lsp::{LsProtoGroup, ToLsp};
+use crate::lang::lsp::LsProtoGroup;
 
 /// Create a Quick Fix code action to add a missing trait given a `CannotCallMethod` diagnostic.
 #[tracing::instrument(level = "trace", skip_all)]
@@ -38,7 +36,6 @@ fn missing_traits_actions(
     unknown_method_name: &str,
     uri: Url,
 ) -> Option<Vec<CodeAction>> {
-    let syntax_db = db.upcast();
     // Get a resolver in the current context.
     let lookup_item_id = lookup_items.into_iter().next()?;
     let function_with_body = lookup_item_id.function_with_body()?;
@@ -63,19 +60,7 @@ fn missing_traits_actions(
         return None;
     }
 
-    let module_start_offset =
-        if let Some(ModuleId::Submodule(submodule_id)) = db.find_module_containing_node(node) {
-            let module_def_ast = submodule_id.stable_ptr(db.upcast()).lookup(syntax_db);
-            if let ast::MaybeModuleBody::Some(body) = module_def_ast.body(syntax_db) {
-                body.items(syntax_db).as_syntax_node().span_start_without_trivia(syntax_db)
-            } else {
-                TextOffset::default()
-            }
-        } else {
-            TextOffset::default()
-        };
-    let module_start_position =
-        module_start_offset.position_in_file(db.upcast(), file_id).unwrap().to_lsp();
+    let module_start_position = module_start_position(db, file_id, node)?;
     let relevant_methods = find_methods_for_type(db, resolver, ty, stable_ptr);
     let current_module = db.find_module_containing_node(node)?;
     let module_visible_traits = db.visible_traits_from_module(current_module)?;
@@ -84,20 +69,14 @@ fn missing_traits_actions(
         let method_name = method.name(db.upcast());
         if method_name == unknown_method_name {
             if let Some(trait_path) = module_visible_traits.get(&method.trait_id(db.upcast())) {
-                code_actions.push(CodeAction {
-                    title: format!("Import {}", trait_path),
-                    kind: Some(CodeActionKind::QUICKFIX),
-                    edit: Some(WorkspaceEdit {
-                        changes: Some(HashMap::from_iter([(uri.clone(), vec![TextEdit {
-                            range: Range::new(module_start_position, module_start_position),
-                            new_text: format!("use {};\n", trait_path),
-                        }])])),
-                        document_changes: None,
-                        change_annotations: None,
-                    }),
-                    diagnostics: None,
-                    ..Default::default()
-                });
+                code_actions.push(quick_fix(
+                    format!("Import {}", trait_path),
+                    uri.clone(),
+                    vec![TextEdit {
+                        range: Range::new(module_start_position, module_start_position),
+                        new_text: format!("use {};\n", trait_path),
+                    }],
+                ));
             }
         }
     }
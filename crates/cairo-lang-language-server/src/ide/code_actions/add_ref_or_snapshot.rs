@@ -0,0 +1,79 @@
+use cairo_lang_semantic::db::SemanticGroup;
+use cairo_lang_semantic::items::function_with_body::SemanticExprLookup;
+use cairo_lang_semantic::lookup_item::LookupItemEx;
+use cairo_lang_semantic::types::peel_snapshots;
+use cairo_lang_semantic::{Expr, ExprFunctionCallArg, TypeLongId};
+use cairo_lang_syntax::node::kind::SyntaxKind;
+use cairo_lang_syntax::node::{SyntaxNode, TypedSyntaxNode, ast};
+use cairo_lang_utils::Upcast;
+use lsp_types::{CodeAction, Range, TextEdit, Url};
+
+use crate::ide::code_actions::quick_fix;
+use crate::lang::db::{AnalysisDatabase, LsSemanticGroup, LsSyntaxGroup};
+use crate::lang::lsp::{LsProtoGroup, ToLsp};
+
+/// Create a Quick Fix code action passing an argument as `ref`, given a `RefArgNotExplicit`
+/// diagnostic.
+#[tracing::instrument(level = "trace", skip_all)]
+pub fn add_ref(db: &AnalysisDatabase, node: &SyntaxNode, uri: Url) -> Option<CodeAction> {
+    let arg = db.first_ancestor_of_kind(node.clone(), SyntaxKind::Arg)?;
+    insert_before(db, &arg, "ref ", uri)
+}
+
+/// Create a Quick Fix code action passing a snapshot of an argument, given a `WrongArgumentType`
+/// diagnostic where a snapshot of the argument is expected.
+#[tracing::instrument(level = "trace", skip_all)]
+pub fn add_snapshot(db: &AnalysisDatabase, node: &SyntaxNode, uri: Url) -> Option<CodeAction> {
+    let syntax_db = db.upcast();
+    let arg = db.first_ancestor_of_kind(node.clone(), SyntaxKind::Arg)?;
+    let value = match ast::Arg::from_syntax_node(syntax_db, arg.clone()).arg_clause(syntax_db) {
+        ast::ArgClause::Unnamed(clause) => clause.value(syntax_db),
+        ast::ArgClause::Named(clause) => clause.value(syntax_db),
+        ast::ArgClause::FieldInitShorthand(_) => return None,
+    };
+
+    let function_id = db.find_lookup_item(&arg)?.function_with_body()?;
+    let expr_id = db.lookup_expr_by_ptr(function_id, value.stable_ptr()).ok()?;
+    let body = db.function_body(function_id).ok()?;
+    // Find the type of the parameter the argument is passed to.
+    let param_ty = body.arenas.exprs.iter().find_map(|(_, expr)| {
+        let Expr::FunctionCall(call) = expr else { return None };
+        let index = call.args.iter().position(
+            |call_arg| matches!(call_arg, ExprFunctionCallArg::Value(id) if *id == expr_id),
+        )?;
+        Some(db.concrete_function_signature(call.function).ok()?.params.get(index)?.ty)
+    })?;
+    let arg_ty = db.expr_semantic(function_id, expr_id).ty();
+    // The generic arguments of the argument type may be not inferred yet, because of the error.
+    let (param_snapshots, param_ty) = peel_snapshots(db, param_ty);
+    let (arg_snapshots, arg_ty) = peel_snapshots(db, arg_ty);
+    let same_type = match (&param_ty, &arg_ty) {
+        (TypeLongId::Concrete(param_ty), TypeLongId::Concrete(arg_ty)) => {
+            param_ty.generic_type(db) == arg_ty.generic_type(db)
+        }
+        _ => param_ty == arg_ty,
+    };
+    if !same_type || param_snapshots != arg_snapshots + 1 {
+        return None;
+    }
+    insert_before(db, &value.as_syntax_node(), "@", uri)
+}
+
+/// Returns a code action inserting the prefix before the node.
+fn insert_before(
+    db: &AnalysisDatabase,
+    node: &SyntaxNode,
+    prefix: &str,
+    uri: Url,
+) -> Option<CodeAction> {
+    let file_id = db.file_for_url(&uri)?;
+    let position = node
+        .span_start_without_trivia(db.upcast())
+        .position_in_file(db.upcast(), file_id)?
+        .to_lsp();
+    Some(quick_fix(
+        format!("Add `{}`", prefix.trim_end()),
+        uri,
+        vec![TextEdit { range: Range::new(position, position), new_text: prefix.to_owned() }],
+    ))
+}
@@ -0,0 +1,49 @@
+use cairo_lang_semantic::GenericArgumentId;
+use cairo_lang_semantic::corelib::{CoreTraitContext, get_core_trait};
+use cairo_lang_semantic::items::imp::ImplLookupContext;
+use cairo_lang_semantic::items::trt::ConcreteTraitLongId;
+use cairo_lang_semantic::types::get_impl_at_context;
+use cairo_lang_syntax::node::{Terminal, TypedSyntaxNode};
+use cairo_lang_utils::{Intern, Upcast};
+use lsp_types::{CodeAction, Diagnostic, Range, TextEdit};
+
+use crate::ide::code_actions::quick_fix;
+use crate::ide::utils::identifier_type;
+use crate::lang::db::{AnalysisDatabase, LsSemanticGroup, LsSyntaxGroup};
+use crate::lang::lsp::{LsProtoGroup, ToCairo, ToLsp};
+
+/// Create a Quick Fix code action cloning a variable where it was previously used, given a
+/// `VariableMoved` diagnostic.
+#[tracing::instrument(level = "trace", skip_all)]
+pub fn clone_moved_variable(db: &AnalysisDatabase, diagnostic: &Diagnostic) -> Option<CodeAction> {
+    let syntax_db = db.upcast();
+    // The previous usage is the only location attached to the diagnostic.
+    let previous_usage = &diagnostic.related_information.as_ref()?.first()?.location;
+    let file_id = db.file_for_url(&previous_usage.uri)?;
+    let identifier =
+        db.find_identifier_at_position(file_id, previous_usage.range.start.to_cairo())?;
+    let end = identifier
+        .as_syntax_node()
+        .span_end_without_trivia(syntax_db)
+        .position_in_file(db.upcast(), file_id)?
+        .to_lsp();
+    // Only plain variables are cloned, not e.g. members of them.
+    if end != previous_usage.range.end {
+        return None;
+    }
+
+    let ty = identifier_type(db, &identifier)?;
+    let module_id = db.find_module_containing_node(&identifier.as_syntax_node())?;
+    let clone_trait = ConcreteTraitLongId {
+        trait_id: get_core_trait(db, CoreTraitContext::TopLevel, "Clone".into()),
+        generic_args: vec![GenericArgumentId::Type(ty)],
+    }
+    .intern(db);
+    get_impl_at_context(db, ImplLookupContext::new(module_id, vec![]), clone_trait, None).ok()?;
+
+    Some(quick_fix(
+        format!("Clone `{}` before it is moved", identifier.text(syntax_db)),
+        previous_usage.uri.clone(),
+        vec![TextEdit { range: Range::new(end, end), new_text: ".clone()".to_owned() }],
+    ))
+}
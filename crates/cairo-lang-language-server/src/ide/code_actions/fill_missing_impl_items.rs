@@ -0,0 +1,156 @@
+use cairo_lang_defs::ids::{LookupItemId, ModuleItemId, TraitId};
+use cairo_lang_filesystem::ids::FileId;
+use cairo_lang_filesystem::span::TextOffset;
+use cairo_lang_semantic::db::SemanticGroup;
+use cairo_lang_syntax::node::kind::SyntaxKind;
+use cairo_lang_syntax::node::{SyntaxNode, Terminal, TypedStablePtr, TypedSyntaxNode, ast};
+use cairo_lang_utils::Upcast;
+use lsp_types::{CodeAction, Range, TextEdit, Url};
+
+use crate::ide::code_actions::{column, quick_fix};
+use crate::lang::db::{AnalysisDatabase, LsSemanticGroup, LsSyntaxGroup};
+use crate::lang::lsp::{LsProtoGroup, ToLsp};
+
+/// Create a Quick Fix code action adding stubs of the trait items missing in an impl, given a
+/// `MissingItemsInImpl` diagnostic.
+#[tracing::instrument(level = "trace", skip_all)]
+pub fn fill_missing_impl_items(
+    db: &AnalysisDatabase,
+    node: &SyntaxNode,
+    uri: Url,
+) -> Option<CodeAction> {
+    let syntax_db = db.upcast();
+    let file_id = db.file_for_url(&uri)?;
+    let impl_node = db.first_ancestor_of_kind(node.clone(), SyntaxKind::ItemImpl)?;
+    let LookupItemId::ModuleItem(ModuleItemId::Impl(impl_def_id)) =
+        db.find_lookup_item(&impl_node)?
+    else {
+        return None;
+    };
+    let item_impl = ast::ItemImpl::from_syntax_node(syntax_db, impl_node);
+    let ast::MaybeImplBody::Some(body) = item_impl.body(syntax_db) else { return None };
+    let trait_id = db.impl_def_trait(impl_def_id).ok()?;
+
+    // Add the stubs after the last item, with its indentation, or as the first items otherwise.
+    let items = body.items(syntax_db).elements(syntax_db);
+    let (offset, indent, closing) = match (items.first(), items.last()) {
+        (Some(first_item), Some(last_item)) => {
+            let first_item_start = first_item.as_syntax_node().span_start_without_trivia(syntax_db);
+            let indent = column(db, file_id, first_item_start)?;
+            (last_item.as_syntax_node().span_end_without_trivia(syntax_db), indent, String::new())
+        }
+        _ => {
+            let impl_start = item_impl.as_syntax_node().span_start_without_trivia(syntax_db);
+            let impl_indent = column(db, file_id, impl_start)?;
+            let lbrace_end =
+                body.lbrace(syntax_db).as_syntax_node().span_end_without_trivia(syntax_db);
+            let rbrace_start =
+                body.rbrace(syntax_db).as_syntax_node().span_start_without_trivia(syntax_db);
+            // Move the closing brace to its own line, e.g. in `impl A of B<C> {}`.
+            let closing = if line(db, file_id, lbrace_end)? == line(db, file_id, rbrace_start)? {
+                format!("\n{}", " ".repeat(impl_indent))
+            } else {
+                String::new()
+            };
+            (lbrace_end, impl_indent + 4, closing)
+        }
+    };
+    let indent = " ".repeat(indent);
+
+    let generic_args = trait_generic_args(db, &item_impl, trait_id);
+    let mut new_text = String::new();
+    for name in db.trait_required_item_names(trait_id).ok()?.iter() {
+        if db.impl_item_by_name(impl_def_id, name.clone()).ok()?.is_some() {
+            continue;
+        }
+        if let Some(function_id) = db.trait_function_by_name(trait_id, name.clone()).ok()? {
+            let declaration =
+                function_id.stable_ptr(db.upcast()).lookup(syntax_db).declaration(syntax_db);
+            let declaration = substitute_words(
+                &declaration.as_syntax_node().get_text_without_trivia(syntax_db),
+                &generic_args,
+            );
+            new_text += &format!(
+                "\n{indent}{declaration} {{\n{indent}    panic!(\"Not implemented\")\n{indent}}}"
+            );
+        } else if db.trait_type_by_name(trait_id, name.clone()).ok()?.is_some() {
+            new_text += &format!("\n{indent}type {name} = ();");
+        }
+    }
+    if new_text.is_empty() {
+        return None;
+    }
+    new_text += &closing;
+
+    let position = offset.position_in_file(db.upcast(), file_id)?.to_lsp();
+    Some(quick_fix(
+        "Implement missing trait items".to_owned(),
+        uri,
+        vec![TextEdit { range: Range::new(position, position), new_text }],
+    ))
+}
+
+/// Returns the line of the given offset in the file.
+fn line(db: &AnalysisDatabase, file_id: FileId, offset: TextOffset) -> Option<usize> {
+    Some(offset.position_in_file(db.upcast(), file_id)?.line)
+}
+
+/// Returns the names of the generic parameters of the trait, paired with the generic arguments
+/// the impl passes for them.
+fn trait_generic_args(
+    db: &AnalysisDatabase,
+    item_impl: &ast::ItemImpl,
+    trait_id: TraitId,
+) -> Vec<(String, String)> {
+    let syntax_db = db.upcast();
+    let Some(ast::PathSegment::WithGenericArgs(segment)) =
+        item_impl.trait_path(syntax_db).elements(syntax_db).last().cloned()
+    else {
+        return vec![];
+    };
+    let Ok(params) = db.trait_generic_params(trait_id) else { return vec![] };
+    let param_names = params.iter().map(|param| param.id().name(db.upcast())).collect::<Vec<_>>();
+    let mut result = vec![];
+    for (index, arg) in segment
+        .generic_args(syntax_db)
+        .generic_args(syntax_db)
+        .elements(syntax_db)
+        .iter()
+        .enumerate()
+    {
+        let (name, value) = match arg {
+            ast::GenericArg::Unnamed(arg) => {
+                let Some(Some(name)) = param_names.get(index).cloned() else { continue };
+                (name, arg.value(syntax_db))
+            }
+            ast::GenericArg::Named(arg) => {
+                (arg.name(syntax_db).text(syntax_db), arg.value(syntax_db))
+            }
+        };
+        result.push((name.to_string(), value.as_syntax_node().get_text_without_trivia(syntax_db)));
+    }
+    result
+}
+
+/// Replaces the whole words of the text that are keys of the replacements.
+fn substitute_words(text: &str, replacements: &[(String, String)]) -> String {
+    let mut result = String::new();
+    let mut word = String::new();
+    let flush = |word: &mut String, result: &mut String| {
+        match replacements.iter().find(|(name, _)| name == word) {
+            Some((_, value)) => result.push_str(value),
+            None => result.push_str(word),
+        }
+        word.clear();
+    };
+    for char in text.chars() {
+        if char.is_alphanumeric() || char == '_' {
+            word.push(char);
+        } else {
+            flush(&mut word, &mut result);
+            result.push(char);
+        }
+    }
+    flush(&mut word, &mut result);
+    result
+}
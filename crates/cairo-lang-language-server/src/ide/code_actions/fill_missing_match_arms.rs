@@ -0,0 +1,114 @@
+use cairo_lang_defs::ids::NamedLanguageElementId;
+use cairo_lang_semantic::db::SemanticGroup;
+use cairo_lang_semantic::items::function_with_body::SemanticExprLookup;
+use cairo_lang_semantic::lookup_item::LookupItemEx;
+use cairo_lang_semantic::types::peel_snapshots;
+use cairo_lang_semantic::{ConcreteTypeId, Expr, Pattern, TypeLongId};
+use cairo_lang_syntax::node::db::SyntaxGroup;
+use cairo_lang_syntax::node::kind::SyntaxKind;
+use cairo_lang_syntax::node::{SyntaxNode, TypedSyntaxNode, ast};
+use cairo_lang_utils::Upcast;
+use cairo_lang_utils::unordered_hash_set::UnorderedHashSet;
+use lsp_types::{CodeAction, Range, TextEdit, Url};
+
+use crate::ide::code_actions::{column, quick_fix};
+use crate::lang::db::{AnalysisDatabase, LsSemanticGroup, LsSyntaxGroup};
+use crate::lang::lsp::{LsProtoGroup, ToLsp};
+
+/// Create a Quick Fix code action adding arms for the enum variants not covered by a match, given
+/// a `MissingMatchArm` diagnostic.
+#[tracing::instrument(level = "trace", skip_all)]
+pub fn fill_missing_match_arms(
+    db: &AnalysisDatabase,
+    node: &SyntaxNode,
+    uri: Url,
+) -> Option<CodeAction> {
+    let syntax_db = db.upcast();
+    let file_id = db.file_for_url(&uri)?;
+    let match_node = db.first_ancestor_of_kind(node.clone(), SyntaxKind::ExprMatch)?;
+    let function_id = db.find_lookup_item(&match_node)?.function_with_body()?;
+    let match_ast = ast::ExprMatch::from_syntax_node(syntax_db, match_node);
+    let expr_id = db.lookup_expr_by_ptr(function_id, match_ast.stable_ptr().into()).ok()?;
+    let Expr::Match(semantic_match) = db.expr_semantic(function_id, expr_id) else { return None };
+
+    let matched_ty = db.expr_semantic(function_id, semantic_match.matched_expr).ty();
+    let (_, TypeLongId::Concrete(ConcreteTypeId::Enum(concrete_enum))) =
+        peel_snapshots(db, matched_ty)
+    else {
+        return None;
+    };
+    let enum_id = concrete_enum.enum_id(db);
+    let mut covered_variants = UnorderedHashSet::<_>::default();
+    for pattern_id in semantic_match.arms.iter().flat_map(|arm| arm.patterns.iter()) {
+        match db.pattern_semantic(function_id, *pattern_id) {
+            Pattern::EnumVariant(pattern) => {
+                covered_variants.insert(pattern.variant.id);
+            }
+            // All the variants are already covered.
+            Pattern::Otherwise(_) => return None,
+            _ => {}
+        }
+    }
+
+    // Use the same path to the variants as the existing arms.
+    let arms = match_ast.arms(syntax_db);
+    let arm_asts = arms.elements(syntax_db);
+    let enum_path = arm_asts
+        .first()
+        .and_then(|arm| {
+            let pattern_text =
+                arm.patterns(syntax_db).as_syntax_node().get_text_without_trivia(syntax_db);
+            let path = pattern_text.split('(').next()?.trim();
+            Some(path.rsplit_once("::")?.0.to_owned())
+        })
+        .unwrap_or_else(|| enum_id.name(db.upcast()).to_string());
+    let mut missing_arms = vec![];
+    for (name, variant_id) in db.enum_variants(enum_id).ok()?.iter() {
+        if covered_variants.contains(variant_id) {
+            continue;
+        }
+        let inner =
+            if db.variant_semantic(enum_id, *variant_id).ok()?.ty.is_unit(db) { "" } else { "(_)" };
+        missing_arms.push(format!("{enum_path}::{name}{inner} => panic!(\"Not implemented\"),"));
+    }
+    if missing_arms.is_empty() {
+        return None;
+    }
+
+    // Add the arms after the last arm, with the indentation of the first one.
+    let match_indent =
+        column(db, file_id, match_ast.as_syntax_node().span_start_without_trivia(syntax_db))?;
+    let (offset, indent, mut new_text) = match arm_asts.first() {
+        Some(first_arm) => {
+            let indent = column(
+                db,
+                file_id,
+                first_arm.as_syntax_node().span_start_without_trivia(syntax_db),
+            )?;
+            let has_trailing_comma = db
+                .get_children(arms.as_syntax_node())
+                .last()
+                .is_some_and(|child| child.kind(syntax_db) == SyntaxKind::TerminalComma);
+            let separator = if has_trailing_comma { "" } else { "," };
+            (arms.as_syntax_node().span_end_without_trivia(syntax_db), indent, separator.to_owned())
+        }
+        None => (
+            match_ast.lbrace(syntax_db).as_syntax_node().span_end_without_trivia(syntax_db),
+            match_indent + 4,
+            String::new(),
+        ),
+    };
+    let indent = " ".repeat(indent);
+    for arm in missing_arms {
+        new_text += &format!("\n{indent}{arm}");
+    }
+    if arm_asts.is_empty() {
+        new_text += &format!("\n{}", " ".repeat(match_indent));
+    }
+    let position = offset.position_in_file(db.upcast(), file_id)?.to_lsp();
+    Some(quick_fix(
+        "Add missing match arms".to_owned(),
+        uri,
+        vec![TextEdit { range: Range::new(position, position), new_text }],
+    ))
+}
@@ -1,15 +1,28 @@
-use cairo_lang_syntax::node::SyntaxNode;
+use std::collections::HashMap;
+
+use cairo_lang_defs::ids::ModuleId;
+use cairo_lang_filesystem::ids::FileId;
+use cairo_lang_filesystem::span::TextOffset;
+use cairo_lang_syntax::node::kind::SyntaxKind;
+use cairo_lang_syntax::node::{SyntaxNode, TypedStablePtr, TypedSyntaxNode, ast};
+use cairo_lang_utils::Upcast;
 use lsp_types::{
-    CodeAction, CodeActionOrCommand, CodeActionParams, CodeActionResponse, Diagnostic,
-    NumberOrString,
+    CodeAction, CodeActionKind, CodeActionOrCommand, CodeActionParams, CodeActionResponse,
+    Diagnostic, NumberOrString, Position, TextEdit, Url, WorkspaceEdit,
 };
 use tracing::debug;
 
-use crate::lang::db::{AnalysisDatabase, LsSyntaxGroup};
-use crate::lang::lsp::{LsProtoGroup, ToCairo};
+use crate::lang::db::{AnalysisDatabase, LsSemanticGroup, LsSyntaxGroup};
+use crate::lang::lsp::{LsProtoGroup, ToCairo, ToLsp};
 
+mod add_missing_derive;
+mod add_missing_import;
 mod add_missing_trait;
+mod add_ref_or_snapshot;
+mod clone_moved_variable;
 mod expand_macro;
+mod fill_missing_impl_items;
+mod fill_missing_match_arms;
 mod rename_unused_variable;
 
 /// Compute commands for a given text document and range. These commands are typically code fixes to
@@ -23,12 +36,18 @@ pub fn code_actions(params: CodeActionParams, db: &AnalysisDatabase) -> Option<C
     let mut actions = Vec::with_capacity(params.context.diagnostics.len());
     let file_id = db.file_for_url(&params.text_document.uri)?;
     let node = db.find_syntax_node_at_position(file_id, params.range.start.to_cairo())?;
-    for diagnostic in params.context.diagnostics.iter() {
-        actions.extend(
-            get_code_actions_for_diagnostic(db, &node, diagnostic, &params)
-                .into_iter()
-                .map(CodeActionOrCommand::from),
-        );
+    // Clients send all diagnostics overlapping the requested range, so only the ones at the
+    // requested position are fixed.
+    for diagnostic in params.context.diagnostics.iter().filter(|diagnostic| {
+        diagnostic.range.start <= params.range.start && params.range.start <= diagnostic.range.end
+    }) {
+        for action in get_code_actions_for_diagnostic(db, &node, diagnostic, &params) {
+            // Several diagnostics may lead to the same fix, e.g. one per missing match arm.
+            let action = CodeActionOrCommand::from(action);
+            if !actions.contains(&action) {
+                actions.push(action);
+            }
+        }
     }
     actions.extend(expand_macro::expand_macro(db, node).into_iter().map(CodeActionOrCommand::from));
 
@@ -75,9 +94,84 @@ fn get_code_actions_for_diagnostic(
             )]
         }
         "E0002" => add_missing_trait::add_missing_trait(db, node, params.text_document.uri.clone()),
+        "E0003" => {
+            add_missing_import::add_missing_import(db, node, params.text_document.uri.clone())
+        }
+        "E0004" => fill_missing_impl_items::fill_missing_impl_items(
+            db,
+            node,
+            params.text_document.uri.clone(),
+        )
+        .into_iter()
+        .collect(),
+        "E0005" => add_ref_or_snapshot::add_ref(db, node, params.text_document.uri.clone())
+            .into_iter()
+            .collect(),
+        "E0006" => add_ref_or_snapshot::add_snapshot(db, node, params.text_document.uri.clone())
+            .into_iter()
+            .collect(),
+        "E0007" => add_missing_derive::add_drop_derive(db, node).into_iter().collect(),
+        "E0008" => clone_moved_variable::clone_moved_variable(db, diagnostic).into_iter().collect(),
+        "E0009" => fill_missing_match_arms::fill_missing_match_arms(
+            db,
+            node,
+            params.text_document.uri.clone(),
+        )
+        .into_iter()
+        .collect(),
         code => {
             debug!("no code actions for diagnostic code: {code}");
             vec![]
         }
     }
 }
+
+/// Creates a Quick Fix code action applying the given edits to a single file.
+fn quick_fix(title: String, uri: Url, edits: Vec<TextEdit>) -> CodeAction {
+    CodeAction {
+        title,
+        kind: Some(CodeActionKind::QUICKFIX),
+        edit: Some(WorkspaceEdit {
+            changes: Some(HashMap::from_iter([(uri, edits)])),
+            document_changes: None,
+            change_annotations: None,
+        }),
+        diagnostics: None,
+        ..Default::default()
+    }
+}
+
+/// Returns the position of the first item of the module containing the node, where new `use`
+/// statements are inserted.
+fn module_start_position(
+    db: &AnalysisDatabase,
+    file_id: FileId,
+    node: &SyntaxNode,
+) -> Option<Position> {
+    let syntax_db = db.upcast();
+    let module_start_offset =
+        if let Some(ModuleId::Submodule(submodule_id)) = db.find_module_containing_node(node) {
+            let module_def_ast = submodule_id.stable_ptr(db.upcast()).lookup(syntax_db);
+            if let ast::MaybeModuleBody::Some(body) = module_def_ast.body(syntax_db) {
+                body.items(syntax_db).as_syntax_node().span_start_without_trivia(syntax_db)
+            } else {
+                TextOffset::default()
+            }
+        } else {
+            TextOffset::default()
+        };
+    Some(module_start_offset.position_in_file(db.upcast(), file_id)?.to_lsp())
+}
+
+/// Returns the identifier the node is a token of, if any.
+fn identifier_of(db: &AnalysisDatabase, node: &SyntaxNode) -> Option<ast::TerminalIdentifier> {
+    if node.kind(db.upcast()) != SyntaxKind::TokenIdentifier {
+        return None;
+    }
+    Some(ast::TerminalIdentifier::from_syntax_node(db.upcast(), node.parent()?))
+}
+
+/// Returns the column of the given offset in the file.
+fn column(db: &AnalysisDatabase, file_id: FileId, offset: TextOffset) -> Option<usize> {
+    Some(offset.position_in_file(db.upcast(), file_id)?.col)
+}
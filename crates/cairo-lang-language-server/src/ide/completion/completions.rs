@@ -1,29 +1,38 @@
 use cairo_lang_defs::db::DefsGroup;
+use std::collections::HashSet;
+
 use cairo_lang_defs::ids::{
     LanguageElementId, LookupItemId, ModuleFileId, ModuleId, NamedLanguageElementId,
-    TopLevelLanguageElementId, TraitFunctionId,
+    TopLevelLanguageElementId, TraitFunctionId, TraitId, TraitItemId,
 };
+use cairo_lang_doc::db::DocGroup;
 use cairo_lang_filesystem::db::FilesGroup;
 use cairo_lang_filesystem::ids::FileId;
 use cairo_lang_filesystem::span::TextOffset;
-use cairo_lang_semantic::corelib::{core_submodule, get_submodule};
+use cairo_lang_semantic::corelib::{core_submodule, deref_mut_trait, deref_trait, get_submodule};
 use cairo_lang_semantic::db::SemanticGroup;
 use cairo_lang_semantic::diagnostic::{NotFoundItemType, SemanticDiagnostics};
 use cairo_lang_semantic::expr::inference::InferenceId;
+use cairo_lang_semantic::expr::inference::canonic::ResultNoErrEx;
 use cairo_lang_semantic::items::function_with_body::SemanticExprLookup;
 use cairo_lang_semantic::items::us::SemanticUseEx;
 use cairo_lang_semantic::lookup_item::{HasResolverData, LookupItemEx};
 use cairo_lang_semantic::resolve::{ResolvedConcreteItem, ResolvedGenericItem, Resolver};
-use cairo_lang_semantic::types::peel_snapshots;
-use cairo_lang_semantic::{ConcreteTypeId, Pattern, TypeLongId};
+use cairo_lang_semantic::substitution::SemanticRewriter;
+use cairo_lang_semantic::types::{ImplTypeId, peel_snapshots};
+use cairo_lang_semantic::{
+    ConcreteTraitLongId, ConcreteTypeId, GenericArgumentId, Member, Pattern, TypeId, TypeLongId,
+};
 use cairo_lang_syntax::node::ast::PathSegment;
+use cairo_lang_syntax::node::ids::SyntaxStablePtrId;
 use cairo_lang_syntax::node::{TypedStablePtr, TypedSyntaxNode, ast};
-use cairo_lang_utils::{LookupIntern, Upcast};
+use cairo_lang_utils::{Intern, LookupIntern, Upcast};
 use lsp_types::{CompletionItem, CompletionItemKind, InsertTextFormat, Position, Range, TextEdit};
 use tracing::debug;
 
-use crate::ide::utils::find_methods_for_type;
+use crate::ide::utils::{find_methods_for_type, markdown_documentation};
 use crate::lang::db::{AnalysisDatabase, LsSemanticGroup};
+use crate::lang::inspect::origin::{MemberOrigin, find_item_origin};
 use crate::lang::lsp::ToLsp;
 
 #[tracing::instrument(level = "trace", skip_all)]
@@ -194,7 +203,7 @@ pub fn dot_completions(
     let function_with_body = lookup_item_id.function_with_body()?;
     let module_id = function_with_body.module_file_id(db.upcast()).0;
     let resolver_data = lookup_item_id.resolver_data(db).ok()?;
-    let resolver = Resolver::with_data(
+    let mut resolver = Resolver::with_data(
         db,
         resolver_data.as_ref().clone_with_inference_id(db, InferenceId::NoContext),
     );
@@ -226,6 +235,7 @@ pub fn dot_completions(
         TextOffset::default()
     };
     let position = offset.position_in_file(db.upcast(), file_id).unwrap().to_lsp();
+    let deref_chain = deref_chain(db, &mut resolver, ty, stable_ptr);
     let relevant_methods = find_methods_for_type(db, resolver, ty, stable_ptr);
 
     let mut completions = Vec::new();
@@ -237,22 +247,85 @@ pub fn dot_completions(
         completions.push(completion);
     }
 
-    // Find members of the type.
-    let (_, long_ty) = peel_snapshots(db, ty);
-    if let TypeLongId::Concrete(ConcreteTypeId::Struct(concrete_struct_id)) = long_ty {
-        db.concrete_struct_members(concrete_struct_id).ok()?.iter().for_each(|(name, member)| {
-            let completion = CompletionItem {
-                label: name.to_string(),
-                detail: Some(member.ty.format(db.upcast())),
-                kind: Some(CompletionItemKind::FIELD),
-                ..CompletionItem::default()
-            };
-            completions.push(completion);
-        });
+    // Find members of the type and of the types it dereferences to, e.g. storage members of
+    // a contract state.
+    let mut member_names = HashSet::new();
+    for ty in deref_chain {
+        let (_, long_ty) = peel_snapshots(db, ty);
+        let TypeLongId::Concrete(ConcreteTypeId::Struct(concrete_struct_id)) = long_ty else {
+            continue;
+        };
+        for (name, member) in db.concrete_struct_members(concrete_struct_id).ok()?.iter() {
+            if member_names.insert(name.clone()) {
+                completions.push(completion_for_member(db, name, member));
+            }
+        }
     }
     Some(completions)
 }
 
+/// Returns a completion item for a struct member.
+///
+/// Members generated by plugins are described by the user-written declarations they originate
+/// from.
+fn completion_for_member(db: &AnalysisDatabase, name: &str, member: &Member) -> CompletionItem {
+    let (detail, documentation) = match MemberOrigin::find(db, member.id) {
+        Some(origin) => (origin.ty(db), origin.documentation(db)),
+        None => (member.ty.format(db.upcast()), db.get_item_documentation(member.id.into())),
+    };
+    CompletionItem {
+        label: name.to_string(),
+        detail: Some(detail),
+        documentation: documentation.map(markdown_documentation),
+        kind: Some(CompletionItemKind::FIELD),
+        ..CompletionItem::default()
+    }
+}
+
+/// Returns the chain of types which the type dereferences to, starting with the type itself.
+fn deref_chain(
+    db: &AnalysisDatabase,
+    resolver: &mut Resolver<'_>,
+    ty: TypeId,
+    stable_ptr: SyntaxStablePtrId,
+) -> Vec<TypeId> {
+    let mut chain = vec![ty];
+    // Like member access, try the mutable dereference first.
+    let mut traits = vec![deref_mut_trait(db), deref_trait(db)];
+    while let Some(target) = traits.iter().find_map(|trait_id| {
+        deref_target(db, resolver, *trait_id, *chain.last().unwrap(), stable_ptr)
+    }) {
+        if chain.contains(&target) {
+            break;
+        }
+        chain.push(target);
+        traits = vec![deref_trait(db)];
+    }
+    chain
+}
+
+/// Returns the target type of the dereference trait implementation for the type, if there is one.
+fn deref_target(
+    db: &AnalysisDatabase,
+    resolver: &mut Resolver<'_>,
+    trait_id: TraitId,
+    ty: TypeId,
+    stable_ptr: SyntaxStablePtrId,
+) -> Option<TypeId> {
+    let target_type = db.trait_type_by_name(trait_id, "Target".into()).ok()??;
+    let lookup_context = resolver.impl_lookup_context();
+    let clone_data = &mut resolver.inference().clone_with_inference_id(db, InferenceId::NoContext);
+    let mut inference = clone_data.inference(db);
+    let concrete_trait_id =
+        ConcreteTraitLongId { trait_id, generic_args: vec![GenericArgumentId::Type(ty)] }
+            .intern(db);
+    let impl_id = inference.new_impl_var(concrete_trait_id, Some(stable_ptr), lookup_context);
+    let target = inference.reduce_impl_ty(ImplTypeId::new(impl_id, target_type, db)).ok()?;
+    inference.solve().ok()?;
+    let target = inference.rewrite(target).no_err();
+    (target.is_var_free(db) && !target.is_missing(db)).then_some(target)
+}
+
 /// Returns a completion item for a method.
 #[tracing::instrument(level = "trace", skip_all)]
 pub fn completion_for_method(
@@ -267,6 +340,15 @@ pub fn completion_for_method(
 
     // TODO(spapini): Add signature.
     let detail = trait_id.full_path(db.upcast());
+    // Methods generated by plugins, e.g. of dispatchers, are documented by their origin.
+    let definition_node =
+        trait_function.stable_ptr(db.upcast()).lookup(db.upcast()).as_syntax_node();
+    let documentation = find_item_origin(
+        db,
+        LookupItemId::TraitItem(TraitItemId::Function(trait_function)),
+        &definition_node,
+    )
+    .and_then(|origin| db.get_item_documentation(origin.into()));
     let mut additional_text_edits = vec![];
 
     // If the trait is not in scope, add a use statement.
@@ -284,6 +366,7 @@ pub fn completion_for_method(
         insert_text: Some(format!("{}($0)", name)),
         insert_text_format: Some(InsertTextFormat::SNIPPET),
         detail: Some(detail),
+        documentation: documentation.map(markdown_documentation),
         kind: Some(CompletionItemKind::METHOD),
         additional_text_edits: Some(additional_text_edits),
         ..CompletionItem::default()
@@ -0,0 +1,180 @@
+use cairo_lang_diagnostics::ToOption;
+use cairo_lang_filesystem::ids::FileId;
+use cairo_lang_filesystem::span::TextOffset;
+use cairo_lang_parser::db::ParserGroup;
+use cairo_lang_syntax::node::db::SyntaxGroup;
+use cairo_lang_syntax::node::kind::SyntaxKind;
+use cairo_lang_syntax::node::{SyntaxNode, TypedSyntaxNode, ast};
+use cairo_lang_utils::Upcast;
+use lsp_types::{FoldingRange, FoldingRangeKind, FoldingRangeParams};
+
+use crate::lang::db::AnalysisDatabase;
+use crate::lang::lsp::LsProtoGroup;
+
+/// Get the foldable regions of a text document.
+///
+/// Regions delimited by braces (module, impl and trait bodies, blocks, structs, enums, matches and
+/// `use` groups) fold up to the line before the closing brace, so it stays visible.
+/// Multiline match arms, attribute lists, runs of `use` items and comment blocks fold entirely.
+#[tracing::instrument(level = "debug", skip_all, fields(uri = %params.text_document.uri))]
+pub fn folding_ranges(
+    params: FoldingRangeParams,
+    db: &AnalysisDatabase,
+) -> Option<Vec<FoldingRange>> {
+    let file = db.file_for_url(&params.text_document.uri)?;
+    let syntax_db = db.upcast();
+    let root = db.file_syntax(file).to_option()?;
+
+    let mut folder = Folder { db, file, ranges: vec![] };
+    for node in root.descendants(syntax_db) {
+        match node.kind(syntax_db) {
+            SyntaxKind::MatchArm => {
+                let arm = ast::MatchArm::from_syntax_node(syntax_db, node.clone());
+                // Arms with block expressions are folded by their braces.
+                if !matches!(arm.expression(syntax_db), ast::Expr::Block(_)) {
+                    folder.fold_node(&node, None);
+                }
+            }
+            SyntaxKind::AttributeList => folder.fold_node(&node, None),
+            SyntaxKind::ModuleItemList => folder.fold_use_items(&node),
+            SyntaxKind::Trivia => folder.fold_comments(&node),
+            kind => {
+                let kind = (kind == SyntaxKind::UsePathMulti).then_some(FoldingRangeKind::Imports);
+                folder.fold_braces(&node, kind);
+            }
+        }
+    }
+
+    // Editors show a single folding region per line, so keep the largest one.
+    let mut ranges = folder.ranges;
+    ranges.sort_by_key(|range| (range.start_line, std::cmp::Reverse(range.end_line)));
+    ranges.dedup_by_key(|range| range.start_line);
+    Some(ranges)
+}
+
+/// Collects folding ranges of a single file.
+struct Folder<'a> {
+    db: &'a AnalysisDatabase,
+    file: FileId,
+    ranges: Vec<FoldingRange>,
+}
+
+impl Folder<'_> {
+    /// Returns the line of the given offset in the file.
+    fn line(&self, offset: TextOffset) -> Option<u32> {
+        Some(offset.position_in_file(self.db.upcast(), self.file)?.line as u32)
+    }
+
+    /// Adds a folding range between the given offsets, if it spans multiple lines.
+    fn fold(&mut self, start: TextOffset, end: TextOffset, kind: Option<FoldingRangeKind>) {
+        let (Some(start_line), Some(end_line)) = (self.line(start), self.line(end)) else {
+            return;
+        };
+        if end_line > start_line {
+            self.ranges.push(FoldingRange {
+                start_line,
+                start_character: None,
+                end_line,
+                end_character: None,
+                kind,
+                collapsed_text: None,
+            });
+        }
+    }
+
+    /// Folds the whole node.
+    fn fold_node(&mut self, node: &SyntaxNode, kind: Option<FoldingRangeKind>) {
+        let syntax_db = self.db.upcast();
+        self.fold(
+            node.span_start_without_trivia(syntax_db),
+            node.span_end_without_trivia(syntax_db),
+            kind,
+        );
+    }
+
+    /// Folds the contents of the node delimited by braces, if it has any, leaving the closing brace
+    /// visible.
+    fn fold_braces(&mut self, node: &SyntaxNode, kind: Option<FoldingRangeKind>) {
+        let syntax_db: &dyn SyntaxGroup = self.db.upcast();
+        let children = syntax_db.get_children(node.clone());
+        let lbrace =
+            children.iter().find(|child| child.kind(syntax_db) == SyntaxKind::TerminalLBrace);
+        let rbrace =
+            children.iter().rev().find(|child| child.kind(syntax_db) == SyntaxKind::TerminalRBrace);
+        let (Some(lbrace), Some(rbrace)) = (lbrace, rbrace) else { return };
+        let (Some(start_line), Some(rbrace_line)) = (
+            self.line(lbrace.span_start_without_trivia(syntax_db)),
+            self.line(rbrace.span_start_without_trivia(syntax_db)),
+        ) else {
+            return;
+        };
+        if rbrace_line > start_line + 1 {
+            self.ranges.push(FoldingRange {
+                start_line,
+                start_character: None,
+                end_line: rbrace_line - 1,
+                end_character: None,
+                kind,
+                collapsed_text: None,
+            });
+        }
+    }
+
+    /// Folds the runs of consecutive `use` items in a module.
+    fn fold_use_items(&mut self, items: &SyntaxNode) {
+        let syntax_db: &dyn SyntaxGroup = self.db.upcast();
+        let mut run: Option<(TextOffset, TextOffset)> = None;
+        for item in syntax_db.get_children(items.clone()).iter() {
+            if item.kind(syntax_db) == SyntaxKind::ItemUse {
+                let end = item.span_end_without_trivia(syntax_db);
+                run = Some(match run {
+                    Some((start, _)) => (start, end),
+                    None => (item.span_start_without_trivia(syntax_db), end),
+                });
+            } else if let Some((start, end)) = run.take() {
+                self.fold(start, end, Some(FoldingRangeKind::Imports));
+            }
+        }
+        if let Some((start, end)) = run {
+            self.fold(start, end, Some(FoldingRangeKind::Imports));
+        }
+    }
+
+    /// Folds the blocks of comments of the same kind on consecutive lines.
+    fn fold_comments(&mut self, trivia: &SyntaxNode) {
+        let syntax_db: &dyn SyntaxGroup = self.db.upcast();
+        // The kind, start and end of the current block, and the newlines after its last comment.
+        let mut block: Option<(SyntaxKind, TextOffset, TextOffset)> = None;
+        let mut newlines = 0;
+        for trivium in syntax_db.get_children(trivia.clone()).iter() {
+            let kind = trivium.kind(syntax_db);
+            match kind {
+                SyntaxKind::TokenSingleLineComment
+                | SyntaxKind::TokenSingleLineDocComment
+                | SyntaxKind::TokenSingleLineInnerComment => {
+                    let span = trivium.span(syntax_db);
+                    block = match block {
+                        Some((block_kind, start, _)) if block_kind == kind && newlines <= 1 => {
+                            Some((kind, start, span.end))
+                        }
+                        _ => {
+                            self.fold_comment_block(block);
+                            Some((kind, span.start, span.end))
+                        }
+                    };
+                    newlines = 0;
+                }
+                SyntaxKind::TokenNewline => newlines += 1,
+                SyntaxKind::TokenWhitespace => {}
+                _ => self.fold_comment_block(block.take()),
+            }
+        }
+        self.fold_comment_block(block);
+    }
+
+    fn fold_comment_block(&mut self, block: Option<(SyntaxKind, TextOffset, TextOffset)>) {
+        if let Some((_, start, end)) = block {
+            self.fold(start, end, Some(FoldingRangeKind::Comment));
+        }
+    }
+}
@@ -42,7 +42,24 @@ pub fn definition(
             }
             md
         }
-        SymbolDef::Member(MemberDef { member, structure }) => {
+        SymbolDef::Member(MemberDef { origin: Some(origin), structure, .. }) => {
+            let mut md = String::new();
+
+            // Show the declaration written by the user, instead of the generated struct.
+            md += &fenced_code_block(&structure.definition_path(db));
+            md += &fenced_code_block(&format!(
+                "{}\n{}",
+                origin.structure_signature(db),
+                origin.signature(db)
+            ));
+
+            if let Some(doc) = origin.documentation(db) {
+                md += RULE;
+                md += &doc;
+            }
+            md
+        }
+        SymbolDef::Member(MemberDef { member, structure, origin: None }) => {
             let mut md = String::new();
 
             // Signature is the signature of the struct, so it makes sense that the definition
@@ -0,0 +1,233 @@
+use cairo_lang_defs::ids::{FunctionWithBodyId, NamedLanguageElementId};
+use cairo_lang_filesystem::ids::FileId;
+use cairo_lang_filesystem::span::{TextOffset, TextSpan};
+use cairo_lang_parser::db::ParserGroup;
+use cairo_lang_semantic::db::SemanticGroup;
+use cairo_lang_semantic::items::functions::GenericFunctionId;
+use cairo_lang_semantic::items::imp::ImplLongId;
+use cairo_lang_semantic::lookup_item::LookupItemEx;
+use cairo_lang_semantic::{Arenas, Expr, ExprFunctionCall, ExprFunctionCallArg, Pattern, TypeId};
+use cairo_lang_syntax::node::ids::SyntaxStablePtrId;
+use cairo_lang_syntax::node::kind::SyntaxKind;
+use cairo_lang_syntax::node::{SyntaxNode, TypedStablePtr, TypedSyntaxNode, ast};
+use cairo_lang_utils::{LookupIntern, Upcast};
+use lsp_types::{InlayHint, InlayHintKind, InlayHintLabel, InlayHintParams};
+
+use crate::config::InlayHintsConfig;
+use crate::ide::utils::called_function_name;
+use crate::lang::db::{AnalysisDatabase, LsSemanticGroup, LsSyntaxGroup};
+use crate::lang::lsp::{LsProtoGroup, ToCairo, ToLsp};
+
+/// Get the inlay hints of a given range of a text document.
+#[tracing::instrument(level = "debug", skip_all, fields(uri = %params.text_document.uri))]
+pub fn inlay_hints(
+    params: InlayHintParams,
+    db: &AnalysisDatabase,
+    config: &InlayHintsConfig,
+) -> Option<Vec<InlayHint>> {
+    let file = db.file_for_url(&params.text_document.uri)?;
+    let range = params.range.to_cairo().offset_in_file(db.upcast(), file)?;
+
+    let mut collector = HintsCollector { db, file, range, hints: vec![] };
+    for function_id in functions_in_file(db, file) {
+        collector.collect_function(function_id, config);
+    }
+    collector.hints.sort_by_key(|hint| (hint.position.line, hint.position.character));
+    Some(collector.hints)
+}
+
+/// Returns the functions with bodies whose definitions are written in the given file.
+fn functions_in_file(db: &AnalysisDatabase, file: FileId) -> Vec<FunctionWithBodyId> {
+    let Ok(syntax) = db.file_syntax(file) else { return vec![] };
+    syntax
+        .descendants(db.upcast())
+        .filter(|node| {
+            matches!(node.kind(db), SyntaxKind::FunctionWithBody | SyntaxKind::TraitItemFunction)
+        })
+        .filter_map(|node| db.find_lookup_item(&node)?.function_with_body())
+        .collect()
+}
+
+/// Collects the inlay hints of function bodies in a range of a file.
+struct HintsCollector<'a> {
+    db: &'a AnalysisDatabase,
+    file: FileId,
+    range: TextSpan,
+    hints: Vec<InlayHint>,
+}
+
+impl HintsCollector<'_> {
+    /// Collects the enabled hints of the given function body.
+    fn collect_function(&mut self, function_id: FunctionWithBodyId, config: &InlayHintsConfig) {
+        let Ok(body) = self.db.function_body(function_id) else { return };
+        if config.type_hints {
+            for (_, pattern) in body.arenas.patterns.iter() {
+                if let Pattern::Variable(variable) = pattern {
+                    self.collect_let_type(variable.stable_ptr, variable.var.ty);
+                }
+            }
+        }
+        for (_, expr) in body.arenas.exprs.iter() {
+            match expr {
+                Expr::FunctionCall(call) => {
+                    if config.parameter_hints {
+                        self.collect_parameter_names(&body.arenas, call);
+                    }
+                    if config.impl_hints {
+                        self.collect_impl(call);
+                    }
+                }
+                Expr::Snapshot(snapshot) if config.snapshot_hints => {
+                    self.collect_implicit_snapshot(snapshot.stable_ptr, "@");
+                }
+                Expr::Desnap(desnap) if config.snapshot_hints => {
+                    self.collect_implicit_snapshot(desnap.stable_ptr, "*");
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Adds a hint with the type of a variable bound by a `let` statement without a type clause.
+    fn collect_let_type(&mut self, pattern_ptr: ast::PatternPtr, ty: TypeId) {
+        let db = self.db;
+        let Some(node) = self.lookup_in_file(pattern_ptr.untyped()) else { return };
+        let Some(statement) = db.first_ancestor_of_kind(node.clone(), SyntaxKind::StatementLet)
+        else {
+            return;
+        };
+        let statement = ast::StatementLet::from_syntax_node(db, statement);
+        let in_let_pattern = statement
+            .pattern(db)
+            .as_syntax_node()
+            .span_without_trivia(db)
+            .contains(node.span_without_trivia(db));
+        if !in_let_pattern
+            || !matches!(statement.type_clause(db), ast::OptionTypeClause::Empty(_))
+            || ty.is_missing(db)
+        {
+            return;
+        }
+        let position = node.span_without_trivia(db).end;
+        let label = format!(": {}", short_type_name(&ty.format(db)));
+        self.push(position, label, Some(InlayHintKind::TYPE), false, false);
+    }
+
+    /// Adds hints with the names of parameters before the positional arguments of a call.
+    fn collect_parameter_names(&mut self, arenas: &Arenas, call: &ExprFunctionCall) {
+        let db = self.db;
+        let Ok(signature) = db.concrete_function_signature(call.function) else { return };
+        for (arg, param) in call.args.iter().zip(signature.params.iter()) {
+            let arg_ptr = match arg {
+                ExprFunctionCallArg::Reference(member_path) => member_path.stable_ptr(),
+                ExprFunctionCallArg::Value(expr_id) => arenas.exprs[*expr_id].stable_ptr(),
+            };
+            let Some(node) = self.lookup_in_file(arg_ptr.untyped()) else { continue };
+            // Only positional arguments of calls are hinted, not e.g. method receivers or
+            // operands of operators.
+            let Some(clause) =
+                node.parent().filter(|parent| parent.kind(db) == SyntaxKind::ArgClauseUnnamed)
+            else {
+                continue;
+            };
+            let Some(arg_node) = clause.parent() else { continue };
+            let text = node.get_text_without_trivia(db);
+            if param.name.starts_with('_') || text == param.name {
+                continue;
+            }
+            let position = arg_node.span_without_trivia(db).start;
+            let label = format!("{}:", param.name);
+            self.push(position, label, Some(InlayHintKind::PARAMETER), false, true);
+        }
+    }
+
+    /// Adds a hint with the impl chosen for a call of a trait function, after the function name.
+    fn collect_impl(&mut self, call: &ExprFunctionCall) {
+        let db = self.db;
+        let GenericFunctionId::Impl(impl_function) =
+            call.function.get_concrete(db).generic_function
+        else {
+            return;
+        };
+        let ImplLongId::Concrete(concrete_impl) = impl_function.impl_id.lookup_intern(db) else {
+            return;
+        };
+        let Some(node) = self.lookup_in_file(call.stable_ptr.untyped()) else { return };
+        let Some(name) = called_function_name(db, node) else { return };
+        let position = name.as_syntax_node().span_without_trivia(db).end;
+        let label = format!("<impl {}>", concrete_impl.impl_def_id(db).name(db));
+        self.push(position, label, None, false, false);
+    }
+
+    /// Adds a hint for a snapshot taken or desnapped implicitly, e.g. of the receiver of a method
+    /// taking a snapshot. The hint is the unary `operator` that would make it explicit.
+    fn collect_implicit_snapshot(&mut self, expr_ptr: ast::ExprPtr, operator: &str) {
+        let db = self.db;
+        let Some(node) = self.lookup_in_file(expr_ptr.untyped()) else { return };
+        let is_explicit = node.kind(db) == SyntaxKind::ExprUnary
+            && ast::ExprUnary::from_syntax_node(db, node.clone())
+                .op(db)
+                .as_syntax_node()
+                .get_text_without_trivia(db)
+                == operator;
+        if !is_explicit {
+            let position = node.span_without_trivia(db).start;
+            self.push(position, operator.into(), None, false, false);
+        }
+    }
+
+    /// Looks up the node of a stable pointer, if it is in the processed file, i.e. not in code
+    /// generated by plugins.
+    fn lookup_in_file(&self, ptr: SyntaxStablePtrId) -> Option<SyntaxNode> {
+        (ptr.file_id(self.db) == self.file).then(|| ptr.lookup(self.db))
+    }
+
+    /// Adds a hint at the given offset, if it is in the requested range.
+    fn push(
+        &mut self,
+        offset: TextOffset,
+        label: String,
+        kind: Option<InlayHintKind>,
+        padding_left: bool,
+        padding_right: bool,
+    ) {
+        if offset < self.range.start || self.range.end < offset {
+            return;
+        }
+        let Some(position) = offset.position_in_file(self.db.upcast(), self.file) else { return };
+        self.hints.push(InlayHint {
+            position: position.to_lsp(),
+            label: InlayHintLabel::String(label),
+            kind,
+            text_edits: None,
+            tooltip: None,
+            padding_left: Some(padding_left),
+            padding_right: Some(padding_right),
+            data: None,
+        });
+    }
+}
+
+/// Shortens a formatted type by removing the paths of the items, e.g.
+/// `core::array::Array::<core::integer::u32>` becomes `Array<u32>`.
+fn short_type_name(ty: &str) -> String {
+    let mut result = String::new();
+    let mut segment = String::new();
+    let mut chars = ty.chars().peekable();
+    while let Some(char) = chars.next() {
+        if char.is_alphanumeric() || char == '_' {
+            segment.push(char);
+        } else if char == ':' && chars.peek() == Some(&':') {
+            chars.next();
+            // Keep the name of a generic type, e.g. `Array` in `core::array::Array::<u32>`.
+            if chars.peek() != Some(&'<') {
+                segment.clear();
+            }
+        } else {
+            result.push_str(&segment);
+            segment.clear();
+            result.push(char);
+        }
+    }
+    result + &segment
+}
@@ -1,8 +1,14 @@
 pub mod code_actions;
 pub mod completion;
+pub mod folding_range;
 pub mod formatter;
 pub mod hover;
+pub mod inlay_hints;
 pub mod macros;
 pub mod navigation;
+pub mod rename;
+pub mod selection_range;
 pub mod semantic_highlighting;
+pub mod signature_help;
+pub mod testing;
 pub mod utils;
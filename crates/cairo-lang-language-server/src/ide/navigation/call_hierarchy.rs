@@ -0,0 +1,282 @@
+use cairo_lang_defs::db::DefsGroup;
+use cairo_lang_defs::ids::{
+    FunctionWithBodyId, LanguageElementId, ModuleId, NamedLanguageElementId,
+    TopLevelLanguageElementId,
+};
+use cairo_lang_filesystem::ids::CrateId;
+use cairo_lang_lowering::db::LoweringGroup;
+use cairo_lang_lowering::ids::FunctionWithBodyLongId;
+use cairo_lang_lowering::{DependencyType, Statement, ids as lowering_ids};
+use cairo_lang_semantic::db::SemanticGroup;
+use cairo_lang_semantic::lookup_item::LookupItemEx;
+use cairo_lang_syntax::node::ids::SyntaxStablePtrId;
+use cairo_lang_syntax::node::kind::SyntaxKind;
+use cairo_lang_syntax::node::{TypedStablePtr, TypedSyntaxNode};
+use cairo_lang_utils::ordered_hash_set::OrderedHashSet;
+use cairo_lang_utils::{Intern, LookupIntern, Upcast};
+use lsp_types::{
+    CallHierarchyIncomingCall, CallHierarchyIncomingCallsParams, CallHierarchyItem,
+    CallHierarchyOutgoingCall, CallHierarchyOutgoingCallsParams, CallHierarchyPrepareParams,
+    Location, Range, SymbolKind,
+};
+
+use crate::ide::utils::{called_function_name, crates_using, location_in_user_code};
+use crate::lang::db::{AnalysisDatabase, LsSemanticGroup, LsSyntaxGroup};
+use crate::lang::inspect::defs::SymbolDef;
+use crate::lang::lsp::{LsProtoGroup, ToCairo, ToLsp};
+
+/// Get the call hierarchy item of the function at a given text document position.
+#[tracing::instrument(
+    level = "debug",
+    skip_all,
+    fields(uri = %params.text_document_position_params.text_document.uri)
+)]
+pub fn prepare_call_hierarchy(
+    params: CallHierarchyPrepareParams,
+    db: &AnalysisDatabase,
+) -> Option<Vec<CallHierarchyItem>> {
+    let file = db.file_for_url(&params.text_document_position_params.text_document.uri)?;
+    let position = params.text_document_position_params.position.to_cairo();
+    let function_id = function_at_position(db, file, position)?;
+    Some(vec![call_hierarchy_item(db, function_id)?])
+}
+
+/// Get the functions calling the function of a call hierarchy item, with the locations of the
+/// calls.
+#[tracing::instrument(level = "debug", skip_all, fields(item = %params.item.name))]
+pub fn incoming_calls(
+    params: CallHierarchyIncomingCallsParams,
+    db: &AnalysisDatabase,
+) -> Option<Vec<CallHierarchyIncomingCall>> {
+    let callee = function_of_item(db, &params.item)?;
+    let callee_crate = callee.parent_module(db).owning_crate(db);
+    let calls = crates_using(db, callee_crate)
+        .into_iter()
+        .flat_map(|crate_id| functions_in_crate(db, crate_id))
+        .filter(|caller| direct_callees(db, *caller).contains(&callee))
+        .filter_map(|caller| {
+            let from = call_hierarchy_item(db, caller)?;
+            let from_ranges = call_ranges(db, caller, &from, |function| function == callee);
+            Some(CallHierarchyIncomingCall { from, from_ranges })
+        })
+        .collect();
+    Some(calls)
+}
+
+/// Get the functions called by the function of a call hierarchy item, with the locations of the
+/// calls.
+#[tracing::instrument(level = "debug", skip_all, fields(item = %params.item.name))]
+pub fn outgoing_calls(
+    params: CallHierarchyOutgoingCallsParams,
+    db: &AnalysisDatabase,
+) -> Option<Vec<CallHierarchyOutgoingCall>> {
+    let caller = function_of_item(db, &params.item)?;
+    let calls = direct_callees(db, caller)
+        .into_iter()
+        .filter_map(|callee| {
+            let to = call_hierarchy_item(db, callee)?;
+            let from_ranges = call_ranges(db, caller, &params.item, |function| function == callee);
+            Some(CallHierarchyOutgoingCall { to, from_ranges })
+        })
+        .collect();
+    Some(calls)
+}
+
+/// Returns the function with a body whose name is at the given position, either in its
+/// definition or in a reference to it.
+fn function_at_position(
+    db: &AnalysisDatabase,
+    file: cairo_lang_filesystem::ids::FileId,
+    position: cairo_lang_filesystem::span::TextPosition,
+) -> Option<FunctionWithBodyId> {
+    let identifier = db.find_identifier_at_position(file, position)?;
+    let parent = identifier.as_syntax_node().parent()?;
+    // The name in a declaration of a function is not resolved as a reference to it.
+    if parent.kind(db) == SyntaxKind::FunctionDeclaration {
+        return db.find_lookup_item(&parent)?.function_with_body();
+    }
+    let SymbolDef::Item(item) = SymbolDef::find(db, &identifier)? else { return None };
+    item.lookup_item_id().function_with_body()
+}
+
+/// Returns the function of a call hierarchy item, by its name at the start of its selection range.
+fn function_of_item(db: &AnalysisDatabase, item: &CallHierarchyItem) -> Option<FunctionWithBodyId> {
+    let file = db.file_for_url(&item.uri)?;
+    function_at_position(db, file, item.selection_range.start.to_cairo())
+}
+
+/// Builds the call hierarchy item of a function.
+fn call_hierarchy_item(
+    db: &AnalysisDatabase,
+    function_id: FunctionWithBodyId,
+) -> Option<CallHierarchyItem> {
+    let selection = location_in_user_code(db, function_name_ptr(db, function_id))?;
+    let declaration = location_in_user_code(db, function_id.untyped_stable_ptr(db))?;
+    // Functions generated by plugins may originate from user code which doesn't contain the name.
+    let range = if declaration.uri == selection.uri
+        && declaration.range.start <= selection.range.start
+        && selection.range.end <= declaration.range.end
+    {
+        declaration.range
+    } else {
+        selection.range
+    };
+    let (kind, detail) = match function_id {
+        FunctionWithBodyId::Free(id) => (SymbolKind::FUNCTION, id.parent_module(db).full_path(db)),
+        FunctionWithBodyId::Impl(id) => (SymbolKind::METHOD, id.impl_def_id(db).full_path(db)),
+        FunctionWithBodyId::Trait(id) => (SymbolKind::METHOD, id.trait_id(db).full_path(db)),
+    };
+    Some(CallHierarchyItem {
+        name: function_id.name(db).into(),
+        kind,
+        tags: None,
+        detail: Some(detail),
+        uri: selection.uri,
+        range,
+        selection_range: selection.range,
+        data: None,
+    })
+}
+
+/// Returns the name in the declaration of a function.
+fn function_name_ptr(db: &AnalysisDatabase, function_id: FunctionWithBodyId) -> SyntaxStablePtrId {
+    let syntax_db = db.upcast();
+    let name = match function_id {
+        FunctionWithBodyId::Free(id) => {
+            id.stable_ptr(db).lookup(syntax_db).declaration(syntax_db).name(syntax_db)
+        }
+        FunctionWithBodyId::Impl(id) => {
+            id.stable_ptr(db).lookup(syntax_db).declaration(syntax_db).name(syntax_db)
+        }
+        FunctionWithBodyId::Trait(id) => {
+            id.stable_ptr(db).lookup(syntax_db).declaration(syntax_db).name(syntax_db)
+        }
+    };
+    name.stable_ptr().untyped()
+}
+
+/// Returns the functions with bodies defined in the modules of a crate.
+fn functions_in_crate(db: &AnalysisDatabase, crate_id: CrateId) -> Vec<FunctionWithBodyId> {
+    db.crate_modules(crate_id)
+        .iter()
+        .flat_map(|module_id| functions_in_module(db, *module_id))
+        .collect()
+}
+
+/// Returns the free functions, impl functions and trait functions with a default body of a module.
+fn functions_in_module(db: &AnalysisDatabase, module_id: ModuleId) -> Vec<FunctionWithBodyId> {
+    let mut functions = vec![];
+    if let Ok(free_functions) = db.module_free_functions_ids(module_id) {
+        functions.extend(free_functions.iter().copied().map(FunctionWithBodyId::Free));
+    }
+    for impl_def_id in db.module_impls_ids(module_id).iter().flat_map(|ids| ids.iter()) {
+        if let Ok(impl_functions) = db.impl_functions(*impl_def_id) {
+            functions.extend(impl_functions.values().copied().map(FunctionWithBodyId::Impl));
+        }
+    }
+    for trait_id in db.module_traits_ids(module_id).iter().flat_map(|ids| ids.iter()) {
+        if let Ok(trait_functions) = db.trait_functions(*trait_id) {
+            functions.extend(
+                trait_functions
+                    .values()
+                    .copied()
+                    .filter(|id| matches!(db.trait_function_body(*id), Ok(Some(_))))
+                    .map(FunctionWithBodyId::Trait),
+            );
+        }
+    }
+    functions
+}
+
+/// Returns the lowering functions of a function with a body: the function itself and the
+/// functions generated from it, e.g. for its loops.
+fn lowering_functions(
+    db: &AnalysisDatabase,
+    function_id: FunctionWithBodyId,
+) -> Vec<lowering_ids::FunctionWithBodyId> {
+    let mut functions = OrderedHashSet::<_>::default();
+    let mut stack = vec![FunctionWithBodyLongId::Semantic(function_id).intern(db)];
+    while let Some(current) = stack.pop() {
+        if !functions.insert(current) {
+            continue;
+        }
+        let Ok(callees) =
+            db.function_with_body_direct_function_with_body_callees(current, DependencyType::Call)
+        else {
+            continue;
+        };
+        stack.extend(callees.into_iter().filter(|callee| {
+            matches!(
+                callee.lookup_intern(db),
+                FunctionWithBodyLongId::Generated { parent, .. } if parent == function_id
+            )
+        }));
+    }
+    functions.into_iter().collect()
+}
+
+/// Returns the functions with bodies directly called by a function, according to the lowering
+/// call graph.
+fn direct_callees(
+    db: &AnalysisDatabase,
+    function_id: FunctionWithBodyId,
+) -> OrderedHashSet<FunctionWithBodyId> {
+    lowering_functions(db, function_id)
+        .into_iter()
+        .filter_map(|function| {
+            db.function_with_body_direct_function_with_body_callees(function, DependencyType::Call)
+                .ok()
+        })
+        .flat_map(|callees| callees.into_iter().collect::<Vec<_>>())
+        .map(|callee| callee.base_semantic_function(db))
+        .filter(|callee| *callee != function_id)
+        .collect()
+}
+
+/// Returns the ranges of the names of the calls of the selected callees in a function, which are
+/// in the document of the given call hierarchy item of the function.
+fn call_ranges(
+    db: &AnalysisDatabase,
+    caller: FunctionWithBodyId,
+    caller_item: &CallHierarchyItem,
+    is_selected: impl Fn(FunctionWithBodyId) -> bool,
+) -> Vec<Range> {
+    let mut ranges = vec![];
+    for function in lowering_functions(db, caller) {
+        let Ok(lowered) = db.function_with_body_lowering(function) else { continue };
+        for (_, block) in lowered.blocks.iter() {
+            for statement in &block.statements {
+                let Statement::Call(call) = statement else { continue };
+                let Ok(Some(callee)) = call.function.body(db) else { continue };
+                if !is_selected(callee.function_with_body_id(db).base_semantic_function(db)) {
+                    continue;
+                }
+                let node = call.location.lookup_intern(db).stable_location.syntax_node(db);
+                let ptr = called_function_name(db, node.clone())
+                    .map(|name| name.stable_ptr().untyped())
+                    .unwrap_or_else(|| node.stable_ptr());
+                let Some(location) = call_location(db, ptr) else { continue };
+                if location.uri == caller_item.uri && !ranges.contains(&location.range) {
+                    ranges.push(location.range);
+                }
+            }
+        }
+    }
+    ranges
+}
+
+/// Returns the location of a call site, where calls in code generated by inline macros are located
+/// at the whole macro call.
+fn call_location(db: &AnalysisDatabase, ptr: SyntaxStablePtrId) -> Option<Location> {
+    let mut location = location_in_user_code(db, ptr)?;
+    let file = db.file_for_url(&location.uri)?;
+    if file == ptr.file_id(db.upcast()) {
+        return Some(location);
+    }
+    let node = db.find_syntax_node_at_position(file, location.range.start.to_cairo())?;
+    if let Some(inline_macro) = db.first_ancestor_of_kind(node, SyntaxKind::ExprInlineMacro) {
+        let span = inline_macro.span_without_trivia(db.upcast());
+        location.range = span.position_in_file(db.upcast(), file)?.to_lsp();
+    }
+    Some(location)
+}
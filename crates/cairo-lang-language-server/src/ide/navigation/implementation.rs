@@ -0,0 +1,74 @@
+use cairo_lang_defs::db::DefsGroup;
+use cairo_lang_defs::ids::{
+    ImplDefId, LanguageElementId, LookupItemId, ModuleItemId, TraitId, TraitItemId,
+};
+use cairo_lang_semantic::db::SemanticGroup;
+use cairo_lang_syntax::node::{TypedStablePtr, TypedSyntaxNode};
+use cairo_lang_utils::Upcast;
+use lsp_types::request::{GotoImplementationParams, GotoImplementationResponse};
+
+use crate::ide::utils::{crates_using, location_in_user_code};
+use crate::lang::db::{AnalysisDatabase, LsSyntaxGroup};
+use crate::lang::inspect::defs::SymbolDef;
+use crate::lang::lsp::{LsProtoGroup, ToCairo};
+
+/// Get the locations of the impls implementing the trait, or the trait function, at a given text
+/// document position.
+#[tracing::instrument(
+    level = "debug",
+    skip_all,
+    fields(uri = %params.text_document_position_params.text_document.uri)
+)]
+pub fn goto_implementation(
+    params: GotoImplementationParams,
+    db: &AnalysisDatabase,
+) -> Option<GotoImplementationResponse> {
+    let file = db.file_for_url(&params.text_document_position_params.text_document.uri)?;
+    let position = params.text_document_position_params.position.to_cairo();
+    let identifier = db.find_identifier_at_position(file, position)?;
+    let SymbolDef::Item(item) = SymbolDef::find(db, &identifier)? else { return None };
+
+    let syntax_db = db.upcast();
+    let stable_ptrs: Vec<_> = match item.lookup_item_id() {
+        LookupItemId::ModuleItem(ModuleItemId::Trait(trait_id)) => impls_of_trait(db, trait_id)
+            .into_iter()
+            .map(|impl_def_id| {
+                impl_def_id.stable_ptr(db).lookup(syntax_db).name(syntax_db).stable_ptr().untyped()
+            })
+            .collect(),
+        LookupItemId::TraitItem(TraitItemId::Function(trait_function_id)) => {
+            impls_of_trait(db, trait_function_id.trait_id(db))
+                .into_iter()
+                .filter_map(|impl_def_id| {
+                    db.impl_function_by_trait_function(impl_def_id, trait_function_id).ok()?
+                })
+                .map(|impl_function_id| {
+                    let function = impl_function_id.stable_ptr(db).lookup(syntax_db);
+                    function.declaration(syntax_db).name(syntax_db).stable_ptr().untyped()
+                })
+                .collect()
+        }
+        _ => return None,
+    };
+
+    let mut locations = vec![];
+    for location in stable_ptrs.into_iter().filter_map(|ptr| location_in_user_code(db, ptr)) {
+        // Impls generated by plugins, e.g. derives, may originate from the same user code.
+        if !locations.contains(&location) {
+            locations.push(location);
+        }
+    }
+    Some(GotoImplementationResponse::Array(locations))
+}
+
+/// Returns the impls of the given trait, in the crates which may use it.
+fn impls_of_trait(db: &AnalysisDatabase, trait_id: TraitId) -> Vec<ImplDefId> {
+    let trait_crate = trait_id.parent_module(db).owning_crate(db);
+    crates_using(db, trait_crate)
+        .into_iter()
+        .flat_map(|crate_id| db.crate_modules(crate_id).to_vec())
+        .filter_map(|module_id| db.module_impls_ids(module_id).ok())
+        .flat_map(|impls| impls.to_vec())
+        .filter(|impl_def_id| db.impl_def_trait(*impl_def_id).ok() == Some(trait_id))
+        .collect()
+}
@@ -1 +1,6 @@
+pub mod call_hierarchy;
 pub mod goto_definition;
+pub mod implementation;
+pub mod references;
+pub mod symbols;
+pub mod type_definition;
@@ -0,0 +1,30 @@
+use cairo_lang_utils::Upcast;
+use lsp_types::{Location, ReferenceParams};
+
+use crate::lang::db::AnalysisDatabase;
+use crate::lang::inspect::references::find_references;
+use crate::lang::lsp::{LsProtoGroup, ToCairo, ToLsp};
+
+/// Get the locations of all references to a symbol at a given text document position.
+#[tracing::instrument(
+    level = "debug",
+    skip_all,
+    fields(uri = %params.text_document_position.text_document.uri)
+)]
+pub fn references(params: ReferenceParams, db: &AnalysisDatabase) -> Option<Vec<Location>> {
+    let file = db.file_for_url(&params.text_document_position.text_document.uri)?;
+    let position = params.text_document_position.position.to_cairo();
+    let symbol = find_references(db, file, position)?;
+
+    let include_declaration = params.context.include_declaration;
+    let locations = symbol
+        .references
+        .into_iter()
+        .filter(|reference| include_declaration || !reference.is_definition)
+        .filter_map(|reference| {
+            let range = reference.span.position_in_file(db.upcast(), reference.file)?.to_lsp();
+            Some(Location { uri: db.url_for_file(reference.file), range })
+        })
+        .collect();
+    Some(locations)
+}
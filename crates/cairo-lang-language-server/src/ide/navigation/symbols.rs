@@ -0,0 +1,344 @@
+use cairo_lang_defs::db::DefsGroup;
+use cairo_lang_defs::ids::{LanguageElementId, ModuleId, ModuleItemId};
+use cairo_lang_filesystem::db::FilesGroup;
+use cairo_lang_filesystem::ids::{FileId, FileLongId};
+use cairo_lang_starknet::plugin::consts::{
+    COMPONENT_ATTR, CONTRACT_ATTR, EVENT_ATTR, STORAGE_ATTR,
+};
+use cairo_lang_syntax::node::helpers::QueryAttrs;
+use cairo_lang_syntax::node::{Terminal, TypedStablePtr, TypedSyntaxNode, ast};
+use cairo_lang_utils::ordered_hash_set::OrderedHashSet;
+use cairo_lang_utils::{LookupIntern, Upcast};
+use lsp_types::{
+    DocumentSymbol, DocumentSymbolParams, DocumentSymbolResponse, Location, SymbolInformation,
+    SymbolKind, WorkspaceSymbolParams, WorkspaceSymbolResponse,
+};
+
+use crate::lang::db::AnalysisDatabase;
+use crate::lang::lsp::{LsProtoGroup, ToLsp};
+
+/// The maximal number of symbols returned by a workspace symbol search.
+const MAX_WORKSPACE_SYMBOLS: usize = 128;
+
+/// Get the hierarchical outline of the items defined in a text document.
+#[tracing::instrument(level = "debug", skip_all, fields(uri = %params.text_document.uri))]
+pub fn document_symbols(
+    params: DocumentSymbolParams,
+    db: &AnalysisDatabase,
+) -> Option<DocumentSymbolResponse> {
+    let file = db.file_for_url(&params.text_document.uri)?;
+    Some(DocumentSymbolResponse::Nested(file_symbols(db, file)?))
+}
+
+/// Search the items defined in all crates of the analyzed workspace by a fuzzy match of their
+/// names against the query.
+#[tracing::instrument(level = "debug", skip_all, fields(query = %params.query))]
+pub fn workspace_symbols(
+    params: WorkspaceSymbolParams,
+    db: &AnalysisDatabase,
+) -> Option<WorkspaceSymbolResponse> {
+    let query = params.query.to_lowercase();
+
+    // Inline submodules share files with their parents, so symbols are collected per file.
+    let files: OrderedHashSet<FileId> = db
+        .crates()
+        .into_iter()
+        .flat_map(|crate_id| db.crate_modules(crate_id).to_vec())
+        .filter_map(|module_id| db.module_main_file(module_id).ok())
+        .filter(|file| matches!(file.lookup_intern(db), FileLongId::OnDisk(_)))
+        .collect();
+
+    let mut matches = vec![];
+    for file in files {
+        let Some(symbols) = file_symbols(db, file) else { continue };
+        let uri = db.url_for_file(file);
+        let mut stack: Vec<_> = symbols.into_iter().map(|symbol| (symbol, None)).collect();
+        while let Some((mut symbol, container_name)) = stack.pop() {
+            for child in symbol.children.take().into_iter().flatten() {
+                stack.push((child, Some(symbol.name.clone())));
+            }
+            let Some(score) = fuzzy_match(&query, &symbol.name) else { continue };
+            #[allow(deprecated)]
+            let information = SymbolInformation {
+                name: symbol.name,
+                kind: symbol.kind,
+                tags: None,
+                deprecated: None,
+                location: Location { uri: uri.clone(), range: symbol.selection_range },
+                container_name,
+            };
+            matches.push((score, information));
+        }
+    }
+
+    matches.sort_by(|(a_score, a), (b_score, b)| {
+        (a_score, a.name.len(), &a.name, a.location.uri.as_str(), a.location.range.start).cmp(&(
+            b_score,
+            b.name.len(),
+            &b.name,
+            b.location.uri.as_str(),
+            b.location.range.start,
+        ))
+    });
+    let symbols =
+        matches.into_iter().take(MAX_WORKSPACE_SYMBOLS).map(|(_, symbol)| symbol).collect();
+    Some(WorkspaceSymbolResponse::Flat(symbols))
+}
+
+/// Returns the symbols of the module items written in the given file.
+fn file_symbols(db: &AnalysisDatabase, file: FileId) -> Option<Vec<DocumentSymbol>> {
+    let module_id = *db.file_modules(file).ok()?.first()?;
+    Some(module_symbols(db, file, module_id))
+}
+
+/// Returns the symbols of the items of the given module, which are written in the given file.
+///
+/// Items generated by compiler plugins are skipped, as they are not written by the user.
+fn module_symbols(db: &AnalysisDatabase, file: FileId, module_id: ModuleId) -> Vec<DocumentSymbol> {
+    let Ok(items) = db.module_items(module_id) else { return vec![] };
+    items
+        .iter()
+        .filter(|item| item.untyped_stable_ptr(db).file_id(db.upcast()) == file)
+        .filter_map(|item| module_item_symbol(db, file, *item))
+        .collect()
+}
+
+/// Returns the symbol of a module item, with the symbols of its members as children.
+fn module_item_symbol(
+    db: &AnalysisDatabase,
+    file: FileId,
+    item: ModuleItemId,
+) -> Option<DocumentSymbol> {
+    let syntax_db = db.upcast();
+    match item {
+        ModuleItemId::Submodule(id) => {
+            let module = id.stable_ptr(db).lookup(syntax_db);
+            let children = match module.body(syntax_db) {
+                ast::MaybeModuleBody::Some(_) => module_symbols(db, file, ModuleId::Submodule(id)),
+                ast::MaybeModuleBody::None(_) => vec![],
+            };
+            let detail = if module.has_attr(syntax_db, CONTRACT_ATTR) {
+                Some("contract")
+            } else if module.has_attr(syntax_db, COMPONENT_ATTR) {
+                Some("component")
+            } else {
+                None
+            };
+            let symbol = symbol(db, file, module.name(syntax_db), &module, SymbolKind::MODULE)?;
+            Some(with_children(
+                DocumentSymbol { detail: detail.map(Into::into), ..symbol },
+                children,
+            ))
+        }
+        ModuleItemId::Struct(id) => {
+            let item = id.stable_ptr(db).lookup(syntax_db);
+            let children = item
+                .members(syntax_db)
+                .elements(syntax_db)
+                .into_iter()
+                .filter_map(|member| {
+                    symbol(db, file, member.name(syntax_db), &member, SymbolKind::FIELD)
+                })
+                .collect();
+            let detail = item.has_attr(syntax_db, STORAGE_ATTR).then(|| "storage".into());
+            let symbol = symbol(db, file, item.name(syntax_db), &item, SymbolKind::STRUCT)?;
+            Some(with_children(DocumentSymbol { detail, ..symbol }, children))
+        }
+        ModuleItemId::Enum(id) => {
+            let item = id.stable_ptr(db).lookup(syntax_db);
+            let is_event = item.has_attr(syntax_db, EVENT_ATTR);
+            let variant_kind = if is_event { SymbolKind::EVENT } else { SymbolKind::ENUM_MEMBER };
+            let children = item
+                .variants(syntax_db)
+                .elements(syntax_db)
+                .into_iter()
+                .filter_map(|variant| {
+                    symbol(db, file, variant.name(syntax_db), &variant, variant_kind)
+                })
+                .collect();
+            let detail = is_event.then(|| "event".into());
+            let symbol = symbol(db, file, item.name(syntax_db), &item, SymbolKind::ENUM)?;
+            Some(with_children(DocumentSymbol { detail, ..symbol }, children))
+        }
+        ModuleItemId::Trait(id) => {
+            let item = id.stable_ptr(db).lookup(syntax_db);
+            let children = match item.body(syntax_db) {
+                ast::MaybeTraitBody::Some(body) => body
+                    .items(syntax_db)
+                    .elements(syntax_db)
+                    .into_iter()
+                    .filter_map(|item| trait_item_symbol(db, file, item))
+                    .collect(),
+                ast::MaybeTraitBody::None(_) => vec![],
+            };
+            let symbol = symbol(db, file, item.name(syntax_db), &item, SymbolKind::INTERFACE)?;
+            Some(with_children(symbol, children))
+        }
+        ModuleItemId::Impl(id) => {
+            let item = id.stable_ptr(db).lookup(syntax_db);
+            let children = match item.body(syntax_db) {
+                ast::MaybeImplBody::Some(body) => body
+                    .items(syntax_db)
+                    .elements(syntax_db)
+                    .into_iter()
+                    .filter_map(|item| impl_item_symbol(db, file, item))
+                    .collect(),
+                ast::MaybeImplBody::None(_) => vec![],
+            };
+            let detail = text_of(db, &item.trait_path(syntax_db));
+            let symbol = symbol(db, file, item.name(syntax_db), &item, SymbolKind::OBJECT)?;
+            Some(with_children(DocumentSymbol { detail: Some(detail), ..symbol }, children))
+        }
+        ModuleItemId::FreeFunction(id) => {
+            function_symbol(db, file, id.stable_ptr(db).lookup(syntax_db), SymbolKind::FUNCTION)
+        }
+        ModuleItemId::Constant(id) => {
+            let item = id.stable_ptr(db).lookup(syntax_db);
+            symbol(db, file, item.name(syntax_db), &item, SymbolKind::CONSTANT)
+        }
+        ModuleItemId::TypeAlias(id) => {
+            let item = id.stable_ptr(db).lookup(syntax_db);
+            symbol(db, file, item.name(syntax_db), &item, SymbolKind::TYPE_PARAMETER)
+        }
+        ModuleItemId::ImplAlias(id) => {
+            let item = id.stable_ptr(db).lookup(syntax_db);
+            symbol(db, file, item.name(syntax_db), &item, SymbolKind::OBJECT)
+        }
+        ModuleItemId::ExternType(id) => {
+            let item = id.stable_ptr(db).lookup(syntax_db);
+            symbol(db, file, item.name(syntax_db), &item, SymbolKind::STRUCT)
+        }
+        ModuleItemId::ExternFunction(id) => {
+            let item = id.stable_ptr(db).lookup(syntax_db);
+            let declaration = item.declaration(syntax_db);
+            let symbol =
+                symbol(db, file, declaration.name(syntax_db), &item, SymbolKind::FUNCTION)?;
+            let detail = text_of(db, &declaration.signature(syntax_db));
+            Some(DocumentSymbol { detail: Some(detail), ..symbol })
+        }
+        ModuleItemId::Use(_) => None,
+    }
+}
+
+/// Returns the symbol of an item in a trait body.
+fn trait_item_symbol(
+    db: &AnalysisDatabase,
+    file: FileId,
+    item: ast::TraitItem,
+) -> Option<DocumentSymbol> {
+    let syntax_db = db.upcast();
+    match item {
+        ast::TraitItem::Function(item) => {
+            let declaration = item.declaration(syntax_db);
+            let symbol = symbol(db, file, declaration.name(syntax_db), &item, SymbolKind::METHOD)?;
+            let detail = text_of(db, &declaration.signature(syntax_db));
+            Some(DocumentSymbol { detail: Some(detail), ..symbol })
+        }
+        ast::TraitItem::Type(item) => {
+            symbol(db, file, item.name(syntax_db), &item, SymbolKind::TYPE_PARAMETER)
+        }
+        ast::TraitItem::Constant(item) => {
+            symbol(db, file, item.name(syntax_db), &item, SymbolKind::CONSTANT)
+        }
+        ast::TraitItem::Impl(item) => {
+            symbol(db, file, item.name(syntax_db), &item, SymbolKind::OBJECT)
+        }
+        ast::TraitItem::Missing(_) => None,
+    }
+}
+
+/// Returns the symbol of an item in an impl body.
+fn impl_item_symbol(
+    db: &AnalysisDatabase,
+    file: FileId,
+    item: ast::ImplItem,
+) -> Option<DocumentSymbol> {
+    let syntax_db = db.upcast();
+    match item {
+        ast::ImplItem::Function(item) => function_symbol(db, file, item, SymbolKind::METHOD),
+        ast::ImplItem::Type(item) => {
+            symbol(db, file, item.name(syntax_db), &item, SymbolKind::TYPE_PARAMETER)
+        }
+        ast::ImplItem::Constant(item) => {
+            symbol(db, file, item.name(syntax_db), &item, SymbolKind::CONSTANT)
+        }
+        ast::ImplItem::Impl(item) => {
+            symbol(db, file, item.name(syntax_db), &item, SymbolKind::OBJECT)
+        }
+        // Other items are not allowed in impls.
+        _ => None,
+    }
+}
+
+/// Returns the symbol of a function with a body, with its signature as the detail.
+fn function_symbol(
+    db: &AnalysisDatabase,
+    file: FileId,
+    function: ast::FunctionWithBody,
+    kind: SymbolKind,
+) -> Option<DocumentSymbol> {
+    let syntax_db = db.upcast();
+    let declaration = function.declaration(syntax_db);
+    let symbol = symbol(db, file, declaration.name(syntax_db), &function, kind)?;
+    let detail = text_of(db, &declaration.signature(syntax_db));
+    Some(DocumentSymbol { detail: Some(detail), ..symbol })
+}
+
+/// Returns a symbol without detail and children, spanning the given node and selecting its name.
+fn symbol(
+    db: &AnalysisDatabase,
+    file: FileId,
+    name: ast::TerminalIdentifier,
+    node: &impl TypedSyntaxNode,
+    kind: SymbolKind,
+) -> Option<DocumentSymbol> {
+    let syntax_db = db.upcast();
+    let range = node
+        .as_syntax_node()
+        .span_without_trivia(syntax_db)
+        .position_in_file(db.upcast(), file)?
+        .to_lsp();
+    let selection_range = name
+        .as_syntax_node()
+        .span_without_trivia(syntax_db)
+        .position_in_file(db.upcast(), file)?
+        .to_lsp();
+    #[allow(deprecated)]
+    Some(DocumentSymbol {
+        name: name.text(syntax_db).into(),
+        detail: None,
+        kind,
+        tags: None,
+        deprecated: None,
+        range,
+        selection_range,
+        children: None,
+    })
+}
+
+/// Sets the children of a symbol, if there are any.
+fn with_children(symbol: DocumentSymbol, children: Vec<DocumentSymbol>) -> DocumentSymbol {
+    DocumentSymbol { children: (!children.is_empty()).then_some(children), ..symbol }
+}
+
+/// Returns the text of a node, without its leading and trailing trivia.
+fn text_of(db: &AnalysisDatabase, node: &impl TypedSyntaxNode) -> String {
+    node.as_syntax_node().get_text_without_trivia(db.upcast())
+}
+
+/// Matches a name against a lowercase query, returning a score that is lower for better matches.
+///
+/// A name matches if the characters of the query appear in it in order, ignoring case.
+fn fuzzy_match(query: &str, name: &str) -> Option<u8> {
+    let name = name.to_lowercase();
+    if name == query {
+        Some(0)
+    } else if name.starts_with(query) {
+        Some(1)
+    } else if name.contains(query) {
+        Some(2)
+    } else {
+        let mut chars = name.chars();
+        query.chars().all(|query_char| chars.any(|char| char == query_char)).then_some(3)
+    }
+}
@@ -0,0 +1,46 @@
+use cairo_lang_defs::ids::GenericTypeId;
+use cairo_lang_semantic::{TypeId, TypeLongId};
+use cairo_lang_syntax::node::ids::SyntaxStablePtrId;
+use cairo_lang_syntax::node::{TypedStablePtr, TypedSyntaxNode};
+use cairo_lang_utils::{LookupIntern, Upcast};
+use lsp_types::request::{GotoTypeDefinitionParams, GotoTypeDefinitionResponse};
+
+use crate::ide::utils::{identifier_type, location_in_user_code};
+use crate::lang::db::{AnalysisDatabase, LsSyntaxGroup};
+use crate::lang::lsp::{LsProtoGroup, ToCairo};
+
+/// Get the location of the declaration of the type of the variable or expression at a given text
+/// document position.
+#[tracing::instrument(
+    level = "debug",
+    skip_all,
+    fields(uri = %params.text_document_position_params.text_document.uri)
+)]
+pub fn goto_type_definition(
+    params: GotoTypeDefinitionParams,
+    db: &AnalysisDatabase,
+) -> Option<GotoTypeDefinitionResponse> {
+    let file = db.file_for_url(&params.text_document_position_params.text_document.uri)?;
+    let position = params.text_document_position_params.position.to_cairo();
+    let identifier = db.find_identifier_at_position(file, position)?;
+    let ty = identifier_type(db, &identifier)?;
+    let stable_ptr = type_declaration(db, ty)?;
+    Some(GotoTypeDefinitionResponse::Scalar(location_in_user_code(db, stable_ptr)?))
+}
+
+/// Returns the name in the declaration of the given type, looking through snapshots.
+fn type_declaration(db: &AnalysisDatabase, ty: TypeId) -> Option<SyntaxStablePtrId> {
+    let syntax_db = db.upcast();
+    match ty.lookup_intern(db) {
+        TypeLongId::Concrete(concrete_type) => {
+            let name = match concrete_type.generic_type(db) {
+                GenericTypeId::Struct(id) => id.stable_ptr(db).lookup(syntax_db).name(syntax_db),
+                GenericTypeId::Enum(id) => id.stable_ptr(db).lookup(syntax_db).name(syntax_db),
+                GenericTypeId::Extern(id) => id.stable_ptr(db).lookup(syntax_db).name(syntax_db),
+            };
+            Some(name.stable_ptr().untyped())
+        }
+        TypeLongId::Snapshot(inner) => type_declaration(db, inner),
+        _ => None,
+    }
+}
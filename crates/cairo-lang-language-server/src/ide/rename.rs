@@ -0,0 +1,103 @@
+use std::collections::HashMap;
+
+use anyhow::anyhow;
+use cairo_lang_filesystem::ids::FileId;
+use cairo_lang_filesystem::span::TextPosition;
+use cairo_lang_parser::lexer::Lexer;
+use cairo_lang_syntax::node::kind::SyntaxKind;
+use cairo_lang_utils::Upcast;
+use lsp_server::ErrorCode;
+use lsp_types::{
+    PrepareRenameResponse, RenameParams, TextDocumentPositionParams, TextEdit, WorkspaceEdit,
+};
+use tracing::error;
+
+use crate::lang::db::AnalysisDatabase;
+use crate::lang::inspect::references::{ReferenceKind, SymbolReferences, find_references};
+use crate::lang::lsp::{LsProtoGroup, ToCairo, ToLsp};
+use crate::lsp::result::{LSPError, LSPResult};
+
+/// Get the range of the name of a symbol at a given text document position, if it can be renamed.
+///
+/// Symbols defined outside of user code, e.g. in the core library or by compiler plugins, cannot be
+/// renamed.
+#[tracing::instrument(level = "debug", skip_all, fields(uri = %params.text_document.uri))]
+pub fn prepare_rename(
+    params: TextDocumentPositionParams,
+    db: &AnalysisDatabase,
+) -> Option<PrepareRenameResponse> {
+    let file = db.file_for_url(&params.text_document.uri)?;
+    let position = params.position.to_cairo();
+    let symbol = renamable_symbol(db, file, position)?;
+    let offset = position.offset_in_file(db.upcast(), file)?;
+    let reference = symbol.references.iter().find(|reference| {
+        reference.file == file && reference.span.start <= offset && offset <= reference.span.end
+    })?;
+    let range = reference.span.position_in_file(db.upcast(), file)?.to_lsp();
+    Some(PrepareRenameResponse::Range(range))
+}
+
+/// Rename a symbol at a given text document position, with all its references.
+#[tracing::instrument(
+    level = "debug",
+    skip_all,
+    fields(uri = %params.text_document_position.text_document.uri)
+)]
+pub fn rename(params: RenameParams, db: &AnalysisDatabase) -> LSPResult<Option<WorkspaceEdit>> {
+    let new_name = params.new_name;
+    if !is_identifier(db, &new_name) {
+        return Err(LSPError::new(
+            anyhow!("`{new_name}` is not a valid identifier."),
+            ErrorCode::InvalidParams,
+        ));
+    }
+
+    let Some(file) = db.file_for_url(&params.text_document_position.text_document.uri) else {
+        return Ok(None);
+    };
+    let position = params.text_document_position.position.to_cairo();
+    let Some(symbol) = renamable_symbol(db, file, position) else {
+        return Ok(None);
+    };
+
+    let mut changes = HashMap::<_, Vec<TextEdit>>::new();
+    for reference in symbol.references {
+        let Some(span) = reference.span.position_in_file(db.upcast(), reference.file) else {
+            error!("failed to get the position of a reference to `{}`", symbol.name);
+            continue;
+        };
+        // Shorthands of struct members are expanded, so they keep referring to the symbols that
+        // are not renamed.
+        let new_text = match reference.kind {
+            ReferenceKind::Plain => new_name.clone(),
+            ReferenceKind::ShorthandMember => format!("{new_name}: {}", symbol.name),
+            ReferenceKind::ShorthandVariable => format!("{}: {new_name}", symbol.name),
+        };
+        changes
+            .entry(db.url_for_file(reference.file))
+            .or_default()
+            .push(TextEdit { range: span.to_lsp(), new_text });
+    }
+
+    Ok(Some(WorkspaceEdit { changes: Some(changes), ..Default::default() }))
+}
+
+/// Finds the symbol at the given position with its references, if it is defined in user code.
+fn renamable_symbol(
+    db: &AnalysisDatabase,
+    file: FileId,
+    position: TextPosition,
+) -> Option<SymbolReferences> {
+    let symbol = find_references(db, file, position)?;
+    (symbol.is_definition_in_user_code && symbol.references.iter().any(|r| r.is_definition))
+        .then_some(symbol)
+}
+
+/// Checks whether the given text is a single identifier, which is not a keyword.
+fn is_identifier(db: &AnalysisDatabase, text: &str) -> bool {
+    let mut terminals = Lexer::from_text(db.upcast(), text);
+    let Some(terminal) = terminals.next() else { return false };
+    terminal.kind == SyntaxKind::TerminalIdentifier
+        && terminal.text == text
+        && terminals.next().is_some_and(|terminal| terminal.kind == SyntaxKind::TerminalEndOfFile)
+}
@@ -0,0 +1,64 @@
+use cairo_lang_filesystem::ids::FileId;
+use cairo_lang_filesystem::span::{TextPosition, TextSpan};
+use cairo_lang_utils::Upcast;
+use lsp_types::{Range, SelectionRange, SelectionRangeParams};
+
+use crate::lang::db::{AnalysisDatabase, LsSyntaxGroup};
+use crate::lang::lsp::{LsProtoGroup, ToCairo, ToLsp};
+
+/// Get the ranges for expanding and shrinking the selection at given text document positions.
+///
+/// The ranges are the spans of the syntax nodes around each position, from the innermost token up
+/// to the whole file.
+#[tracing::instrument(level = "debug", skip_all, fields(uri = %params.text_document.uri))]
+pub fn selection_ranges(
+    params: SelectionRangeParams,
+    db: &AnalysisDatabase,
+) -> Option<Vec<SelectionRange>> {
+    let file = db.file_for_url(&params.text_document.uri)?;
+    Some(
+        params
+            .positions
+            .into_iter()
+            .map(|position| {
+                selection_range(db, file, position.to_cairo()).unwrap_or(SelectionRange {
+                    // The response must have an entry for every position.
+                    range: Range::new(position, position),
+                    parent: None,
+                })
+            })
+            .collect(),
+    )
+}
+
+/// Builds the chain of selection ranges of the syntax nodes containing the position.
+fn selection_range(
+    db: &AnalysisDatabase,
+    file: FileId,
+    position: TextPosition,
+) -> Option<SelectionRange> {
+    let offset = position.offset_in_file(db.upcast(), file)?;
+    let mut node = db.find_syntax_node_at_position(file, position);
+
+    // Collect distinct spans, from the innermost one.
+    let mut spans: Vec<TextSpan> = vec![];
+    while let Some(current) = node {
+        let span = current.span_without_trivia(db.upcast());
+        // Skip nodes that are empty, equal to their child or located in trivia around the position.
+        if span.start < span.end
+            && span.start <= offset
+            && offset <= span.end
+            && spans.last() != Some(&span)
+        {
+            spans.push(span);
+        }
+        node = current.parent();
+    }
+
+    let mut result: Option<SelectionRange> = None;
+    for span in spans.into_iter().rev() {
+        let range = span.position_in_file(db.upcast(), file)?.to_lsp();
+        result = Some(SelectionRange { range, parent: result.map(Box::new) });
+    }
+    result
+}
@@ -0,0 +1,195 @@
+use cairo_lang_defs::db::DefsGroup;
+use cairo_lang_defs::ids::{ImplItemId, LookupItemId, ModuleItemId, TraitItemId};
+use cairo_lang_doc::db::DocGroup;
+use cairo_lang_doc::documentable_item::DocumentableItemId;
+use cairo_lang_filesystem::span::TextOffset;
+use cairo_lang_syntax::node::db::SyntaxGroup;
+use cairo_lang_syntax::node::helpers::PathSegmentEx;
+use cairo_lang_syntax::node::kind::SyntaxKind;
+use cairo_lang_syntax::node::{SyntaxNode, TypedSyntaxNode, ast};
+use cairo_lang_utils::Upcast;
+use lsp_types::{
+    ParameterInformation, ParameterLabel, SignatureHelp, SignatureHelpParams, SignatureInformation,
+};
+
+use crate::ide::utils::markdown_documentation;
+use crate::lang::db::{AnalysisDatabase, LsSyntaxGroup};
+use crate::lang::inspect::defs::SymbolDef;
+use crate::lang::lsp::{LsProtoGroup, ToCairo};
+
+/// Get the signature of the function or inline macro called at a given text document position.
+#[tracing::instrument(
+    level = "debug",
+    skip_all,
+    fields(uri = %params.text_document_position_params.text_document.uri)
+)]
+pub fn signature_help(params: SignatureHelpParams, db: &AnalysisDatabase) -> Option<SignatureHelp> {
+    let file = db.file_for_url(&params.text_document_position_params.text_document.uri)?;
+    let position = params.text_document_position_params.position.to_cairo();
+    let offset = position.offset_in_file(db.upcast(), file)?;
+    let node = db.find_syntax_node_at_position(file, position)?;
+
+    let arg_list = enclosing_arg_list(db, node, offset)?;
+    let call = arg_list.parent()?;
+    let active_parameter = active_parameter(db, &arg_list, offset);
+    let signature = match call.kind(db) {
+        SyntaxKind::ExprFunctionCall => {
+            let call = ast::ExprFunctionCall::from_syntax_node(db, call);
+            // The receiver of a method call is passed as the first parameter.
+            let active_parameter = active_parameter + is_method_call(db, &call) as u32;
+            function_signature(db, &call, active_parameter)?
+        }
+        SyntaxKind::ExprInlineMacro => {
+            inline_macro_signature(db, &ast::ExprInlineMacro::from_syntax_node(db, call))?
+        }
+        _ => return None,
+    };
+
+    Some(SignatureHelp {
+        active_parameter: signature.active_parameter,
+        signatures: vec![signature],
+        active_signature: Some(0),
+    })
+}
+
+/// Finds the innermost argument list of a call or an inline macro, whose delimiters surround the
+/// given offset.
+fn enclosing_arg_list(
+    db: &AnalysisDatabase,
+    node: SyntaxNode,
+    offset: TextOffset,
+) -> Option<SyntaxNode> {
+    let mut node = Some(node);
+    while let Some(current) = node {
+        if matches!(
+            current.kind(db),
+            SyntaxKind::ArgListParenthesized
+                | SyntaxKind::ArgListBracketed
+                | SyntaxKind::ArgListBraced
+        ) {
+            let children = db.get_children(current.clone());
+            let [open, _, close] = &children[..] else { return None };
+            let close = close.span_without_trivia(db);
+            // A missing closing delimiter is typical while the call is being typed.
+            let before_close = close.start == close.end || offset <= close.start;
+            if open.span_without_trivia(db).end <= offset && before_close {
+                return Some(current);
+            }
+        }
+        node = current.parent();
+    }
+    None
+}
+
+/// Returns the index of the argument at the given offset, by counting the preceding commas.
+fn active_parameter(db: &AnalysisDatabase, arg_list: &SyntaxNode, offset: TextOffset) -> u32 {
+    let children = db.get_children(arg_list.clone());
+    db.get_children(children[1].clone())
+        .iter()
+        .filter(|node| node.kind(db) == SyntaxKind::TerminalComma)
+        .filter(|comma| comma.span_without_trivia(db).end <= offset)
+        .count() as u32
+}
+
+/// Checks whether the call is written in the method call syntax, i.e. `receiver.method(...)`.
+fn is_method_call(db: &AnalysisDatabase, call: &ast::ExprFunctionCall) -> bool {
+    let Some(parent) = call.as_syntax_node().parent() else { return false };
+    if parent.kind(db) != SyntaxKind::ExprBinary {
+        return false;
+    }
+    let binary = ast::ExprBinary::from_syntax_node(db, parent);
+    matches!(binary.op(db), ast::BinaryOperator::Dot(_))
+        && binary.rhs(db).as_syntax_node() == call.as_syntax_node()
+}
+
+/// Builds the signature of the function called by the given call expression.
+fn function_signature(
+    db: &AnalysisDatabase,
+    call: &ast::ExprFunctionCall,
+    active_parameter: u32,
+) -> Option<SignatureInformation> {
+    let identifier = call.path(db).elements(db).last()?.identifier_ast(db);
+    let SymbolDef::Item(item) = SymbolDef::find(db, &identifier)? else { return None };
+    let lookup_item_id = item.lookup_item_id();
+    if !is_function(lookup_item_id) {
+        return None;
+    }
+
+    let label = db.get_item_signature(lookup_item_id.into());
+    // The signature is still helpful without the parameters, if they can't be found in it.
+    let parameters = parameter_labels(db, lookup_item_id, &label);
+    Some(SignatureInformation {
+        documentation: item.documentation(db).map(markdown_documentation),
+        active_parameter: Some(active_parameter),
+        parameters,
+        label,
+    })
+}
+
+/// Checks whether the item is a function that can be called.
+fn is_function(item: LookupItemId) -> bool {
+    matches!(
+        item,
+        LookupItemId::ModuleItem(ModuleItemId::FreeFunction(_) | ModuleItemId::ExternFunction(_))
+            | LookupItemId::TraitItem(TraitItemId::Function(_))
+            | LookupItemId::ImplItem(ImplItemId::Function(_))
+    )
+}
+
+/// Finds the parameters of a function in its signature label, as offsets usable by the client.
+/// Returns `None` if any of the parameters can't be found in the label.
+fn parameter_labels(
+    db: &AnalysisDatabase,
+    item: LookupItemId,
+    label: &str,
+) -> Option<Vec<ParameterInformation>> {
+    let declaration = DocumentableItemId::from(item).stable_location(db)?.syntax_node(db);
+    let param_list =
+        declaration.descendants(db).find(|node| node.kind(db) == SyntaxKind::ParamList)?;
+
+    let mut search_start = label.find('(')?;
+    let mut parameters = vec![];
+    for param in db.get_children(param_list).iter() {
+        if param.kind(db) != SyntaxKind::Param {
+            continue;
+        }
+        // Format the parameter the same way as the signature does.
+        let text = param
+            .clone()
+            .get_text_without_all_comment_trivia(db)
+            .lines()
+            .map(|line| line.trim())
+            .collect::<String>();
+        let start = search_start + label[search_start..].find(text.as_str())?;
+        let end = start + text.len();
+        search_start = end;
+        parameters.push(ParameterInformation {
+            label: ParameterLabel::LabelOffsets([
+                utf16_len(&label[..start]),
+                utf16_len(&label[..end]),
+            ]),
+            documentation: None,
+        });
+    }
+    Some(parameters)
+}
+
+/// Builds the signature of an inline macro, which is just its name, with its documentation.
+fn inline_macro_signature(
+    db: &AnalysisDatabase,
+    inline_macro: &ast::ExprInlineMacro,
+) -> Option<SignatureInformation> {
+    let name = inline_macro.path(db).as_syntax_node().get_text_without_trivia(db);
+    let documentation = db.inline_macro_plugins().get(&name)?.documentation();
+    Some(SignatureInformation {
+        label: format!("{name}!"),
+        documentation: documentation.map(markdown_documentation),
+        parameters: None,
+        active_parameter: None,
+    })
+}
+
+/// Returns the length of the text in UTF-16 code units, as expected in LSP offsets.
+fn utf16_len(text: &str) -> u32 {
+    text.encode_utf16().count() as u32
+}
@@ -0,0 +1,106 @@
+use cairo_lang_defs::db::DefsGroup;
+use cairo_lang_defs::ids::{LanguageElementId, ModuleId, ModuleItemId, TopLevelLanguageElementId};
+use cairo_lang_filesystem::ids::FileId;
+use cairo_lang_syntax::node::helpers::QueryAttrs;
+use cairo_lang_syntax::node::{TypedStablePtr, TypedSyntaxNode, ast};
+use cairo_lang_test_plugin::TEST_ATTR;
+use cairo_lang_utils::Upcast;
+use lsp_types::{CodeLens, CodeLensParams, Command, TextDocumentIdentifier};
+
+use crate::lang::db::AnalysisDatabase;
+use crate::lang::lsp::{LsProtoGroup, ToLsp};
+use crate::lsp::ext::RunTestsParams;
+
+/// The client command run by the code lenses of tests, expected to send a
+/// [`RunTests`](crate::lsp::ext::RunTests) request with the lens arguments.
+pub const RUN_TESTS_COMMAND: &str = "cairo.runTests";
+
+/// Get the code lenses running the tests, and the modules containing tests, of a text document.
+#[tracing::instrument(level = "debug", skip_all, fields(uri = %params.text_document.uri))]
+pub fn code_lens(params: CodeLensParams, db: &AnalysisDatabase) -> Option<Vec<CodeLens>> {
+    let file = db.file_for_url(&params.text_document.uri)?;
+    let module_id = *db.file_modules(file).ok()?.first()?;
+    let mut collector =
+        LensesCollector { db, file, document: params.text_document, lenses: vec![] };
+    collector.collect_module(module_id);
+    collector.lenses.sort_by_key(|lens| (lens.range.start.line, lens.range.start.character));
+    Some(collector.lenses)
+}
+
+/// Collects the code lenses of the tests written in a file.
+struct LensesCollector<'a> {
+    db: &'a AnalysisDatabase,
+    file: FileId,
+    document: TextDocumentIdentifier,
+    lenses: Vec<CodeLens>,
+}
+
+impl LensesCollector<'_> {
+    /// Adds the lenses of the tests in the given module and its submodules, and returns whether
+    /// the module contains any tests.
+    ///
+    /// Submodules defined in other files are searched too, so that their declarations get lenses.
+    fn collect_module(&mut self, module_id: ModuleId) -> bool {
+        let db = self.db;
+        let syntax_db = db.upcast();
+        let Ok(items) = db.module_items(module_id) else { return false };
+        let mut has_tests = false;
+        for item in items.iter() {
+            match *item {
+                ModuleItemId::FreeFunction(id) => {
+                    let function = id.stable_ptr(db).lookup(syntax_db);
+                    if !function.has_attr(syntax_db, TEST_ATTR) {
+                        continue;
+                    }
+                    has_tests = true;
+                    let name = function.declaration(syntax_db).name(syntax_db);
+                    let file = id.untyped_stable_ptr(db).file_id(syntax_db);
+                    self.push(file, &name, "▶ Run test", id.full_path(db));
+                }
+                ModuleItemId::Submodule(id) => {
+                    if !self.collect_module(ModuleId::Submodule(id)) {
+                        continue;
+                    }
+                    has_tests = true;
+                    let name = id.stable_ptr(db).lookup(syntax_db).name(syntax_db);
+                    let file = id.untyped_stable_ptr(db).file_id(syntax_db);
+                    self.push(file, &name, "▶ Run tests", id.full_path(db));
+                }
+                _ => {}
+            }
+        }
+        has_tests
+    }
+
+    /// Adds the lenses running the tests at the given path, above the name of the item, if it is
+    /// written in the processed file.
+    fn push(&mut self, file: FileId, name: &ast::TerminalIdentifier, title: &str, path: String) {
+        if file != self.file {
+            return;
+        }
+        let db = self.db;
+        let Some(range) = name
+            .as_syntax_node()
+            .span_without_trivia(db.upcast())
+            .position_in_file(db.upcast(), self.file)
+        else {
+            return;
+        };
+        for (title, profiler) in [(title, false), ("Run with profiler", true)] {
+            let params = RunTestsParams {
+                text_document: self.document.clone(),
+                path: path.clone(),
+                profiler,
+            };
+            self.lenses.push(CodeLens {
+                range: range.to_lsp(),
+                command: Some(Command {
+                    title: title.into(),
+                    command: RUN_TESTS_COMMAND.into(),
+                    arguments: Some(vec![serde_json::to_value(params).unwrap()]),
+                }),
+                data: None,
+            });
+        }
+    }
+}
@@ -0,0 +1,2 @@
+pub mod code_lens;
+pub mod run;
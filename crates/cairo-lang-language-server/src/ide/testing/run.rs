@@ -0,0 +1,142 @@
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use anyhow::{Context, anyhow};
+use cairo_lang_runner::profiling::ProfilerOutputFormat;
+use cairo_lang_test_plugin::TestsCompilationConfig;
+use cairo_lang_test_runner::reporter::{TestOutputFormat, TestReporter, failure_message};
+use cairo_lang_test_runner::{
+    CompiledTestRunner, RunProfilerConfig, TestCompiler, TestReport, TestRunConfig, TestStatus,
+    TestsSummary,
+};
+use lsp_server::ErrorCode;
+
+use crate::lsp::ext::{
+    RunTestsParams, RunTestsResult, TestResult, TestResultParams, TestResultStatus,
+};
+use crate::lsp::result::{LSPResult, LSPResultEx};
+use crate::project::ProjectManifestPath;
+use crate::server::client::Notifier;
+
+/// Compiles the project of a text document and runs the tests selected by the request, notifying
+/// the client about the result of each test as soon as it finishes.
+///
+/// The tests are compiled in a fresh compiler database, the same way `cairo-test` does, so that the
+/// analysis database is never blocked by the run.
+#[tracing::instrument(level = "debug", skip_all, fields(path = %params.path))]
+pub fn run_tests(params: RunTestsParams, notifier: Notifier) -> LSPResult<RunTestsResult> {
+    let file = params
+        .text_document
+        .uri
+        .to_file_path()
+        .map_err(|()| anyhow!("tests can only be run in files on disk"))
+        .with_failure_code(ErrorCode::InvalidParams)?;
+
+    let run_profiler =
+        if params.profiler { RunProfilerConfig::Cairo } else { RunProfilerConfig::None };
+    let compiler = TestCompiler::try_new(
+        &project_path(&file),
+        true,
+        true,
+        TestsCompilationConfig {
+            starknet: true,
+            add_statements_functions: run_profiler == RunProfilerConfig::Cairo,
+            add_statements_code_locations: false,
+            contract_declarations: None,
+            contract_crate_ids: None,
+            executable_crate_ids: None,
+        },
+    )
+    .with_failure_code(ErrorCode::RequestFailed)?;
+    let mut compiled = compiler
+        .build()
+        .context("failed to compile the tests")
+        .with_failure_code(ErrorCode::RequestFailed)?;
+    compiled.metadata.named_tests.retain(|(name, _)| is_selected(name, &params.path));
+
+    let config = TestRunConfig {
+        filter: String::new(),
+        include_ignored: false,
+        ignored: false,
+        run_profiler,
+        profiler_format: ProfilerOutputFormat::Text,
+        profiler_output_dir: PathBuf::new(),
+        gas_enabled: true,
+        print_resource_usage: false,
+        // The standard output is the channel to the client, so the prints must not reach it.
+        capture_output: true,
+        jobs: None,
+        format: TestOutputFormat::Pretty,
+        fuzzer_runs: 256,
+        fuzzer_seed: None,
+        starknet_state_fixture: None,
+        coverage: None,
+    };
+    let summary = CompiledTestRunner::new(compiled, config)
+        .run_with_reporter(Some(&compiler.db), &mut NotifyingReporter { notifier })
+        .with_failure_code(ErrorCode::RequestFailed)?;
+
+    Ok(RunTestsResult {
+        passed: summary.passed().count(),
+        failed: summary.failed().count(),
+        ignored: summary.ignored().count(),
+    })
+}
+
+/// Returns the path to run the tests of the given file in: the directory of its
+/// `cairo_project.toml`, or the file itself when it is not part of a Cairo project.
+fn project_path(file: &Path) -> PathBuf {
+    match ProjectManifestPath::discover(file) {
+        Some(ProjectManifestPath::CairoProject(manifest)) => {
+            manifest.parent().map(Path::to_path_buf).unwrap_or_else(|| file.to_path_buf())
+        }
+        _ => file.to_path_buf(),
+    }
+}
+
+/// Checks whether a test is the selected one, or is in the selected module.
+///
+/// The crate names are not compared, as they depend on how the project is compiled, e.g. a
+/// detached file is compiled as a crate named after the file.
+fn is_selected(test: &str, selection: &str) -> bool {
+    let without_crate = |path: &str| path.split_once("::").map(|(_, path)| path.to_string());
+    let (Some(test), selection) = (without_crate(test), without_crate(selection)) else {
+        return false;
+    };
+    match selection {
+        None => true,
+        Some(selection) => test == selection || test.starts_with(&format!("{selection}::")),
+    }
+}
+
+/// Reports the result of each test in a notification to the client.
+struct NotifyingReporter {
+    notifier: Notifier,
+}
+
+impl TestReporter for NotifyingReporter {
+    fn on_run_started(&mut self, _n_tests: usize) {}
+
+    fn on_test_finished(&mut self, report: &TestReport) {
+        let (status, message) = match &report.status {
+            TestStatus::Success => (TestResultStatus::Passed, None),
+            TestStatus::Fail(_) => (TestResultStatus::Failed, Some(failure_message(report))),
+            TestStatus::Ignored => (TestResultStatus::Ignored, None),
+        };
+        self.notifier.notify::<TestResult>(TestResultParams {
+            name: report.name.clone(),
+            status,
+            message,
+            output: report.output.clone(),
+            profiling_info: report.profiling_info.clone(),
+        });
+    }
+
+    fn on_run_finished(
+        &mut self,
+        _summary: &TestsSummary,
+        _filtered_out: usize,
+        _duration: Duration,
+    ) {
+    }
+}
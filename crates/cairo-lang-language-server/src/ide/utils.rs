@@ -1,14 +1,26 @@
 use cairo_lang_defs::ids::TraitFunctionId;
-use cairo_lang_filesystem::db::FilesGroup;
+use cairo_lang_filesystem::db::{FilesGroup, get_originating_location};
+use cairo_lang_filesystem::ids::CrateId;
+use cairo_lang_semantic::TypeId;
 use cairo_lang_semantic::db::SemanticGroup;
 use cairo_lang_semantic::expr::inference::InferenceId;
 use cairo_lang_semantic::expr::inference::infers::InferenceEmbeddings;
 use cairo_lang_semantic::expr::inference::solver::SolutionSet;
+use cairo_lang_semantic::items::function_with_body::SemanticExprLookup;
+use cairo_lang_semantic::lookup_item::LookupItemEx;
 use cairo_lang_semantic::lsp_helpers::TypeFilter;
 use cairo_lang_semantic::resolve::Resolver;
+use cairo_lang_syntax::node::helpers::PathSegmentEx;
+use cairo_lang_syntax::node::ids::SyntaxStablePtrId;
+use cairo_lang_syntax::node::kind::SyntaxKind;
+use cairo_lang_syntax::node::{SyntaxNode, Terminal, TypedSyntaxNode, ast};
+use cairo_lang_utils::Upcast;
+use lsp_types::{Documentation, Location, MarkupContent, MarkupKind};
 use tracing::debug;
 
-use crate::lang::db::AnalysisDatabase;
+use crate::lang::db::{AnalysisDatabase, LsSemanticGroup};
+use crate::lang::inspect::defs::SymbolDef;
+use crate::lang::lsp::{LsProtoGroup, ToLsp};
 
 /// Finds all methods that can be called on a type.
 #[tracing::instrument(level = "trace", skip_all)]
@@ -60,3 +72,97 @@ pub fn find_methods_for_type(
     }
     relevant_methods
 }
+
+/// Returns the location of a syntax node, where code generated by plugins is located at the user
+/// code it originates from.
+pub fn location_in_user_code(
+    db: &AnalysisDatabase,
+    stable_ptr: SyntaxStablePtrId,
+) -> Option<Location> {
+    let syntax_db = db.upcast();
+    let span = stable_ptr.lookup(syntax_db).span_without_trivia(syntax_db);
+    let width = span.width();
+    let (file, mut span) =
+        get_originating_location(db.upcast(), stable_ptr.file_id(syntax_db), span.start_only());
+    span.end = span.end.add_width(width);
+    let range = span.position_in_file(db.upcast(), file)?.to_lsp();
+    Some(Location { uri: db.url_for_file(file), range })
+}
+
+/// Wraps markdown documentation in a type accepted by the client.
+pub fn markdown_documentation(value: String) -> Documentation {
+    Documentation::MarkupContent(MarkupContent { kind: MarkupKind::Markdown, value })
+}
+
+/// Returns the crates which may use the items of the given crate.
+///
+/// The core crate cannot refer to items of other crates, so it is included only for its own items.
+pub fn crates_using(db: &AnalysisDatabase, crate_id: CrateId) -> Vec<CrateId> {
+    db.crates()
+        .into_iter()
+        .filter(|other| *other != CrateId::core(db) || *other == crate_id)
+        .collect()
+}
+
+/// Returns the name of the function called by a call expression or a method call.
+pub fn called_function_name(
+    db: &AnalysisDatabase,
+    node: SyntaxNode,
+) -> Option<ast::TerminalIdentifier> {
+    let call = match node.kind(db) {
+        SyntaxKind::ExprFunctionCall => ast::ExprFunctionCall::from_syntax_node(db, node),
+        SyntaxKind::ExprBinary => {
+            let binary = ast::ExprBinary::from_syntax_node(db, node);
+            match (binary.op(db), binary.rhs(db)) {
+                (ast::BinaryOperator::Dot(_), ast::Expr::FunctionCall(call)) => call,
+                _ => return None,
+            }
+        }
+        _ => return None,
+    };
+    Some(call.path(db).elements(db).last()?.identifier_ast(db))
+}
+
+/// Returns the type of the variable, parameter or innermost expression at the identifier.
+pub fn identifier_type(
+    db: &AnalysisDatabase,
+    identifier: &ast::TerminalIdentifier,
+) -> Option<TypeId> {
+    match SymbolDef::find(db, identifier) {
+        Some(SymbolDef::Variable(variable)) => Some(variable.ty()),
+        _ => param_type(db, identifier).or_else(|| expression_type(db, identifier)),
+    }
+}
+
+/// Returns the type of the parameter declared by the identifier, if any.
+fn param_type(db: &AnalysisDatabase, identifier: &ast::TerminalIdentifier) -> Option<TypeId> {
+    let param = identifier.as_syntax_node().parent()?;
+    if param.kind(db) != SyntaxKind::Param {
+        return None;
+    }
+    let function_id = db.find_lookup_item(&param)?.function_with_body()?;
+    let signature = db.function_with_body_signature(function_id).ok()?;
+    let name = identifier.text(db);
+    Some(signature.params.into_iter().find(|param| param.name == name)?.ty)
+}
+
+/// Returns the type of the innermost expression or pattern containing the identifier, within its
+/// statement.
+fn expression_type(db: &AnalysisDatabase, identifier: &ast::TerminalIdentifier) -> Option<TypeId> {
+    let function_id = db.find_lookup_item(&identifier.as_syntax_node())?.function_with_body()?;
+    let mut node = Some(identifier.as_syntax_node());
+    while let Some(current) = node {
+        if ast::Statement::is_variant(current.kind(db)) {
+            break;
+        }
+        let ptr = current.stable_ptr();
+        if let Ok(expr_id) = db.lookup_expr_by_ptr(function_id, ast::ExprPtr(ptr)) {
+            return Some(db.expr_semantic(function_id, expr_id).ty());
+        }
+        if let Ok(pattern_id) = db.lookup_pattern_by_ptr(function_id, ast::PatternPtr(ptr)) {
+            return Some(db.pattern_semantic(function_id, pattern_id).ty());
+        }
+        node = current.parent();
+    }
+    None
+}
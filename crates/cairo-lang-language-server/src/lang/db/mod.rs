@@ -20,6 +20,7 @@ pub use self::semantic::*;
 pub use self::swapper::*;
 pub use self::syntax::*;
 use crate::Tricks;
+use crate::lang::inspect::references::LsReferencesDatabase;
 
 mod semantic;
 mod swapper;
@@ -33,7 +34,8 @@ mod syntax;
     ParserDatabase,
     SemanticDatabase,
     SyntaxDatabase,
-    DocDatabase
+    DocDatabase,
+    LsReferencesDatabase
 )]
 pub struct AnalysisDatabase {
     storage: salsa::Storage<Self>,
@@ -0,0 +1,292 @@
+//! Persistent cache of diagnostics of immutable crates.
+//!
+//! Computing diagnostics of a crate requires a full analysis of it, which for `core` and large
+//! dependencies makes the language server slow to become responsive after startup.
+//! Crates that cannot be changed by the user, like the corelib and packages fetched into the Scarb
+//! cache, always produce the same diagnostics, so these are stored on disk and loaded in
+//! subsequent runs instead of being recomputed.
+//!
+//! Only the diagnostics are persisted, not the parsed and semantic results they are computed from:
+//! the salsa database cannot be serialized, and the analysis of crates not opened in the editor is
+//! only needed at startup for their diagnostics. Other requests still analyze such crates lazily,
+//! only as far as they need.
+//!
+//! Cache entries are keyed by a [`CrateKey`], which fingerprints everything the analysis of
+//! a crate depends on: the language server version, compiler plugins, configuration, settings
+//! of the crate and its dependencies, and the metadata of all their source files.
+//! Immutable crates do not change during a session, so the fingerprints of crates are computed
+//! once per session.
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write as _;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::io::Write as _;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::UNIX_EPOCH;
+
+use cairo_lang_defs::db::DefsGroup;
+use cairo_lang_filesystem::db::{CORELIB_CRATE_NAME, FilesGroup};
+use cairo_lang_filesystem::ids::{CrateId, CrateLongId, Directory, FileLongId};
+use cairo_lang_utils::{Intern, LookupIntern};
+use lsp_types::{Diagnostic, Url};
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+use crate::lang::db::AnalysisDatabase;
+
+#[cfg(test)]
+#[path = "cache_test.rs"]
+mod test;
+
+/// Version of the format of cache entries, bumped on each incompatible change.
+const FORMAT_VERSION: u32 = 1;
+
+/// Diagnostics of a single file, as published to the client.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CachedFileDiagnostics {
+    pub uri: Url,
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+/// A fingerprint of the inputs of a crate analysis.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct CrateKey {
+    fingerprint: String,
+}
+
+impl CrateKey {
+    /// Name of the file storing the cache entry for this key.
+    fn file_name(&self) -> String {
+        let mut hasher = DefaultHasher::new();
+        self.fingerprint.hash(&mut hasher);
+        format!("{:016x}.json", hasher.finish())
+    }
+}
+
+/// A cache entry, as stored on disk.
+#[derive(Serialize, Deserialize)]
+struct CacheEntry {
+    /// The full fingerprint, to detect collisions of file names.
+    fingerprint: String,
+    files: Vec<CachedFileDiagnostics>,
+}
+
+/// Cache of diagnostics of immutable crates, backed by the file system.
+pub struct DiagnosticsCache {
+    /// Directory of the cache entries, `None` if the cache is disabled.
+    dir: Option<PathBuf>,
+    /// Entries already read or written in this session.
+    entries: HashMap<CrateKey, Arc<[CachedFileDiagnostics]>>,
+    /// Keys of the entries which diagnostics were already published to the client.
+    published: HashSet<CrateKey>,
+    /// Fingerprints of immutable crates computed in this session, `None` for crates that cannot be
+    /// fingerprinted.
+    crate_fingerprints: HashMap<CrateId, Option<CrateFingerprint>>,
+}
+
+/// The fingerprint of a crate and its dependencies.
+#[derive(Default)]
+struct CrateFingerprint {
+    fingerprint: String,
+    /// The roots of the crate and its dependencies.
+    roots: Vec<PathBuf>,
+}
+
+impl DiagnosticsCache {
+    /// Creates a cache storing entries in the given directory, or a disabled one if `None`.
+    pub fn new(dir: Option<PathBuf>) -> Self {
+        Self {
+            dir: dir.map(|dir| dir.join(env!("CARGO_PKG_VERSION")).join("diagnostics")),
+            entries: Default::default(),
+            published: Default::default(),
+            crate_fingerprints: Default::default(),
+        }
+    }
+
+    /// Whether the cache is enabled.
+    pub fn is_enabled(&self) -> bool {
+        self.dir.is_some()
+    }
+
+    /// Computes the key of a crate, if its diagnostics can be cached.
+    ///
+    /// Only `core` and crates rooted in one of `immutable_roots` can be cached, and only as long as
+    /// none of their files is overridden (i.e. opened in the editor).
+    pub fn crate_key(
+        &mut self,
+        db: &AnalysisDatabase,
+        crate_id: CrateId,
+        immutable_roots: &[PathBuf],
+        trace_macro_diagnostics: bool,
+    ) -> Option<CrateKey> {
+        if !self.is_enabled() {
+            return None;
+        }
+        let root = crate_root(db, crate_id)?;
+        let is_immutable = crate_id.name(db) == CORELIB_CRATE_NAME
+            || immutable_roots.iter().any(|immutable_root| root.starts_with(immutable_root));
+        if !is_immutable {
+            return None;
+        }
+        let crate_fingerprint = self
+            .crate_fingerprints
+            .entry(crate_id)
+            .or_insert_with(|| {
+                let mut crate_fingerprint = CrateFingerprint::default();
+                write_crate_fingerprint(
+                    db,
+                    crate_id,
+                    &mut crate_fingerprint,
+                    &mut HashSet::default(),
+                )
+                .map(|()| crate_fingerprint)
+            })
+            .as_ref()?;
+        let is_overridden = db.file_overrides().keys().any(|file_id| {
+            matches!(file_id.lookup_intern(db), FileLongId::OnDisk(path) if crate_fingerprint
+                .roots
+                .iter()
+                .any(|root| path.starts_with(root)))
+        });
+        if is_overridden {
+            return None;
+        }
+
+        let mut fingerprint = format!(
+            "format {FORMAT_VERSION}\nversion {}\ntrace_macro_diagnostics \
+             {trace_macro_diagnostics}\ncfg {}\n",
+            env!("CARGO_PKG_VERSION"),
+            serde_json::to_string(&*db.cfg_set()).ok()?,
+        );
+        for plugin in db.macro_plugins() {
+            writeln!(fingerprint, "plugin {plugin:?}").ok()?;
+        }
+        let mut inline_macros = db.inline_macro_plugins().keys().cloned().collect::<Vec<_>>();
+        inline_macros.sort();
+        writeln!(fingerprint, "inline_macros {}", inline_macros.join(" ")).ok()?;
+        fingerprint.push_str(&crate_fingerprint.fingerprint);
+        Some(CrateKey { fingerprint })
+    }
+
+    /// Returns the cached diagnostics of the crate with the given key, if there are any.
+    pub fn get(&mut self, key: &CrateKey) -> Option<Arc<[CachedFileDiagnostics]>> {
+        if let Some(files) = self.entries.get(key) {
+            return Some(files.clone());
+        }
+        let path = self.dir.as_ref()?.join(key.file_name());
+        let content = fs::read_to_string(path).ok()?;
+        let entry: CacheEntry = serde_json::from_str(&content)
+            .inspect_err(|err| warn!("failed to read diagnostics cache entry: {err:?}"))
+            .ok()?;
+        if entry.fingerprint != key.fingerprint {
+            return None;
+        }
+        let files: Arc<[CachedFileDiagnostics]> = entry.files.into();
+        self.entries.insert(key.clone(), files.clone());
+        Some(files)
+    }
+
+    /// Stores the diagnostics of the crate with the given key.
+    pub fn insert(&mut self, key: CrateKey, files: Vec<CachedFileDiagnostics>) {
+        let Some(dir) = &self.dir else { return };
+        let entry = CacheEntry { fingerprint: key.fingerprint.clone(), files };
+        if let Err(err) = write_entry(dir, &key.file_name(), &entry) {
+            warn!("failed to write diagnostics cache entry: {err:?}");
+        }
+        self.entries.insert(key, entry.files.into());
+    }
+
+    /// Marks the diagnostics of the crate with the given key as published to the client.
+    ///
+    /// Returns `false` if they were already published.
+    pub fn mark_published(&mut self, key: CrateKey) -> bool {
+        self.published.insert(key)
+    }
+}
+
+/// Returns the root directory of a crate on disk.
+fn crate_root(db: &AnalysisDatabase, crate_id: CrateId) -> Option<PathBuf> {
+    match db.crate_config(crate_id)?.root {
+        Directory::Real(root) => Some(root),
+        Directory::Virtual { .. } => None,
+    }
+}
+
+/// Appends the fingerprint of a crate and its dependencies to `crate_fingerprint`.
+///
+/// Returns `None` if any of these crates is not on disk.
+fn write_crate_fingerprint(
+    db: &AnalysisDatabase,
+    crate_id: CrateId,
+    crate_fingerprint: &mut CrateFingerprint,
+    visited: &mut HashSet<CrateId>,
+) -> Option<()> {
+    if !visited.insert(crate_id) {
+        return Some(());
+    }
+    let CrateLongId::Real { name, discriminator } = crate_id.lookup_intern(db) else {
+        return None;
+    };
+    let config = db.crate_config(crate_id)?;
+    let root = crate_root(db, crate_id)?;
+    let fingerprint = &mut crate_fingerprint.fingerprint;
+    writeln!(
+        fingerprint,
+        "crate {name} {discriminator:?} {}\nsettings {}",
+        root.display(),
+        serde_json::to_string(&config.settings).ok()?
+    )
+    .ok()?;
+    let mut files = vec![];
+    collect_cairo_files(&root, &mut files).ok()?;
+    files.sort();
+    for path in files {
+        let metadata = fs::metadata(&path).ok()?;
+        let modified = metadata.modified().ok()?.duration_since(UNIX_EPOCH).ok()?;
+        writeln!(fingerprint, "file {} {} {}", path.display(), metadata.len(), modified.as_nanos())
+            .ok()?;
+    }
+    crate_fingerprint.roots.push(root);
+
+    // `core` is an implicit dependency of every crate.
+    let dependencies = config
+        .settings
+        .dependencies
+        .iter()
+        .map(|(name, dependency)| {
+            CrateLongId::Real { name: name.into(), discriminator: dependency.discriminator.clone() }
+                .intern(db)
+        })
+        .chain([CrateId::core(db)]);
+    for dependency_id in dependencies {
+        if db.crate_config(dependency_id).is_some() {
+            write_crate_fingerprint(db, dependency_id, crate_fingerprint, visited)?;
+        }
+    }
+    Some(())
+}
+
+/// Collects the paths of all Cairo files in a directory, recursively.
+fn collect_cairo_files(dir: &Path, files: &mut Vec<PathBuf>) -> std::io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            collect_cairo_files(&path, files)?;
+        } else if path.extension().is_some_and(|extension| extension == "cairo") {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// Writes a cache entry atomically, so that concurrent language servers never read partial data.
+fn write_entry(dir: &Path, file_name: &str, entry: &CacheEntry) -> anyhow::Result<()> {
+    fs::create_dir_all(dir)?;
+    let mut file = tempfile::NamedTempFile::new_in(dir)?;
+    file.write_all(serde_json::to_string(entry)?.as_bytes())?;
+    file.persist(dir.join(file_name))?;
+    Ok(())
+}
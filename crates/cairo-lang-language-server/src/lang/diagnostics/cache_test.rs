@@ -0,0 +1,100 @@
+use assert_fs::TempDir;
+use assert_fs::prelude::*;
+use cairo_lang_filesystem::db::{CrateConfiguration, FilesGroupEx};
+use cairo_lang_filesystem::ids::{CrateId, Directory, FileId};
+use lsp_types::{Diagnostic, Range, Url};
+
+use super::{CachedFileDiagnostics, DiagnosticsCache};
+use crate::Tricks;
+use crate::lang::db::AnalysisDatabase;
+
+/// Creates a database with a single crate named `dep`, rooted in `root`.
+fn setup_db(root: &TempDir) -> (AnalysisDatabase, CrateId) {
+    let mut db = AnalysisDatabase::new(&Tricks::default());
+    let crate_id = CrateId::plain(&db, "dep");
+    db.set_crate_config(
+        crate_id,
+        Some(CrateConfiguration::default_for_root(Directory::Real(root.path().to_path_buf()))),
+    );
+    (db, crate_id)
+}
+
+fn cached_files(root: &TempDir) -> Vec<CachedFileDiagnostics> {
+    vec![CachedFileDiagnostics {
+        uri: Url::from_file_path(root.child("lib.cairo").path()).unwrap(),
+        diagnostics: vec![Diagnostic {
+            range: Range::default(),
+            message: "Unused variable.".into(),
+            ..Default::default()
+        }],
+    }]
+}
+
+#[test]
+fn round_trip() {
+    let cache_dir = TempDir::new().unwrap();
+    let deps_dir = TempDir::new().unwrap();
+    deps_dir.child("lib.cairo").write_str("fn foo() {}").unwrap();
+    let (db, crate_id) = setup_db(&deps_dir);
+    let immutable_roots = [deps_dir.path().to_path_buf()];
+
+    let mut cache = DiagnosticsCache::new(Some(cache_dir.path().to_path_buf()));
+    let key = cache.crate_key(&db, crate_id, &immutable_roots, false).unwrap();
+    assert!(cache.get(&key).is_none());
+    cache.insert(key.clone(), cached_files(&deps_dir));
+
+    // A new cache instance, as in a new language server run, reads the entry from disk.
+    let mut cache = DiagnosticsCache::new(Some(cache_dir.path().to_path_buf()));
+    assert_eq!(cache.crate_key(&db, crate_id, &immutable_roots, false), Some(key.clone()));
+    assert_eq!(cache.get(&key).unwrap().to_vec(), cached_files(&deps_dir));
+    assert!(cache.mark_published(key.clone()));
+    assert!(!cache.mark_published(key));
+}
+
+#[test]
+fn invalidated_by_inputs_changes() {
+    let cache_dir = TempDir::new().unwrap();
+    let deps_dir = TempDir::new().unwrap();
+    deps_dir.child("lib.cairo").write_str("fn foo() {}").unwrap();
+    let (db, crate_id) = setup_db(&deps_dir);
+    let immutable_roots = [deps_dir.path().to_path_buf()];
+
+    let mut cache = DiagnosticsCache::new(Some(cache_dir.path().to_path_buf()));
+    let key = cache.crate_key(&db, crate_id, &immutable_roots, false).unwrap();
+    cache.insert(key.clone(), cached_files(&deps_dir));
+
+    let other_key = cache.crate_key(&db, crate_id, &immutable_roots, true).unwrap();
+    assert_ne!(other_key, key);
+    assert!(cache.get(&other_key).is_none());
+
+    // Files of immutable crates are only fingerprinted once per session, so changes are detected
+    // in the next session.
+    deps_dir.child("lib.cairo").write_str("fn foo() { bar() }").unwrap();
+    assert_eq!(cache.crate_key(&db, crate_id, &immutable_roots, false), Some(key.clone()));
+    let mut cache = DiagnosticsCache::new(Some(cache_dir.path().to_path_buf()));
+    let changed_key = cache.crate_key(&db, crate_id, &immutable_roots, false).unwrap();
+    assert_ne!(changed_key, key);
+    assert!(cache.get(&changed_key).is_none());
+}
+
+#[test]
+fn mutable_crates_are_not_cached() {
+    let cache_dir = TempDir::new().unwrap();
+    let deps_dir = TempDir::new().unwrap();
+    deps_dir.child("lib.cairo").write_str("fn foo() {}").unwrap();
+    let (mut db, crate_id) = setup_db(&deps_dir);
+    let immutable_roots = [deps_dir.path().to_path_buf()];
+
+    let mut cache = DiagnosticsCache::new(Some(cache_dir.path().to_path_buf()));
+    assert!(cache.crate_key(&db, crate_id, &[], false).is_none());
+    assert!(
+        DiagnosticsCache::new(None).crate_key(&db, crate_id, &immutable_roots, false).is_none()
+    );
+
+    assert!(cache.crate_key(&db, crate_id, &immutable_roots, false).is_some());
+    let file_id = FileId::new(&db, deps_dir.child("lib.cairo").path().to_path_buf());
+    db.override_file_content(file_id, Some("fn bar() {}".into()));
+    assert!(cache.crate_key(&db, crate_id, &immutable_roots, false).is_none());
+    db.override_file_content(file_id, None);
+    assert!(cache.crate_key(&db, crate_id, &immutable_roots, false).is_some());
+}
@@ -1,3 +1,4 @@
+pub mod cache;
 pub mod lsp;
 // TODO(mkaput): This is pub temporarily.
 pub mod refresh;
@@ -1,11 +1,12 @@
 use std::collections::{HashMap, HashSet};
 use std::panic::{AssertUnwindSafe, catch_unwind};
+use std::path::Path;
 
 use cairo_lang_defs::db::DefsGroup;
 use cairo_lang_defs::ids::ModuleId;
 use cairo_lang_diagnostics::Diagnostics;
 use cairo_lang_filesystem::db::FilesGroup;
-use cairo_lang_filesystem::ids::FileId;
+use cairo_lang_filesystem::ids::{CrateId, FileId};
 use cairo_lang_lowering::db::LoweringGroup;
 use cairo_lang_lowering::diagnostic::LoweringDiagnostic;
 use cairo_lang_parser::db::ParserGroup;
@@ -13,10 +14,11 @@ use cairo_lang_semantic::SemanticDiagnostic;
 use cairo_lang_semantic::db::SemanticGroup;
 use cairo_lang_utils::Upcast;
 use lsp_types::notification::PublishDiagnostics;
-use lsp_types::{PublishDiagnosticsParams, Url};
+use lsp_types::{Diagnostic, PublishDiagnosticsParams, Url};
 use tracing::{error, trace_span};
 
 use crate::lang::db::AnalysisDatabase;
+use crate::lang::diagnostics::cache::{CachedFileDiagnostics, CrateKey};
 use crate::lang::diagnostics::lsp::map_cairo_diagnostics_to_lsp;
 use crate::lang::lsp::LsProtoGroup;
 use crate::server::client::Notifier;
@@ -52,33 +54,68 @@ pub fn refresh_diagnostics(state: &mut State, notifier: Notifier) {
         }
     });
 
-    let rest_of_files = trace_span!("get_rest_of_files").in_scope(|| {
-        let mut rest_of_files: HashSet<FileId> = HashSet::default();
+    // Refresh rest of files after, since they are not viewed currently.
+    // Diagnostics of immutable crates are restored from the cache if possible.
+    trace_span!("refresh_other_files_modules").in_scope(|| {
+        let immutable_roots = if state.diagnostics_cache.is_enabled() {
+            state.scarb_toolchain.silent().cache_path().map(Path::to_path_buf).into_iter().collect()
+        } else {
+            vec![]
+        };
+        let trace_macro_diagnostics = state.config.trace_macro_diagnostics;
+
         for crate_id in state.db.crates() {
-            for module_id in state.db.crate_modules(crate_id).iter() {
-                if let Ok(module_files) = state.db.module_files(*module_id) {
-                    let unprocessed_files =
-                        module_files.iter().filter(|file| !open_files_ids.contains(file));
-                    rest_of_files.extend(unprocessed_files);
+            let key = state.diagnostics_cache.crate_key(
+                &state.db,
+                crate_id,
+                &immutable_roots,
+                trace_macro_diagnostics,
+            );
+            if let Some(key) = &key {
+                if let Some(cached_files) = state.diagnostics_cache.get(key) {
+                    publish_cached_diagnostics(
+                        state,
+                        key.clone(),
+                        &cached_files,
+                        &mut files_with_set_diagnostics,
+                        &notifier,
+                    );
+                    continue;
                 }
             }
-        }
-        rest_of_files
-    });
-
-    let rest_of_files_modules = get_files_modules(&state.db, rest_of_files.iter().copied());
 
-    // Refresh rest of files after, since they are not viewed currently
-    trace_span!("refresh_other_files_modules").in_scope(|| {
-        for (file, file_modules_ids) in rest_of_files_modules {
-            refresh_file_diagnostics(
-                state,
-                &file,
-                &file_modules_ids,
-                &mut processed_modules,
-                &mut files_with_set_diagnostics,
-                &notifier,
-            );
+            let crate_files = get_crate_files(&state.db, crate_id, &open_files_ids);
+            let crate_files_modules = get_files_modules(&state.db, crate_files.into_iter());
+            let mut files_to_cache = key.is_some().then(Vec::new);
+            for (file, file_modules_ids) in crate_files_modules {
+                let published_diagnostics = refresh_file_diagnostics(
+                    state,
+                    &file,
+                    &file_modules_ids,
+                    &mut processed_modules,
+                    &mut files_with_set_diagnostics,
+                    &notifier,
+                );
+                let Some(files_to_cache) = &mut files_to_cache else { continue };
+                let uri = state.db.url_for_file(file);
+                // Unchanged diagnostics are not published again, so they are taken from the state.
+                let diagnostics = published_diagnostics.unwrap_or_else(|| {
+                    to_lsp_diagnostics(
+                        &state.db,
+                        &file,
+                        &state.file_diagnostics[&uri],
+                        trace_macro_diagnostics,
+                    )
+                });
+                // Virtual files get different URLs in each run.
+                if !diagnostics.is_empty() && uri.scheme() == "file" {
+                    files_to_cache.push(CachedFileDiagnostics { uri, diagnostics });
+                }
+            }
+            if let (Some(key), Some(files_to_cache)) = (key, files_to_cache) {
+                state.diagnostics_cache.mark_published(key.clone());
+                state.diagnostics_cache.insert(key, files_to_cache);
+            }
         }
     });
 
@@ -106,7 +143,32 @@ pub fn refresh_diagnostics(state: &mut State, notifier: Notifier) {
     });
 }
 
+/// Publishes diagnostics of a crate restored from the cache, unless this was already done.
+fn publish_cached_diagnostics(
+    state: &mut State,
+    key: CrateKey,
+    cached_files: &[CachedFileDiagnostics],
+    files_with_set_diagnostics: &mut HashSet<Url>,
+    notifier: &Notifier,
+) {
+    files_with_set_diagnostics.extend(cached_files.iter().map(|file| file.uri.clone()));
+    if !state.diagnostics_cache.mark_published(key) {
+        return;
+    }
+    for file in cached_files {
+        trace_span!("publish_diagnostics").in_scope(|| {
+            notifier.notify::<PublishDiagnostics>(PublishDiagnosticsParams {
+                uri: file.uri.clone(),
+                diagnostics: file.diagnostics.clone(),
+                version: None,
+            });
+        });
+    }
+}
+
 /// Refresh diagnostics for a single file.
+///
+/// Returns the published diagnostics, or `None` if they did not change.
 fn refresh_file_diagnostics(
     state: &mut State,
     file: &FileId,
@@ -114,7 +176,7 @@ fn refresh_file_diagnostics(
     processed_modules: &mut HashSet<ModuleId>,
     files_with_set_diagnostics: &mut HashSet<Url>,
     notifier: &Notifier,
-) {
+) -> Option<Vec<Diagnostic>> {
     let db = &state.db;
     let file_uri = db.url_for_file(*file);
     let mut semantic_file_diagnostics: Vec<SemanticDiagnostic> = vec![];
@@ -163,43 +225,70 @@ fn refresh_file_diagnostics(
     // Since we are using Arcs, this comparison should be efficient.
     if let Some(old_file_diagnostics) = state.file_diagnostics.get(&file_uri) {
         if old_file_diagnostics == &new_file_diagnostics {
-            return;
+            return None;
         }
 
         state.file_diagnostics.insert(file_uri.clone(), new_file_diagnostics.clone());
     };
 
+    let diags =
+        to_lsp_diagnostics(db, file, &new_file_diagnostics, state.config.trace_macro_diagnostics);
+
+    trace_span!("publish_diagnostics").in_scope(|| {
+        notifier.notify::<PublishDiagnostics>(PublishDiagnosticsParams {
+            uri: file_uri,
+            diagnostics: diags.clone(),
+            version: None,
+        });
+    });
+    Some(diags)
+}
+
+/// Maps the diagnostics of a file to their LSP representation.
+fn to_lsp_diagnostics(
+    db: &AnalysisDatabase,
+    file: &FileId,
+    file_diagnostics: &FileDiagnostics,
+    trace_macro_diagnostics: bool,
+) -> Vec<Diagnostic> {
     let mut diags = Vec::new();
-    let trace_macro_diagnostics = state.config.trace_macro_diagnostics;
     map_cairo_diagnostics_to_lsp(
-        (*db).upcast(),
+        db.upcast(),
         &mut diags,
-        &new_file_diagnostics.parser,
+        &file_diagnostics.parser,
         file,
         trace_macro_diagnostics,
     );
     map_cairo_diagnostics_to_lsp(
-        (*db).upcast(),
+        db.upcast(),
         &mut diags,
-        &new_file_diagnostics.semantic,
+        &file_diagnostics.semantic,
         file,
         trace_macro_diagnostics,
     );
     map_cairo_diagnostics_to_lsp(
-        (*db).upcast(),
+        db.upcast(),
         &mut diags,
-        &new_file_diagnostics.lowering,
+        &file_diagnostics.lowering,
         file,
         trace_macro_diagnostics,
     );
+    diags
+}
 
-    trace_span!("publish_diagnostics").in_scope(|| {
-        notifier.notify::<PublishDiagnostics>(PublishDiagnosticsParams {
-            uri: file_uri,
-            diagnostics: diags,
-            version: None,
-        });
-    })
+/// Gets the files of a crate modules, except the given ones.
+fn get_crate_files(
+    db: &AnalysisDatabase,
+    crate_id: CrateId,
+    excluded_files_ids: &HashSet<FileId>,
+) -> HashSet<FileId> {
+    let mut result = HashSet::default();
+    for module_id in db.crate_modules(crate_id).iter() {
+        if let Ok(module_files) = db.module_files(*module_id) {
+            result.extend(module_files.iter().filter(|file| !excluded_files_ids.contains(file)));
+        }
+    }
+    result
 }
 
 /// Gets the mapping of files to their respective modules.
@@ -15,7 +15,7 @@ use cairo_lang_semantic::items::functions::GenericFunctionId;
 use cairo_lang_semantic::items::imp::ImplLongId;
 use cairo_lang_semantic::lookup_item::LookupItemEx;
 use cairo_lang_semantic::resolve::{ResolvedConcreteItem, ResolvedGenericItem};
-use cairo_lang_semantic::{Binding, Expr, Mutability, TypeLongId};
+use cairo_lang_semantic::{Binding, Expr, Mutability, TypeId, TypeLongId};
 use cairo_lang_syntax::node::ast::{Param, PatternIdentifier, PatternPtr, TerminalIdentifier};
 use cairo_lang_syntax::node::ids::SyntaxStablePtrId;
 use cairo_lang_syntax::node::kind::SyntaxKind;
@@ -28,6 +28,7 @@ use tracing::error;
 
 use crate::lang::db::{AnalysisDatabase, LsSemanticGroup, LsSyntaxGroup};
 use crate::lang::inspect::defs::SymbolDef::Member;
+use crate::lang::inspect::origin::{MemberOrigin, find_item_origin};
 
 /// Keeps information about the symbol that is being searched for/inspected.
 ///
@@ -44,6 +45,8 @@ pub enum SymbolDef {
 pub struct MemberDef {
     pub member: MemberId,
     pub structure: ItemDef,
+    /// The user-written declaration of the member, if it was generated by a plugin.
+    pub origin: Option<MemberOrigin>,
 }
 
 /// Either [`ResolvedGenericItem`], [`ResolvedConcreteItem`] or [`MemberId`].
@@ -102,10 +105,19 @@ impl SymbolDef {
             ResolvedItem::Generic(ResolvedGenericItem::Variable(_)) => {
                 VariableDef::new(db, definition_node).map(Self::Variable)
             }
-            ResolvedItem::Member(member_id) => Some(Member(MemberDef {
-                member: member_id,
-                structure: ItemDef::new(db, &definition_node)?,
-            })),
+            ResolvedItem::Member(member_id) => {
+                let origin = MemberOrigin::find(db, member_id);
+                // Present the user-written struct instead of the generated one, if there is any.
+                let structure_node = match &origin {
+                    Some(origin) => origin.structure.as_syntax_node(),
+                    None => definition_node,
+                };
+                Some(Member(MemberDef {
+                    member: member_id,
+                    structure: ItemDef::new(db, &structure_node)?,
+                    origin,
+                }))
+            }
         }
     }
 }
@@ -127,11 +139,17 @@ pub struct ItemDef {
 
 impl ItemDef {
     /// Constructs new [`ItemDef`] instance.
+    ///
+    /// Items generated by plugins are replaced with the user-written items they originate from.
     fn new(db: &AnalysisDatabase, definition_node: &SyntaxNode) -> Option<Self> {
         let mut lookup_item_ids = db.collect_lookup_items_stack(definition_node)?.into_iter();
 
         // Pull the lookup item representing the defining item.
         let lookup_item_id = lookup_item_ids.next()?;
+        if let Some(origin) = find_item_origin(db, lookup_item_id, definition_node) {
+            let origin_node = origin.untyped_stable_ptr(db.upcast()).lookup(db.upcast());
+            return Self::new(db, &origin_node);
+        }
 
         // Collect context items.
         let context_items = lookup_item_ids
@@ -150,6 +168,11 @@ impl ItemDef {
         Some(Self { lookup_item_id, context_items })
     }
 
+    /// Gets the [`LookupItemId`] of the item.
+    pub fn lookup_item_id(&self) -> LookupItemId {
+        self.lookup_item_id
+    }
+
     /// Get item signature without its body including signatures of its contexts.
     pub fn signature(&self, db: &AnalysisDatabase) -> String {
         let contexts = self.context_items.iter().copied().rev();
@@ -259,6 +282,11 @@ impl VariableDef {
         Some(Self { name, var })
     }
 
+    /// Gets the type of the variable.
+    pub fn ty(&self) -> TypeId {
+        self.var.ty()
+    }
+
     /// Gets variable signature, which tries to resemble the way how it is defined in code.
     pub fn signature(&self, db: &AnalysisDatabase) -> String {
         let Self { name, var } = self;
@@ -381,7 +409,11 @@ fn try_extract_member(
 
     let expr_id =
         db.lookup_expr_by_ptr(function_with_body, binary_expr.stable_ptr().into()).ok()?;
-    let semantic_expr = db.expr_semantic(function_with_body, expr_id);
+    let mut semantic_expr = db.expr_semantic(function_with_body, expr_id);
+    // Receivers of method calls may be wrapped in snapshots sharing the same stable pointer.
+    while let Expr::Snapshot(snapshot) = semantic_expr {
+        semantic_expr = db.expr_semantic(function_with_body, snapshot.inner);
+    }
 
     if let Expr::MemberAccess(expr_member_access) = semantic_expr {
         let pointer_to_rhs = binary_expr.rhs(db).stable_ptr().untyped();
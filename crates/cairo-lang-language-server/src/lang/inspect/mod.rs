@@ -2,3 +2,5 @@
 
 pub mod crates;
 pub mod defs;
+pub mod origin;
+pub mod references;
@@ -0,0 +1,119 @@
+//! Mapping of items generated by compiler plugins back to the user code they originate from.
+//!
+//! Plugins, like the Starknet one, generate items in virtual files: storage base structs of
+//! contracts, dispatchers of interfaces, component states, etc.
+//! The user never sees these files, so it is more helpful to present the user-written declarations
+//! these items are generated from instead.
+
+use cairo_lang_defs::db::DefsGroup;
+use cairo_lang_defs::ids::{LookupItemId, MemberId, NamedLanguageElementId};
+use cairo_lang_diagnostics::ToOption;
+use cairo_lang_doc::db::get_node_outer_documentation;
+use cairo_lang_filesystem::db::get_originating_location;
+use cairo_lang_parser::db::ParserGroup;
+use cairo_lang_syntax::node::kind::SyntaxKind;
+use cairo_lang_syntax::node::{SyntaxNode, Terminal, TypedStablePtr, TypedSyntaxNode, ast};
+use cairo_lang_utils::Upcast;
+use smol_str::SmolStr;
+
+use crate::lang::db::{AnalysisDatabase, LsSemanticGroup, LsSyntaxGroup};
+
+/// The user-written declaration of a struct member generated by a plugin.
+///
+/// For example, the members of storage base structs of contracts originate from the members of
+/// their `Storage` structs.
+pub struct MemberOrigin {
+    /// The user-written member.
+    pub member: ast::Member,
+    /// The user-written struct containing the member.
+    pub structure: ast::ItemStruct,
+}
+
+impl MemberOrigin {
+    /// Finds the origin of a member, if it was generated by a plugin.
+    pub fn find(db: &AnalysisDatabase, member_id: MemberId) -> Option<Self> {
+        let syntax_db = db.upcast();
+        let name = member_id.stable_ptr(db).lookup(syntax_db).name(syntax_db);
+        let origin_node = find_origin_node(db, &name.as_syntax_node())?;
+        let member = ast::Member::from_syntax_node(
+            syntax_db,
+            db.first_ancestor_of_kind(origin_node, SyntaxKind::Member)?,
+        );
+        if member.name(syntax_db).text(syntax_db) != name.text(syntax_db) {
+            return None;
+        }
+        let structure = ast::ItemStruct::from_syntax_node(
+            syntax_db,
+            db.first_ancestor_of_kind(member.as_syntax_node(), SyntaxKind::ItemStruct)?,
+        );
+        Some(Self { member, structure })
+    }
+
+    /// Gets the member declaration, as written by the user, e.g. `value: u128`.
+    pub fn signature(&self, db: &AnalysisDatabase) -> String {
+        let syntax_db = db.upcast();
+        format!("{}: {}", self.member.name(syntax_db).text(syntax_db), self.ty(db))
+    }
+
+    /// Gets the header of the containing struct, e.g. `struct Storage`.
+    pub fn structure_signature(&self, db: &AnalysisDatabase) -> String {
+        let syntax_db = db.upcast();
+        format!("struct {}", self.structure.name(syntax_db).text(syntax_db))
+    }
+
+    /// Gets the member type, as written by the user.
+    pub fn ty(&self, db: &AnalysisDatabase) -> String {
+        let syntax_db = db.upcast();
+        self.member
+            .type_clause(syntax_db)
+            .ty(syntax_db)
+            .as_syntax_node()
+            .get_text_without_trivia(syntax_db)
+    }
+
+    /// Gets the member documentation in a final form usable for display.
+    pub fn documentation(&self, db: &AnalysisDatabase) -> Option<String> {
+        get_node_outer_documentation(db.upcast(), &self.member.as_syntax_node())
+    }
+}
+
+/// Finds the user-written item, which the item defined at `definition_node` was generated from.
+///
+/// For example, dispatcher trait functions originate from the functions of their interface
+/// traits.
+/// The origin is only accepted if it has the same name as the generated item.
+pub fn find_item_origin(
+    db: &AnalysisDatabase,
+    lookup_item_id: LookupItemId,
+    definition_node: &SyntaxNode,
+) -> Option<LookupItemId> {
+    let origin_node = find_origin_node(db, definition_node)?;
+    let origin = db.find_lookup_item(&origin_node)?;
+    (origin != lookup_item_id
+        && lookup_item_name(db, origin) == lookup_item_name(db, lookup_item_id))
+    .then_some(origin)
+}
+
+/// Finds the innermost user-written node at the origin of the generated code starting at `node`.
+///
+/// Returns `None` if `node` is not a part of generated code.
+fn find_origin_node(db: &AnalysisDatabase, node: &SyntaxNode) -> Option<SyntaxNode> {
+    let syntax_db = db.upcast();
+    let file_id = node.stable_ptr().file_id(syntax_db);
+    let span = node.span_without_trivia(syntax_db).start_only();
+    let (origin_file_id, origin_span) = get_originating_location(db.upcast(), file_id, span);
+    if origin_file_id == file_id {
+        return None;
+    }
+    Some(db.file_syntax(origin_file_id).to_option()?.lookup_offset(syntax_db, origin_span.start))
+}
+
+/// Gets the name of a lookup item.
+fn lookup_item_name(db: &AnalysisDatabase, lookup_item_id: LookupItemId) -> SmolStr {
+    let defs_db: &dyn DefsGroup = db.upcast();
+    match lookup_item_id {
+        LookupItemId::ModuleItem(item) => item.name(defs_db),
+        LookupItemId::TraitItem(item) => item.name(defs_db),
+        LookupItemId::ImplItem(item) => item.name(defs_db),
+    }
+}
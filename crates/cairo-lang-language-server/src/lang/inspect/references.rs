@@ -0,0 +1,344 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use cairo_lang_defs::db::DefsGroup;
+use cairo_lang_defs::ids::{ImplItemId, LanguageElementId, LookupItemId, MemberId};
+use cairo_lang_filesystem::db::{FilesGroup, get_originating_location};
+use cairo_lang_filesystem::ids::{CrateId, FileId, FileLongId};
+use cairo_lang_filesystem::span::{TextOffset, TextPosition, TextSpan};
+use cairo_lang_parser::db::ParserGroup;
+use cairo_lang_semantic::db::SemanticGroup;
+use cairo_lang_semantic::items::function_with_body::SemanticExprLookup;
+use cairo_lang_semantic::lookup_item::LookupItemEx;
+use cairo_lang_semantic::{Expr, Pattern};
+use cairo_lang_syntax::node::ids::SyntaxStablePtrId;
+use cairo_lang_syntax::node::kind::SyntaxKind;
+use cairo_lang_syntax::node::{SyntaxNode, Terminal, TypedSyntaxNode, ast};
+use cairo_lang_utils::{LookupIntern, Upcast};
+use salsa::InternKey;
+use smol_str::SmolStr;
+
+use crate::lang::db::{AnalysisDatabase, LsSemanticGroup, LsSyntaxGroup};
+use crate::lang::inspect::defs::find_definition;
+
+/// A symbol, identified by the location in user code of the name in its definition.
+///
+/// Definitions generated by compiler plugins are identified by the user code they originate from,
+/// so a symbol and the code generated from it are considered the same symbol.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct SymbolId {
+    file: FileId,
+    offset: TextOffset,
+}
+
+/// The way a reference to a symbol is written.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReferenceKind {
+    /// The name of the symbol.
+    Plain,
+    /// A struct member shorthand, e.g. `x` in `S { x }`, referring to the struct member.
+    ShorthandMember,
+    /// A struct member shorthand, e.g. `x` in `S { x }`, referring to the variable.
+    ShorthandVariable,
+}
+
+/// A reference to a symbol in user code, including its definition.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Reference {
+    pub file: FileId,
+    /// The span of the name of the symbol.
+    pub span: TextSpan,
+    pub kind: ReferenceKind,
+    /// Whether this is the name in the definition of the symbol.
+    pub is_definition: bool,
+}
+
+/// A symbol found at a position, with all its references.
+pub struct SymbolReferences {
+    /// The name of the symbol, as written at the searched position.
+    pub name: SmolStr,
+    /// Whether the definition of the symbol is in user written code.
+    pub is_definition_in_user_code: bool,
+    /// The references, sorted by location.
+    pub references: Vec<Reference>,
+}
+
+/// Language server queries of the references to symbols.
+#[salsa::query_group(LsReferencesDatabase)]
+pub trait LsReferencesGroup: Upcast<AnalysisDatabase> {
+    /// Returns the references in the given file of the identifiers named `name`, with the symbols
+    /// they refer to.
+    fn file_references(&self, file: FileId, name: SmolStr) -> Arc<[(SymbolId, Reference)]>;
+}
+
+fn file_references(
+    db: &dyn LsReferencesGroup,
+    file: FileId,
+    name: SmolStr,
+) -> Arc<[(SymbolId, Reference)]> {
+    let db: &AnalysisDatabase = db.upcast();
+    let Ok(syntax) = db.file_module_syntax(file) else { return [].into() };
+    let mut references = vec![];
+    for node in syntax.as_syntax_node().descendants(db.upcast()) {
+        if node.kind(db.upcast()) != SyntaxKind::TerminalIdentifier {
+            continue;
+        }
+        let identifier = ast::TerminalIdentifier::from_syntax_node(db, node);
+        if identifier.text(db) != name {
+            continue;
+        }
+        let Some((file, span)) = user_location(db, &identifier, &name) else {
+            continue;
+        };
+        for (symbol, kind) in symbols_of_identifier(db, &identifier) {
+            let is_definition =
+                symbol_of_definition_ptr(db, identifier.as_syntax_node().stable_ptr(), &name)
+                    == Some(symbol);
+            references.push((symbol, Reference { file, span, kind, is_definition }));
+        }
+    }
+    references.into()
+}
+
+/// A reverse index from symbols to their references, of all the identifiers with a given name in
+/// the analyzed crates.
+///
+/// The identifiers are resolved using the same lookup items and resolved items as goto definition,
+/// so the index agrees with the definitions the user navigates to. The references of each file are
+/// computed by [`LsReferencesGroup::file_references`], so only files affected by changes are
+/// indexed again.
+struct ReferencesIndex {
+    references: HashMap<SymbolId, Vec<Reference>>,
+}
+
+impl ReferencesIndex {
+    /// Builds the index of the identifiers named `name` in the given crates.
+    fn build(db: &AnalysisDatabase, name: &SmolStr, crates: impl Iterator<Item = CrateId>) -> Self {
+        let mut references = HashMap::<SymbolId, Vec<Reference>>::new();
+        for crate_id in crates {
+            for module_id in db.crate_modules(crate_id).iter() {
+                let Ok(files) = db.module_files(*module_id) else { continue };
+                for file in files.iter() {
+                    for (symbol, reference) in db.file_references(*file, name.clone()).iter() {
+                        references.entry(*symbol).or_default().push(*reference);
+                    }
+                }
+            }
+        }
+        // Code generated by plugins may be mapped to the same user code more than once.
+        for symbol_references in references.values_mut() {
+            symbol_references
+                .sort_by_key(|reference| (reference.file.as_intern_id(), reference.span.start));
+            symbol_references.dedup_by_key(|reference| (reference.file, reference.span));
+        }
+        Self { references }
+    }
+}
+
+/// Finds the symbol whose name is at the given position, and all its references in the analyzed
+/// crates.
+pub fn find_references(
+    db: &AnalysisDatabase,
+    file: FileId,
+    position: TextPosition,
+) -> Option<SymbolReferences> {
+    let identifier = db.find_identifier_at_position(file, position)?;
+    let name = identifier.text(db);
+    let (symbol, _) = symbols_of_identifier(db, &identifier).into_iter().next()?;
+
+    let definition_crate = crate_of_file(db, symbol.file);
+    let is_definition_in_user_code = matches!(symbol.file.lookup_intern(db), FileLongId::OnDisk(_))
+        && definition_crate.is_some_and(|crate_id| crate_id != CrateId::core(db));
+    // The core crate cannot refer to symbols of other crates.
+    let crates = db
+        .crates()
+        .into_iter()
+        .filter(|crate_id| *crate_id != CrateId::core(db) || definition_crate == Some(*crate_id));
+    let mut index = ReferencesIndex::build(db, &name, crates);
+    let references = index.references.remove(&symbol).unwrap_or_default();
+    Some(SymbolReferences { name, is_definition_in_user_code, references })
+}
+
+/// Returns the symbols an identifier refers to, with the kind of each reference.
+///
+/// Struct member shorthands refer both to the struct member and to a variable, and any other
+/// identifier refers to at most one symbol. The name in a definition refers to the defined symbol.
+fn symbols_of_identifier(
+    db: &AnalysisDatabase,
+    identifier: &ast::TerminalIdentifier,
+) -> Vec<(SymbolId, ReferenceKind)> {
+    let name = identifier.text(db);
+    let node = identifier.as_syntax_node();
+    let Some(parent) = node.parent() else { return vec![] };
+    let plain = |ptr: Option<SyntaxStablePtrId>| {
+        ptr.and_then(|ptr| symbol_of_definition_ptr(db, ptr, &name))
+            .map(|symbol| (symbol, ReferenceKind::Plain))
+            .into_iter()
+            .collect()
+    };
+    let shorthand = |member: Option<MemberId>, variable: Option<SyntaxStablePtrId>| {
+        let member = member
+            .and_then(|member| symbol_of_definition_ptr(db, member.untyped_stable_ptr(db), &name))
+            .map(|symbol| (symbol, ReferenceKind::ShorthandMember));
+        let variable = variable
+            .and_then(|ptr| symbol_of_definition_ptr(db, ptr, &name))
+            .map(|symbol| (symbol, ReferenceKind::ShorthandVariable));
+        member.into_iter().chain(variable).collect()
+    };
+
+    match parent.kind(db) {
+        // Names of definitions that are not lookup items.
+        SyntaxKind::Member | SyntaxKind::Variant | SyntaxKind::Param => {
+            plain(Some(parent.stable_ptr()))
+        }
+        SyntaxKind::PatternIdentifier => {
+            let Some(grandparent) = parent.parent() else { return vec![] };
+            if grandparent.kind(db) == SyntaxKind::PatternStructParamList {
+                // A shorthand in a struct pattern, e.g. `x` in `let S { x } = s;`.
+                let member = db
+                    .first_ancestor_of_kind(grandparent, SyntaxKind::PatternStruct)
+                    .and_then(|pattern| struct_member_in_pattern(db, pattern, &name));
+                shorthand(member, Some(node.stable_ptr()))
+            } else {
+                plain(Some(node.stable_ptr()))
+            }
+        }
+        // A single identifier pattern is parsed as a path, e.g. `x` in `let x = 1;`.
+        SyntaxKind::PathSegmentSimple => match parent.parent() {
+            Some(path) if is_variable_path_pattern(db, &path) => plain(Some(node.stable_ptr())),
+            _ => plain(definition_ptr_by_lookup(db, identifier)),
+        },
+        SyntaxKind::PatternStructParamWithExpr => plain(
+            db.first_ancestor_of_kind(parent, SyntaxKind::PatternStruct)
+                .and_then(|pattern| struct_member_in_pattern(db, pattern, &name))
+                .map(|member| member.untyped_stable_ptr(db)),
+        ),
+        SyntaxKind::StructArgSingle => {
+            let member = db
+                .first_ancestor_of_kind(parent.clone(), SyntaxKind::ExprStructCtorCall)
+                .and_then(|ctor| struct_member_in_ctor(db, ctor, &name));
+            let arg = ast::StructArgSingle::from_syntax_node(db, parent);
+            match arg.arg_expr(db) {
+                ast::OptionStructArgExpr::Empty(_) => {
+                    shorthand(member, definition_ptr_by_lookup(db, identifier))
+                }
+                ast::OptionStructArgExpr::StructArgExpr(_) => {
+                    plain(member.map(|member| member.untyped_stable_ptr(db)))
+                }
+            }
+        }
+        // The name of an impl function refers to the trait function it implements, as do calls
+        // of the impl function.
+        SyntaxKind::FunctionDeclaration => match db.find_lookup_item(&parent) {
+            Some(LookupItemId::ImplItem(ImplItemId::Function(impl_function))) => plain(
+                db.impl_function_trait_function(impl_function)
+                    .ok()
+                    .map(|trait_function| trait_function.untyped_stable_ptr(db)),
+            ),
+            _ => plain(definition_ptr_by_lookup(db, identifier)),
+        },
+        _ => plain(definition_ptr_by_lookup(db, identifier)),
+    }
+}
+
+/// Returns the definition of an identifier, as found by goto definition.
+fn definition_ptr_by_lookup(
+    db: &AnalysisDatabase,
+    identifier: &ast::TerminalIdentifier,
+) -> Option<SyntaxStablePtrId> {
+    let lookup_items = db.collect_lookup_items_stack(&identifier.as_syntax_node())?;
+    Some(find_definition(db, identifier, &lookup_items)?.1)
+}
+
+/// Returns the symbol defined by the given definition node, where `name` is the name of the
+/// symbol.
+///
+/// The symbol is identified by the first identifier in the definition with the given name, which
+/// is its name in the definition, as names precede the rest of the definitions (e.g. parameters
+/// and bodies).
+fn symbol_of_definition_ptr(
+    db: &AnalysisDatabase,
+    definition: SyntaxStablePtrId,
+    name: &str,
+) -> Option<SymbolId> {
+    let syntax_db = db.upcast();
+    let node = definition.lookup(syntax_db);
+    let name_node = node
+        .descendants(syntax_db)
+        .find(|node| {
+            node.kind(syntax_db) == SyntaxKind::TerminalIdentifier
+                && ast::TerminalIdentifier::from_syntax_node(syntax_db, node.clone())
+                    .text(syntax_db)
+                    == name
+        })
+        .unwrap_or(node);
+    // The whole name is mapped, as its start alone may also be the end of a preceding mapping,
+    // e.g. of an empty visibility copied by a plugin.
+    let span = name_node.span_without_trivia(syntax_db);
+    let (file, span) = get_originating_location(db.upcast(), definition.file_id(syntax_db), span);
+    Some(SymbolId { file, offset: span.start })
+}
+
+/// Returns the location of an identifier in the user code it originates from, if that code is the
+/// name itself.
+fn user_location(
+    db: &AnalysisDatabase,
+    identifier: &ast::TerminalIdentifier,
+    name: &str,
+) -> Option<(FileId, TextSpan)> {
+    let syntax_db = db.upcast();
+    let node = identifier.as_syntax_node();
+    let span = node.span_without_trivia(syntax_db);
+    let (file, span) =
+        get_originating_location(db.upcast(), node.stable_ptr().file_id(syntax_db), span);
+    if !matches!(file.lookup_intern(db), FileLongId::OnDisk(_)) {
+        return None;
+    }
+    let content = db.file_content(file)?;
+    let text = content.get(span.to_str_range())?;
+    (text == name).then_some((file, span))
+}
+
+/// Returns the crate of the given file.
+fn crate_of_file(db: &AnalysisDatabase, file: FileId) -> Option<CrateId> {
+    let module_id = *db.file_modules(file).ok()?.first()?;
+    Some(module_id.owning_crate(db))
+}
+
+/// Checks whether the given path node is a pattern binding a variable.
+fn is_variable_path_pattern(db: &AnalysisDatabase, path: &SyntaxNode) -> bool {
+    let Some(function_id) = db.find_lookup_item(path).and_then(|item| item.function_with_body())
+    else {
+        return false;
+    };
+    let path = ast::ExprPath::from_syntax_node(db, path.clone());
+    path.elements(db).len() == 1
+        && db.lookup_pattern_by_ptr(function_id, ast::PatternPtr::from(path.stable_ptr())).is_ok()
+}
+
+/// Returns the member named `name` of the struct of the given struct pattern node.
+fn struct_member_in_pattern(
+    db: &AnalysisDatabase,
+    pattern: SyntaxNode,
+    name: &str,
+) -> Option<MemberId> {
+    let function_id = db.find_lookup_item(&pattern)?.function_with_body()?;
+    let pattern = ast::PatternStruct::from_syntax_node(db, pattern);
+    let pattern_id =
+        db.lookup_pattern_by_ptr(function_id, ast::PatternPtr::from(pattern.stable_ptr())).ok()?;
+    let Pattern::Struct(pattern) = db.pattern_semantic(function_id, pattern_id) else {
+        return None;
+    };
+    let members = db.struct_members(pattern.concrete_struct_id.struct_id(db)).ok()?;
+    Some(members.get(name)?.id)
+}
+
+/// Returns the member named `name` of the struct of the given struct constructor node.
+fn struct_member_in_ctor(db: &AnalysisDatabase, ctor: SyntaxNode, name: &str) -> Option<MemberId> {
+    let function_id = db.find_lookup_item(&ctor)?.function_with_body()?;
+    let ctor = ast::ExprStructCtorCall::from_syntax_node(db, ctor);
+    let expr_id = db.lookup_expr_by_ptr(function_id, ast::ExprPtr::from(ctor.stable_ptr())).ok()?;
+    let Expr::StructCtor(ctor) = db.expr_semantic(function_id, expr_id) else { return None };
+    let members = db.struct_members(ctor.concrete_struct_id.struct_id(db)).ok()?;
+    Some(members.get(name)?.id)
+}
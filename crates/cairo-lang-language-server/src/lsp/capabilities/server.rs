@@ -14,19 +14,26 @@
 use std::ops::Not;
 
 use lsp_types::{
-    ClientCapabilities, CodeActionProviderCapability, CompletionOptions,
+    CallHierarchyOptions, CallHierarchyServerCapability, ClientCapabilities,
+    CodeActionProviderCapability, CodeLensOptions, CompletionOptions,
     CompletionRegistrationOptions, DefinitionOptions, DidChangeWatchedFilesRegistrationOptions,
-    DocumentFilter, ExecuteCommandOptions, ExecuteCommandRegistrationOptions, FileSystemWatcher,
-    GlobPattern, HoverProviderCapability, HoverRegistrationOptions, OneOf, Registration,
-    SaveOptions, SemanticTokensFullOptions, SemanticTokensLegend, SemanticTokensOptions,
-    SemanticTokensRegistrationOptions, ServerCapabilities, TextDocumentChangeRegistrationOptions,
-    TextDocumentRegistrationOptions, TextDocumentSaveRegistrationOptions,
-    TextDocumentSyncCapability, TextDocumentSyncKind, TextDocumentSyncOptions,
-    TextDocumentSyncSaveOptions,
+    DocumentFilter, DocumentSymbolOptions, ExecuteCommandOptions,
+    ExecuteCommandRegistrationOptions, FileSystemWatcher, FoldingRangeProviderCapability,
+    GlobPattern, HoverProviderCapability, HoverRegistrationOptions,
+    ImplementationProviderCapability, InlayHintOptions, InlayHintRegistrationOptions, OneOf,
+    ReferencesOptions, Registration, RenameOptions, SaveOptions, SelectionRangeProviderCapability,
+    SemanticTokensFullOptions, SemanticTokensLegend, SemanticTokensOptions,
+    SemanticTokensRegistrationOptions, ServerCapabilities, SignatureHelpOptions,
+    TextDocumentChangeRegistrationOptions, TextDocumentRegistrationOptions,
+    TextDocumentSaveRegistrationOptions, TextDocumentSyncCapability, TextDocumentSyncKind,
+    TextDocumentSyncOptions, TextDocumentSyncSaveOptions, TypeDefinitionProviderCapability,
+    WorkspaceSymbolOptions,
 };
 use missing_lsp_types::{
-    CodeActionRegistrationOptions, DefinitionRegistrationOptions,
-    DocumentFormattingRegistrationOptions,
+    CallHierarchyRegistrationOptions, CodeActionRegistrationOptions, CodeLensRegistrationOptions,
+    DefinitionRegistrationOptions, DocumentFormattingRegistrationOptions,
+    DocumentSymbolRegistrationOptions, ReferencesRegistrationOptions, RenameRegistrationOptions,
+    SignatureHelpRegistrationOptions,
 };
 use serde::Serialize;
 
@@ -90,10 +97,60 @@ pub fn collect_server_capabilities(client_capabilities: &ClientCapabilities) ->
             .definition_dynamic_registration()
             .not()
             .then_some(OneOf::Left(true)),
+        implementation_provider: client_capabilities
+            .implementation_dynamic_registration()
+            .not()
+            .then_some(ImplementationProviderCapability::Simple(true)),
+        type_definition_provider: client_capabilities
+            .type_definition_dynamic_registration()
+            .not()
+            .then_some(TypeDefinitionProviderCapability::Simple(true)),
+        call_hierarchy_provider: client_capabilities
+            .call_hierarchy_dynamic_registration()
+            .not()
+            .then_some(CallHierarchyServerCapability::Simple(true)),
         code_action_provider: client_capabilities
             .code_action_dynamic_registration()
             .not()
             .then_some(CodeActionProviderCapability::Simple(true)),
+        references_provider: client_capabilities
+            .references_dynamic_registration()
+            .not()
+            .then_some(OneOf::Left(true)),
+        rename_provider: client_capabilities.rename_dynamic_registration().not().then(|| {
+            OneOf::Right(RenameOptions {
+                prepare_provider: Some(true),
+                work_done_progress_options: Default::default(),
+            })
+        }),
+        document_symbol_provider: client_capabilities
+            .document_symbol_dynamic_registration()
+            .not()
+            .then_some(OneOf::Left(true)),
+        workspace_symbol_provider: client_capabilities
+            .workspace_symbol_dynamic_registration()
+            .not()
+            .then_some(OneOf::Left(true)),
+        signature_help_provider: client_capabilities
+            .signature_help_dynamic_registration()
+            .not()
+            .then(signature_help_options),
+        inlay_hint_provider: client_capabilities
+            .inlay_hint_dynamic_registration()
+            .not()
+            .then_some(OneOf::Left(true)),
+        code_lens_provider: client_capabilities
+            .code_lens_dynamic_registration()
+            .not()
+            .then_some(CodeLensOptions { resolve_provider: Some(false) }),
+        folding_range_provider: client_capabilities
+            .folding_range_dynamic_registration()
+            .not()
+            .then_some(FoldingRangeProviderCapability::Simple(true)),
+        selection_range_provider: client_capabilities
+            .selection_range_dynamic_registration()
+            .not()
+            .then_some(SelectionRangeProviderCapability::Simple(true)),
         ..ServerCapabilities::default()
     }
 }
@@ -236,18 +293,143 @@ pub fn collect_dynamic_registrations(
         registrations.push(create_registration("textDocument/definition", registration_options));
     }
 
+    if client_capabilities.implementation_dynamic_registration() {
+        registrations.push(create_registration(
+            "textDocument/implementation",
+            &text_document_registration_options,
+        ));
+    }
+
+    if client_capabilities.type_definition_dynamic_registration() {
+        registrations.push(create_registration(
+            "textDocument/typeDefinition",
+            &text_document_registration_options,
+        ));
+    }
+
+    if client_capabilities.call_hierarchy_dynamic_registration() {
+        let registration_options = CallHierarchyRegistrationOptions {
+            text_document_registration_options: text_document_registration_options.clone(),
+            call_hierarchy_options: CallHierarchyOptions {
+                work_done_progress_options: Default::default(),
+            },
+        };
+
+        registrations
+            .push(create_registration("textDocument/prepareCallHierarchy", registration_options));
+    }
+
     if client_capabilities.code_action_dynamic_registration() {
         let registration_options = CodeActionRegistrationOptions {
-            text_document_registration_options,
+            text_document_registration_options: text_document_registration_options.clone(),
             code_action_options: Default::default(),
         };
 
         registrations.push(create_registration("textDocument/codeAction", registration_options));
     }
 
+    if client_capabilities.references_dynamic_registration() {
+        let registration_options = ReferencesRegistrationOptions {
+            text_document_registration_options: text_document_registration_options.clone(),
+            references_options: ReferencesOptions {
+                work_done_progress_options: Default::default(),
+            },
+        };
+
+        registrations.push(create_registration("textDocument/references", registration_options));
+    }
+
+    if client_capabilities.rename_dynamic_registration() {
+        let registration_options = RenameRegistrationOptions {
+            text_document_registration_options: text_document_registration_options.clone(),
+            rename_options: RenameOptions {
+                prepare_provider: Some(true),
+                work_done_progress_options: Default::default(),
+            },
+        };
+
+        registrations.push(create_registration("textDocument/rename", registration_options));
+    }
+
+    if client_capabilities.document_symbol_dynamic_registration() {
+        let registration_options = DocumentSymbolRegistrationOptions {
+            text_document_registration_options: text_document_registration_options.clone(),
+            document_symbol_options: DocumentSymbolOptions {
+                label: None,
+                work_done_progress_options: Default::default(),
+            },
+        };
+
+        registrations
+            .push(create_registration("textDocument/documentSymbol", registration_options));
+    }
+
+    if client_capabilities.workspace_symbol_dynamic_registration() {
+        let registration_options = WorkspaceSymbolOptions {
+            work_done_progress_options: Default::default(),
+            resolve_provider: Some(false),
+        };
+
+        registrations.push(create_registration("workspace/symbol", registration_options));
+    }
+
+    if client_capabilities.signature_help_dynamic_registration() {
+        let registration_options = SignatureHelpRegistrationOptions {
+            text_document_registration_options: text_document_registration_options.clone(),
+            signature_help_options: signature_help_options(),
+        };
+
+        registrations.push(create_registration("textDocument/signatureHelp", registration_options));
+    }
+
+    if client_capabilities.inlay_hint_dynamic_registration() {
+        let registration_options = InlayHintRegistrationOptions {
+            inlay_hint_options: InlayHintOptions {
+                work_done_progress_options: Default::default(),
+                resolve_provider: Some(false),
+            },
+            text_document_registration_options: text_document_registration_options.clone(),
+            static_registration_options: Default::default(),
+        };
+
+        registrations.push(create_registration("textDocument/inlayHint", registration_options));
+    }
+
+    if client_capabilities.code_lens_dynamic_registration() {
+        let registration_options = CodeLensRegistrationOptions {
+            text_document_registration_options: text_document_registration_options.clone(),
+            code_lens_options: CodeLensOptions { resolve_provider: Some(false) },
+        };
+
+        registrations.push(create_registration("textDocument/codeLens", registration_options));
+    }
+
+    if client_capabilities.folding_range_dynamic_registration() {
+        registrations.push(create_registration(
+            "textDocument/foldingRange",
+            &text_document_registration_options,
+        ));
+    }
+
+    if client_capabilities.selection_range_dynamic_registration() {
+        registrations.push(create_registration(
+            "textDocument/selectionRange",
+            &text_document_registration_options,
+        ));
+    }
+
     registrations
 }
 
+/// Returns the signature help options, triggered when starting or continuing argument lists.
+fn signature_help_options() -> SignatureHelpOptions {
+    SignatureHelpOptions {
+        trigger_characters: Some(vec!["(".to_string(), "[".to_string(), ",".to_string()]),
+        retrigger_characters: None,
+        work_done_progress_options: Default::default(),
+    }
+}
+
 fn create_registration(method: &str, registration_options: impl Serialize) -> Registration {
     Registration {
         id: method.to_string(),
@@ -258,8 +440,9 @@ fn create_registration(method: &str, registration_options: impl Serialize) -> Re
 
 mod missing_lsp_types {
     use lsp_types::{
-        CodeActionOptions, DefinitionOptions, DocumentFormattingOptions,
-        TextDocumentRegistrationOptions,
+        CallHierarchyOptions, CodeActionOptions, CodeLensOptions, DefinitionOptions,
+        DocumentFormattingOptions, DocumentSymbolOptions, ReferencesOptions, RenameOptions,
+        SignatureHelpOptions, TextDocumentRegistrationOptions,
     };
     use serde::{Deserialize, Serialize};
 
@@ -292,4 +475,64 @@ mod missing_lsp_types {
         #[serde(flatten)]
         pub code_action_options: CodeActionOptions,
     }
+
+    #[derive(Debug, Eq, PartialEq, Clone, Deserialize, Serialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct ReferencesRegistrationOptions {
+        #[serde(flatten)]
+        pub text_document_registration_options: TextDocumentRegistrationOptions,
+
+        #[serde(flatten)]
+        pub references_options: ReferencesOptions,
+    }
+
+    #[derive(Debug, Eq, PartialEq, Clone, Deserialize, Serialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct RenameRegistrationOptions {
+        #[serde(flatten)]
+        pub text_document_registration_options: TextDocumentRegistrationOptions,
+
+        #[serde(flatten)]
+        pub rename_options: RenameOptions,
+    }
+
+    #[derive(Debug, Eq, PartialEq, Clone, Deserialize, Serialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct DocumentSymbolRegistrationOptions {
+        #[serde(flatten)]
+        pub text_document_registration_options: TextDocumentRegistrationOptions,
+
+        #[serde(flatten)]
+        pub document_symbol_options: DocumentSymbolOptions,
+    }
+
+    #[derive(Debug, Eq, PartialEq, Clone, Deserialize, Serialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct SignatureHelpRegistrationOptions {
+        #[serde(flatten)]
+        pub text_document_registration_options: TextDocumentRegistrationOptions,
+
+        #[serde(flatten)]
+        pub signature_help_options: SignatureHelpOptions,
+    }
+
+    #[derive(Debug, Eq, PartialEq, Clone, Deserialize, Serialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct CodeLensRegistrationOptions {
+        #[serde(flatten)]
+        pub text_document_registration_options: TextDocumentRegistrationOptions,
+
+        #[serde(flatten)]
+        pub code_lens_options: CodeLensOptions,
+    }
+
+    #[derive(Debug, Eq, PartialEq, Clone, Deserialize, Serialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct CallHierarchyRegistrationOptions {
+        #[serde(flatten)]
+        pub text_document_registration_options: TextDocumentRegistrationOptions,
+
+        #[serde(flatten)]
+        pub call_hierarchy_options: CallHierarchyOptions,
+    }
 }
@@ -2,7 +2,7 @@
 
 use lsp_types::notification::Notification;
 use lsp_types::request::Request;
-use lsp_types::{TextDocumentPositionParams, Url};
+use lsp_types::{TextDocumentIdentifier, TextDocumentPositionParams, Url};
 use serde::{Deserialize, Serialize};
 
 /// Provides content of virtual file from the database.
@@ -50,3 +50,63 @@ impl Notification for CorelibVersionMismatch {
     type Params = String;
     const METHOD: &'static str = "cairo/corelib-version-mismatch";
 }
+
+/// Compiles the project of a text document and runs the selected tests in it.
+///
+/// The result of each test is sent in a [`TestResult`] notification as soon as the test finishes.
+pub struct RunTests;
+
+#[derive(Debug, Eq, PartialEq, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RunTestsParams {
+    /// A document of the project whose tests are run.
+    pub text_document: TextDocumentIdentifier,
+    /// The full path of the test function or the module whose tests are run.
+    pub path: String,
+    /// Whether to run the profiler and report its result for each test.
+    pub profiler: bool,
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Deserialize, Serialize)]
+pub struct RunTestsResult {
+    pub passed: usize,
+    pub failed: usize,
+    pub ignored: usize,
+}
+
+impl Request for RunTests {
+    type Params = RunTestsParams;
+    type Result = RunTestsResult;
+    const METHOD: &'static str = "cairo/runTests";
+}
+
+/// Notifies about the result of a test run by the [`RunTests`] request.
+#[derive(Debug)]
+pub struct TestResult;
+
+#[derive(Debug, Eq, PartialEq, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TestResultParams {
+    /// The full path of the test function.
+    pub name: String,
+    pub status: TestResultStatus,
+    /// The description of the failure, for a failed test.
+    pub message: Option<String>,
+    /// The debug prints of the test.
+    pub output: Option<String>,
+    /// The profiling info of the test, if run with the profiler.
+    pub profiling_info: Option<String>,
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Copy, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TestResultStatus {
+    Passed,
+    Failed,
+    Ignored,
+}
+
+impl Notification for TestResult {
+    type Params = TestResultParams;
+    const METHOD: &'static str = "cairo/testResult";
+}
@@ -15,13 +15,17 @@ use lsp_types::notification::{
     Notification as NotificationTrait,
 };
 use lsp_types::request::{
-    CodeActionRequest, Completion, ExecuteCommand, Formatting, GotoDefinition, HoverRequest,
-    Request as RequestTrait, SemanticTokensFullRequest,
+    CallHierarchyIncomingCalls, CallHierarchyOutgoingCalls, CallHierarchyPrepare,
+    CodeActionRequest, CodeLensRequest, Completion, DocumentSymbolRequest, ExecuteCommand,
+    FoldingRangeRequest, Formatting, GotoDefinition, GotoImplementation, GotoTypeDefinition,
+    HoverRequest, InlayHintRequest, PrepareRenameRequest, References, Rename,
+    Request as RequestTrait, SelectionRangeRequest, SemanticTokensFullRequest,
+    SignatureHelpRequest, WorkspaceSymbolRequest,
 };
 use tracing::{debug, error, warn};
 
 use super::client::Responder;
-use crate::lsp::ext::{ExpandMacro, ProvideVirtualFile, ViewAnalyzedCrates};
+use crate::lsp::ext::{ExpandMacro, ProvideVirtualFile, RunTests, ViewAnalyzedCrates};
 use crate::lsp::result::{LSPError, LSPResult, LSPResultEx};
 use crate::server::panic::is_cancelled;
 use crate::server::schedule::{BackgroundSchedule, Task};
@@ -33,37 +37,92 @@ pub fn request<'a>(request: Request) -> Task<'a> {
     let id = request.id.clone();
 
     match request.method.as_str() {
+        CallHierarchyIncomingCalls::METHOD => background_request_task::<CallHierarchyIncomingCalls>(
+            request,
+            BackgroundSchedule::Worker,
+        ),
+        CallHierarchyOutgoingCalls::METHOD => background_request_task::<CallHierarchyOutgoingCalls>(
+            request,
+            BackgroundSchedule::Worker,
+        ),
+        CallHierarchyPrepare::METHOD => background_request_task::<CallHierarchyPrepare>(
+            request,
+            BackgroundSchedule::LatencySensitive,
+        ),
         CodeActionRequest::METHOD => background_request_task::<CodeActionRequest>(
             request,
             BackgroundSchedule::LatencySensitive,
         ),
+        CodeLensRequest::METHOD => {
+            background_request_task::<CodeLensRequest>(request, BackgroundSchedule::Worker)
+        }
         Completion::METHOD => {
             background_request_task::<Completion>(request, BackgroundSchedule::LatencySensitive)
         }
+        DocumentSymbolRequest::METHOD => background_request_task::<DocumentSymbolRequest>(
+            request,
+            BackgroundSchedule::LatencySensitive,
+        ),
         ExecuteCommand::METHOD => local_request_task::<ExecuteCommand>(request),
         ExpandMacro::METHOD => {
             background_request_task::<ExpandMacro>(request, BackgroundSchedule::Worker)
         }
+        FoldingRangeRequest::METHOD => background_request_task::<FoldingRangeRequest>(
+            request,
+            BackgroundSchedule::LatencySensitive,
+        ),
         Formatting::METHOD => {
             background_request_task::<Formatting>(request, BackgroundSchedule::LatencySensitive)
         }
         GotoDefinition::METHOD => {
             background_request_task::<GotoDefinition>(request, BackgroundSchedule::LatencySensitive)
         }
+        GotoImplementation::METHOD => background_request_task::<GotoImplementation>(
+            request,
+            BackgroundSchedule::LatencySensitive,
+        ),
+        GotoTypeDefinition::METHOD => background_request_task::<GotoTypeDefinition>(
+            request,
+            BackgroundSchedule::LatencySensitive,
+        ),
         HoverRequest::METHOD => {
             background_request_task::<HoverRequest>(request, BackgroundSchedule::LatencySensitive)
         }
+        InlayHintRequest::METHOD => background_request_task::<InlayHintRequest>(
+            request,
+            BackgroundSchedule::LatencySensitive,
+        ),
+        PrepareRenameRequest::METHOD => background_request_task::<PrepareRenameRequest>(
+            request,
+            BackgroundSchedule::LatencySensitive,
+        ),
         ProvideVirtualFile::METHOD => background_request_task::<ProvideVirtualFile>(
             request,
             BackgroundSchedule::LatencySensitive,
         ),
+        References::METHOD => {
+            background_request_task::<References>(request, BackgroundSchedule::Worker)
+        }
+        Rename::METHOD => background_request_task::<Rename>(request, BackgroundSchedule::Worker),
+        RunTests::METHOD => background_request_task::<RunTests>(request, BackgroundSchedule::Worker),
+        SelectionRangeRequest::METHOD => background_request_task::<SelectionRangeRequest>(
+            request,
+            BackgroundSchedule::LatencySensitive,
+        ),
         SemanticTokensFullRequest::METHOD => background_request_task::<SemanticTokensFullRequest>(
             request,
             BackgroundSchedule::Worker,
         ),
+        SignatureHelpRequest::METHOD => background_request_task::<SignatureHelpRequest>(
+            request,
+            BackgroundSchedule::LatencySensitive,
+        ),
         ViewAnalyzedCrates::METHOD => {
             background_request_task::<ViewAnalyzedCrates>(request, BackgroundSchedule::Worker)
         }
+        WorkspaceSymbolRequest::METHOD => {
+            background_request_task::<WorkspaceSymbolRequest>(request, BackgroundSchedule::Worker)
+        }
 
         method => {
             warn!("received request {method} which does not have a handler");
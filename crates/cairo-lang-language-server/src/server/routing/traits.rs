@@ -13,16 +13,27 @@ use lsp_types::notification::{
     DidCloseTextDocument, DidOpenTextDocument, DidSaveTextDocument, Notification,
 };
 use lsp_types::request::{
-    CodeActionRequest, Completion, ExecuteCommand, Formatting, GotoDefinition, HoverRequest,
-    Request, SemanticTokensFullRequest,
+    CallHierarchyIncomingCalls, CallHierarchyOutgoingCalls, CallHierarchyPrepare,
+    CodeActionRequest, CodeLensRequest, Completion, DocumentSymbolRequest, ExecuteCommand,
+    FoldingRangeRequest, Formatting, GotoDefinition, GotoImplementation, GotoImplementationParams,
+    GotoImplementationResponse, GotoTypeDefinition, GotoTypeDefinitionParams,
+    GotoTypeDefinitionResponse, HoverRequest, InlayHintRequest, PrepareRenameRequest, References,
+    Rename, Request, SelectionRangeRequest, SemanticTokensFullRequest, SignatureHelpRequest,
+    WorkspaceSymbolRequest,
 };
 use lsp_types::{
-    CancelParams, CodeActionParams, CodeActionResponse, CompletionParams, CompletionResponse,
-    DidChangeConfigurationParams, DidChangeTextDocumentParams, DidChangeWatchedFilesParams,
-    DidCloseTextDocumentParams, DidOpenTextDocumentParams, DidSaveTextDocumentParams,
-    DocumentFormattingParams, ExecuteCommandParams, GotoDefinitionParams, GotoDefinitionResponse,
-    Hover, HoverParams, SemanticTokensParams, SemanticTokensResult, TextDocumentContentChangeEvent,
-    TextDocumentPositionParams, TextEdit, Url,
+    CallHierarchyIncomingCall, CallHierarchyIncomingCallsParams, CallHierarchyItem,
+    CallHierarchyOutgoingCall, CallHierarchyOutgoingCallsParams, CallHierarchyPrepareParams,
+    CancelParams, CodeActionParams, CodeActionResponse, CodeLens, CodeLensParams, CompletionParams,
+    CompletionResponse, DidChangeConfigurationParams, DidChangeTextDocumentParams,
+    DidChangeWatchedFilesParams, DidCloseTextDocumentParams, DidOpenTextDocumentParams,
+    DidSaveTextDocumentParams, DocumentFormattingParams, DocumentSymbolParams,
+    DocumentSymbolResponse, ExecuteCommandParams, FoldingRange, FoldingRangeParams,
+    GotoDefinitionParams, GotoDefinitionResponse, Hover, HoverParams, InlayHint, InlayHintParams,
+    Location, PrepareRenameResponse, ReferenceParams, RenameParams, SelectionRange,
+    SelectionRangeParams, SemanticTokensParams, SemanticTokensResult, SignatureHelp,
+    SignatureHelpParams, TextDocumentContentChangeEvent, TextDocumentPositionParams, TextEdit, Url,
+    WorkspaceEdit, WorkspaceSymbolParams, WorkspaceSymbolResponse,
 };
 use serde_json::Value;
 use tracing::{error, warn};
@@ -30,7 +41,7 @@ use tracing::{error, warn};
 use crate::lang::lsp::LsProtoGroup;
 use crate::lsp::ext::{
     ExpandMacro, ProvideVirtualFile, ProvideVirtualFileRequest, ProvideVirtualFileResponse,
-    ViewAnalyzedCrates,
+    RunTests, RunTestsParams, RunTestsResult, ViewAnalyzedCrates,
 };
 use crate::lsp::result::{LSPError, LSPResult};
 use crate::server::client::{Notifier, Requester};
@@ -287,6 +298,171 @@ impl BackgroundDocumentRequestHandler for GotoDefinition {
     }
 }
 
+impl BackgroundDocumentRequestHandler for GotoImplementation {
+    #[tracing::instrument(level = "trace", skip_all)]
+    fn run_with_snapshot(
+        snapshot: StateSnapshot,
+        _notifier: Notifier,
+        params: GotoImplementationParams,
+    ) -> LSPResult<Option<GotoImplementationResponse>> {
+        Ok(ide::navigation::implementation::goto_implementation(params, &snapshot.db))
+    }
+}
+
+impl BackgroundDocumentRequestHandler for GotoTypeDefinition {
+    #[tracing::instrument(level = "trace", skip_all)]
+    fn run_with_snapshot(
+        snapshot: StateSnapshot,
+        _notifier: Notifier,
+        params: GotoTypeDefinitionParams,
+    ) -> LSPResult<Option<GotoTypeDefinitionResponse>> {
+        Ok(ide::navigation::type_definition::goto_type_definition(params, &snapshot.db))
+    }
+}
+
+impl BackgroundDocumentRequestHandler for CallHierarchyPrepare {
+    #[tracing::instrument(level = "trace", skip_all)]
+    fn run_with_snapshot(
+        snapshot: StateSnapshot,
+        _notifier: Notifier,
+        params: CallHierarchyPrepareParams,
+    ) -> LSPResult<Option<Vec<CallHierarchyItem>>> {
+        Ok(ide::navigation::call_hierarchy::prepare_call_hierarchy(params, &snapshot.db))
+    }
+}
+
+impl BackgroundDocumentRequestHandler for CallHierarchyIncomingCalls {
+    #[tracing::instrument(level = "trace", skip_all)]
+    fn run_with_snapshot(
+        snapshot: StateSnapshot,
+        _notifier: Notifier,
+        params: CallHierarchyIncomingCallsParams,
+    ) -> LSPResult<Option<Vec<CallHierarchyIncomingCall>>> {
+        Ok(ide::navigation::call_hierarchy::incoming_calls(params, &snapshot.db))
+    }
+}
+
+impl BackgroundDocumentRequestHandler for CallHierarchyOutgoingCalls {
+    #[tracing::instrument(level = "trace", skip_all)]
+    fn run_with_snapshot(
+        snapshot: StateSnapshot,
+        _notifier: Notifier,
+        params: CallHierarchyOutgoingCallsParams,
+    ) -> LSPResult<Option<Vec<CallHierarchyOutgoingCall>>> {
+        Ok(ide::navigation::call_hierarchy::outgoing_calls(params, &snapshot.db))
+    }
+}
+
+impl BackgroundDocumentRequestHandler for References {
+    #[tracing::instrument(level = "trace", skip_all)]
+    fn run_with_snapshot(
+        snapshot: StateSnapshot,
+        _notifier: Notifier,
+        params: ReferenceParams,
+    ) -> LSPResult<Option<Vec<Location>>> {
+        Ok(ide::navigation::references::references(params, &snapshot.db))
+    }
+}
+
+impl BackgroundDocumentRequestHandler for PrepareRenameRequest {
+    #[tracing::instrument(level = "trace", skip_all)]
+    fn run_with_snapshot(
+        snapshot: StateSnapshot,
+        _notifier: Notifier,
+        params: TextDocumentPositionParams,
+    ) -> LSPResult<Option<PrepareRenameResponse>> {
+        Ok(ide::rename::prepare_rename(params, &snapshot.db))
+    }
+}
+
+impl BackgroundDocumentRequestHandler for Rename {
+    #[tracing::instrument(level = "trace", skip_all)]
+    fn run_with_snapshot(
+        snapshot: StateSnapshot,
+        _notifier: Notifier,
+        params: RenameParams,
+    ) -> LSPResult<Option<WorkspaceEdit>> {
+        ide::rename::rename(params, &snapshot.db)
+    }
+}
+
+impl BackgroundDocumentRequestHandler for DocumentSymbolRequest {
+    #[tracing::instrument(level = "trace", skip_all)]
+    fn run_with_snapshot(
+        snapshot: StateSnapshot,
+        _notifier: Notifier,
+        params: DocumentSymbolParams,
+    ) -> LSPResult<Option<DocumentSymbolResponse>> {
+        Ok(ide::navigation::symbols::document_symbols(params, &snapshot.db))
+    }
+}
+
+impl BackgroundDocumentRequestHandler for WorkspaceSymbolRequest {
+    #[tracing::instrument(level = "trace", skip_all)]
+    fn run_with_snapshot(
+        snapshot: StateSnapshot,
+        _notifier: Notifier,
+        params: WorkspaceSymbolParams,
+    ) -> LSPResult<Option<WorkspaceSymbolResponse>> {
+        Ok(ide::navigation::symbols::workspace_symbols(params, &snapshot.db))
+    }
+}
+
+impl BackgroundDocumentRequestHandler for InlayHintRequest {
+    #[tracing::instrument(level = "trace", skip_all)]
+    fn run_with_snapshot(
+        snapshot: StateSnapshot,
+        _notifier: Notifier,
+        params: InlayHintParams,
+    ) -> LSPResult<Option<Vec<InlayHint>>> {
+        Ok(ide::inlay_hints::inlay_hints(params, &snapshot.db, &snapshot.config.inlay_hints))
+    }
+}
+
+impl BackgroundDocumentRequestHandler for SignatureHelpRequest {
+    #[tracing::instrument(level = "trace", skip_all)]
+    fn run_with_snapshot(
+        snapshot: StateSnapshot,
+        _notifier: Notifier,
+        params: SignatureHelpParams,
+    ) -> LSPResult<Option<SignatureHelp>> {
+        Ok(ide::signature_help::signature_help(params, &snapshot.db))
+    }
+}
+
+impl BackgroundDocumentRequestHandler for CodeLensRequest {
+    #[tracing::instrument(level = "trace", skip_all)]
+    fn run_with_snapshot(
+        snapshot: StateSnapshot,
+        _notifier: Notifier,
+        params: CodeLensParams,
+    ) -> LSPResult<Option<Vec<CodeLens>>> {
+        Ok(ide::testing::code_lens::code_lens(params, &snapshot.db))
+    }
+}
+
+impl BackgroundDocumentRequestHandler for FoldingRangeRequest {
+    #[tracing::instrument(level = "trace", skip_all)]
+    fn run_with_snapshot(
+        snapshot: StateSnapshot,
+        _notifier: Notifier,
+        params: FoldingRangeParams,
+    ) -> LSPResult<Option<Vec<FoldingRange>>> {
+        Ok(ide::folding_range::folding_ranges(params, &snapshot.db))
+    }
+}
+
+impl BackgroundDocumentRequestHandler for SelectionRangeRequest {
+    #[tracing::instrument(level = "trace", skip_all)]
+    fn run_with_snapshot(
+        snapshot: StateSnapshot,
+        _notifier: Notifier,
+        params: SelectionRangeParams,
+    ) -> LSPResult<Option<Vec<SelectionRange>>> {
+        Ok(ide::selection_range::selection_ranges(params, &snapshot.db))
+    }
+}
+
 impl BackgroundDocumentRequestHandler for Completion {
     #[tracing::instrument(level = "trace", skip_all)]
     fn run_with_snapshot(
@@ -348,6 +524,17 @@ impl BackgroundDocumentRequestHandler for ExpandMacro {
     }
 }
 
+impl BackgroundDocumentRequestHandler for RunTests {
+    #[tracing::instrument(level = "trace", skip_all)]
+    fn run_with_snapshot(
+        _snapshot: StateSnapshot,
+        notifier: Notifier,
+        params: RunTestsParams,
+    ) -> LSPResult<RunTestsResult> {
+        ide::testing::run::run_tests(params, notifier)
+    }
+}
+
 fn is_cairo_file_path(file_path: &Url) -> bool {
     file_path.path().ends_with(".cairo")
 }
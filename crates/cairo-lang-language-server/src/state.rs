@@ -11,7 +11,9 @@ use salsa::ParallelDatabase;
 
 use crate::Tricks;
 use crate::config::Config;
+use crate::env_config;
 use crate::lang::db::{AnalysisDatabase, AnalysisDatabaseSwapper};
+use crate::lang::diagnostics::cache::DiagnosticsCache;
 use crate::toolchain::scarb::ScarbToolchain;
 
 /// State of Language server.
@@ -19,6 +21,7 @@ pub struct State {
     pub db: AnalysisDatabase,
     pub open_files: Owned<HashSet<Url>>,
     pub file_diagnostics: Owned<HashMap<Url, FileDiagnostics>>,
+    pub diagnostics_cache: DiagnosticsCache,
     pub config: Owned<Config>,
     pub client_capabilities: Owned<ClientCapabilities>,
     pub scarb_toolchain: ScarbToolchain,
@@ -51,6 +54,7 @@ impl State {
             db,
             open_files: Default::default(),
             file_diagnostics: Default::default(),
+            diagnostics_cache: DiagnosticsCache::new(env_config::cache_dir()),
             config: Default::default(),
             client_capabilities: Owned::new(client_capabilities.into()),
             scarb_toolchain,
@@ -60,13 +64,14 @@ impl State {
     }
 
     pub fn snapshot(&self) -> StateSnapshot {
-        StateSnapshot { db: self.db.snapshot() }
+        StateSnapshot { db: self.db.snapshot(), config: self.config.snapshot() }
     }
 }
 
 /// Readonly snapshot of Language server state.
 pub struct StateSnapshot {
     pub db: salsa::Snapshot<AnalysisDatabase>,
+    pub config: Snapshot<Config>,
 }
 
 impl std::panic::UnwindSafe for StateSnapshot {}
@@ -84,6 +89,11 @@ impl<T: ?Sized> Owned<T> {
     pub fn new(inner: Arc<T>) -> Self {
         Self(inner)
     }
+
+    /// Creates a snapshot of the value's current state.
+    pub fn snapshot(&self) -> Snapshot<T> {
+        Snapshot(self.0.clone())
+    }
 }
 
 impl<T: ?Sized> Deref for Owned<T> {
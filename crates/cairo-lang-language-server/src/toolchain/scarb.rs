@@ -1,4 +1,5 @@
 use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
 use std::sync::{Arc, OnceLock};
 
 use anyhow::{Context, Result, bail};
@@ -23,6 +24,9 @@ pub struct ScarbToolchain {
     /// Cached path to the `scarb` executable.
     scarb_path_cell: Arc<OnceLock<Option<PathBuf>>>,
 
+    /// Cached path to the Scarb cache directory.
+    cache_path_cell: Arc<OnceLock<Option<PathBuf>>>,
+
     /// The notifier object used to send notifications to the language client.
     notifier: Notifier,
 
@@ -35,7 +39,12 @@ pub struct ScarbToolchain {
 impl ScarbToolchain {
     /// Constructs a new [`ScarbToolchain`].
     pub fn new(notifier: Notifier) -> Self {
-        ScarbToolchain { scarb_path_cell: Default::default(), notifier, is_silent: false }
+        ScarbToolchain {
+            scarb_path_cell: Default::default(),
+            cache_path_cell: Default::default(),
+            notifier,
+            is_silent: false,
+        }
     }
 
     /// Finds the path to the `scarb` executable to use.
@@ -90,6 +99,8 @@ impl ScarbToolchain {
                     None => Default::default(),
                 },
 
+                cache_path_cell: self.cache_path_cell.clone(),
+
                 notifier: self.notifier.clone(),
 
                 is_silent: true,
@@ -129,6 +140,31 @@ impl ScarbToolchain {
 
         result
     }
+
+    /// Finds the directory where Scarb stores downloaded packages, by calling `scarb cache path`.
+    ///
+    /// The result is cached, so the command is executed at most once.
+    /// Returns `None` if Scarb could not be found or the command failed, errors are logged.
+    #[tracing::instrument(level = "debug", skip(self))]
+    pub fn cache_path(&self) -> Option<&Path> {
+        self.cache_path_cell
+            .get_or_init(|| {
+                let scarb_path = self.discover()?;
+                let output = Command::new(scarb_path)
+                    .args(["cache", "path"])
+                    .stderr(Stdio::inherit())
+                    .output()
+                    .inspect_err(|err| error!("failed to execute: scarb cache path: {err:?}"))
+                    .ok()?;
+                if !output.status.success() {
+                    error!("scarb cache path exited with: {}", output.status);
+                    return None;
+                }
+                let path = String::from_utf8_lossy(&output.stdout).trim().to_owned();
+                Some(PathBuf::from(path))
+            })
+            .as_deref()
+    }
 }
 
 #[derive(Debug)]
@@ -0,0 +1,125 @@
+use cairo_lang_test_utils::parse_test_file::TestRunnerResult;
+use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
+use lsp_types::{
+    CallHierarchyIncomingCallsParams, CallHierarchyItem, CallHierarchyOutgoingCallsParams,
+    CallHierarchyPrepareParams, ClientCapabilities, DynamicRegistrationClientCapabilities, Range,
+    TextDocumentClientCapabilities, TextDocumentIdentifier, TextDocumentPositionParams,
+    lsp_request,
+};
+
+use crate::support::cursor::{peek_caret, peek_selection};
+use crate::support::{cursors, sandbox};
+
+cairo_lang_test_utils::test_file_test!(
+    call_hierarchy,
+    "tests/test_data/call_hierarchy",
+    {
+        call_hierarchy: "call_hierarchy.txt",
+    },
+    test_call_hierarchy
+);
+
+fn caps(base: ClientCapabilities) -> ClientCapabilities {
+    ClientCapabilities {
+        text_document: base.text_document.or_else(Default::default).map(|it| {
+            TextDocumentClientCapabilities {
+                call_hierarchy: Some(DynamicRegistrationClientCapabilities {
+                    dynamic_registration: Some(false),
+                }),
+                ..it
+            }
+        }),
+        ..base
+    }
+}
+
+/// Perform call hierarchy test.
+///
+/// This function spawns a sandbox language server with the given code in the `src/lib.cairo` file.
+/// The Cairo source code is expected to contain caret markers.
+/// The function then prepares the call hierarchy at each caret position, and renders the incoming
+/// and outgoing calls of the prepared item.
+fn test_call_hierarchy(
+    inputs: &OrderedHashMap<String, String>,
+    _args: &OrderedHashMap<String, String>,
+) -> TestRunnerResult {
+    let (cairo, cursors) = cursors(&inputs["cairo_code"]);
+
+    let mut ls = sandbox! {
+        files {
+            "cairo_project.toml" => inputs["cairo_project.toml"].clone(),
+            "src/lib.cairo" => cairo.clone(),
+        }
+        client_capabilities = caps;
+    };
+    ls.open_and_wait_for_diagnostics("src/lib.cairo");
+
+    let mut outputs = OrderedHashMap::default();
+    for (n, position) in cursors.carets().into_iter().enumerate() {
+        let mut report = peek_caret(&cairo, position);
+        let params = CallHierarchyPrepareParams {
+            text_document_position_params: TextDocumentPositionParams {
+                text_document: TextDocumentIdentifier { uri: ls.doc_id("src/lib.cairo").uri },
+                position,
+            },
+            work_done_progress_params: Default::default(),
+        };
+        let Some(item) = ls
+            .send_request::<lsp_request!("textDocument/prepareCallHierarchy")>(params)
+            .and_then(|items| items.into_iter().next())
+        else {
+            report.push_str("none response\n");
+            outputs.insert(format!("Call hierarchy #{n}"), report);
+            continue;
+        };
+        report.push_str(&render_item(&cairo, &item));
+
+        report.push_str("--- incoming\n");
+        let params = CallHierarchyIncomingCallsParams {
+            item: item.clone(),
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        };
+        for call in ls
+            .send_request::<lsp_request!("callHierarchy/incomingCalls")>(params)
+            .unwrap_or_default()
+        {
+            report.push_str(&render_item(&cairo, &call.from));
+            report.push_str(&render_ranges(&cairo, &call.from_ranges));
+        }
+
+        report.push_str("--- outgoing\n");
+        let params = CallHierarchyOutgoingCallsParams {
+            item,
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        };
+        for call in ls
+            .send_request::<lsp_request!("callHierarchy/outgoingCalls")>(params)
+            .unwrap_or_default()
+        {
+            report.push_str(&render_item(&cairo, &call.to));
+            report.push_str(&render_ranges(&cairo, &call.from_ranges));
+        }
+
+        outputs.insert(format!("Call hierarchy #{n}"), report);
+    }
+
+    TestRunnerResult::success(outputs)
+}
+
+/// Renders the name and detail of a call hierarchy item, with its name selected if it is in the
+/// tested file.
+fn render_item(cairo: &str, item: &CallHierarchyItem) -> String {
+    let detail = item.detail.as_deref().unwrap_or_default();
+    let mut report = format!("{} ({detail})\n", item.name);
+    if item.uri.path().ends_with("src/lib.cairo") {
+        report.push_str(&peek_selection(cairo, &item.selection_range));
+    }
+    report
+}
+
+/// Renders the ranges of calls.
+fn render_ranges(cairo: &str, ranges: &[Range]) -> String {
+    ranges.iter().map(|range| format!("  at {}", peek_selection(cairo, range))).collect()
+}
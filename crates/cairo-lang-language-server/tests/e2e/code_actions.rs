@@ -14,6 +14,12 @@ cairo_lang_test_utils::test_file_test!(
     {
         missing_trait: "missing_trait.txt",
         macro_expand: "macro_expand.txt",
+        missing_import: "missing_import.txt",
+        missing_impl_items: "missing_impl_items.txt",
+        missing_match_arms: "missing_match_arms.txt",
+        missing_derive: "missing_derive.txt",
+        ref_and_snapshot: "ref_and_snapshot.txt",
+        moved_variable: "moved_variable.txt",
     },
     test_quick_fix
 );
@@ -1,9 +1,12 @@
 use cairo_lang_test_utils::parse_test_file::TestRunnerResult;
 use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
-use lsp_types::{CompletionParams, TextDocumentPositionParams, lsp_request};
+use lsp_types::{
+    CompletionItem, CompletionItemKind, CompletionParams, Documentation, Position,
+    TextDocumentPositionParams, lsp_request,
+};
 
 use crate::support::cursor::peek_caret;
-use crate::support::{cursors, sandbox};
+use crate::support::{MockClient, cursors, sandbox};
 
 cairo_lang_test_utils::test_file_test!(
     completions,
@@ -15,6 +18,15 @@ cairo_lang_test_utils::test_file_test!(
 
 );
 
+cairo_lang_test_utils::test_file_test!(
+    documented_completions,
+    "tests/test_data/completions",
+    {
+        starknet: "starknet.txt",
+    },
+    test_documented_completions
+);
+
 /// Perform completions text edits test. Notice that the test shows many possible completions,
 /// however in practice only those who have the same prefix as the existing code are shown.
 ///
@@ -43,32 +55,15 @@ fn test_completions_text_edits(
         let mut report = String::new();
 
         report.push_str(&peek_caret(&cairo, position));
-        let completion_params = CompletionParams {
-            text_document_position: TextDocumentPositionParams {
-                text_document: ls.doc_id("src/lib.cairo"),
-                position,
-            },
-            work_done_progress_params: Default::default(),
-            partial_result_params: Default::default(),
-            context: None,
-        };
-        let caret_completions =
-            ls.send_request::<lsp_request!("textDocument/completion")>(completion_params);
-        if let Some(completions) = caret_completions {
-            let completion_items = match completions {
-                lsp_types::CompletionResponse::Array(items) => items,
-                lsp_types::CompletionResponse::List(list) => list.items,
-            };
-            for completion in completion_items {
-                if let Some(text_edit) = completion.additional_text_edits {
-                    report.push_str("--------------------------\n");
-                    report.push_str(format!("Completion: {}\n", completion.label).as_str());
-                    if let Some(text) = completion.insert_text {
-                        report.push_str(format!("Insert text: {text}\n").as_str());
-                    }
-                    for edit in text_edit {
-                        report.push_str(format!("Text edit: {}", edit.new_text).as_str());
-                    }
+        for completion in request_completions(&mut ls, position) {
+            if let Some(text_edit) = completion.additional_text_edits {
+                report.push_str("--------------------------\n");
+                report.push_str(format!("Completion: {}\n", completion.label).as_str());
+                if let Some(text) = completion.insert_text {
+                    report.push_str(format!("Insert text: {text}\n").as_str());
+                }
+                for edit in text_edit {
+                    report.push_str(format!("Text edit: {}", edit.new_text).as_str());
                 }
             }
         }
@@ -77,3 +72,67 @@ fn test_completions_text_edits(
 
     TestRunnerResult::success(completions)
 }
+
+/// Perform completions test, checking details and documentation of the completions.
+///
+/// This function spawns a sandbox language server with the given code in the `src/lib.cairo` file.
+/// The Cairo source code is expected to contain caret markers.
+/// The function then requests completions at each caret position and renders the fields and the
+/// documented methods among them.
+fn test_documented_completions(
+    inputs: &OrderedHashMap<String, String>,
+    _args: &OrderedHashMap<String, String>,
+) -> TestRunnerResult {
+    let (cairo, cursors) = cursors(&inputs["cairo_code"]);
+    let mut ls = sandbox! {
+        files {
+            "cairo_project.toml" => inputs["cairo_project.toml"].clone(),
+            "src/lib.cairo" => cairo.clone(),
+        }
+    };
+    ls.open("src/lib.cairo");
+    let mut completions = OrderedHashMap::default();
+    for (n, position) in cursors.carets().into_iter().enumerate() {
+        let mut report = String::new();
+        report.push_str(&peek_caret(&cairo, position));
+        for completion in request_completions(&mut ls, position) {
+            // Skip completions of items that need to be imported first, e.g. generic corelib
+            // methods available for any type.
+            let is_field = completion.kind == Some(CompletionItemKind::FIELD);
+            let needs_import =
+                completion.additional_text_edits.as_ref().is_some_and(|edits| !edits.is_empty());
+            if needs_import || (!is_field && completion.documentation.is_none()) {
+                continue;
+            }
+            report.push_str("--------------------------\n");
+            report.push_str(&format!("Completion: {}\n", completion.label));
+            if let Some(detail) = completion.detail {
+                report.push_str(&format!("Detail: {detail}\n"));
+            }
+            if let Some(Documentation::MarkupContent(documentation)) = completion.documentation {
+                report.push_str(&format!("Documentation: {}\n", documentation.value));
+            }
+        }
+        completions.insert(format!("Completions #{}", n), report);
+    }
+
+    TestRunnerResult::success(completions)
+}
+
+/// Requests completions at the given position in the `src/lib.cairo` file.
+fn request_completions(ls: &mut MockClient, position: Position) -> Vec<CompletionItem> {
+    let completion_params = CompletionParams {
+        text_document_position: TextDocumentPositionParams {
+            text_document: ls.doc_id("src/lib.cairo"),
+            position,
+        },
+        work_done_progress_params: Default::default(),
+        partial_result_params: Default::default(),
+        context: None,
+    };
+    match ls.send_request::<lsp_request!("textDocument/completion")>(completion_params) {
+        Some(lsp_types::CompletionResponse::Array(items)) => items,
+        Some(lsp_types::CompletionResponse::List(list)) => list.items,
+        None => vec![],
+    }
+}
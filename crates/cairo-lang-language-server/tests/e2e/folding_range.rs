@@ -0,0 +1,53 @@
+use cairo_lang_test_utils::parse_test_file::TestRunnerResult;
+use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
+use lsp_types::{FoldingRangeParams, TextDocumentIdentifier, lsp_request};
+
+use crate::support::sandbox;
+
+cairo_lang_test_utils::test_file_test!(
+    folding_range,
+    "tests/test_data/folding_range",
+    {
+        folding_ranges: "folding_ranges.txt",
+    },
+    test_folding_ranges
+);
+
+/// Perform folding range test.
+///
+/// This function spawns a sandbox language server with the given code in the `src/lib.cairo` file.
+/// The function then requests the folding ranges of the file and renders each of them as its kind
+/// and line numbers, followed by its first and last line.
+fn test_folding_ranges(
+    inputs: &OrderedHashMap<String, String>,
+    _args: &OrderedHashMap<String, String>,
+) -> TestRunnerResult {
+    let cairo = inputs["cairo_code"].clone();
+
+    let mut ls = sandbox! {
+        files {
+            "cairo_project.toml" => inputs["cairo_project.toml"].clone(),
+            "src/lib.cairo" => cairo.clone(),
+        }
+    };
+    ls.open("src/lib.cairo");
+
+    let params = FoldingRangeParams {
+        text_document: TextDocumentIdentifier { uri: ls.doc_id("src/lib.cairo").uri },
+        work_done_progress_params: Default::default(),
+        partial_result_params: Default::default(),
+    };
+    let ranges = ls.send_request::<lsp_request!("textDocument/foldingRange")>(params);
+
+    let lines = cairo.lines().collect::<Vec<_>>();
+    let mut report = String::new();
+    for range in ranges.unwrap_or_default() {
+        let kind = range.kind.map(|kind| format!("{kind:?}")).unwrap_or_else(|| "Region".into());
+        report.push_str(&format!("{kind}: {}..{}\n", range.start_line, range.end_line));
+        report.push_str(lines[range.start_line as usize]);
+        report.push_str("\n...\n");
+        report.push_str(lines[range.end_line as usize]);
+        report.push('\n');
+    }
+    TestRunnerResult::success(OrderedHashMap::from([("Folding ranges".into(), report)]))
+}
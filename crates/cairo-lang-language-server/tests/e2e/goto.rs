@@ -1,8 +1,9 @@
 use cairo_lang_test_utils::parse_test_file::TestRunnerResult;
 use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
+use lsp_types::request::{GotoImplementationParams, GotoTypeDefinitionParams};
 use lsp_types::{
     ClientCapabilities, GotoCapability, GotoDefinitionParams, GotoDefinitionResponse,
-    TextDocumentClientCapabilities, TextDocumentIdentifier, TextDocumentPositionParams,
+    TextDocumentClientCapabilities, TextDocumentIdentifier, TextDocumentPositionParams, Url,
     lsp_request,
 };
 
@@ -18,6 +19,24 @@ cairo_lang_test_utils::test_file_test!(
     test_goto_members
 );
 
+cairo_lang_test_utils::test_file_test!(
+    goto_implementation,
+    "tests/test_data/goto",
+    {
+        implementations: "implementations.txt",
+    },
+    test_goto_implementation
+);
+
+cairo_lang_test_utils::test_file_test!(
+    goto_type_definition,
+    "tests/test_data/goto",
+    {
+        type_definitions: "type_definitions.txt",
+    },
+    test_goto_type_definition
+);
+
 fn caps(base: ClientCapabilities) -> ClientCapabilities {
     ClientCapabilities {
         text_document: base.text_document.or_else(Default::default).map(|it| {
@@ -26,6 +45,14 @@ fn caps(base: ClientCapabilities) -> ClientCapabilities {
                     dynamic_registration: Some(false),
                     link_support: None,
                 }),
+                implementation: Some(GotoCapability {
+                    dynamic_registration: Some(false),
+                    link_support: None,
+                }),
+                type_definition: Some(GotoCapability {
+                    dynamic_registration: Some(false),
+                    link_support: None,
+                }),
                 ..it
             }
         }),
@@ -85,3 +112,108 @@ fn test_goto_members(
 
     TestRunnerResult::success(goto_definitions)
 }
+
+/// Perform goto implementation test.
+///
+/// This function spawns a sandbox language server with the given code in the `src/lib.cairo` file.
+/// The Cairo source code is expected to contain caret markers.
+/// The function then requests implementations at each caret position and renders every found
+/// location in the tested file.
+fn test_goto_implementation(
+    inputs: &OrderedHashMap<String, String>,
+    _args: &OrderedHashMap<String, String>,
+) -> TestRunnerResult {
+    let (cairo, cursors) = cursors(&inputs["cairo_code"]);
+
+    let mut ls = sandbox! {
+        files {
+            "cairo_project.toml" => inputs["cairo_project.toml"].clone(),
+            "src/lib.cairo" => cairo.clone(),
+        }
+        client_capabilities = caps;
+    };
+    ls.open_and_wait_for_diagnostics("src/lib.cairo");
+
+    let uri = ls.doc_id("src/lib.cairo").uri;
+    let mut outputs = OrderedHashMap::default();
+    for (n, position) in cursors.carets().into_iter().enumerate() {
+        let mut report = peek_caret(&cairo, position);
+        let params = GotoImplementationParams {
+            text_document_position_params: TextDocumentPositionParams {
+                text_document: TextDocumentIdentifier { uri: uri.clone() },
+                position,
+            },
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        };
+        let response = ls.send_request::<lsp_request!("textDocument/implementation")>(params);
+        report.push_str(&render_locations(&cairo, &uri, response));
+        outputs.insert(format!("Goto implementation #{n}"), report);
+    }
+
+    TestRunnerResult::success(outputs)
+}
+
+/// Perform goto type definition test.
+///
+/// This function spawns a sandbox language server with the given code in the `src/lib.cairo` file.
+/// The Cairo source code is expected to contain caret markers.
+/// The function then requests the type definition at each caret position and renders the found
+/// location, if it is in the tested file.
+fn test_goto_type_definition(
+    inputs: &OrderedHashMap<String, String>,
+    _args: &OrderedHashMap<String, String>,
+) -> TestRunnerResult {
+    let (cairo, cursors) = cursors(&inputs["cairo_code"]);
+
+    let mut ls = sandbox! {
+        files {
+            "cairo_project.toml" => inputs["cairo_project.toml"].clone(),
+            "src/lib.cairo" => cairo.clone(),
+        }
+        client_capabilities = caps;
+    };
+    ls.open_and_wait_for_diagnostics("src/lib.cairo");
+
+    let uri = ls.doc_id("src/lib.cairo").uri;
+    let mut outputs = OrderedHashMap::default();
+    for (n, position) in cursors.carets().into_iter().enumerate() {
+        let mut report = peek_caret(&cairo, position);
+        let params = GotoTypeDefinitionParams {
+            text_document_position_params: TextDocumentPositionParams {
+                text_document: TextDocumentIdentifier { uri: uri.clone() },
+                position,
+            },
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        };
+        let response = ls.send_request::<lsp_request!("textDocument/typeDefinition")>(params);
+        report.push_str(&render_locations(&cairo, &uri, response));
+        outputs.insert(format!("Goto type definition #{n}"), report);
+    }
+
+    TestRunnerResult::success(outputs)
+}
+
+/// Renders the locations of a goto response, marking those outside the tested file by their URI.
+fn render_locations(cairo: &str, uri: &Url, response: Option<GotoDefinitionResponse>) -> String {
+    let locations = match response {
+        None => return "none response\n".to_string(),
+        Some(GotoDefinitionResponse::Scalar(location)) => vec![location],
+        Some(GotoDefinitionResponse::Array(locations)) => locations,
+        Some(GotoDefinitionResponse::Link(_)) => {
+            panic!("Unexpected GotoDefinitionResponse variant.")
+        }
+    };
+    let mut report = "---\n".to_string();
+    for location in locations {
+        if &location.uri == uri {
+            report.push_str(&peek_selection(cairo, &location.range));
+        } else {
+            let file_name =
+                location.uri.path_segments().and_then(|mut segments| segments.next_back());
+            report.push_str(&format!("<{}>\n", file_name.unwrap_or_default()));
+        }
+    }
+    report
+}
@@ -0,0 +1,94 @@
+use cairo_lang_test_utils::parse_test_file::TestRunnerResult;
+use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
+use lsp_types::{
+    InlayHint, InlayHintLabel, InlayHintParams, Position, Range, TextDocumentIdentifier,
+    lsp_request,
+};
+use serde_json::json;
+
+use crate::support::sandbox;
+
+cairo_lang_test_utils::test_file_test!(
+    inlay_hints,
+    "tests/test_data/inlay_hints",
+    {
+        inlay_hints: "inlay_hints.txt",
+    },
+    test_inlay_hints
+);
+
+/// Perform inlay hints test.
+///
+/// This function spawns a sandbox language server with the given code in the `src/lib.cairo` file.
+/// The optional `inlay_hints_config` input is passed as the `cairo1.inlayHints` workspace
+/// configuration. The function then requests inlay hints for the whole file and renders the code
+/// with each hint inserted as a `/*label*/` comment at its position.
+fn test_inlay_hints(
+    inputs: &OrderedHashMap<String, String>,
+    _args: &OrderedHashMap<String, String>,
+) -> TestRunnerResult {
+    let cairo = inputs["cairo_code"].clone();
+    let inlay_hints_config: serde_json::Value = inputs
+        .get("inlay_hints_config")
+        .map(|config| serde_json::from_str(config).expect("invalid inlay hints config"))
+        .unwrap_or_else(|| json!({}));
+
+    let mut ls = sandbox! {
+        files {
+            "cairo_project.toml" => inputs["cairo_project.toml"].clone(),
+            "src/lib.cairo" => cairo.clone(),
+        }
+        workspace_configuration = json!({
+            "cairo1": {
+                "inlayHints": inlay_hints_config,
+            }
+        });
+    };
+    ls.open_and_wait_for_diagnostics("src/lib.cairo");
+
+    let params = InlayHintParams {
+        text_document: TextDocumentIdentifier { uri: ls.doc_id("src/lib.cairo").uri },
+        range: Range::new(Position::new(0, 0), Position::new(u32::MAX, 0)),
+        work_done_progress_params: Default::default(),
+    };
+    let hints = ls.send_request::<lsp_request!("textDocument/inlayHint")>(params);
+    let hints = hints.unwrap_or_default();
+
+    TestRunnerResult::success(OrderedHashMap::from([(
+        "Code with hints".into(),
+        render_inlay_hints(&cairo, &hints),
+    )]))
+}
+
+/// Inserts the labels of the hints into the code.
+fn render_inlay_hints(code: &str, hints: &[InlayHint]) -> String {
+    let mut hints_by_offset = hints
+        .iter()
+        .map(|hint| {
+            let InlayHintLabel::String(label) = &hint.label else {
+                panic!("Expected a string label, got: {:?}", hint.label);
+            };
+            let left = if hint.padding_left == Some(true) { " " } else { "" };
+            let right = if hint.padding_right == Some(true) { " " } else { "" };
+            (offset_of(code, hint.position), format!("{left}/*{label}*/{right}"))
+        })
+        .collect::<Vec<_>>();
+    hints_by_offset.sort_by_key(|(offset, _)| *offset);
+
+    let mut result = String::new();
+    let mut last = 0;
+    for (offset, label) in hints_by_offset {
+        result.push_str(&code[last..offset]);
+        result.push_str(&label);
+        last = offset;
+    }
+    result.push_str(&code[last..]);
+    result
+}
+
+/// Converts an LSP position into a byte offset in the (ASCII) code.
+fn offset_of(code: &str, position: Position) -> usize {
+    let line_start: usize =
+        code.split_inclusive('\n').take(position.line as usize).map(str::len).sum();
+    line_start + position.character as usize
+}
@@ -1,9 +1,18 @@
 mod analysis;
+mod call_hierarchy;
 mod code_actions;
 mod completions;
+mod folding_range;
 mod goto;
 mod hover;
+mod inlay_hints;
 mod macro_expand;
+mod references;
+mod rename;
+mod selection_range;
 mod semantic_tokens;
+mod signature_help;
 mod support;
+mod symbols;
+mod testing;
 mod workspace_configuration;
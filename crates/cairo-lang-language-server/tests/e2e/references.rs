@@ -0,0 +1,102 @@
+use cairo_lang_test_utils::parse_test_file::TestRunnerResult;
+use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
+use lsp_types::{
+    ClientCapabilities, DynamicRegistrationClientCapabilities, ReferenceContext, ReferenceParams,
+    TextDocumentClientCapabilities, TextDocumentIdentifier, TextDocumentPositionParams,
+    lsp_request,
+};
+
+use crate::support::cursor::{peek_caret, peek_selection};
+use crate::support::{DEPENDENCY_PATH, cursors, sandbox};
+
+cairo_lang_test_utils::test_file_test!(
+    references,
+    "tests/test_data/references",
+    {
+        fns: "fns.txt",
+        structs: "structs.txt",
+        enums: "enums.txt",
+        traits: "traits.txt",
+        variables: "variables.txt",
+        crates: "crates.txt",
+    },
+    test_references
+);
+
+fn caps(base: ClientCapabilities) -> ClientCapabilities {
+    ClientCapabilities {
+        text_document: base.text_document.or_else(Default::default).map(|it| {
+            TextDocumentClientCapabilities {
+                references: Some(DynamicRegistrationClientCapabilities {
+                    dynamic_registration: Some(false),
+                }),
+                ..it
+            }
+        }),
+        ..base
+    }
+}
+
+/// Perform references test.
+///
+/// This function spawns a sandbox language server with the given code in the `src/lib.cairo` file,
+/// and the optional dependency code in the [`DEPENDENCY_PATH`] file.
+/// The Cairo source code is expected to contain caret markers.
+/// The function then requests references at each caret position, including the declaration, and
+/// renders every found location, with the locations in the dependency after them.
+fn test_references(
+    inputs: &OrderedHashMap<String, String>,
+    _args: &OrderedHashMap<String, String>,
+) -> TestRunnerResult {
+    let (cairo, cursors) = cursors(&inputs["cairo_code"]);
+    let dependency = inputs.get("dependency_code").cloned().unwrap_or_default();
+
+    let mut ls = sandbox! {
+        files {
+            "cairo_project.toml" => inputs["cairo_project.toml"].clone(),
+            "src/lib.cairo" => cairo.clone(),
+            DEPENDENCY_PATH => dependency.clone(),
+        }
+        client_capabilities = caps;
+    };
+    ls.open_and_wait_for_diagnostics("src/lib.cairo");
+
+    let mut outputs = OrderedHashMap::default();
+    for (n, position) in cursors.carets().into_iter().enumerate() {
+        let mut report = peek_caret(&cairo, position);
+        let params = ReferenceParams {
+            text_document_position: TextDocumentPositionParams {
+                text_document: TextDocumentIdentifier { uri: ls.doc_id("src/lib.cairo").uri },
+                position,
+            },
+            context: ReferenceContext { include_declaration: true },
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        };
+        match ls.send_request::<lsp_request!("textDocument/references")>(params) {
+            Some(locations) => {
+                report.push_str("---\n");
+                // Only references in the tested files are rendered, e.g. skipping the core library.
+                let uri = ls.doc_id("src/lib.cairo").uri;
+                for location in locations.iter().filter(|location| location.uri == uri) {
+                    report.push_str(&peek_selection(&cairo, &location.range));
+                }
+                let dependency_uri = ls.doc_id(DEPENDENCY_PATH).uri;
+                let dependency_locations = locations
+                    .iter()
+                    .filter(|location| location.uri == dependency_uri)
+                    .collect::<Vec<_>>();
+                if !dependency_locations.is_empty() {
+                    report.push_str(&format!("--- {DEPENDENCY_PATH}\n"));
+                    for location in dependency_locations {
+                        report.push_str(&peek_selection(&dependency, &location.range));
+                    }
+                }
+            }
+            None => report.push_str("none response\n"),
+        }
+        outputs.insert(format!("References #{n}"), report);
+    }
+
+    TestRunnerResult::success(outputs)
+}
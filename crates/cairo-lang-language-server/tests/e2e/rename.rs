@@ -0,0 +1,115 @@
+use cairo_lang_test_utils::parse_test_file::TestRunnerResult;
+use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
+use lsp_types::{
+    ClientCapabilities, PrepareRenameResponse, RenameClientCapabilities, RenameParams,
+    TextDocumentClientCapabilities, TextDocumentIdentifier, TextDocumentPositionParams, TextEdit,
+    lsp_request,
+};
+
+use crate::support::cursor::{index_in_text, peek_caret, peek_selection};
+use crate::support::{DEPENDENCY_PATH, cursors, sandbox};
+
+cairo_lang_test_utils::test_file_test!(
+    rename,
+    "tests/test_data/rename",
+    {
+        rename: "rename.txt",
+    },
+    test_rename
+);
+
+/// The name symbols are renamed to.
+const NEW_NAME: &str = "renamed";
+
+fn caps(base: ClientCapabilities) -> ClientCapabilities {
+    ClientCapabilities {
+        text_document: base.text_document.or_else(Default::default).map(|it| {
+            TextDocumentClientCapabilities {
+                rename: Some(RenameClientCapabilities {
+                    dynamic_registration: Some(false),
+                    prepare_support: Some(true),
+                    ..Default::default()
+                }),
+                ..it
+            }
+        }),
+        ..base
+    }
+}
+
+/// Perform rename test.
+///
+/// This function spawns a sandbox language server with the given code in the `src/lib.cairo` file,
+/// and the optional dependency code in the [`DEPENDENCY_PATH`] file.
+/// The Cairo source code is expected to contain caret markers.
+/// The function then prepares a rename at each caret position, renames the symbol to
+/// [`NEW_NAME`], and renders the renamed source code, followed by the renamed dependency code if it
+/// was changed.
+fn test_rename(
+    inputs: &OrderedHashMap<String, String>,
+    _args: &OrderedHashMap<String, String>,
+) -> TestRunnerResult {
+    let (cairo, cursors) = cursors(&inputs["cairo_code"]);
+    let dependency = inputs.get("dependency_code").cloned().unwrap_or_default();
+
+    let mut ls = sandbox! {
+        files {
+            "cairo_project.toml" => inputs["cairo_project.toml"].clone(),
+            "src/lib.cairo" => cairo.clone(),
+            DEPENDENCY_PATH => dependency.clone(),
+        }
+        client_capabilities = caps;
+    };
+    ls.open_and_wait_for_diagnostics("src/lib.cairo");
+
+    let mut outputs = OrderedHashMap::default();
+    for (n, position) in cursors.carets().into_iter().enumerate() {
+        let mut report = peek_caret(&cairo, position);
+        let text_document_position = TextDocumentPositionParams {
+            text_document: TextDocumentIdentifier { uri: ls.doc_id("src/lib.cairo").uri },
+            position,
+        };
+        let prepare = ls.send_request::<lsp_request!("textDocument/prepareRename")>(
+            text_document_position.clone(),
+        );
+        let Some(PrepareRenameResponse::Range(range)) = prepare else {
+            report.push_str("not renamable\n");
+            outputs.insert(format!("Rename #{n}"), report);
+            continue;
+        };
+        report.push_str(&peek_selection(&cairo, &range));
+
+        let params = RenameParams {
+            text_document_position,
+            new_name: NEW_NAME.to_string(),
+            work_done_progress_params: Default::default(),
+        };
+        let edit = ls
+            .send_request::<lsp_request!("textDocument/rename")>(params)
+            .expect("Rename request failed.");
+        let mut changes = edit.changes.expect("Rename returned no changes.");
+        let edits = changes.remove(&ls.doc_id("src/lib.cairo").uri).unwrap();
+        report.push_str("---\n");
+        report.push_str(&apply_edits(&cairo, edits));
+        if let Some(edits) = changes.remove(&ls.doc_id(DEPENDENCY_PATH).uri) {
+            report.push_str(&format!("\n--- {DEPENDENCY_PATH}\n"));
+            report.push_str(&apply_edits(&dependency, edits));
+        }
+        assert!(changes.is_empty(), "Rename changed files other than the tested ones.");
+        outputs.insert(format!("Rename #{n}"), report);
+    }
+
+    TestRunnerResult::success(outputs)
+}
+
+/// Applies non-overlapping text edits to the text.
+fn apply_edits(text: &str, mut edits: Vec<TextEdit>) -> String {
+    edits.sort_by_key(|edit| (edit.range.start.line, edit.range.start.character));
+    let mut result = text.to_owned();
+    for edit in edits.into_iter().rev() {
+        let start = index_in_text(text, edit.range.start);
+        let end = index_in_text(text, edit.range.end);
+        result.replace_range(start..end, &edit.new_text);
+    }
+    result
+}
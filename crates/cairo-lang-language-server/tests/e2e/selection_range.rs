@@ -0,0 +1,60 @@
+use cairo_lang_test_utils::parse_test_file::TestRunnerResult;
+use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
+use lsp_types::{SelectionRangeParams, TextDocumentIdentifier, lsp_request};
+
+use crate::support::cursor::{peek_caret, peek_selection};
+use crate::support::{cursors, sandbox};
+
+cairo_lang_test_utils::test_file_test!(
+    selection_range,
+    "tests/test_data/selection_range",
+    {
+        selection_ranges: "selection_ranges.txt",
+    },
+    test_selection_ranges
+);
+
+/// Perform selection range test.
+///
+/// This function spawns a sandbox language server with the given code in the `src/lib.cairo` file.
+/// The Cairo source code is expected to contain caret markers.
+/// The function then requests the selection ranges at all caret positions and renders each chain
+/// of ranges, from the innermost one, up to the enclosing item.
+fn test_selection_ranges(
+    inputs: &OrderedHashMap<String, String>,
+    _args: &OrderedHashMap<String, String>,
+) -> TestRunnerResult {
+    let (cairo, cursors) = cursors(&inputs["cairo_code"]);
+
+    let mut ls = sandbox! {
+        files {
+            "cairo_project.toml" => inputs["cairo_project.toml"].clone(),
+            "src/lib.cairo" => cairo.clone(),
+        }
+    };
+    ls.open("src/lib.cairo");
+
+    let params = SelectionRangeParams {
+        text_document: TextDocumentIdentifier { uri: ls.doc_id("src/lib.cairo").uri },
+        positions: cursors.carets(),
+        work_done_progress_params: Default::default(),
+        partial_result_params: Default::default(),
+    };
+    let ranges =
+        ls.send_request::<lsp_request!("textDocument/selectionRange")>(params).unwrap_or_default();
+    assert_eq!(ranges.len(), cursors.carets().len());
+
+    let mut result = OrderedHashMap::default();
+    for (n, (position, range)) in cursors.carets().into_iter().zip(ranges).enumerate() {
+        let mut report = peek_caret(&cairo, position);
+        let mut range = Some(&range);
+        // The outermost ranges, covering whole items and the file, are not interesting.
+        while let Some(current) = range.filter(|range| range.range.start.character > 0) {
+            report.push_str("---\n");
+            report.push_str(&peek_selection(&cairo, &current.range));
+            range = current.parent.as_deref();
+        }
+        result.insert(format!("Selection ranges #{n}"), report);
+    }
+    TestRunnerResult::success(result)
+}
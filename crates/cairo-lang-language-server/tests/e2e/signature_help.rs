@@ -0,0 +1,100 @@
+use cairo_lang_test_utils::parse_test_file::TestRunnerResult;
+use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
+use lsp_types::{
+    Documentation, MarkupContent, ParameterLabel, SignatureHelp, SignatureHelpParams,
+    TextDocumentPositionParams, lsp_request,
+};
+
+use crate::support::cursor::peek_caret;
+use crate::support::{cursors, sandbox};
+
+cairo_lang_test_utils::test_file_test!(
+    signature_help,
+    "tests/test_data/signature_help",
+    {
+        functions: "functions.txt",
+        macros: "macros.txt",
+        starknet: "starknet.txt",
+    },
+    test_signature_help
+);
+
+/// Perform signature help test.
+///
+/// This function spawns a sandbox language server with the given code in the `src/lib.cairo` file.
+/// The Cairo source code is expected to contain caret markers.
+/// The function then requests signature help at each caret position and renders the signature
+/// with the active parameter wrapped in `<sel>` markers, followed by the documentation.
+fn test_signature_help(
+    inputs: &OrderedHashMap<String, String>,
+    _args: &OrderedHashMap<String, String>,
+) -> TestRunnerResult {
+    let (cairo, cursors) = cursors(&inputs["cairo_code"]);
+
+    let mut ls = sandbox! {
+        files {
+            "cairo_project.toml" => inputs["cairo_project.toml"].clone(),
+            "src/lib.cairo" => cairo.clone(),
+        }
+    };
+
+    ls.open("src/lib.cairo");
+
+    let mut signatures = OrderedHashMap::default();
+
+    for (n, position) in cursors.carets().into_iter().enumerate() {
+        let mut report = String::new();
+
+        report.push_str("// = source context\n");
+        report.push_str(&peek_caret(&cairo, position));
+
+        let signature_help =
+            ls.send_request::<lsp_request!("textDocument/signatureHelp")>(SignatureHelpParams {
+                context: None,
+                text_document_position_params: TextDocumentPositionParams {
+                    text_document: ls.doc_id("src/lib.cairo"),
+                    position,
+                },
+                work_done_progress_params: Default::default(),
+            });
+
+        match signature_help {
+            Some(signature_help) => report.push_str(&render(&signature_help)),
+            None => report.push_str("No signature help.\n"),
+        }
+
+        signatures.insert(format!("signature #{n}"), report);
+    }
+
+    TestRunnerResult::success(signatures)
+}
+
+/// Renders the active signature, marking its active parameter, and its documentation.
+fn render(signature_help: &SignatureHelp) -> String {
+    let signature =
+        &signature_help.signatures[signature_help.active_signature.unwrap_or_default() as usize];
+    let mut label = signature.label.clone();
+    let active_parameter = signature
+        .parameters
+        .as_ref()
+        .zip(signature.active_parameter)
+        .and_then(|(parameters, active)| parameters.get(active as usize));
+    if let Some(parameter) = active_parameter {
+        let ParameterLabel::LabelOffsets([start, end]) = parameter.label else {
+            panic!("Expected parameter label offsets, got: {:?}", parameter.label);
+        };
+        // Test signatures are ASCII, so UTF-16 offsets are byte offsets.
+        let (start, end) = (start as usize, end as usize);
+        label = format!("{}<sel>{}</sel>{}", &label[..start], &label[start..end], &label[end..]);
+    }
+
+    let mut result = format!("// = signature\n{label}\n");
+    if let Some(Documentation::MarkupContent(MarkupContent { value, .. })) =
+        &signature.documentation
+    {
+        result.push_str("// = documentation\n");
+        result.push_str(value);
+        result.push('\n');
+    }
+    result
+}
@@ -125,7 +125,7 @@ pub fn peek_selection(text: &str, range: &Range) -> String {
 /// Converts a [`Position`] to a char-bounded index in the text.
 ///
 /// This function assumes UTF-8 position encoding.
-fn index_in_text(text: &str, position: Position) -> usize {
+pub fn index_in_text(text: &str, position: Position) -> usize {
     let mut offset = 0;
     let mut lines = text.lines();
     for line in lines.by_ref().take(position.line as usize) {
@@ -8,6 +8,10 @@ pub mod normalize;
 pub use self::cursor::cursors;
 pub use self::mock_client::MockClient;
 
+/// The path of the root file of a second crate in tests of features spanning crates. The code of
+/// the crate is taken from the optional `dependency_code` test input.
+pub const DEPENDENCY_PATH: &str = "dependency/lib.cairo";
+
 /// Create a sandboxed environment for testing language server features.
 ///
 /// This macro creates a [`fixture::Fixture`] first and sets it up according to the provided
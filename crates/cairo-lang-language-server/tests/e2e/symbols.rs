@@ -0,0 +1,113 @@
+use cairo_lang_test_utils::parse_test_file::TestRunnerResult;
+use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
+use lsp_types::{
+    DocumentSymbol, DocumentSymbolParams, DocumentSymbolResponse, TextDocumentIdentifier,
+    WorkspaceSymbolParams, WorkspaceSymbolResponse, lsp_request,
+};
+
+use crate::support::cursor::peek_selection;
+use crate::support::sandbox;
+
+cairo_lang_test_utils::test_file_test!(
+    document_symbols,
+    "tests/test_data/symbols",
+    {
+        document_symbols: "document_symbols.txt",
+    },
+    test_document_symbols
+);
+
+cairo_lang_test_utils::test_file_test!(
+    workspace_symbols,
+    "tests/test_data/symbols",
+    {
+        workspace_symbols: "workspace_symbols.txt",
+    },
+    test_workspace_symbols
+);
+
+/// Perform document symbols test.
+///
+/// This function spawns a sandbox language server with the given code in the `src/lib.cairo` file.
+/// The function then requests the document symbols of the file and renders the outline, one
+/// symbol per line, indented by depth.
+fn test_document_symbols(
+    inputs: &OrderedHashMap<String, String>,
+    _args: &OrderedHashMap<String, String>,
+) -> TestRunnerResult {
+    let cairo = inputs["cairo_code"].clone();
+
+    let mut ls = sandbox! {
+        files {
+            "cairo_project.toml" => inputs["cairo_project.toml"].clone(),
+            "src/lib.cairo" => cairo,
+        }
+    };
+    ls.open_and_wait_for_diagnostics("src/lib.cairo");
+
+    let params = DocumentSymbolParams {
+        text_document: TextDocumentIdentifier { uri: ls.doc_id("src/lib.cairo").uri },
+        work_done_progress_params: Default::default(),
+        partial_result_params: Default::default(),
+    };
+    let response = ls.send_request::<lsp_request!("textDocument/documentSymbol")>(params);
+    let Some(DocumentSymbolResponse::Nested(symbols)) = response else {
+        panic!("Expected nested document symbols, got: {response:?}");
+    };
+
+    let mut outline = String::new();
+    render_document_symbols(&mut outline, &symbols, 0);
+    TestRunnerResult::success(OrderedHashMap::from([("Outline".into(), outline)]))
+}
+
+/// Renders document symbols as lines of `name: kind (detail)`, children indented below parents.
+fn render_document_symbols(output: &mut String, symbols: &[DocumentSymbol], depth: usize) {
+    for symbol in symbols {
+        output.push_str(&format!("{}{}: {:?}", "  ".repeat(depth), symbol.name, symbol.kind));
+        if let Some(detail) = &symbol.detail {
+            output.push_str(&format!(" ({detail})"));
+        }
+        output.push('\n');
+        render_document_symbols(output, symbol.children.as_deref().unwrap_or_default(), depth + 1);
+    }
+}
+
+/// Perform workspace symbols test.
+///
+/// This function spawns a sandbox language server with the given code in the `src/lib.cairo` file.
+/// The function then searches workspace symbols with the given query, and renders the found
+/// symbols that are located in the tested file.
+fn test_workspace_symbols(
+    inputs: &OrderedHashMap<String, String>,
+    _args: &OrderedHashMap<String, String>,
+) -> TestRunnerResult {
+    let cairo = inputs["cairo_code"].clone();
+
+    let mut ls = sandbox! {
+        files {
+            "cairo_project.toml" => inputs["cairo_project.toml"].clone(),
+            "src/lib.cairo" => cairo.clone(),
+        }
+    };
+    ls.open_and_wait_for_diagnostics("src/lib.cairo");
+
+    let params = WorkspaceSymbolParams {
+        query: inputs["query"].trim().to_string(),
+        work_done_progress_params: Default::default(),
+        partial_result_params: Default::default(),
+    };
+    let response = ls.send_request::<lsp_request!("workspace/symbol")>(params);
+    let Some(WorkspaceSymbolResponse::Flat(symbols)) = response else {
+        panic!("Expected flat workspace symbols, got: {response:?}");
+    };
+
+    // Only symbols in the tested file are rendered, e.g. skipping the core library.
+    let uri = ls.doc_id("src/lib.cairo").uri;
+    let mut report = String::new();
+    for symbol in symbols.into_iter().filter(|symbol| symbol.location.uri == uri) {
+        let container = symbol.container_name.as_deref().unwrap_or("<root>");
+        report.push_str(&format!("{}: {:?} in {container}\n", symbol.name, symbol.kind));
+        report.push_str(&peek_selection(&cairo, &symbol.location.range));
+    }
+    TestRunnerResult::success(OrderedHashMap::from([("Symbols".into(), report)]))
+}
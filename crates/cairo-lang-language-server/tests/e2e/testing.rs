@@ -0,0 +1,119 @@
+use cairo_lang_language_server::lsp::ext::{
+    RunTests, RunTestsParams, TestResult, TestResultParams, TestResultStatus,
+};
+use cairo_lang_test_utils::parse_test_file::TestRunnerResult;
+use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
+use itertools::Itertools;
+use lsp_server::Message;
+use lsp_types::notification::Notification;
+use lsp_types::{CodeLensParams, lsp_request};
+
+use crate::support::sandbox;
+
+cairo_lang_test_utils::test_file_test!(
+    testing,
+    "tests/test_data/testing",
+    {
+        code_lens: "code_lens.txt",
+        run_tests: "run_tests.txt",
+    },
+    test_testing
+);
+
+/// Perform test code lenses and test running test.
+///
+/// This function spawns a sandbox language server with the given code in the `src/lib.cairo` file.
+/// The function then requests the code lenses of the file and lists them. If the `run_path` input
+/// is given, the tests at this path are run and their results are listed, sorted by name.
+fn test_testing(
+    inputs: &OrderedHashMap<String, String>,
+    _args: &OrderedHashMap<String, String>,
+) -> TestRunnerResult {
+    let cairo = inputs["cairo_code"].clone();
+
+    let mut ls = sandbox! {
+        files {
+            "cairo_project.toml" => inputs["cairo_project.toml"].clone(),
+            "src/lib.cairo" => cairo.clone(),
+        }
+    };
+    ls.open_and_wait_for_diagnostics("src/lib.cairo");
+
+    let lenses = ls.send_request::<lsp_request!("textDocument/codeLens")>(CodeLensParams {
+        text_document: ls.doc_id("src/lib.cairo"),
+        work_done_progress_params: Default::default(),
+        partial_result_params: Default::default(),
+    });
+    let lenses = lenses
+        .unwrap_or_default()
+        .into_iter()
+        .group_by(|lens| lens.range.start.line)
+        .into_iter()
+        .map(|(line, lenses)| {
+            let line = cairo.lines().nth(line as usize).unwrap().trim();
+            let mut lenses = lenses.map(|lens| {
+                let command = lens.command.expect("code lens without a command");
+                let [argument] = &command.arguments.expect("code lens without arguments")[..]
+                else {
+                    panic!("expected a single argument of the code lens command");
+                };
+                let params: RunTestsParams = serde_json::from_value(argument.clone()).unwrap();
+                format!(
+                    "    {}: {} (command: {}, profiler: {})",
+                    command.title, params.path, command.command, params.profiler
+                )
+            });
+            format!("{line}\n{}", lenses.join("\n"))
+        })
+        .join("\n");
+
+    let mut outputs = OrderedHashMap::from([("Code lenses".into(), lenses)]);
+    if let Some(path) = inputs.get("run_path") {
+        let summary = ls.send_request::<RunTests>(RunTestsParams {
+            text_document: ls.doc_id("src/lib.cairo"),
+            path: path.trim().to_string(),
+            profiler: false,
+        });
+        let results = ls
+            .trace()
+            .iter()
+            .filter_map(|message| match message {
+                Message::Notification(notification)
+                    if notification.method == TestResult::METHOD =>
+                {
+                    serde_json::from_value::<TestResultParams>(notification.params.clone()).ok()
+                }
+                _ => None,
+            })
+            .sorted_by(|a, b| a.name.cmp(&b.name))
+            .map(render_test_result)
+            .join("\n");
+        outputs.insert("Test results".into(), results);
+        outputs.insert(
+            "Summary".into(),
+            format!(
+                "passed: {}, failed: {}, ignored: {}",
+                summary.passed, summary.failed, summary.ignored
+            ),
+        );
+    }
+
+    TestRunnerResult::success(outputs)
+}
+
+/// Renders the result of a single test, with its failure message and output.
+fn render_test_result(result: TestResultParams) -> String {
+    let status = match result.status {
+        TestResultStatus::Passed => "passed",
+        TestResultStatus::Failed => "failed",
+        TestResultStatus::Ignored => "ignored",
+    };
+    let mut rendered = format!("{}: {status}", result.name);
+    if let Some(message) = result.message {
+        rendered.push_str(&format!("\n    message: {message}"));
+    }
+    if let Some(output) = result.output.filter(|output| !output.is_empty()) {
+        rendered.push_str(&format!("\n    output: {output:?}"));
+    }
+    rendered
+}
@@ -1,7 +1,7 @@
 use cairo_lang_defs::diagnostic_utils::StableLocation;
 use cairo_lang_diagnostics::{
     DiagnosticAdded, DiagnosticEntry, DiagnosticLocation, DiagnosticNote, DiagnosticsBuilder,
-    Severity,
+    ErrorCode, Severity, error_code,
 };
 use cairo_lang_semantic as semantic;
 use cairo_lang_semantic::corelib::LiteralError;
@@ -96,6 +96,10 @@ impl DiagnosticEntry for LoweringDiagnostic {
         &self.location.notes
     }
 
+    fn error_code(&self) -> Option<ErrorCode> {
+        self.kind.error_code()
+    }
+
     fn location(&self, db: &Self::DbType) -> DiagnosticLocation {
         if let LoweringDiagnosticKind::Unreachable { last_statement_ptr } = &self.kind {
             return self
@@ -214,6 +218,19 @@ pub enum LoweringDiagnosticKind {
     Unsupported,
 }
 
+impl LoweringDiagnosticKind {
+    pub fn error_code(&self) -> Option<ErrorCode> {
+        Some(match &self {
+            Self::VariableNotDropped { .. } => error_code!(E0007),
+            Self::VariableMoved { .. } => error_code!(E0008),
+            Self::MatchError(MatchError { error: MatchDiagnostic::MissingMatchArm(_), .. }) => {
+                error_code!(E0009)
+            }
+            _ => return None,
+        })
+    }
+}
+
 /// Error in a match-like construct.
 /// contains which construct the error occurred in and the error itself.
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
@@ -0,0 +1,416 @@
+//! Decoding of user provided arguments into the [Arg]s of a Sierra function run.
+//!
+//! Arguments may be provided in one of two forms:
+//! * JSON - an array with one value per (non-implicit) parameter of the function:
+//!   * Numeric types (`felt252`, integers, `bytes31`, addresses, etc.) are given as a number or a
+//!     string holding a decimal (optionally negative) or `0x`-prefixed hex value.
+//!   * `u256` may be given as a single number, `ByteArray` as a string.
+//!   * Arrays and spans are given as arrays of their elements.
+//!   * Structs and tuples are given as arrays of their members. A struct with a single member may
+//!     also be given as the value of that member.
+//!   * Enums are given as `{"variant": <index>, "value": <payload>}`, where `value` may be omitted
+//!     for unit variants. Enums with two unit variants (such as `bool`) may also be given as JSON
+//!     booleans.
+//! * A felt list - whitespace or comma separated felts, decoded as the Cairo `Serde` serialization
+//!   of the parameters (arrays are prefixed by their length, enums by their variant index).
+
+use std::str::FromStr;
+
+use cairo_lang_sierra::extensions::ConcreteType;
+use cairo_lang_sierra::extensions::core::{CoreLibfunc, CoreType, CoreTypeConcrete};
+use cairo_lang_sierra::extensions::starknet::StarkNetTypeConcrete;
+use cairo_lang_sierra::extensions::utils::Range;
+use cairo_lang_sierra::ids::{ConcreteTypeId, UserTypeId};
+use cairo_lang_sierra::program::GenericArg;
+use cairo_lang_sierra::program_registry::ProgramRegistry;
+use cairo_lang_sierra_to_casm::invocations::enm::get_variant_selector;
+use cairo_lang_sierra_type_size::TypeSizeMap;
+use cairo_lang_utils::byte_array::BYTES_IN_WORD;
+use itertools::Itertools;
+use num_bigint::BigInt;
+use num_traits::{Num, ToPrimitive, Zero};
+use serde_json::Value;
+use starknet_types_core::felt::{CAIRO_PRIME_BIGINT, Felt as Felt252};
+use thiserror::Error;
+
+use crate::Arg;
+
+#[derive(Debug, Error, Eq, PartialEq)]
+pub enum ArgsError {
+    #[error("Failed parsing arguments as JSON: {0}")]
+    InvalidJson(String),
+    #[error("Failed parsing `{0}` as a felt252.")]
+    InvalidFelt(String),
+    #[error("Expected {expected} arguments, got {actual}.")]
+    WrongNumberOfArgs { expected: usize, actual: usize },
+    #[error("Expected a value of type `{ty}`, got `{value}`.")]
+    TypeMismatch { ty: String, value: String },
+    #[error("Value `{value}` is out of range for type `{ty}`.")]
+    OutOfRange { ty: String, value: String },
+    #[error("Variant index {index} is out of range for enum `{ty}`.")]
+    InvalidVariant { ty: String, index: usize },
+    #[error("Parameters of type `{0}` are not supported as run arguments.")]
+    UnsupportedType(String),
+    #[error("Not enough felts to decode all the arguments.")]
+    MissingFelts,
+    #[error("{0} felts were left after decoding all the arguments.")]
+    UnusedFelts(usize),
+}
+
+/// Arguments for a function run, as provided by the user.
+#[derive(Debug, Clone, PartialEq)]
+pub enum UserArgs {
+    /// A JSON array with a value per parameter.
+    Json(Value),
+    /// The `Serde` serialization of all the parameters.
+    Felts(Vec<Felt252>),
+}
+impl FromStr for UserArgs {
+    type Err = ArgsError;
+
+    /// Parses the arguments as JSON if they start with `[`, otherwise as a felt list.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s.starts_with('[') {
+            serde_json::from_str(s)
+                .map(UserArgs::Json)
+                .map_err(|err| ArgsError::InvalidJson(err.to_string()))
+        } else {
+            s.split(|c: char| c.is_whitespace() || c == ',')
+                .filter(|s| !s.is_empty())
+                .map(parse_felt)
+                .collect::<Result<_, _>>()
+                .map(UserArgs::Felts)
+        }
+    }
+}
+
+/// Parses a decimal (possibly negative) or `0x` prefixed hex number.
+fn parse_bigint(s: &str) -> Option<BigInt> {
+    let (is_negative, abs) = match s.strip_prefix('-') {
+        Some(abs) => (true, abs),
+        None => (false, s),
+    };
+    let value = match abs.strip_prefix("0x") {
+        Some(hex) => BigInt::from_str_radix(hex, 16),
+        None => BigInt::from_str_radix(abs, 10),
+    }
+    .ok()?;
+    Some(if is_negative { -value } else { value })
+}
+
+/// Parses a felt252 from a decimal or hex string.
+fn parse_felt(s: &str) -> Result<Felt252, ArgsError> {
+    parse_bigint(s).map(Felt252::from).ok_or_else(|| ArgsError::InvalidFelt(s.to_string()))
+}
+
+/// Decodes user arguments into run arguments, according to the types of the parameters.
+pub struct ArgsDecoder<'a> {
+    pub sierra_program_registry: &'a ProgramRegistry<CoreType, CoreLibfunc>,
+    pub type_sizes: &'a TypeSizeMap,
+}
+impl ArgsDecoder<'_> {
+    /// Decodes `args` as the values of parameters of the types `param_types`.
+    pub fn decode(
+        &self,
+        param_types: &[ConcreteTypeId],
+        args: &UserArgs,
+    ) -> Result<Vec<Arg>, ArgsError> {
+        let mut result = vec![];
+        match args {
+            UserArgs::Json(value) => {
+                let Value::Array(values) = value else {
+                    return Err(ArgsError::InvalidJson("Expected an array of arguments.".into()));
+                };
+                if values.len() != param_types.len() {
+                    return Err(ArgsError::WrongNumberOfArgs {
+                        expected: param_types.len(),
+                        actual: values.len(),
+                    });
+                }
+                for (ty, value) in param_types.iter().zip(values) {
+                    self.decode_json(ty, value, &mut result)?;
+                }
+            }
+            UserArgs::Felts(felts) => {
+                let mut felts = felts.iter().cloned();
+                for ty in param_types {
+                    self.decode_felts(ty, &mut felts, &mut result)?;
+                }
+                let unused = felts.count();
+                if unused != 0 {
+                    return Err(ArgsError::UnusedFelts(unused));
+                }
+            }
+        }
+        Ok(result)
+    }
+
+    /// Decodes a JSON value of type `ty`, adding its memory representation to `result`.
+    fn decode_json(
+        &self,
+        ty: &ConcreteTypeId,
+        value: &Value,
+        result: &mut Vec<Arg>,
+    ) -> Result<(), ArgsError> {
+        let mismatch = || ArgsError::TypeMismatch { ty: ty.to_string(), value: value.to_string() };
+        match self.get_type(ty)? {
+            CoreTypeConcrete::Struct(info) => {
+                if let Some(user_type) = user_type_of(&info.info.long_id.generic_args) {
+                    if *user_type == UserTypeId::from_string("core::integer::u256") {
+                        if let Some(value) = json_as_bigint(value) {
+                            let value = self.check_range(ty, value, &u256_range())?;
+                            let low = &value & ((BigInt::from(1) << 128) - 1);
+                            let high = value >> 128;
+                            result.extend([low, high].map(|x| Arg::Value(Felt252::from(x))));
+                            return Ok(());
+                        }
+                    } else if *user_type == UserTypeId::from_string("core::byte_array::ByteArray") {
+                        if let Value::String(s) = value {
+                            result.extend(encode_byte_array(s.as_bytes()));
+                            return Ok(());
+                        }
+                    }
+                }
+                if let [member] = &info.members[..] {
+                    let start = result.len();
+                    match self.decode_json(member, value, result) {
+                        Ok(()) => return Ok(()),
+                        Err(err) if !matches!(value, Value::Array(values) if values.len() == 1) => {
+                            return Err(err);
+                        }
+                        // Retrying as the positional form of a single member struct.
+                        Err(_) => result.truncate(start),
+                    }
+                }
+                let Value::Array(values) = value else { return Err(mismatch()) };
+                if values.len() != info.members.len() {
+                    return Err(mismatch());
+                }
+                for (member, value) in info.members.iter().zip(values) {
+                    self.decode_json(member, value, result)?;
+                }
+            }
+            CoreTypeConcrete::Enum(info) => {
+                let unit = Value::Array(vec![]);
+                let (index, payload) = match value {
+                    Value::Bool(b) if self.is_two_unit_variants_enum(&info.variants) => {
+                        (usize::from(*b), &unit)
+                    }
+                    Value::Object(obj) => {
+                        let index = obj
+                            .get("variant")
+                            .and_then(Value::as_u64)
+                            .and_then(|index| index.to_usize())
+                            .ok_or_else(mismatch)?;
+                        (index, obj.get("value").unwrap_or(&unit))
+                    }
+                    _ => return Err(mismatch()),
+                };
+                let mut payload_args = vec![];
+                let variant = self.enum_variant(ty, &info.variants, index)?;
+                self.decode_json(variant, payload, &mut payload_args)?;
+                self.push_enum(ty, &info.variants, index, payload_args, result);
+            }
+            CoreTypeConcrete::Array(info) => {
+                let Value::Array(values) = value else { return Err(mismatch()) };
+                let mut elements = vec![];
+                for value in values {
+                    self.decode_json(&info.ty, value, &mut elements)?;
+                }
+                result.push(Arg::Array(elements));
+            }
+            CoreTypeConcrete::Snapshot(info) => self.decode_json(&info.ty, value, result)?,
+            CoreTypeConcrete::NonZero(info) => {
+                let start = result.len();
+                self.decode_json(&info.ty, value, result)?;
+                check_non_zero(ty, &result[start..])?;
+            }
+            concrete => {
+                let range = felt_like_range(ty, concrete)?;
+                let value = json_as_bigint(value).ok_or_else(mismatch)?;
+                let value = self.check_range(ty, value, &range)?;
+                result.push(Arg::Value(Felt252::from(value)));
+            }
+        }
+        Ok(())
+    }
+
+    /// Decodes the `Serde` serialization of a value of type `ty`, adding its memory representation
+    /// to `result`.
+    fn decode_felts(
+        &self,
+        ty: &ConcreteTypeId,
+        felts: &mut impl Iterator<Item = Felt252>,
+        result: &mut Vec<Arg>,
+    ) -> Result<(), ArgsError> {
+        match self.get_type(ty)? {
+            CoreTypeConcrete::Struct(info) => {
+                for member in &info.members {
+                    self.decode_felts(member, felts, result)?;
+                }
+            }
+            CoreTypeConcrete::Enum(info) => {
+                let index = next_usize(ty, felts)?;
+                let mut payload_args = vec![];
+                let variant = self.enum_variant(ty, &info.variants, index)?;
+                self.decode_felts(variant, felts, &mut payload_args)?;
+                self.push_enum(ty, &info.variants, index, payload_args, result);
+            }
+            CoreTypeConcrete::Array(info) => {
+                let len = next_usize(ty, felts)?;
+                let mut elements = vec![];
+                for _ in 0..len {
+                    self.decode_felts(&info.ty, felts, &mut elements)?;
+                }
+                result.push(Arg::Array(elements));
+            }
+            CoreTypeConcrete::Snapshot(info) => self.decode_felts(&info.ty, felts, result)?,
+            CoreTypeConcrete::NonZero(info) => {
+                let start = result.len();
+                self.decode_felts(&info.ty, felts, result)?;
+                check_non_zero(ty, &result[start..])?;
+            }
+            concrete => {
+                let range = felt_like_range(ty, concrete)?;
+                let value = felts.next().ok_or(ArgsError::MissingFelts)?;
+                // Felts are taken as signed values, to allow negative values for signed types.
+                let value = self.check_range(ty, value.to_bigint(), &range)?;
+                result.push(Arg::Value(Felt252::from(value)));
+            }
+        }
+        Ok(())
+    }
+
+    /// Adds the memory representation of the enum variant `index` with the given payload.
+    fn push_enum(
+        &self,
+        ty: &ConcreteTypeId,
+        variants: &[ConcreteTypeId],
+        index: usize,
+        payload: Vec<Arg>,
+        result: &mut Vec<Arg>,
+    ) {
+        let selector = get_variant_selector(variants.len(), index).unwrap();
+        result.push(Arg::Value(Felt252::from(selector)));
+        let padding = self.type_sizes[ty] - 1 - self.type_sizes[&variants[index]];
+        result.extend((0..padding).map(|_| Arg::Value(Felt252::from(0))));
+        result.extend(payload);
+    }
+
+    /// Returns the variant `index` of the enum `ty`.
+    fn enum_variant<'b>(
+        &self,
+        ty: &ConcreteTypeId,
+        variants: &'b [ConcreteTypeId],
+        index: usize,
+    ) -> Result<&'b ConcreteTypeId, ArgsError> {
+        variants.get(index).ok_or_else(|| ArgsError::InvalidVariant { ty: ty.to_string(), index })
+    }
+
+    /// Returns whether the given enum variants are exactly two zero sized variants.
+    fn is_two_unit_variants_enum(&self, variants: &[ConcreteTypeId]) -> bool {
+        variants.len() == 2 && variants.iter().all(|variant| self.type_sizes[variant] == 0)
+    }
+
+    /// Checks that `value` is in `range`, returning it as a non-negative value if it represents
+    /// a negative felt252.
+    fn check_range(
+        &self,
+        ty: &ConcreteTypeId,
+        value: BigInt,
+        range: &Range,
+    ) -> Result<BigInt, ArgsError> {
+        // Felts above half the prime are interpreted as negative values.
+        let prime: &BigInt = &CAIRO_PRIME_BIGINT;
+        let value =
+            if range.lower < BigInt::zero() && value > prime / 2 { value - prime } else { value };
+        if range.lower <= value && value < range.upper {
+            Ok(value)
+        } else {
+            Err(ArgsError::OutOfRange { ty: ty.to_string(), value: value.to_string() })
+        }
+    }
+
+    /// Returns the concrete type of `ty`.
+    fn get_type(&self, ty: &ConcreteTypeId) -> Result<&CoreTypeConcrete, ArgsError> {
+        self.sierra_program_registry
+            .get_type(ty)
+            .map_err(|_| ArgsError::UnsupportedType(ty.to_string()))
+    }
+}
+
+/// Returns the range of values of a type represented by a single felt252.
+pub(crate) fn felt_like_range(
+    ty: &ConcreteTypeId,
+    concrete: &CoreTypeConcrete,
+) -> Result<Range, ArgsError> {
+    match concrete {
+        CoreTypeConcrete::StarkNet(
+            StarkNetTypeConcrete::ClassHash(_)
+            | StarkNetTypeConcrete::ContractAddress(_)
+            | StarkNetTypeConcrete::StorageBaseAddress(_)
+            | StarkNetTypeConcrete::StorageAddress(_),
+        ) => Ok(Range::half_open(0, CAIRO_PRIME_BIGINT.clone())),
+        _ => Range::from_type_info(concrete.info())
+            .map_err(|_| ArgsError::UnsupportedType(ty.to_string())),
+    }
+}
+
+/// Returns the user type of a struct or enum from its generic args.
+pub(crate) fn user_type_of(generic_args: &[GenericArg]) -> Option<&UserTypeId> {
+    match generic_args.first()? {
+        GenericArg::UserType(user_type) => Some(user_type),
+        _ => None,
+    }
+}
+
+/// The range of a `u256` value.
+pub(crate) fn u256_range() -> Range {
+    Range::half_open(0, BigInt::from(1) << 256)
+}
+
+/// Returns the numeric value of a JSON number or string.
+pub(crate) fn json_as_bigint(value: &Value) -> Option<BigInt> {
+    match value {
+        Value::Number(n) => parse_bigint(&n.to_string()),
+        Value::String(s) => parse_bigint(s),
+        _ => None,
+    }
+}
+
+/// Returns the next felt as a `usize`, to be used as a length or a variant index.
+fn next_usize(
+    ty: &ConcreteTypeId,
+    felts: &mut impl Iterator<Item = Felt252>,
+) -> Result<usize, ArgsError> {
+    let value = felts.next().ok_or(ArgsError::MissingFelts)?;
+    value
+        .to_usize()
+        .ok_or_else(|| ArgsError::TypeMismatch { ty: ty.to_string(), value: value.to_string() })
+}
+
+/// Checks that `args`, the memory representation of a value of the `NonZero` type `ty`, is not
+/// zero - a value is zero only if all of its cells are.
+fn check_non_zero(ty: &ConcreteTypeId, args: &[Arg]) -> Result<(), ArgsError> {
+    if args.iter().all(|arg| matches!(arg, Arg::Value(value) if value.is_zero())) {
+        return Err(ArgsError::OutOfRange { ty: ty.to_string(), value: "0".into() });
+    }
+    Ok(())
+}
+
+/// Returns the memory representation of a `ByteArray` holding `bytes`.
+fn encode_byte_array(bytes: &[u8]) -> Vec<Arg> {
+    let chunks = bytes.chunks(BYTES_IN_WORD).collect_vec();
+    let (full_words, pending_word) = match chunks.last() {
+        Some(last) if last.len() < BYTES_IN_WORD => (&chunks[..chunks.len() - 1], *last),
+        _ => (&chunks[..], &[][..]),
+    };
+    vec![
+        Arg::Array(
+            full_words.iter().map(|word| Arg::Value(Felt252::from_bytes_be_slice(word))).collect(),
+        ),
+        Arg::Value(Felt252::from_bytes_be_slice(pending_word)),
+        Arg::Value(Felt252::from(pending_word.len())),
+    ]
+}
@@ -2,17 +2,20 @@ use std::any::Any;
 use std::borrow::Cow;
 use std::collections::{HashMap, VecDeque};
 use std::ops::{Shl, Sub};
+use std::sync::Arc;
 use std::vec::IntoIter;
 
 use ark_ff::{BigInteger, PrimeField};
-use cairo_lang_casm::hints::{CoreHint, DeprecatedHint, Hint, StarknetHint};
+use cairo_lang_casm::hints::{CoreHint, CoreHintBase, DeprecatedHint, Hint, StarknetHint};
 use cairo_lang_casm::operand::{
     BinOpOperand, CellRef, DerefOrImmediate, Operation, Register, ResOperand,
 };
 use cairo_lang_sierra::ids::FunctionId;
+use cairo_lang_sierra::program::StatementIdx;
 use cairo_lang_utils::bigint::BigIntAsHex;
 use cairo_lang_utils::byte_array::{BYTE_ARRAY_MAGIC, BYTES_IN_WORD};
 use cairo_lang_utils::extract_matches;
+use cairo_lang_utils::unordered_hash_map::UnorderedHashMap;
 use cairo_vm::hint_processor::hint_processor_definition::{
     HintProcessor, HintProcessorLogic, HintReference,
 };
@@ -39,6 +42,7 @@ use num_bigint::{BigInt, BigUint};
 use num_integer::{ExtendedGcd, Integer};
 use num_traits::{Signed, ToPrimitive, Zero};
 use rand::Rng;
+use serde::{Deserialize, Serialize};
 use starknet_types_core::felt::{Felt as Felt252, NonZeroFelt};
 use {ark_secp256k1 as secp256k1, ark_secp256r1 as secp256r1};
 
@@ -95,6 +99,12 @@ pub struct CairoHintProcessor<'a> {
     /// Resources used during syscalls - does not include resources used during the current VM run.
     /// At the end of the run - adding both would result in the actual expected resource usage.
     pub syscalls_used_resources: StarknetExecutionResources,
+    /// The number of times each Sierra statement was executed in nested runs (e.g. contract
+    /// calls), collected only when collecting profiling info.
+    pub nested_sierra_statement_hits: UnorderedHashMap<StatementIdx, usize>,
+    /// The output of the debug prints of the run, if captured instead of printed to the standard
+    /// output.
+    pub debug_output: Option<String>,
 }
 
 pub fn cell_ref_to_relocatable(cell_ref: &CellRef, vm: &VirtualMachine) -> Relocatable {
@@ -121,7 +131,11 @@ type L2ToL1Message = (Felt252, Vec<Felt252>);
 
 /// Execution scope for starknet related data.
 /// All values will be 0 and by default if not setup by the test.
-#[derive(Clone, Default)]
+///
+/// May be loaded from a JSON fixture, in which all the felt252 values are hex strings, and missing
+/// fields are set to their default values.
+#[derive(Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
 pub struct StarknetState {
     /// The values of addresses in the simulated storage per contract.
     storage: HashMap<Felt252, HashMap<Felt252, Felt252>>,
@@ -133,8 +147,57 @@ pub struct StarknetState {
     exec_info: ExecutionInfo,
     /// A mock history, mapping block number to the class hash.
     block_hash: HashMap<u64, Felt252>,
+    /// The mocked results of calls, by contract address and entry point selector.
+    mocked_calls: HashMap<Felt252, HashMap<Felt252, MockedCall>>,
+    /// The mocked results of library calls, by class hash and entry point selector.
+    mocked_library_calls: HashMap<Felt252, HashMap<Felt252, MockedCall>>,
+    /// The syscalls forced to fail, by syscall selector, with the revert reason of the failure.
+    failing_syscalls: HashMap<String, Vec<Felt252>>,
+    /// The number of events emitted so far, used for ordering the events of all the contracts.
+    #[serde(skip)]
+    n_emitted_events: usize,
+    /// The snapshots taken of the state, where the id of a snapshot is its index.
+    #[serde(skip)]
+    snapshots: Vec<Arc<StarknetState>>,
 }
 impl StarknetState {
+    /// Takes a snapshot of the state, and returns its id.
+    pub fn take_snapshot(&mut self) -> usize {
+        let snapshot = Self { snapshots: vec![], ..self.clone() };
+        self.snapshots.push(Arc::new(snapshot));
+        self.snapshots.len() - 1
+    }
+
+    /// Reverts the state to the snapshot with the given id. The snapshot remains available for
+    /// further reverts, as do all the other snapshots.
+    ///
+    /// Returns `None` if there is no snapshot with the given id.
+    pub fn revert_to_snapshot(&mut self, id: usize) -> Option<()> {
+        let snapshot = self.snapshots.get(id)?.as_ref().clone();
+        let snapshots = std::mem::take(&mut self.snapshots);
+        *self = Self { snapshots, ..snapshot };
+        Some(())
+    }
+
+    /// Returns the events that were emitted and not popped, of all the contracts, in the order of
+    /// their emission.
+    pub fn unpopped_events(&self) -> Vec<EmittedEvent> {
+        self.logs
+            .iter()
+            .flat_map(|(from_address, contract_logs)| {
+                contract_logs.events.iter().map(move |(index, (keys, data))| {
+                    (*index, EmittedEvent {
+                        from_address: *from_address,
+                        keys: keys.clone(),
+                        data: data.clone(),
+                    })
+                })
+            })
+            .sorted_by_key(|(index, event)| (*index, event.from_address))
+            .map(|(_, event)| event)
+            .collect()
+    }
+
     /// Replaces the addresses in the context.
     pub fn open_caller_context(
         &mut self,
@@ -157,17 +220,65 @@ impl StarknetState {
     }
 }
 
+/// The mocked result of a call to an entry point.
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum MockedCall {
+    /// The call returns the given data.
+    Success(Vec<Felt252>),
+    /// The call reverts with the given revert reason.
+    Revert(Vec<Felt252>),
+}
+
+/// An event emitted by a contract.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EmittedEvent {
+    /// The address of the emitting contract.
+    pub from_address: Felt252,
+    /// The keys of the event.
+    pub keys: Vec<Felt252>,
+    /// The data of the event.
+    pub data: Vec<Felt252>,
+}
+
+/// Serializes indexed events as their logs, keeping the format of the fixtures free of the
+/// emission indices. Deserialized events get the index 0, as they were emitted before the events
+/// of the run, whose indices start at 1.
+mod indexed_events {
+    use std::collections::VecDeque;
+
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    use super::Log;
+
+    pub fn serialize<S: Serializer>(
+        events: &VecDeque<(usize, Log)>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(events.iter().map(|(_, log)| log))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<VecDeque<(usize, Log)>, D::Error> {
+        Ok(Vec::<Log>::deserialize(deserializer)?.into_iter().map(|log| (0, log)).collect())
+    }
+}
+
 /// Object storing logs for a contract.
-#[derive(Clone, Default)]
+#[derive(Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
 struct ContractLogs {
-    /// Events.
-    events: VecDeque<Log>,
+    /// Events, with the index of their emission among the events of all the contracts.
+    #[serde(with = "indexed_events")]
+    events: VecDeque<(usize, Log)>,
     /// Messages sent to L1.
     l2_to_l1_messages: VecDeque<L2ToL1Message>,
 }
 
 /// Copy of the cairo `ExecutionInfo` struct.
-#[derive(Clone, Default)]
+#[derive(Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
 struct ExecutionInfo {
     block_info: BlockInfo,
     tx_info: TxInfo,
@@ -177,7 +288,8 @@ struct ExecutionInfo {
 }
 
 /// Copy of the cairo `BlockInfo` struct.
-#[derive(Clone, Default)]
+#[derive(Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
 struct BlockInfo {
     block_number: Felt252,
     block_timestamp: Felt252,
@@ -185,7 +297,8 @@ struct BlockInfo {
 }
 
 /// Copy of the cairo `TxInfo` struct.
-#[derive(Clone, Default)]
+#[derive(Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
 struct TxInfo {
     version: Felt252,
     account_contract_address: Felt252,
@@ -203,7 +316,8 @@ struct TxInfo {
 }
 
 /// Copy of the cairo `ResourceBounds` struct.
-#[derive(Clone, Default)]
+#[derive(Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
 struct ResourceBounds {
     resource: Felt252,
     max_amount: Felt252,
@@ -306,6 +420,27 @@ enum SyscallResult {
     Failure(Vec<Felt252>),
 }
 
+/// Returns the size of the request of the syscall with the given selector, not including the
+/// selector and the gas counter, or `None` if the selector is unknown.
+/// Must match the requests read by `CairoHintProcessor::execute_syscall`.
+fn syscall_request_size(selector: &str) -> Option<usize> {
+    Some(match selector {
+        "GetExecutionInfo" => 0,
+        "GetBlockHash" | "Secp256k1GetXy" | "Secp256r1GetXy" | "ReplaceClass"
+        | "GetClassHashAt" => 1,
+        "StorageRead" | "Keccak" | "Sha256ProcessBlock" | "Secp256k1Add" | "Secp256r1Add" => 2,
+        "StorageWrite"
+        | "SendMessageToL1"
+        | "Secp256k1Mul"
+        | "Secp256r1Mul"
+        | "Secp256k1GetPointFromX"
+        | "Secp256r1GetPointFromX" => 3,
+        "EmitEvent" | "Secp256k1New" | "Secp256r1New" | "CallContract" | "LibraryCall" => 4,
+        "Deploy" => 5,
+        _ => return None,
+    })
+}
+
 macro_rules! fail_syscall {
     ([$reason1:expr, $reason2:expr]) => {
         return Ok(SyscallResult::Failure(vec![
@@ -413,6 +548,12 @@ impl HintProcessorLogic for CairoHintProcessor<'_> {
         _constants: &HashMap<String, Felt252>,
     ) -> Result<(), HintError> {
         let hint = hint_data.downcast_ref::<Hint>().unwrap();
+        if let (Some(output), Hint::Core(CoreHintBase::Core(CoreHint::DebugPrint { start, end }))) =
+            (&mut self.debug_output, hint)
+        {
+            output.push_str(&format_for_debug(read_felts(vm, start, end)?.into_iter()));
+            return Ok(());
+        }
         let hint = match hint {
             Hint::Core(core_hint_base) => {
                 return execute_core_hint_base(vm, exec_scopes, core_hint_base);
@@ -661,6 +802,14 @@ impl CairoHintProcessor<'_> {
             };
         let selector = std::str::from_utf8(&selector).unwrap().trim_start_matches('\0');
         *self.syscalls_used_resources.syscalls.entry(selector.into()).or_default() += 1;
+        if let Some(revert_reason) = self.starknet_state.failing_syscalls.get(selector) {
+            let request_size =
+                syscall_request_size(selector).expect("Only known syscalls are forced to fail.");
+            return execute_handle_helper(&mut |system_buffer, _| {
+                system_buffer.ptr += request_size;
+                Ok(SyscallResult::Failure(revert_reason.clone()))
+            });
+        }
         match selector {
             "StorageWrite" => execute_handle_helper(&mut |system_buffer, gas_counter| {
                 self.storage_write(
@@ -934,7 +1083,14 @@ impl CairoHintProcessor<'_> {
     ) -> Result<SyscallResult, HintError> {
         deduct_gas!(gas_counter, EMIT_EVENT);
         let contract = self.starknet_state.exec_info.contract_address;
-        self.starknet_state.logs.entry(contract).or_default().events.push_back((keys, data));
+        self.starknet_state.n_emitted_events += 1;
+        let index = self.starknet_state.n_emitted_events;
+        self.starknet_state
+            .logs
+            .entry(contract)
+            .or_default()
+            .events
+            .push_back((index, (keys, data)));
         Ok(SyscallResult::Success(vec![]))
     }
 
@@ -1039,6 +1195,15 @@ impl CairoHintProcessor<'_> {
     ) -> Result<SyscallResult, HintError> {
         deduct_gas!(gas_counter, CALL_CONTRACT);
 
+        let mocked_call = self
+            .starknet_state
+            .mocked_calls
+            .get(&contract_address)
+            .and_then(|mocked_calls| mocked_calls.get(&selector));
+        if let Some(mocked_call) = mocked_call {
+            return mocked_call_result(mocked_call.clone(), vm);
+        }
+
         // Get the class hash of the contract.
         let Some(class_hash) = self.starknet_state.deployed_contracts.get(&contract_address) else {
             fail_syscall!([b"CONTRACT_NOT_DEPLOYED", b"ENTRYPOINT_FAILED"]);
@@ -1083,6 +1248,14 @@ impl CairoHintProcessor<'_> {
         vm: &mut dyn VMWrapper,
     ) -> Result<SyscallResult, HintError> {
         deduct_gas!(gas_counter, LIBRARY_CALL);
+        let mocked_call = self
+            .starknet_state
+            .mocked_library_calls
+            .get(&class_hash)
+            .and_then(|mocked_calls| mocked_calls.get(&selector));
+        if let Some(mocked_call) = mocked_call {
+            return mocked_call_result(mocked_call.clone(), vm);
+        }
         // Prepare runner for running the call.
         let runner = self.runner.expect("Runner is needed for starknet.");
         let Some(contract_info) = runner.starknet_contracts_info.get(&class_hash) else {
@@ -1165,6 +1338,13 @@ impl CairoHintProcessor<'_> {
             )
             .expect("Internal runner error.");
         self.syscalls_used_resources += res.used_resources;
+        if let (Some(output), Some(nested_output)) = (&mut self.debug_output, &res.debug_output) {
+            output.push_str(nested_output);
+        }
+        if let Some(profiling_info) = &res.profiling_info {
+            self.nested_sierra_statement_hits
+                .merge(&profiling_info.sierra_statement_hits, |mut e, hits| *e.get_mut() += hits);
+        }
         *gas_counter = res.gas_counter.unwrap().to_usize().unwrap();
         match res.value {
             RunResultValue::Success(value) => {
@@ -1259,7 +1439,7 @@ impl CairoHintProcessor<'_> {
             }
             "pop_log" => {
                 let contract_logs = self.starknet_state.logs.get_mut(&as_single_input(inputs)?);
-                if let Some((keys, data)) =
+                if let Some((_, (keys, data))) =
                     contract_logs.and_then(|contract_logs| contract_logs.events.pop_front())
                 {
                     res_segment.write(keys.len())?;
@@ -1268,6 +1448,135 @@ impl CairoHintProcessor<'_> {
                     res_segment.write_data(data.iter())?;
                 }
             }
+            "snapshot_state" => {
+                res_segment.write(self.starknet_state.take_snapshot())?;
+            }
+            "revert_to_snapshot" => {
+                let id = as_single_input(inputs)?;
+                // An empty result marks success, while an invalid id is reported by a non-empty
+                // result, so the Cairo code can panic.
+                if id.to_usize().and_then(|id| self.starknet_state.revert_to_snapshot(id)).is_none()
+                {
+                    res_segment.write(id)?;
+                }
+            }
+            "mock_call" | "mock_library_call" => {
+                let [address, entry_point_selector, result_variant, result_len]: [Felt252; 4] =
+                    inputs.get(..4).and_then(|header| header.try_into().ok()).ok_or_else(|| {
+                        HintError::CustomHint(Box::from(format!(
+                            "`{selector}` cheatcode invalid args: pass the address, the selector \
+                             and the serialized result",
+                        )))
+                    })?;
+                let data = inputs[4..].to_vec();
+                if result_len.to_usize() != Some(data.len()) {
+                    Err(HintError::CustomHint(Box::from(format!(
+                        "`{selector}` cheatcode invalid args: result length mismatch"
+                    ))))?;
+                }
+                let mocked_call = if result_variant.is_zero() {
+                    MockedCall::Success(data)
+                } else {
+                    MockedCall::Revert(data)
+                };
+                let mocked_calls = if selector == "mock_call" {
+                    &mut self.starknet_state.mocked_calls
+                } else {
+                    &mut self.starknet_state.mocked_library_calls
+                };
+                mocked_calls.entry(address).or_default().insert(entry_point_selector, mocked_call);
+            }
+            "clear_mock_call" | "clear_mock_library_call" => {
+                let [address, entry_point_selector] = vec_as_array(inputs, || {
+                    format!(
+                        "`{selector}` cheatcode invalid args: pass span of an array with exactly \
+                         two elements",
+                    )
+                })?;
+                let mocked_calls = if selector == "clear_mock_call" {
+                    &mut self.starknet_state.mocked_calls
+                } else {
+                    &mut self.starknet_state.mocked_library_calls
+                };
+                if let Some(address_mocked_calls) = mocked_calls.get_mut(&address) {
+                    address_mocked_calls.remove(&entry_point_selector);
+                }
+            }
+            "fail_syscall" => {
+                let Some((syscall, revert_reason)) = inputs.split_first() else {
+                    Err(HintError::CustomHint(Box::from(format!(
+                        "`{selector}` cheatcode invalid args: pass the syscall selector and the \
+                         revert reason",
+                    ))))?
+                };
+                let syscall = as_known_syscall(syscall, selector)?;
+                self.starknet_state.failing_syscalls.insert(syscall, revert_reason.to_vec());
+            }
+            "clear_failing_syscall" => {
+                let syscall = as_known_syscall(&as_single_input(inputs)?, selector)?;
+                self.starknet_state.failing_syscalls.remove(&syscall);
+            }
+            "get_logs" => {
+                let (address, key) = match inputs[..] {
+                    [address] => (address, None),
+                    [address, key] => (address, Some(key)),
+                    _ => Err(HintError::CustomHint(Box::from(format!(
+                        "`{selector}` cheatcode invalid args: pass the address, and optionally a \
+                         key",
+                    ))))?,
+                };
+                let events = self
+                    .starknet_state
+                    .logs
+                    .get(&address)
+                    .into_iter()
+                    .flat_map(|contract_logs| contract_logs.events.iter())
+                    .map(|(_, log)| log)
+                    .filter(|(keys, _)| key.is_none_or(|key| keys.contains(&key)))
+                    .collect_vec();
+                res_segment.write(events.len())?;
+                for (keys, data) in events {
+                    res_segment.write(keys.len())?;
+                    res_segment.write_data(keys.iter())?;
+                    res_segment.write(data.len())?;
+                    res_segment.write_data(data.iter())?;
+                }
+            }
+            "get_all_logs" => {
+                let events = self.starknet_state.unpopped_events();
+                res_segment.write(events.len())?;
+                for EmittedEvent { from_address, keys, data } in events {
+                    res_segment.write(from_address)?;
+                    res_segment.write(keys.len())?;
+                    res_segment.write_data(keys.iter())?;
+                    res_segment.write(data.len())?;
+                    res_segment.write_data(data.iter())?;
+                }
+            }
+            "clear_logs" => {
+                if let Some(contract_logs) =
+                    self.starknet_state.logs.get_mut(&as_single_input(inputs)?)
+                {
+                    contract_logs.events.clear();
+                }
+            }
+            "get_l2_to_l1_messages" => {
+                let contract_logs = self.starknet_state.logs.get(&as_single_input(inputs)?);
+                let messages = contract_logs.map(|contract_logs| &contract_logs.l2_to_l1_messages);
+                res_segment.write(messages.map_or(0, |messages| messages.len()))?;
+                for (to_address, payload) in messages.into_iter().flatten() {
+                    res_segment.write(*to_address)?;
+                    res_segment.write(payload.len())?;
+                    res_segment.write_data(payload.iter())?;
+                }
+            }
+            "clear_l2_to_l1_messages" => {
+                if let Some(contract_logs) =
+                    self.starknet_state.logs.get_mut(&as_single_input(inputs)?)
+                {
+                    contract_logs.l2_to_l1_messages.clear();
+                }
+            }
             "pop_l2_to_l1_message" => {
                 let contract_logs = self.starknet_state.logs.get_mut(&as_single_input(inputs)?);
                 if let Some((to_address, payload)) = contract_logs
@@ -1289,6 +1598,35 @@ impl CairoHintProcessor<'_> {
     }
 }
 
+/// Returns the result of a call syscall with the given mocked result. A mocked revert is treated as
+/// a panic of the called entry point.
+fn mocked_call_result(
+    mocked_call: MockedCall,
+    vm: &mut dyn VMWrapper,
+) -> Result<SyscallResult, HintError> {
+    match mocked_call {
+        MockedCall::Success(ret_data) => {
+            let (res_data_start, res_data_end) = segment_with_data(vm, ret_data.into_iter())?;
+            Ok(SyscallResult::Success(vec![res_data_start.into(), res_data_end.into()]))
+        }
+        MockedCall::Revert(mut revert_reason) => {
+            fail_syscall!(revert_reason, b"ENTRYPOINT_FAILED");
+        }
+    }
+}
+
+/// Returns the name of the syscall with the given selector, given as a short string to the
+/// cheatcode `cheatcode_selector`. Fails if there is no such syscall.
+fn as_known_syscall(syscall: &Felt252, cheatcode_selector: &str) -> Result<String, HintError> {
+    as_cairo_short_string(syscall)
+        .filter(|syscall| syscall_request_size(syscall).is_some())
+        .ok_or_else(|| {
+            HintError::CustomHint(Box::from(format!(
+                "`{cheatcode_selector}` cheatcode invalid args: unknown syscall selector {syscall}",
+            )))
+        })
+}
+
 /// Extracts an array of felt252s from a vector of such.
 fn vec_as_array<const COUNT: usize>(
     inputs: Vec<Felt252>,
@@ -2300,6 +2638,10 @@ impl FormattedItem {
     pub fn get(self) -> String {
         self.item
     }
+    /// Returns whether the item is a string.
+    pub fn is_string(&self) -> bool {
+        self.is_string
+    }
     /// Wraps the formatted item with quote, if it's a string. Otherwise returns it as is.
     pub fn quote_if_string(self) -> String {
         if self.is_string { format!("\"{}\"", self.item) } else { self.item }
@@ -7,6 +7,7 @@ use cairo_vm::vm::runners::cairo_runner::RunResources;
 use indoc::indoc;
 use itertools::Itertools;
 use num_traits::ToPrimitive;
+use serde_json::json;
 use starknet_types_core::felt::Felt as Felt252;
 use test_case::test_case;
 
@@ -14,7 +15,7 @@ use super::format_for_debug;
 use crate::casm_run::contract_address::calculate_contract_address;
 use crate::casm_run::{RunFunctionResult, run_function};
 use crate::short_string::{as_cairo_short_string, as_cairo_short_string_ex};
-use crate::{CairoHintProcessor, StarknetState, build_hints_dict};
+use crate::{CairoHintProcessor, EmittedEvent, StarknetState, build_hints_dict};
 
 /// Creates a new `AssembledCairoProgram` from the given `CasmContext`.
 fn assembled(casm: CasmContext) -> AssembledCairoProgram {
@@ -128,6 +129,8 @@ fn test_runner(function: CasmContext, n_returns: usize, expected: &[i128]) {
         starknet_state: StarknetState::default(),
         run_resources: RunResources::default(),
         syscalls_used_resources: Default::default(),
+        nested_sierra_statement_hits: Default::default(),
+        debug_output: None,
     };
 
     let RunFunctionResult { ap, memory, .. } =
@@ -155,6 +158,8 @@ fn test_allocate_segment() {
         starknet_state: StarknetState::default(),
         run_resources: RunResources::default(),
         syscalls_used_resources: Default::default(),
+        nested_sierra_statement_hits: Default::default(),
+        debug_output: None,
     };
 
     let RunFunctionResult { ap, memory, .. } =
@@ -477,3 +482,36 @@ fn test_calculate_contract_address() {
         deployed_contract_address
     );
 }
+
+#[test]
+fn test_starknet_state_logs_fixture() {
+    let contract_logs = json!({ "events": [[["0x1"], ["0x2"]]], "l2_to_l1_messages": [] });
+    let mut state: StarknetState =
+        serde_json::from_value(json!({ "logs": { "0x1234": contract_logs } })).unwrap();
+    // An event emitted during the run, by a contract with a lower address.
+    state.n_emitted_events += 1;
+    state
+        .logs
+        .entry(Felt252::from(0x10))
+        .or_default()
+        .events
+        .push_back((state.n_emitted_events, (vec![Felt252::from(3)], vec![Felt252::from(4)])));
+
+    // The events of the fixture were emitted before the events of the run.
+    assert_eq!(state.unpopped_events(), [
+        EmittedEvent {
+            from_address: Felt252::from(0x1234),
+            keys: vec![Felt252::from(1)],
+            data: vec![Felt252::from(2)],
+        },
+        EmittedEvent {
+            from_address: Felt252::from(0x10),
+            keys: vec![Felt252::from(3)],
+            data: vec![Felt252::from(4)],
+        },
+    ]);
+    // The emission indices are not part of the fixture format.
+    let serialized = serde_json::to_value(&state).unwrap();
+    assert_eq!(serialized["logs"]["0x1234"], contract_logs);
+    assert!(serialized.get("n_emitted_events").is_none());
+}
@@ -0,0 +1,333 @@
+//! Generation of random arguments for fuzzing a function, and shrinking of failing arguments.
+//!
+//! Arguments are generated in the JSON form accepted by [crate::args::ArgsDecoder], so that
+//! generated inputs can be reported, and replayed with `cairo-run --args`.
+
+use cairo_lang_sierra::extensions::core::{CoreLibfunc, CoreType, CoreTypeConcrete};
+use cairo_lang_sierra::extensions::utils::Range;
+use cairo_lang_sierra::ids::{ConcreteTypeId, UserTypeId};
+use cairo_lang_sierra::program_registry::ProgramRegistry;
+use cairo_lang_sierra_type_size::TypeSizeMap;
+use itertools::Itertools;
+use num_bigint::BigInt;
+use num_traits::{One, Signed, ToPrimitive, Zero};
+use rand::Rng;
+use rand::seq::SliceRandom;
+use serde_json::{Value, json};
+use starknet_types_core::felt::CAIRO_PRIME_BIGINT;
+
+use crate::args::{ArgsError, felt_like_range, json_as_bigint, u256_range, user_type_of};
+
+/// The maximal length of generated arrays.
+const MAX_ARRAY_LEN: usize = 16;
+/// The maximal length of generated `ByteArray`s.
+const MAX_BYTE_ARRAY_LEN: usize = 40;
+/// The number of attempts to generate a non-zero value for a `NonZero` type.
+const NON_ZERO_ATTEMPTS: usize = 16;
+
+/// The shape of a type, as relevant for generating and shrinking its values.
+enum Shape<'a> {
+    /// A number in the given range.
+    Number(Range),
+    /// A `ByteArray`, represented as a string.
+    ByteArray,
+    /// A type represented as the value of another type, e.g. single member structs.
+    Transparent(&'a ConcreteTypeId),
+    /// A type represented as the array of values of its members, e.g. structs and tuples.
+    Members(&'a [ConcreteTypeId]),
+    /// An enum with two unit variants, represented as a JSON boolean.
+    Bool,
+    /// An enum with the given variants.
+    Enum(&'a [ConcreteTypeId]),
+    /// An array of elements of the given type.
+    Array(&'a ConcreteTypeId),
+    /// A non-zero value of the given type.
+    NonZero(&'a ConcreteTypeId),
+}
+
+/// Generates random arguments for a function, according to the types of its parameters.
+pub struct ArgsGenerator<'a> {
+    pub sierra_program_registry: &'a ProgramRegistry<CoreType, CoreLibfunc>,
+    pub type_sizes: &'a TypeSizeMap,
+}
+impl ArgsGenerator<'_> {
+    /// Generates random values for parameters of the types `param_types`.
+    pub fn generate(
+        &self,
+        param_types: &[ConcreteTypeId],
+        rng: &mut impl Rng,
+    ) -> Result<Value, ArgsError> {
+        Ok(Value::Array(param_types.iter().map(|ty| self.generate_value(ty, rng)).try_collect()?))
+    }
+
+    /// Returns simpler variations of `args`, the values of parameters of the types `param_types`,
+    /// ordered from the simplest.
+    pub fn shrink(&self, param_types: &[ConcreteTypeId], args: &Value) -> Vec<Value> {
+        let Value::Array(values) = args else { return vec![] };
+        self.shrink_members(param_types, values)
+    }
+
+    /// Generates a random value of type `ty`.
+    fn generate_value(&self, ty: &ConcreteTypeId, rng: &mut impl Rng) -> Result<Value, ArgsError> {
+        Ok(match self.shape(ty)? {
+            Shape::Number(range) => number_to_json(generate_number(&range, rng)),
+            Shape::ByteArray => {
+                let len =
+                    if rng.gen_ratio(1, 4) { 0 } else { rng.gen_range(1..=MAX_BYTE_ARRAY_LEN) };
+                Value::String((0..len).map(|_| char::from(rng.gen_range(b' '..=b'~'))).collect())
+            }
+            Shape::Transparent(inner) => self.generate_value(inner, rng)?,
+            Shape::Members(members) => self.generate(members, rng)?,
+            Shape::Bool => Value::Bool(rng.gen()),
+            Shape::Enum(variants) => {
+                let index = rng.gen_range(0..variants.len());
+                json!({ "variant": index, "value": self.generate_value(&variants[index], rng)? })
+            }
+            Shape::Array(element) => {
+                let len = if rng.gen_ratio(1, 4) {
+                    rng.gen_range(0..=1)
+                } else {
+                    rng.gen_range(0..=MAX_ARRAY_LEN)
+                };
+                Value::Array((0..len).map(|_| self.generate_value(element, rng)).try_collect()?)
+            }
+            Shape::NonZero(inner) => {
+                for _ in 0..NON_ZERO_ATTEMPTS {
+                    let value = self.generate_value(inner, rng)?;
+                    if !is_zero(&value) {
+                        return Ok(value);
+                    }
+                }
+                self.minimal_value(ty)?
+            }
+        })
+    }
+
+    /// Returns the simplest value of type `ty`.
+    fn minimal_value(&self, ty: &ConcreteTypeId) -> Result<Value, ArgsError> {
+        Ok(match self.shape(ty)? {
+            Shape::Number(range) => number_to_json(shrink_target(&range)),
+            Shape::ByteArray => Value::String(String::new()),
+            Shape::Transparent(inner) => self.minimal_value(inner)?,
+            Shape::Members(members) => {
+                Value::Array(members.iter().map(|member| self.minimal_value(member)).try_collect()?)
+            }
+            Shape::Bool => Value::Bool(false),
+            Shape::Enum(variants) => {
+                json!({ "variant": 0, "value": self.minimal_value(&variants[0])? })
+            }
+            Shape::Array(_) => Value::Array(vec![]),
+            Shape::NonZero(inner) => {
+                let value = self.minimal_value(inner)?;
+                if is_zero(&value) { 1.into() } else { value }
+            }
+        })
+    }
+
+    /// Returns simpler variations of `value`, a value of type `ty`, ordered from the simplest.
+    fn shrink_value(&self, ty: &ConcreteTypeId, value: &Value) -> Vec<Value> {
+        let Ok(shape) = self.shape(ty) else { return vec![] };
+        let mut candidates = match shape {
+            Shape::Number(range) => {
+                let Some(value) = json_as_bigint(value) else { return vec![] };
+                let target = shrink_target(&range);
+                let diff = &value - &target;
+                [target.clone(), &target + &diff / 2, &value - diff.signum()]
+                    .into_iter()
+                    .filter(|candidate| *candidate != value)
+                    .map(number_to_json)
+                    .collect()
+            }
+            Shape::ByteArray => {
+                let Value::String(s) = value else { return vec![] };
+                let chars = s.chars().collect_vec();
+                if chars.is_empty() {
+                    return vec![];
+                }
+                let simplest = vec!['a'; chars.len()];
+                [&chars[..0], &chars[..chars.len() / 2], &chars[1..], &chars[..chars.len() - 1]]
+                    .into_iter()
+                    .chain((chars != simplest).then_some(&simplest[..]))
+                    .map(|chars| Value::String(chars.iter().collect()))
+                    .collect()
+            }
+            Shape::Transparent(inner) => self.shrink_value(inner, value),
+            Shape::Members(members) => {
+                let Value::Array(values) = value else { return vec![] };
+                self.shrink_members(members, values)
+            }
+            Shape::Bool => {
+                if value == &Value::Bool(true) {
+                    vec![Value::Bool(false)]
+                } else {
+                    vec![]
+                }
+            }
+            Shape::Enum(variants) => {
+                let (Some(index), Some(payload)) = (
+                    value.get("variant").and_then(Value::as_u64).and_then(|index| index.to_usize()),
+                    value.get("value"),
+                ) else {
+                    return vec![];
+                };
+                let Some(variant) = variants.get(index) else { return vec![] };
+                let mut candidates = vec![];
+                if index != 0 {
+                    if let Ok(minimal) = self.minimal_value(ty) {
+                        candidates.push(minimal);
+                    }
+                }
+                candidates.extend(
+                    self.shrink_value(variant, payload)
+                        .into_iter()
+                        .map(|payload| json!({ "variant": index, "value": payload })),
+                );
+                candidates
+            }
+            Shape::Array(element) => {
+                let Value::Array(values) = value else { return vec![] };
+                if values.is_empty() {
+                    return vec![];
+                }
+                let mut candidates = vec![Value::Array(vec![])];
+                if values.len() > 2 {
+                    candidates.push(Value::Array(values[..values.len() / 2].to_vec()));
+                }
+                for i in 0..values.len() {
+                    let mut values = values.clone();
+                    values.remove(i);
+                    candidates.push(Value::Array(values));
+                }
+                candidates
+                    .extend(self.shrink_members(&vec![element.clone(); values.len()], values));
+                candidates
+            }
+            Shape::NonZero(inner) => self.shrink_value(inner, value),
+        };
+        if matches!(self.shape(ty), Ok(Shape::NonZero(_))) {
+            candidates.retain(|candidate| !is_zero(candidate));
+        }
+        candidates
+    }
+
+    /// Returns simpler variations of `values`, the values of the types `types`, each differing from
+    /// `values` by a single simplified value.
+    fn shrink_members(&self, types: &[ConcreteTypeId], values: &[Value]) -> Vec<Value> {
+        if types.len() != values.len() {
+            return vec![];
+        }
+        let mut candidates = vec![];
+        for (i, (ty, value)) in types.iter().zip(values).enumerate() {
+            for shrunk in self.shrink_value(ty, value) {
+                let mut values = values.to_vec();
+                values[i] = shrunk;
+                candidates.push(Value::Array(values));
+            }
+        }
+        candidates
+    }
+
+    /// Returns the shape of the type `ty`.
+    fn shape(&self, ty: &ConcreteTypeId) -> Result<Shape<'_>, ArgsError> {
+        let concrete = self
+            .sierra_program_registry
+            .get_type(ty)
+            .map_err(|_| ArgsError::UnsupportedType(ty.to_string()))?;
+        Ok(match concrete {
+            CoreTypeConcrete::Struct(info) => match user_type_of(&info.info.long_id.generic_args) {
+                Some(user_type) if *user_type == UserTypeId::from_string("core::integer::u256") => {
+                    Shape::Number(u256_range())
+                }
+                Some(user_type)
+                    if *user_type == UserTypeId::from_string("core::byte_array::ByteArray") =>
+                {
+                    Shape::ByteArray
+                }
+                _ => match &info.members[..] {
+                    [member] => Shape::Transparent(member),
+                    members => Shape::Members(members),
+                },
+            },
+            CoreTypeConcrete::Enum(info) if info.variants.is_empty() => {
+                return Err(ArgsError::UnsupportedType(ty.to_string()));
+            }
+            CoreTypeConcrete::Enum(info) => {
+                if info.variants.len() == 2
+                    && info.variants.iter().all(|variant| self.type_sizes[variant] == 0)
+                {
+                    Shape::Bool
+                } else {
+                    Shape::Enum(&info.variants)
+                }
+            }
+            CoreTypeConcrete::Array(info) => Shape::Array(&info.ty),
+            CoreTypeConcrete::Snapshot(info) => Shape::Transparent(&info.ty),
+            CoreTypeConcrete::NonZero(info) => Shape::NonZero(&info.ty),
+            concrete => {
+                let range = felt_like_range(ty, concrete)?;
+                if range.is_full_felt252_range() {
+                    // Using the signed representation of felt252s, so that small negative values
+                    // are generated and shrunk as such.
+                    let prime: &BigInt = &CAIRO_PRIME_BIGINT;
+                    Shape::Number(Range::half_open(
+                        -(prime - BigInt::one()) / 2,
+                        (prime + BigInt::one()) / 2,
+                    ))
+                } else {
+                    Shape::Number(range)
+                }
+            }
+        })
+    }
+}
+
+/// Generates a random number in `range`, preferring edge cases.
+fn generate_number(range: &Range, rng: &mut impl Rng) -> BigInt {
+    if rng.gen_ratio(1, 4) {
+        let edge_cases =
+            [range.lower.clone(), &range.upper - 1, BigInt::zero(), BigInt::one(), -BigInt::one()]
+                .into_iter()
+                .filter(|value| range.lower <= *value && *value < range.upper)
+                .collect_vec();
+        return edge_cases.choose(rng).unwrap().clone();
+    }
+    let size = &range.upper - &range.lower;
+    // Sampling uniformly in `[0, size)` by rejection sampling of numbers of the bit size of `size`.
+    let bits = size.bits();
+    loop {
+        let bytes = (0..bits.div_ceil(8)).map(|_| rng.gen::<u8>()).collect_vec();
+        let value = BigInt::from_bytes_le(num_bigint::Sign::Plus, &bytes)
+            % (BigInt::one() << (bits as usize));
+        if value < size {
+            return value + &range.lower;
+        }
+    }
+}
+
+/// Returns the value that numbers in `range` are shrunk towards: the value closest to zero.
+fn shrink_target(range: &Range) -> BigInt {
+    if range.lower > BigInt::zero() {
+        range.lower.clone()
+    } else if range.upper <= BigInt::zero() {
+        &range.upper - 1
+    } else {
+        BigInt::zero()
+    }
+}
+
+/// Returns the JSON representation of a number: a JSON number if it fits in 64 bits, otherwise a
+/// string holding its decimal value.
+fn number_to_json(value: BigInt) -> Value {
+    if let Some(value) = value.to_i64() {
+        value.into()
+    } else if let Some(value) = value.to_u64() {
+        value.into()
+    } else {
+        Value::String(value.to_string())
+    }
+}
+
+/// Returns whether a generated value is the number zero.
+fn is_zero(value: &Value) -> bool {
+    json_as_bigint(value).is_some_and(|value| value.is_zero())
+}
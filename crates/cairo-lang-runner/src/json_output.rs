@@ -0,0 +1,307 @@
+//! Structured JSON representations of run results.
+//!
+//! Values are decoded according to their Sierra types, mirroring the JSON form accepted for
+//! arguments (see [crate::args]):
+//! * Integers that fit in 64 bits are emitted as JSON numbers, larger integers (including `u256`)
+//!   as decimal strings, and `felt252`s, `bytes31`s and addresses as `0x`-prefixed hex strings.
+//! * `ByteArray`s are emitted as strings, `bool`s as JSON booleans.
+//! * Arrays and spans are emitted as arrays of their elements, structs and tuples as arrays of
+//!   their members (structs with a single member as that member).
+//! * Enums are emitted as `{"variant": <index>, "value": <payload>}`, with an additional `"name"`
+//!   for the variants of `Option` and `Result`.
+//! * Values of types that can't be decoded are emitted as the array of their raw cells.
+
+use cairo_lang_sierra::extensions::ConcreteType;
+use cairo_lang_sierra::extensions::core::{CoreLibfunc, CoreType, CoreTypeConcrete};
+use cairo_lang_sierra::extensions::utils::Range;
+use cairo_lang_sierra::ids::{ConcreteTypeId, UserTypeId};
+use cairo_lang_sierra::program_registry::ProgramRegistry;
+use cairo_lang_sierra_type_size::TypeSizeMap;
+use cairo_lang_utils::byte_array::BYTES_IN_WORD;
+use itertools::Itertools;
+use num_bigint::BigInt;
+use num_traits::{ToPrimitive, Zero};
+use serde_json::{Map, Value, json};
+use starknet_types_core::felt::{CAIRO_PRIME_BIGINT, Felt as Felt252};
+use thiserror::Error;
+
+use crate::StarknetExecutionResources;
+use crate::args::user_type_of;
+use crate::casm_run::format_next_item;
+use crate::short_string::as_cairo_short_string;
+
+#[cfg(test)]
+#[path = "json_output_test.rs"]
+mod test;
+
+#[derive(Debug, Error, Eq, PartialEq)]
+pub enum ValueDecodeError {
+    #[error("The cells of type `{0}` were not fully consumed.")]
+    UnconsumedCells(String),
+    #[error("The size of type `{0}` is unknown.")]
+    MissingTypeSize(String),
+}
+
+/// Decodes the values of a run into JSON, according to their types.
+pub struct ValueDecoder<'a> {
+    pub sierra_program_registry: &'a ProgramRegistry<CoreType, CoreLibfunc>,
+    pub type_sizes: &'a TypeSizeMap,
+    /// The memory of the run, used for reading the values behind pointers (e.g. array contents).
+    pub memory: &'a [Option<Felt252>],
+}
+impl ValueDecoder<'_> {
+    /// Decodes a value of type `ty` from its memory representation `cells`.
+    pub fn decode(
+        &self,
+        ty: &ConcreteTypeId,
+        cells: &[Felt252],
+    ) -> Result<Value, ValueDecodeError> {
+        let mut cells = cells.iter().cloned();
+        let value = self.decode_next(ty, &mut cells)?;
+        if cells.next().is_some() {
+            return Err(ValueDecodeError::UnconsumedCells(ty.to_string()));
+        }
+        Ok(value)
+    }
+
+    /// Decodes a value of type `ty` from the next cells of `cells`.
+    fn decode_next(
+        &self,
+        ty: &ConcreteTypeId,
+        cells: &mut impl Iterator<Item = Felt252>,
+    ) -> Result<Value, ValueDecodeError> {
+        let ty_cells = cells.take(self.type_size(ty)?).collect_vec();
+        Ok(self.try_decode(ty, &ty_cells)?.unwrap_or_else(|| raw_cells(&ty_cells)))
+    }
+
+    /// Decodes a value of type `ty` from exactly its cells, returning `None` if the value can't be
+    /// decoded.
+    fn try_decode(
+        &self,
+        ty: &ConcreteTypeId,
+        cells: &[Felt252],
+    ) -> Result<Option<Value>, ValueDecodeError> {
+        let mut iter = cells.iter().cloned();
+        let Ok(concrete) = self.sierra_program_registry.get_type(ty) else { return Ok(None) };
+        Ok(Some(match concrete {
+            CoreTypeConcrete::Struct(info) => {
+                let user_type = user_type_of(&info.info.long_id.generic_args);
+                if user_type == Some(&UserTypeId::from_string("core::integer::u256")) {
+                    let [low, high] = cells else { return Ok(None) };
+                    let value: BigInt = (high.to_bigint() << 128) + low.to_bigint();
+                    return Ok(Some(Value::String(value.to_string())));
+                }
+                let members: Vec<_> = info
+                    .members
+                    .iter()
+                    .map(|member| self.decode_next(member, &mut iter))
+                    .try_collect()?;
+                if user_type == Some(&UserTypeId::from_string("core::byte_array::ByteArray")) {
+                    if let Some(s) = byte_array_as_string(&members) {
+                        return Ok(Some(Value::String(s)));
+                    }
+                }
+                match <[Value; 1]>::try_from(members) {
+                    Ok([member]) => member,
+                    Err(members) => Value::Array(members),
+                }
+            }
+            CoreTypeConcrete::Enum(info) => {
+                let Some((selector, payload)) = cells.split_first() else { return Ok(None) };
+                let n_variants = info.variants.len();
+                let Some(selector) = selector.to_usize() else { return Ok(None) };
+                let index = if n_variants <= 2 {
+                    selector
+                } else {
+                    // See `get_variant_selector` for the selector of enums with more variants.
+                    let Some(index) = n_variants.checked_sub(selector.div_ceil(2)) else {
+                        return Ok(None);
+                    };
+                    index
+                };
+                let Some(variant) = info.variants.get(index) else { return Ok(None) };
+                let Some(padding) = payload.len().checked_sub(self.type_size(variant)?) else {
+                    return Ok(None);
+                };
+                let value = self.decode(variant, &payload[padding..])?;
+                let Some(user_type) = user_type_of(&info.info.long_id.generic_args) else {
+                    return Ok(None);
+                };
+                if *user_type == UserTypeId::from_string("core::bool") {
+                    return Ok(Some(Value::Bool(index == 1)));
+                }
+                let mut obj = Map::new();
+                obj.insert("variant".into(), index.into());
+                let debug_name = user_type.debug_name.as_deref().unwrap_or_default();
+                let name = if debug_name.starts_with("core::option::Option::") {
+                    ["Some", "None"].get(index)
+                } else if debug_name.starts_with("core::result::Result::") {
+                    ["Ok", "Err"].get(index)
+                } else {
+                    None
+                };
+                if let Some(name) = name {
+                    obj.insert("name".into(), (*name).into());
+                }
+                obj.insert("value".into(), value);
+                Value::Object(obj)
+            }
+            CoreTypeConcrete::Array(info) => {
+                let [start, end] = cells else { return Ok(None) };
+                let Some(elements) = self.read_memory(start, end) else { return Ok(None) };
+                let mut elements = elements.into_iter();
+                let mut values = vec![];
+                while elements.len() != 0 {
+                    values.push(self.decode_next(&info.ty, &mut elements)?);
+                }
+                Value::Array(values)
+            }
+            CoreTypeConcrete::Snapshot(info) | CoreTypeConcrete::NonZero(info) => {
+                self.decode(&info.ty, cells)?
+            }
+            CoreTypeConcrete::Box(info) => {
+                let [ptr] = cells else { return Ok(None) };
+                return self.decode_pointed(&info.ty, ptr);
+            }
+            CoreTypeConcrete::Nullable(info) => {
+                let [ptr] = cells else { return Ok(None) };
+                if ptr.is_zero() {
+                    Value::Null
+                } else {
+                    return self.decode_pointed(&info.ty, ptr);
+                }
+            }
+            concrete => {
+                let [value] = cells else { return Ok(None) };
+                felt_like_to_json(value, &Range::from_type_info(concrete.info()).ok())
+            }
+        }))
+    }
+
+    /// Decodes a value of type `ty` stored in memory at `ptr`, returning `None` if the memory
+    /// can't be read.
+    fn decode_pointed(
+        &self,
+        ty: &ConcreteTypeId,
+        ptr: &Felt252,
+    ) -> Result<Option<Value>, ValueDecodeError> {
+        let size = self.type_size(ty)?;
+        let Some(cells) = self.read_memory(ptr, &(ptr + Felt252::from(size))) else {
+            return Ok(None);
+        };
+        self.decode(ty, &cells).map(Some)
+    }
+
+    /// Returns the size of type `ty`.
+    fn type_size(&self, ty: &ConcreteTypeId) -> Result<usize, ValueDecodeError> {
+        match self.type_sizes.get(ty) {
+            Some(size) => Ok(*size as usize),
+            None => Err(ValueDecodeError::MissingTypeSize(ty.to_string())),
+        }
+    }
+
+    /// Returns the values of the memory cells in `[start, end)`.
+    fn read_memory(&self, start: &Felt252, end: &Felt252) -> Option<Vec<Felt252>> {
+        let (start, end) = (start.to_usize()?, end.to_usize()?);
+        self.memory.get(start..end)?.iter().cloned().collect()
+    }
+}
+
+/// Returns the JSON representation of raw cells.
+fn raw_cells(cells: &[Felt252]) -> Value {
+    Value::Array(cells.iter().map(felt_to_hex).collect())
+}
+
+/// Returns the JSON representation of a felt252 as a hex string.
+fn felt_to_hex(value: &Felt252) -> Value {
+    Value::String(format!("{:#x}", value.to_biguint()))
+}
+
+/// Returns the JSON representation of a value represented by a single felt252, where `range` is
+/// the range of the type of the value, if it is an integer type.
+fn felt_like_to_json(value: &Felt252, range: &Option<Range>) -> Value {
+    let Some(range) = range else { return felt_to_hex(value) };
+    if range.is_full_felt252_range() || range.upper > BigInt::from(1) << 128 {
+        // `felt252` and `bytes31` values.
+        return felt_to_hex(value);
+    }
+    let mut value = value.to_bigint();
+    if range.lower < BigInt::zero() && value >= range.upper {
+        value -= &*CAIRO_PRIME_BIGINT;
+    }
+    if let Some(value) = value.to_i64() {
+        value.into()
+    } else if let Some(value) = value.to_u64() {
+        value.into()
+    } else {
+        Value::String(value.to_string())
+    }
+}
+
+/// Returns the string held by a `ByteArray` given its decoded members, if it is valid UTF-8.
+fn byte_array_as_string(members: &[Value]) -> Option<String> {
+    let [Value::Array(data), Value::String(pending_word), pending_word_len] = members else {
+        return None;
+    };
+    let pending_word_len = pending_word_len.as_u64()?.to_usize()?;
+    let mut bytes = vec![];
+    for (word, len) in data
+        .iter()
+        .map(|word| (word.as_str(), BYTES_IN_WORD))
+        .chain([(Some(pending_word.as_str()), pending_word_len)])
+    {
+        let word = BigInt::parse_bytes(word?.strip_prefix("0x")?.as_bytes(), 16)?;
+        let (_, word_bytes) = word.to_bytes_be();
+        let word_bytes = if word.is_zero() { vec![] } else { word_bytes };
+        if word_bytes.len() > len {
+            return None;
+        }
+        bytes.extend(std::iter::repeat_n(0, len - word_bytes.len()));
+        bytes.extend(word_bytes);
+    }
+    String::from_utf8(bytes).ok()
+}
+
+/// Returns the JSON representation of panic data: an array of items, each is either a
+/// `{"byte_array": <string>}` for serialized `ByteArray`s, or a `{"felt252": <hex>}` with an
+/// additional `"short_string"` if the felt252 is a valid short string.
+pub fn panic_data_to_json(values: &[Felt252]) -> Value {
+    let mut felts = values.iter().cloned();
+    let mut items = vec![];
+    loop {
+        let next_felt = felts.clone().next();
+        let Some(item) = format_next_item(&mut felts) else { break };
+        let next_felt = next_felt.unwrap();
+        items.push(if item.is_string() {
+            json!({ "byte_array": item.get() })
+        } else if let Some(short_string) = as_cairo_short_string(&next_felt) {
+            json!({ "felt252": felt_to_hex(&next_felt), "short_string": short_string })
+        } else {
+            json!({ "felt252": felt_to_hex(&next_felt) })
+        });
+    }
+    Value::Array(items)
+}
+
+/// Returns the JSON representation of the used resources of a run.
+pub fn resources_to_json(resources: &StarknetExecutionResources) -> Value {
+    let basic_resources = resources.basic_resources.filter_unused_builtins();
+    let builtins: Map<String, Value> = basic_resources
+        .builtin_instance_counter
+        .iter()
+        .map(|(name, count)| (name.to_string(), (*count).into()))
+        .sorted_by(|(a, _), (b, _)| a.cmp(b))
+        .collect();
+    let syscalls: Map<String, Value> = resources
+        .syscalls
+        .iter()
+        .map(|(name, count)| (name.clone(), (*count).into()))
+        .sorted_by(|(a, _), (b, _)| a.cmp(b))
+        .collect();
+    json!({
+        "steps": basic_resources.n_steps,
+        "memory_holes": basic_resources.n_memory_holes,
+        "builtins": builtins,
+        "syscalls": syscalls,
+    })
+}
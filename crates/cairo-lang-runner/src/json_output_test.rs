@@ -0,0 +1,91 @@
+use cairo_lang_sierra::ProgramParser;
+use cairo_lang_sierra::extensions::core::{CoreLibfunc, CoreType};
+use cairo_lang_sierra::ids::ConcreteTypeId;
+use cairo_lang_sierra::program_registry::ProgramRegistry;
+use cairo_lang_sierra_type_size::TypeSizeMap;
+use cairo_lang_utils::byte_array::BYTE_ARRAY_MAGIC;
+use cairo_vm::types::builtin_name::BuiltinName;
+use cairo_vm::vm::runners::cairo_runner::ExecutionResources;
+use serde_json::json;
+use starknet_types_core::felt::Felt as Felt252;
+
+use super::{ValueDecodeError, ValueDecoder, panic_data_to_json, resources_to_json};
+use crate::StarknetExecutionResources;
+
+#[test]
+fn test_decode_errors() {
+    let program = ProgramParser::new().parse("type felt252 = felt252;").unwrap();
+    let registry = ProgramRegistry::<CoreType, CoreLibfunc>::new(&program).unwrap();
+    let ty = ConcreteTypeId::from_string("felt252");
+    let mut type_sizes = TypeSizeMap::default();
+    let decoder =
+        ValueDecoder { sierra_program_registry: &registry, type_sizes: &type_sizes, memory: &[] };
+    assert_eq!(
+        decoder.decode(&ty, &[Felt252::from(5)]),
+        Err(ValueDecodeError::MissingTypeSize("felt252".into()))
+    );
+
+    type_sizes.insert(ty.clone(), 1);
+    let decoder =
+        ValueDecoder { sierra_program_registry: &registry, type_sizes: &type_sizes, memory: &[] };
+    assert_eq!(decoder.decode(&ty, &[Felt252::from(5)]), Ok(json!("0x5")));
+    assert_eq!(
+        decoder.decode(&ty, &[Felt252::from(5), Felt252::from(6)]),
+        Err(ValueDecodeError::UnconsumedCells("felt252".into()))
+    );
+}
+
+#[test]
+fn test_panic_data_to_json() {
+    assert_eq!(panic_data_to_json(&[]), json!([]));
+    assert_eq!(
+        panic_data_to_json(&[
+            // 'hello'
+            Felt252::from_hex_unchecked("68656c6c6f"),
+            Felt252::from(0),
+            Felt252::from_hex_unchecked(BYTE_ARRAY_MAGIC),
+            // No full words.
+            Felt252::from(0),
+            // pending word: 'short, but string'
+            Felt252::from_hex_unchecked("73686f72742c2062757420737472696e67"),
+            // pending word length
+            Felt252::from(17),
+            Felt252::from(-1),
+        ]),
+        json!([
+            { "felt252": "0x68656c6c6f", "short_string": "hello" },
+            { "felt252": "0x0", "short_string": "" },
+            { "byte_array": "short, but string" },
+            { "felt252": "0x800000000000011000000000000000000000000000000000000000000000000" },
+        ])
+    );
+}
+
+#[test]
+fn test_resources_to_json() {
+    let resources = StarknetExecutionResources {
+        basic_resources: ExecutionResources {
+            n_steps: 120,
+            n_memory_holes: 3,
+            builtin_instance_counter: [
+                (BuiltinName::range_check, 4),
+                (BuiltinName::pedersen, 1),
+                (BuiltinName::bitwise, 0),
+            ]
+            .into_iter()
+            .collect(),
+        },
+        syscalls: [("StorageWrite".to_string(), 2), ("CallContract".to_string(), 1)]
+            .into_iter()
+            .collect(),
+    };
+    assert_eq!(
+        resources_to_json(&resources),
+        json!({
+            "steps": 120,
+            "memory_holes": 3,
+            "builtins": { "pedersen_builtin": 1, "range_check_builtin": 4 },
+            "syscalls": { "CallContract": 1, "StorageWrite": 2 },
+        })
+    );
+}
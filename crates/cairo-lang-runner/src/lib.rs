@@ -41,17 +41,25 @@ use cairo_vm::vm::runners::cairo_runner::{ExecutionResources, RunResources};
 use cairo_vm::vm::trace::trace_entry::RelocatedTraceEntry;
 use cairo_vm::vm::vm_core::VirtualMachine;
 use casm_run::hint_to_hint_params;
-pub use casm_run::{CairoHintProcessor, StarknetState};
-use itertools::chain;
+pub use casm_run::{CairoHintProcessor, EmittedEvent, StarknetState};
+use itertools::{Itertools, chain};
 use num_bigint::BigInt;
 use num_traits::ToPrimitive;
 use profiling::{ProfilingInfo, user_function_idx_by_sierra_statement_idx};
+use rand::Rng;
+use serde_json::Value;
 use starknet_types_core::felt::Felt as Felt252;
 use thiserror::Error;
 
+use crate::args::{ArgsDecoder, ArgsError, UserArgs};
 use crate::casm_run::RunFunctionResult;
+use crate::fuzz::ArgsGenerator;
+use crate::json_output::{ValueDecodeError, ValueDecoder};
 
+pub mod args;
 pub mod casm_run;
+pub mod fuzz;
+pub mod json_output;
 pub mod profiling;
 pub mod short_string;
 
@@ -87,6 +95,8 @@ pub struct RunResultStarknet {
     pub used_resources: StarknetExecutionResources,
     /// The profiling info of the run, if requested.
     pub profiling_info: Option<ProfilingInfo>,
+    /// The output of the debug prints of the run, if captured.
+    pub debug_output: Option<String>,
 }
 
 /// The full result of a run.
@@ -190,10 +200,15 @@ pub struct SierraCasmRunner {
     type_sizes: TypeSizeMap,
     /// The casm program matching the Sierra code.
     casm_program: CairoProgram,
+    /// The configuration the casm program was compiled with.
+    casm_config: SierraToCasmConfig,
     /// Mapping from class_hash to contract info.
     starknet_contracts_info: OrderedHashMap<Felt252, ContractInfo>,
     /// Whether to run the profiler when running using this runner.
     run_profiler: Option<ProfilingInfoCollectionConfig>,
+    /// Whether to capture the debug prints of the runs into their results, instead of printing
+    /// them to the standard output.
+    capture_debug_output: bool,
 }
 impl SierraCasmRunner {
     pub fn new(
@@ -207,11 +222,13 @@ impl SierraCasmRunner {
         let sierra_program_registry =
             ProgramRegistry::<CoreType, CoreLibfunc>::new(&sierra_program)?;
         let type_sizes = get_type_size_map(&sierra_program, &sierra_program_registry).unwrap();
-        let casm_program = cairo_lang_sierra_to_casm::compiler::compile(
-            &sierra_program,
-            &metadata,
-            SierraToCasmConfig { gas_usage_check, max_bytecode_size: usize::MAX },
-        )?;
+        let casm_config = SierraToCasmConfig {
+            gas_usage_check,
+            max_bytecode_size: usize::MAX,
+            peephole_optimizations: false,
+        };
+        let casm_program =
+            cairo_lang_sierra_to_casm::compiler::compile(&sierra_program, &metadata, casm_config)?;
 
         // Find all contracts.
         Ok(Self {
@@ -220,11 +237,31 @@ impl SierraCasmRunner {
             sierra_program_registry,
             type_sizes,
             casm_program,
+            casm_config,
             starknet_contracts_info,
             run_profiler,
+            capture_debug_output: false,
         })
     }
 
+    /// Captures the debug prints of runs with Starknet context into their results, instead of
+    /// printing them to the standard output.
+    pub fn with_captured_debug_output(mut self) -> Self {
+        self.capture_debug_output = true;
+        self
+    }
+
+    /// Recompiles the casm program with peephole optimizations.
+    pub fn with_peephole_optimizations(mut self) -> Result<Self, RunnerError> {
+        self.casm_config.peephole_optimizations = true;
+        self.casm_program = cairo_lang_sierra_to_casm::compiler::compile(
+            &self.sierra_program,
+            &self.metadata,
+            self.casm_config,
+        )?;
+        Ok(self)
+    }
+
     /// Runs the vm starting from a function in the context of a given starknet state.
     pub fn run_function_with_starknet_context(
         &self,
@@ -245,8 +282,10 @@ impl SierraCasmRunner {
             string_to_hint,
             run_resources: RunResources::default(),
             syscalls_used_resources: Default::default(),
+            nested_sierra_statement_hits: Default::default(),
+            debug_output: self.capture_debug_output.then(String::new),
         };
-        let RunResult { gas_counter, memory, value, used_resources, profiling_info } = self
+        let RunResult { gas_counter, memory, value, used_resources, mut profiling_info } = self
             .run_function(
                 func,
                 (initial_gas, args),
@@ -261,6 +300,13 @@ impl SierraCasmRunner {
         // The used resources of the code is just its length, as it is a simple param setup, and a
         // call and ret.
         all_used_resources.basic_resources.n_steps -= entry_code.len();
+        if let Some(profiling_info) = &mut profiling_info {
+            profiling_info
+                .sierra_statement_hits
+                .merge(&hint_processor.nested_sierra_statement_hits, |mut e, hits| {
+                    *e.get_mut() += hits
+                });
+        }
         Ok(RunResultStarknet {
             gas_counter,
             memory,
@@ -268,6 +314,7 @@ impl SierraCasmRunner {
             starknet_state: hint_processor.starknet_state,
             used_resources: all_used_resources,
             profiling_info,
+            debug_output: hint_processor.debug_output,
         })
     }
 
@@ -313,6 +360,9 @@ impl SierraCasmRunner {
         // runner). The header is not counted, and the footer is, but then the relevant
         // entry is removed.
         let mut sierra_statement_weights = UnorderedHashMap::default();
+        // The number of executions of each Sierra statement, counted by the executions of its
+        // first CASM instruction.
+        let mut sierra_statement_hits = UnorderedHashMap::default();
         for step in trace.iter() {
             // Skip the header.
             if step.pc < real_pc_0 {
@@ -342,6 +392,12 @@ impl SierraCasmRunner {
             );
 
             *sierra_statement_weights.entry(sierra_statement_idx).or_insert(0) += 1;
+            if self.casm_program.debug_info.sierra_statement_info[sierra_statement_idx.0]
+                .start_offset
+                == real_pc
+            {
+                *sierra_statement_hits.entry(sierra_statement_idx).or_insert(0) += 1;
+            }
 
             let Some(gen_statement) = self.sierra_program.statements.get(sierra_statement_idx.0)
             else {
@@ -386,7 +442,20 @@ impl SierraCasmRunner {
         // Remove the footer.
         sierra_statement_weights.remove(&StatementIdx(sierra_len));
 
-        ProfilingInfo { sierra_statement_weights, stack_trace_weights }
+        ProfilingInfo { sierra_statement_weights, sierra_statement_hits, stack_trace_weights }
+    }
+
+    /// Returns the indices of the Sierra statements that have CASM instructions, which are the
+    /// statements that may be counted as executed in [ProfilingInfo::sierra_statement_hits].
+    pub fn executable_sierra_statements(&self) -> impl Iterator<Item = StatementIdx> + '_ {
+        self.casm_program
+            .debug_info
+            .sierra_statement_info
+            .iter()
+            .enumerate()
+            .take(self.sierra_program.statements.len())
+            .filter(|(_, info)| info.end_offset > info.start_offset)
+            .map(|(idx, _)| StatementIdx(idx))
     }
 
     fn sierra_statement_index_by_pc(&self, pc: usize) -> StatementIdx {
@@ -530,16 +599,7 @@ impl SierraCasmRunner {
                 assert!(values.is_empty());
                 false
             } else {
-                *generic_ty != RangeCheckType::ID
-                    && *generic_ty != BitwiseType::ID
-                    && *generic_ty != EcOpType::ID
-                    && *generic_ty != PedersenType::ID
-                    && *generic_ty != PoseidonType::ID
-                    && *generic_ty != SystemType::ID
-                    && *generic_ty != SegmentArenaType::ID
-                    && *generic_ty != RangeCheck96Type::ID
-                    && *generic_ty != AddModType::ID
-                    && *generic_ty != MulModType::ID
+                !is_implicit_generic_id(generic_ty)
             }
         });
 
@@ -557,6 +617,79 @@ impl SierraCasmRunner {
             .ok_or_else(|| RunnerError::MissingFunction { suffix: name_suffix.to_owned() })
     }
 
+    /// Decodes user provided arguments into the arguments for running `func`.
+    /// The arguments are decoded according to the non-implicit parameters of `func`, see [args].
+    pub fn decode_args(&self, func: &Function, args: &UserArgs) -> Result<Vec<Arg>, ArgsError> {
+        ArgsDecoder {
+            sierra_program_registry: &self.sierra_program_registry,
+            type_sizes: &self.type_sizes,
+        }
+        .decode(&self.user_param_types(func), args)
+    }
+
+    /// Generates random arguments for `func`, in the JSON form accepted by
+    /// [SierraCasmRunner::decode_args].
+    pub fn generate_args(&self, func: &Function, rng: &mut impl Rng) -> Result<Value, ArgsError> {
+        self.args_generator().generate(&self.user_param_types(func), rng)
+    }
+
+    /// Returns simpler variations of the arguments `args` for `func`, ordered from the simplest.
+    pub fn shrink_args(&self, func: &Function, args: &Value) -> Vec<Value> {
+        self.args_generator().shrink(&self.user_param_types(func), args)
+    }
+
+    /// Returns the types of the non-implicit parameters of `func`.
+    fn user_param_types(&self, func: &Function) -> Vec<ConcreteTypeId> {
+        func.signature
+            .param_types
+            .iter()
+            .filter(|ty| !is_implicit_generic_id(&self.get_info(ty).long_id.generic_id))
+            .cloned()
+            .collect_vec()
+    }
+
+    /// Returns a generator of arguments for the functions of the program.
+    fn args_generator(&self) -> ArgsGenerator<'_> {
+        ArgsGenerator {
+            sierra_program_registry: &self.sierra_program_registry,
+            type_sizes: &self.type_sizes,
+        }
+    }
+
+    /// Returns the type of the value returned by a successful run of `func`, if it returns a
+    /// non-implicit value. For functions that may panic, this is the type wrapped by the panic
+    /// result.
+    pub fn return_value_type(&self, func: &Function) -> Option<ConcreteTypeId> {
+        let ty = func
+            .signature
+            .ret_types
+            .iter()
+            .find(|ty| !is_implicit_generic_id(&self.get_info(ty).long_id.generic_id))?;
+        Some(
+            self.inner_type_from_panic_wrapper(&self.get_info(ty).long_id.generic_id, func)
+                .unwrap_or_else(|| ty.clone()),
+        )
+    }
+
+    /// Returns the JSON representation of the values returned by a successful run of `func`, see
+    /// [json_output].
+    pub fn return_value_to_json(
+        &self,
+        func: &Function,
+        values: &[Felt252],
+        memory: &[Option<Felt252>],
+    ) -> Result<serde_json::Value, ValueDecodeError> {
+        let Some(ty) = self.return_value_type(func) else {
+            return Ok(serde_json::Value::Null);
+        };
+        ValueDecoder {
+            sierra_program_registry: &self.sierra_program_registry,
+            type_sizes: &self.type_sizes,
+            memory,
+        }
+        .decode(&ty, values)
+    }
+
     /// Converts array of `ConcreteTypeId`s into corresponding `GenericTypeId`s and their sizes
     fn generic_id_and_size_from_concrete(
         &self,
@@ -834,6 +967,24 @@ pub fn initialize_vm(
     Ok(())
 }
 
+/// Returns whether the given generic type is of an implicit parameter of a function.
+fn is_implicit_generic_id(generic_ty: &GenericTypeId) -> bool {
+    [
+        GasBuiltinType::ID,
+        RangeCheckType::ID,
+        BitwiseType::ID,
+        EcOpType::ID,
+        PedersenType::ID,
+        PoseidonType::ID,
+        SystemType::ID,
+        SegmentArenaType::ID,
+        RangeCheck96Type::ID,
+        AddModType::ID,
+        MulModType::ID,
+    ]
+    .contains(generic_ty)
+}
+
 /// The size in memory of the arguments.
 fn args_size(args: &[Arg]) -> usize {
     args.iter()
@@ -1,3 +1,4 @@
+use std::collections::BTreeMap;
 use std::fmt::Display;
 
 use cairo_lang_lowering::ids::FunctionLongId;
@@ -21,6 +22,10 @@ pub struct ProfilingInfo {
     /// The number of steps in the trace that originated from each sierra statement.
     pub sierra_statement_weights: UnorderedHashMap<StatementIdx, usize>,
 
+    /// The number of times each sierra statement was executed, including in nested runs (e.g.
+    /// contract calls). Statements without CASM instructions are never counted as executed.
+    pub sierra_statement_hits: UnorderedHashMap<StatementIdx, usize>,
+
     /// A map of weights of each stack trace.
     /// The key is a function stack trace of an executed function. The stack trace is represented
     /// as a vector of indices of the functions in the stack (indices of the functions according to
@@ -181,6 +186,201 @@ impl Display for ProcessedProfilingInfo {
     }
 }
 
+/// The formats in which processed profiling info can be exported.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ProfilerOutputFormat {
+    /// Human readable text tables, as in the `Display` of `ProcessedProfilingInfo`.
+    #[default]
+    Text,
+    /// Folded stacks, a line of `;` separated function names and the self weight of the stack per
+    /// stack trace, as consumed by `flamegraph.pl` and `inferno`.
+    Folded,
+    /// A Chrome trace-event JSON document, as consumed by `chrome://tracing` and Perfetto, where
+    /// each step is shown as a microsecond.
+    ChromeTrace,
+    /// An uncompressed pprof protobuf profile, as consumed by `go tool pprof`.
+    Pprof,
+}
+impl ProfilerOutputFormat {
+    /// The extension of files of the format.
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ProfilerOutputFormat::Text => "txt",
+            ProfilerOutputFormat::Folded => "folded",
+            ProfilerOutputFormat::ChromeTrace => "json",
+            ProfilerOutputFormat::Pprof => "pb",
+        }
+    }
+}
+
+impl ProcessedProfilingInfo {
+    /// Exports the profiling info in the given format.
+    ///
+    /// All formats but `Text` are built from the Sierra stack trace weights, so are empty if they
+    /// were not processed.
+    pub fn export(&self, format: ProfilerOutputFormat) -> Vec<u8> {
+        match format {
+            ProfilerOutputFormat::Text => self.to_string().into_bytes(),
+            ProfilerOutputFormat::Folded => self.to_folded_stacks().into_bytes(),
+            ProfilerOutputFormat::ChromeTrace => self.to_chrome_trace().to_string().into_bytes(),
+            ProfilerOutputFormat::Pprof => self.to_pprof(),
+        }
+    }
+
+    /// Returns the stack traces with their self weights (excluding the weights of the functions
+    /// they called), sorted by the stack traces. Stack traces with no self weight are omitted.
+    fn self_stack_trace_weights(&self) -> BTreeMap<&[String], usize> {
+        let Some(weights) = &self.stack_trace_weights.sierra_stack_trace_weights else {
+            return BTreeMap::new();
+        };
+        // The stack trace weights include the weights of the called functions.
+        let mut self_weights: BTreeMap<&[String], usize> =
+            weights.iter().map(|(trace, weight)| (trace.as_slice(), *weight)).collect();
+        for (trace, weight) in weights.iter() {
+            if let [caller @ .., _] = trace.as_slice() {
+                if let Some(caller_weight) = self_weights.get_mut(caller) {
+                    *caller_weight = caller_weight.saturating_sub(*weight);
+                }
+            }
+        }
+        self_weights.retain(|_, weight| *weight > 0);
+        self_weights
+    }
+
+    /// Returns the profiling info in the folded stacks format.
+    pub fn to_folded_stacks(&self) -> String {
+        let mut folded = String::new();
+        for (trace, weight) in self.self_stack_trace_weights() {
+            folded.push_str(&format!("{} {weight}\n", trace.join(";")));
+        }
+        folded
+    }
+
+    /// Returns the profiling info as a Chrome trace-event JSON document. Each stack trace is a
+    /// complete event spanning its weight, nested within the event of its caller.
+    pub fn to_chrome_trace(&self) -> serde_json::Value {
+        let Some(weights) = &self.stack_trace_weights.sierra_stack_trace_weights else {
+            return serde_json::json!({ "traceEvents": [] });
+        };
+        // The sorting places each stack trace right after its caller and earlier siblings.
+        let traces: BTreeMap<&[String], usize> =
+            weights.iter().map(|(trace, weight)| (trace.as_slice(), *weight)).collect();
+        // The start time of the next callee of each stack trace, where the empty trace is the
+        // root.
+        let mut next_start = UnorderedHashMap::<&[String], usize>::default();
+        let mut events = vec![];
+        for (trace, weight) in traces {
+            let [caller @ .., name] = trace else { continue };
+            let start = *next_start.get(caller).unwrap_or(&0);
+            next_start.insert(caller, start + weight);
+            next_start.insert(trace, start);
+            events.push(serde_json::json!({
+                "name": name,
+                "ph": "X",
+                "ts": start,
+                "dur": weight,
+                "pid": 0,
+                "tid": 0,
+            }));
+        }
+        serde_json::json!({ "traceEvents": events })
+    }
+
+    /// Returns the profiling info as an uncompressed pprof protobuf profile, with a sample of the
+    /// self weight of each stack trace.
+    pub fn to_pprof(&self) -> Vec<u8> {
+        let mut strings = pprof::StringTable::default();
+        let steps_idx = strings.index("steps");
+        let count_idx = strings.index("count");
+
+        let mut profile = vec![];
+        let mut sample_type = vec![];
+        pprof::write_uint(&mut sample_type, 1, steps_idx);
+        pprof::write_uint(&mut sample_type, 2, count_idx);
+        pprof::write_bytes(&mut profile, 1, &sample_type);
+
+        // The id of each function, also used as the id of its single location.
+        let mut function_ids = OrderedHashMap::<&str, u64>::default();
+        for (trace, weight) in self.self_stack_trace_weights() {
+            let mut location_ids = vec![];
+            // Locations are ordered from the leaf to the root.
+            for name in trace.iter().rev() {
+                let next_id = function_ids.len() as u64 + 1;
+                let id = *function_ids.entry(name.as_str()).or_insert(next_id);
+                pprof::write_varint(&mut location_ids, id);
+            }
+            let mut values = vec![];
+            pprof::write_varint(&mut values, weight as u64);
+            let mut sample = vec![];
+            pprof::write_bytes(&mut sample, 1, &location_ids);
+            pprof::write_bytes(&mut sample, 2, &values);
+            pprof::write_bytes(&mut profile, 2, &sample);
+        }
+        for (name, id) in function_ids.iter() {
+            let mut line = vec![];
+            pprof::write_uint(&mut line, 1, *id);
+            let mut location = vec![];
+            pprof::write_uint(&mut location, 1, *id);
+            pprof::write_bytes(&mut location, 4, &line);
+            pprof::write_bytes(&mut profile, 4, &location);
+
+            let name_idx = strings.index(name);
+            let mut function = vec![];
+            pprof::write_uint(&mut function, 1, *id);
+            pprof::write_uint(&mut function, 2, name_idx);
+            pprof::write_uint(&mut function, 3, name_idx);
+            pprof::write_bytes(&mut profile, 5, &function);
+        }
+        for string in strings.0.keys() {
+            pprof::write_bytes(&mut profile, 6, string.as_bytes());
+        }
+        profile
+    }
+}
+
+/// Minimal protobuf encoding, for the messages of the pprof `Profile` format.
+mod pprof {
+    use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
+
+    /// The string table of a profile, mapping each string to its index. The first string is
+    /// always the empty string, as required by the format.
+    pub struct StringTable<'a>(pub OrderedHashMap<&'a str, u64>);
+    impl Default for StringTable<'_> {
+        fn default() -> Self {
+            Self([("", 0)].into_iter().collect())
+        }
+    }
+    impl<'a> StringTable<'a> {
+        /// Returns the index of the given string, adding it to the table if missing.
+        pub fn index(&mut self, string: &'a str) -> u64 {
+            let next_idx = self.0.len() as u64;
+            *self.0.entry(string).or_insert(next_idx)
+        }
+    }
+
+    /// Writes a base 128 varint.
+    pub fn write_varint(buf: &mut Vec<u8>, mut value: u64) {
+        while value >= 0x80 {
+            buf.push((value as u8) | 0x80);
+            value >>= 7;
+        }
+        buf.push(value as u8);
+    }
+
+    /// Writes a varint field.
+    pub fn write_uint(buf: &mut Vec<u8>, field: u64, value: u64) {
+        write_varint(buf, field << 3);
+        write_varint(buf, value);
+    }
+
+    /// Writes a length delimited field - a string, a message or a packed repeated field.
+    pub fn write_bytes(buf: &mut Vec<u8>, field: u64, bytes: &[u8]) {
+        write_varint(buf, (field << 3) | 2);
+        write_varint(buf, bytes.len() as u64);
+        buf.extend_from_slice(bytes);
+    }
+}
+
 /// Parameters controlling what profiling info is processed and how, by the
 /// `ProfilingInfoProcessor`.
 pub struct ProfilingInfoProcessorParams {
@@ -11,7 +11,10 @@ use cairo_lang_test_utils::get_direct_or_file_content;
 use cairo_lang_test_utils::parse_test_file::TestRunnerResult;
 use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
 
-use super::ProfilingInfoProcessor;
+use super::{
+    LibfuncWeights, ProcessedProfilingInfo, ProfilerOutputFormat, ProfilingInfoProcessor,
+    StackTraceWeights, UserFunctionWeights,
+};
 use crate::{ProfilingInfoCollectionConfig, SierraCasmRunner};
 
 cairo_lang_test_utils::test_file_test!(
@@ -84,3 +87,74 @@ pub fn test_profiling(
         error: None,
     }
 }
+
+/// Returns processed profiling info with only Sierra stack trace weights, where `main` calls `f`
+/// and `h`, and `f` calls `g`.
+fn stack_traces_profiling_info() -> ProcessedProfilingInfo {
+    let trace = |names: &[&str]| names.iter().map(|name| name.to_string()).collect::<Vec<_>>();
+    ProcessedProfilingInfo {
+        sierra_statement_weights: None,
+        stack_trace_weights: StackTraceWeights {
+            sierra_stack_trace_weights: Some(
+                [
+                    (trace(&["main"]), 10),
+                    (trace(&["main", "f"]), 6),
+                    (trace(&["main", "f", "g"]), 2),
+                    (trace(&["main", "h"]), 1),
+                ]
+                .into_iter()
+                .collect(),
+            ),
+            cairo_stack_trace_weights: None,
+        },
+        libfunc_weights: LibfuncWeights::default(),
+        user_function_weights: UserFunctionWeights::default(),
+        cairo_function_weights: None,
+    }
+}
+
+#[test]
+fn test_export_folded_stacks() {
+    assert_eq!(
+        String::from_utf8(stack_traces_profiling_info().export(ProfilerOutputFormat::Folded))
+            .unwrap(),
+        "main 3\nmain;f 4\nmain;f;g 2\nmain;h 1\n"
+    );
+}
+
+#[test]
+fn test_export_chrome_trace() {
+    let events = stack_traces_profiling_info().to_chrome_trace()["traceEvents"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|event| {
+            (
+                event["name"].as_str().unwrap().to_string(),
+                event["ts"].as_u64().unwrap(),
+                event["dur"].as_u64().unwrap(),
+            )
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(events, [
+        ("main".into(), 0, 10),
+        ("f".into(), 0, 6),
+        ("g".into(), 0, 2),
+        ("h".into(), 6, 1),
+    ]);
+}
+
+#[test]
+fn test_export_pprof() {
+    let profile = stack_traces_profiling_info().export(ProfilerOutputFormat::Pprof);
+    // The sample type, with `steps` and `count` as the second and third strings.
+    assert_eq!(profile[..6], [0x0a, 4, 0x08, 1, 0x10, 2]);
+    // The first sample, of `main` alone, with the location of `main` and a value of 3.
+    assert_eq!(profile[6..14], [0x12, 6, 0x0a, 1, 1, 0x12, 1, 3]);
+    // The string table.
+    assert!(
+        profile.ends_with(
+            b"\x32\x00\x32\x05steps\x32\x05count\x32\x04main\x32\x01f\x32\x01g\x32\x01h"
+        )
+    );
+}
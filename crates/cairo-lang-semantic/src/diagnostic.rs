@@ -1256,6 +1256,10 @@ impl SemanticDiagnosticKind {
             Self::CannotCallMethod { .. } => {
                 error_code!(E0002)
             }
+            Self::PathNotFound(_) => error_code!(E0003),
+            Self::MissingItemsInImpl(_) => error_code!(E0004),
+            Self::RefArgNotExplicit => error_code!(E0005),
+            Self::WrongArgumentType { .. } => error_code!(E0006),
             _ => return None,
         })
     }
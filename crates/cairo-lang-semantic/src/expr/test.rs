@@ -227,7 +227,7 @@ fn test_expr_call_failures() {
 
     // Check expr.
     assert_eq!(diagnostics, indoc! { "
-            error: Function not found.
+            error[E0003]: Function not found.
              --> lib.cairo:2:1
             foo()
             ^*^
@@ -33,6 +33,7 @@ use crate::invocations::{
     BranchChanges, InvocationError, ProgramInfo, check_references_on_stack, compile_invocation,
 };
 use crate::metadata::Metadata;
+use crate::peephole::optimize_instructions;
 use crate::references::{ReferenceValue, ReferencesError, check_types_match};
 use crate::relocations::{RelocationEntry, relocate_instructions};
 
@@ -92,6 +93,15 @@ pub struct SierraToCasmConfig {
     pub gas_usage_check: bool,
     /// CASM bytecode size limit.
     pub max_bytecode_size: usize,
+    /// Whether to run peephole optimizations on the CASM instructions, reducing the bytecode size.
+    pub peephole_optimizations: bool,
+}
+
+impl Default for SierraToCasmConfig {
+    /// Checks the gas usage, without a bytecode size limit and without peephole optimizations.
+    fn default() -> Self {
+        Self { gas_usage_check: true, max_bytecode_size: usize::MAX, peephole_optimizations: false }
+    }
 }
 
 /// The casm program representation.
@@ -466,7 +476,8 @@ pub fn compile(
     for (statement_id, statement) in program.statements.iter().enumerate() {
         let statement_idx = StatementIdx(statement_id);
 
-        if program_offset > config.max_bytecode_size {
+        // The size of the code may still be reduced by the optimizations.
+        if !config.peephole_optimizations && program_offset > config.max_bytecode_size {
             return Err(Box::new(CompilationError::CodeSizeLimitExceeded));
         }
         match statement {
@@ -620,6 +631,17 @@ pub fn compile(
         }
     }
 
+    if config.peephole_optimizations {
+        let entry_points: Vec<_> = program.funcs.iter().map(|func| func.entry_point).collect();
+        optimize_instructions(
+            &mut instructions,
+            &mut relocations,
+            &mut sierra_statement_info,
+            &entry_points,
+        );
+        program_offset = instructions.iter().map(|instruction| instruction.body.op_size()).sum();
+    }
+
     let statement_offsets: Vec<usize> = std::iter::once(0)
         .chain(sierra_statement_info.iter().map(|s: &SierraStatementDebugInfo| s.end_offset))
         .collect();
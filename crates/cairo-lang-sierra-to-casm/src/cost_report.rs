@@ -0,0 +1,272 @@
+//! Static cost report of the functions of a compiled Sierra program.
+//!
+//! The report is computed from the gas and ap change information used in the compilation, and is
+//! meant to be compared between versions of a program to catch cost regressions.
+
+use std::collections::BTreeMap;
+
+use cairo_lang_sierra::extensions::NamedLibfunc;
+use cairo_lang_sierra::extensions::function_call::{CouponCallLibfunc, FunctionCallLibfunc};
+use cairo_lang_sierra::extensions::gas::{
+    BuiltinCostWithdrawGasLibfunc, CostTokenType, WithdrawGasLibfunc,
+};
+use cairo_lang_sierra::ids::{ConcreteLibfuncId, FunctionId};
+use cairo_lang_sierra::program::{GenericArg, Program, Statement, StatementIdx};
+use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
+use cairo_lang_utils::unordered_hash_map::UnorderedHashMap;
+use cairo_lang_utils::unordered_hash_set::UnorderedHashSet;
+use serde::{Deserialize, Serialize};
+
+use crate::compiler::CairoProgram;
+use crate::metadata::Metadata;
+
+#[cfg(test)]
+#[path = "cost_report_test.rs"]
+mod test;
+
+/// Costs by the names of their token types. Token types with no cost are omitted.
+pub type TokenCosts = BTreeMap<String, i64>;
+
+/// A static cost report of the functions of a program.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CostReport {
+    pub functions: Vec<FunctionCostReport>,
+}
+
+/// The static costs of a single function.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FunctionCostReport {
+    /// The name of the function.
+    pub name: String,
+    /// The number of CASM instructions of the function, not including the functions it calls.
+    pub casm_instructions: usize,
+    /// The ap change of calling the function, if it is known at compile time.
+    pub ap_change: Option<usize>,
+    /// The gas the caller pays for calling the function, before the function withdraws gas by
+    /// itself. `None` if gas was not computed for the program.
+    pub entry_cost: Option<TokenCosts>,
+    /// The worst-case gas of calling the function, including the gas withdrawn by it and by the
+    /// functions it calls. `None` if unbounded (when reaching a loop or a recursion) or if gas was
+    /// not computed for the program.
+    pub max_cost: Option<TokenCosts>,
+}
+
+impl CostReport {
+    /// Computes the report of `program`, given the metadata it was compiled with and the compiled
+    /// program.
+    pub fn new(program: &Program, metadata: &Metadata, cairo_program: &CairoProgram) -> Self {
+        let mut computer = MaxCostComputer::new(program, metadata);
+        let functions = program
+            .funcs
+            .iter()
+            .map(|func| {
+                let entry_cost = metadata.gas_info.function_costs.get(&func.id);
+                let max_cost = entry_cost.and_then(|entry_cost| {
+                    let mut max_cost = computer.withdrawals(func.entry_point)?;
+                    for (token, cost) in entry_cost.iter() {
+                        *max_cost.entry(*token).or_default() += cost;
+                    }
+                    Some(max_cost)
+                });
+                FunctionCostReport {
+                    name: func.id.to_string(),
+                    casm_instructions: function_casm_instructions(
+                        program,
+                        cairo_program,
+                        func.entry_point,
+                    ),
+                    ap_change: metadata.ap_change_info.function_ap_change.get(&func.id).copied(),
+                    entry_cost: entry_cost.map(token_costs),
+                    max_cost: max_cost.as_ref().map(token_costs),
+                }
+            })
+            .collect();
+        Self { functions }
+    }
+}
+
+/// Converts costs by token types to costs by the names of the token types.
+fn token_costs(costs: &OrderedHashMap<CostTokenType, i64>) -> TokenCosts {
+    costs
+        .iter()
+        .filter(|(_, cost)| **cost != 0)
+        .map(|(token, cost)| (token.name(), *cost))
+        .collect()
+}
+
+/// Returns the number of CASM instructions of the statements reachable from `entry_point` without
+/// following function calls.
+fn function_casm_instructions(
+    program: &Program,
+    cairo_program: &CairoProgram,
+    entry_point: StatementIdx,
+) -> usize {
+    let statement_info = &cairo_program.debug_info.sierra_statement_info;
+    let statement_instructions = |idx: StatementIdx| {
+        let end = statement_info
+            .get(idx.0 + 1)
+            .map_or(cairo_program.instructions.len(), |info| info.instruction_idx);
+        end - statement_info[idx.0].instruction_idx
+    };
+    let mut visited = UnorderedHashSet::<StatementIdx>::default();
+    let mut stack = vec![entry_point];
+    let mut count = 0;
+    while let Some(idx) = stack.pop() {
+        if !visited.insert(idx) {
+            continue;
+        }
+        count += statement_instructions(idx);
+        if let Some(Statement::Invocation(invocation)) = program.get_statement(&idx) {
+            stack.extend(invocation.branches.iter().map(|branch| idx.next(&branch.target)));
+        }
+    }
+    count
+}
+
+/// The kinds of libfuncs affecting the worst-case cost of a function.
+enum LibfuncKind {
+    /// Withdraws gas on its first branch.
+    WithdrawGas,
+    /// Calls a user function.
+    Call(FunctionId),
+}
+
+/// Computes the worst-case gas withdrawn from statements onwards.
+struct MaxCostComputer<'a> {
+    program: &'a Program,
+    libfunc_kinds: UnorderedHashMap<ConcreteLibfuncId, LibfuncKind>,
+    function_entry_points: UnorderedHashMap<FunctionId, StatementIdx>,
+    /// The gas withdrawn by the withdraw gas statements.
+    statement_withdrawals: UnorderedHashMap<StatementIdx, OrderedHashMap<CostTokenType, i64>>,
+    /// The computed results, `None` for unbounded withdrawals.
+    cache: UnorderedHashMap<StatementIdx, Option<OrderedHashMap<CostTokenType, i64>>>,
+}
+
+impl<'a> MaxCostComputer<'a> {
+    fn new(program: &'a Program, metadata: &Metadata) -> Self {
+        let libfunc_kinds: UnorderedHashMap<_, _> = program
+            .libfunc_declarations
+            .iter()
+            .filter_map(|declaration| {
+                let kind = match declaration.long_id.generic_id.0.as_str() {
+                    WithdrawGasLibfunc::STR_ID | BuiltinCostWithdrawGasLibfunc::STR_ID => {
+                        LibfuncKind::WithdrawGas
+                    }
+                    FunctionCallLibfunc::STR_ID | CouponCallLibfunc::STR_ID => {
+                        match declaration.long_id.generic_args.first()? {
+                            GenericArg::UserFunc(function_id) => {
+                                LibfuncKind::Call(function_id.clone())
+                            }
+                            _ => return None,
+                        }
+                    }
+                    _ => return None,
+                };
+                Some((declaration.id.clone(), kind))
+            })
+            .collect();
+        let mut statement_withdrawals =
+            UnorderedHashMap::<StatementIdx, OrderedHashMap<CostTokenType, i64>>::default();
+        for ((idx, token), value) in metadata.gas_info.variable_values.iter() {
+            if let Some(Statement::Invocation(invocation)) = program.get_statement(idx) {
+                if matches!(
+                    libfunc_kinds.get(&invocation.libfunc_id),
+                    Some(LibfuncKind::WithdrawGas)
+                ) {
+                    statement_withdrawals.entry(*idx).or_default().insert(*token, *value);
+                }
+            }
+        }
+        Self {
+            program,
+            libfunc_kinds,
+            function_entry_points: program
+                .funcs
+                .iter()
+                .map(|func| (func.id.clone(), func.entry_point))
+                .collect(),
+            statement_withdrawals,
+            cache: Default::default(),
+        }
+    }
+
+    /// Returns the worst-case gas withdrawn when running from `start` until returning, or `None`
+    /// if it is unbounded.
+    fn withdrawals(&mut self, start: StatementIdx) -> Option<OrderedHashMap<CostTokenType, i64>> {
+        // An iterative post-order traversal, as the paths may be too long for recursion. A
+        // statement reached again while its dependencies are computed is in a loop.
+        let mut in_progress = UnorderedHashSet::<StatementIdx>::default();
+        let mut stack = vec![(start, false)];
+        while let Some((idx, dependencies_done)) = stack.pop() {
+            if self.cache.contains_key(&idx) {
+                continue;
+            }
+            if dependencies_done {
+                let result = self.compute_statement(idx);
+                in_progress.remove(&idx);
+                self.cache.insert(idx, result);
+                continue;
+            }
+            if !in_progress.insert(idx) {
+                continue;
+            }
+            stack.push((idx, true));
+            stack.extend(
+                self.dependencies(idx)
+                    .into_iter()
+                    .filter(|dependency| !self.cache.contains_key(dependency))
+                    .map(|dependency| (dependency, false)),
+            );
+        }
+        self.cache[&start].clone()
+    }
+
+    /// Returns the statements the withdrawals from `idx` depend on.
+    fn dependencies(&self, idx: StatementIdx) -> Vec<StatementIdx> {
+        let Some(Statement::Invocation(invocation)) = self.program.get_statement(&idx) else {
+            return vec![];
+        };
+        let mut dependencies: Vec<_> =
+            invocation.branches.iter().map(|branch| idx.next(&branch.target)).collect();
+        if let Some(LibfuncKind::Call(function_id)) = self.libfunc_kinds.get(&invocation.libfunc_id)
+        {
+            dependencies.extend(self.function_entry_points.get(function_id).copied());
+        }
+        dependencies
+    }
+
+    /// Computes the withdrawals from `idx`, given that all its bounded dependencies are cached.
+    /// A dependency missing from the cache is in a loop with `idx`.
+    fn compute_statement(&self, idx: StatementIdx) -> Option<OrderedHashMap<CostTokenType, i64>> {
+        let invocation = match self.program.get_statement(&idx)? {
+            Statement::Return(_) => return Some(Default::default()),
+            Statement::Invocation(invocation) => invocation,
+        };
+        let kind = self.libfunc_kinds.get(&invocation.libfunc_id);
+        let mut base = match kind {
+            Some(LibfuncKind::Call(function_id)) => {
+                self.cache.get(self.function_entry_points.get(function_id)?)?.clone()?
+            }
+            _ => Default::default(),
+        };
+        let mut result = OrderedHashMap::<CostTokenType, i64>::default();
+        for (branch_idx, branch) in invocation.branches.iter().enumerate() {
+            let mut branch_withdrawals = self.cache.get(&idx.next(&branch.target))?.clone()?;
+            if branch_idx == 0 && matches!(kind, Some(LibfuncKind::WithdrawGas)) {
+                if let Some(withdrawals) = self.statement_withdrawals.get(&idx) {
+                    for (token, value) in withdrawals.iter() {
+                        *branch_withdrawals.entry(*token).or_default() += value;
+                    }
+                }
+            }
+            for (token, value) in branch_withdrawals {
+                let entry = result.entry(token).or_default();
+                *entry = (*entry).max(value);
+            }
+        }
+        for (token, value) in result {
+            *base.entry(token).or_default() += value;
+        }
+        Some(base)
+    }
+}
@@ -0,0 +1,140 @@
+use cairo_lang_sierra::ProgramParser;
+use indoc::indoc;
+use pretty_assertions::assert_eq;
+
+use super::{CostReport, FunctionCostReport, TokenCosts};
+use crate::compiler::{SierraToCasmConfig, compile};
+use crate::metadata::{MetadataComputationConfig, calc_metadata, calc_metadata_ap_change_only};
+use crate::test_utils::read_sierra_example_file;
+
+/// Returns the costs of the `const` token type.
+fn const_costs(value: i64) -> Option<TokenCosts> {
+    Some([("const".to_string(), value)].into())
+}
+
+#[test]
+fn test_cost_report() {
+    let program = ProgramParser::new()
+        .parse(indoc! {"
+            type RangeCheck = RangeCheck;
+            type GasBuiltin = GasBuiltin;
+
+            libfunc withdraw_gas = withdraw_gas;
+            libfunc branch_align = branch_align;
+            libfunc store_temp_rc = store_temp<RangeCheck>;
+            libfunc store_temp_gb = store_temp<GasBuiltin>;
+            libfunc call_leaf = function_call<user@Leaf>;
+            libfunc call_recursive = function_call<user@Recursive>;
+            libfunc revoke_ap_tracking = revoke_ap_tracking;
+
+            withdraw_gas(rc, gb) { fallthrough(rc, gb) LeafFail(rc, gb) };
+            branch_align() -> ();
+            store_temp_rc(rc) -> (rc);
+            store_temp_rc(rc) -> (rc);
+            store_temp_rc(rc) -> (rc);
+            store_temp_rc(rc) -> (rc);
+            store_temp_rc(rc) -> (rc);
+            store_temp_gb(gb) -> (gb);
+            return(rc, gb);
+            LeafFail:
+            branch_align() -> ();
+            store_temp_rc(rc) -> (rc);
+            store_temp_gb(gb) -> (gb);
+            return(rc, gb);
+
+            store_temp_rc(rc) -> (rc);
+            store_temp_gb(gb) -> (gb);
+            call_leaf(rc, gb) -> (rc, gb);
+            store_temp_rc(rc) -> (rc);
+            store_temp_gb(gb) -> (gb);
+            call_leaf(rc, gb) -> (rc, gb);
+            return(rc, gb);
+
+            revoke_ap_tracking() -> ();
+            withdraw_gas(rc, gb) { fallthrough(rc, gb) RecursiveFail(rc, gb) };
+            branch_align() -> ();
+            store_temp_rc(rc) -> (rc);
+            store_temp_gb(gb) -> (gb);
+            call_recursive(rc, gb) -> (rc, gb);
+            return(rc, gb);
+            RecursiveFail:
+            branch_align() -> ();
+            store_temp_rc(rc) -> (rc);
+            store_temp_gb(gb) -> (gb);
+            return(rc, gb);
+
+            Leaf@0(rc: RangeCheck, gb: GasBuiltin) -> (RangeCheck, GasBuiltin);
+            Caller@13(rc: RangeCheck, gb: GasBuiltin) -> (RangeCheck, GasBuiltin);
+            Recursive@20(rc: RangeCheck, gb: GasBuiltin) -> (RangeCheck, GasBuiltin);
+        "})
+        .unwrap();
+    let metadata = calc_metadata(&program, MetadataComputationConfig::default()).unwrap();
+    let cairo_program = compile(&program, &metadata, SierraToCasmConfig {
+        gas_usage_check: true,
+        max_bytecode_size: usize::MAX,
+        peephole_optimizations: false,
+    })
+    .unwrap();
+    assert_eq!(CostReport::new(&program, &metadata, &cairo_program), CostReport {
+        functions: vec![
+            FunctionCostReport {
+                name: "Leaf".into(),
+                casm_instructions: 17,
+                ap_change: Some(8),
+                entry_cost: const_costs(810),
+                max_cost: const_costs(970),
+            },
+            FunctionCostReport {
+                name: "Caller".into(),
+                casm_instructions: 7,
+                ap_change: Some(24),
+                entry_cost: const_costs(2420),
+                // Includes the gas withdrawn by both calls to `Leaf`.
+                max_cost: const_costs(2740),
+            },
+            FunctionCostReport {
+                name: "Recursive".into(),
+                casm_instructions: 13,
+                ap_change: None,
+                entry_cost: const_costs(670),
+                max_cost: None,
+            },
+        ]
+    });
+}
+
+#[test]
+fn test_cost_report_loop() {
+    let program = ProgramParser::new().parse(&read_sierra_example_file("fib_jumps")).unwrap();
+    let metadata = calc_metadata(&program, MetadataComputationConfig::default()).unwrap();
+    let cairo_program = compile(&program, &metadata, SierraToCasmConfig {
+        gas_usage_check: true,
+        max_bytecode_size: usize::MAX,
+        peephole_optimizations: false,
+    })
+    .unwrap();
+    let report = CostReport::new(&program, &metadata, &cairo_program);
+    assert_eq!(report.functions.len(), 1);
+    let function = &report.functions[0];
+    assert_eq!(function.casm_instructions, cairo_program.instructions.len());
+    assert!(function.entry_cost.is_some());
+    // The loop of the function withdraws gas for each iteration.
+    assert_eq!(function.max_cost, None);
+}
+
+#[test]
+fn test_cost_report_no_gas() {
+    let program = ProgramParser::new().parse(&read_sierra_example_file("fib_no_gas")).unwrap();
+    let metadata = calc_metadata_ap_change_only(&program).unwrap_or_default();
+    let cairo_program = compile(&program, &metadata, SierraToCasmConfig {
+        gas_usage_check: false,
+        max_bytecode_size: usize::MAX,
+        peephole_optimizations: false,
+    })
+    .unwrap();
+    let report = CostReport::new(&program, &metadata, &cairo_program);
+    assert!(
+        report.functions.iter().all(|function| function.entry_cost.is_none()
+            && function.max_cost.is_none())
+    );
+}
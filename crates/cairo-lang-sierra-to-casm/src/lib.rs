@@ -3,9 +3,11 @@
 pub mod annotations;
 pub mod circuit;
 pub mod compiler;
+pub mod cost_report;
 pub mod environment;
 pub mod invocations;
 pub mod metadata;
+mod peephole;
 pub mod references;
 pub mod relocations;
 #[cfg(any(feature = "testing", test))]
@@ -0,0 +1,348 @@
+//! Peephole optimizations of the CASM instructions of a compiled program.
+//!
+//! The optimizations run before relocation, so that the targets of jumps to statements are still
+//! known, and all jump offsets are recomputed after instructions are removed. The optimizations
+//! are:
+//! - Merging `ap += 1` into an adjacent assertion as `ap++`.
+//! - Removing jumps to the next instruction.
+//! - Removing assertions repeating the previous assertion, such as a store followed by a reload.
+//! - Threading jumps to unconditional jumps, and replacing jumps to `ret` with `ret`.
+
+use cairo_lang_casm::instructions::{
+    AddApInstruction, AssertEqInstruction, CallInstruction, Instruction, InstructionBody,
+    JnzInstruction, JumpInstruction, RetInstruction,
+};
+use cairo_lang_casm::operand::{CellRef, DerefOrImmediate, Register, ResOperand};
+use cairo_lang_sierra::program::StatementIdx;
+use cairo_lang_utils::bigint::BigIntAsHex;
+use cairo_lang_utils::unordered_hash_map::UnorderedHashMap;
+use cairo_lang_utils::unordered_hash_set::UnorderedHashSet;
+use num_traits::{One, ToPrimitive};
+
+use crate::compiler::SierraStatementDebugInfo;
+use crate::relocations::{Relocation, RelocationEntry};
+
+#[cfg(test)]
+#[path = "peephole_test.rs"]
+mod test;
+
+/// An instruction in the optimized program.
+struct Node {
+    instruction: Instruction,
+    /// The index of the instruction the jump or call instruction targets.
+    target: Option<usize>,
+    /// A relocation that does not target a statement. The instruction is kept as is, as such
+    /// relocations depend on the position of the instruction.
+    pinned_relocation: Option<Relocation>,
+}
+
+/// Optimizes `instructions` of a compiled program before relocation, updating the `relocations`
+/// and the debug info of the statements accordingly.
+///
+/// `entry_points` are the statements that may be jumped to from outside the program. The
+/// instructions are left unchanged if they contain jumps whose target can not be determined.
+pub fn optimize_instructions(
+    instructions: &mut Vec<Instruction>,
+    relocations: &mut Vec<RelocationEntry>,
+    sierra_statement_info: &mut [SierraStatementDebugInfo],
+    entry_points: &[StatementIdx],
+) {
+    let Some(mut nodes) = build_nodes(instructions, relocations, sierra_statement_info) else {
+        return;
+    };
+    let entry_instructions: Vec<usize> = entry_points
+        .iter()
+        .map(|statement_idx| sierra_statement_info[statement_idx.0].instruction_idx)
+        .collect();
+    while optimize_nodes(&mut nodes, &entry_instructions) {}
+
+    // Compute the new positions of the instructions.
+    let mut new_indices = Vec::with_capacity(nodes.len() + 1);
+    let mut new_offsets = vec![0];
+    for node in &nodes {
+        new_indices.push(new_offsets.len() - 1);
+        if let Some(node) = node {
+            new_offsets.push(new_offsets.last().unwrap() + node.instruction.body.op_size());
+        }
+    }
+    new_indices.push(new_offsets.len() - 1);
+
+    instructions.clear();
+    relocations.clear();
+    for node in nodes.into_iter().flatten() {
+        let Node { mut instruction, target, pinned_relocation } = node;
+        let instruction_idx = instructions.len();
+        if let Some(target) = target {
+            let offset =
+                new_offsets[new_indices[target]] as i128 - new_offsets[instruction_idx] as i128;
+            *jump_offset_mut(&mut instruction).unwrap() = BigIntAsHex { value: offset.into() };
+        }
+        if let Some(relocation) = pinned_relocation {
+            relocations.push(RelocationEntry { instruction_idx, relocation });
+        }
+        instructions.push(instruction);
+    }
+    let code_size = *new_offsets.last().unwrap();
+    let statement_starts: Vec<usize> =
+        sierra_statement_info.iter().map(|info| new_indices[info.instruction_idx]).collect();
+    for (i, info) in sierra_statement_info.iter_mut().enumerate() {
+        info.instruction_idx = statement_starts[i];
+        info.start_offset = new_offsets[statement_starts[i]];
+        info.end_offset = statement_starts.get(i + 1).map_or(code_size, |next| new_offsets[*next]);
+    }
+}
+
+/// Builds the nodes of the instructions, resolving the targets of all the jumps and calls.
+/// Returns `None` if some target can not be resolved.
+fn build_nodes(
+    instructions: &[Instruction],
+    relocations: &[RelocationEntry],
+    sierra_statement_info: &[SierraStatementDebugInfo],
+) -> Option<Vec<Option<Node>>> {
+    let mut offsets = Vec::with_capacity(instructions.len());
+    let mut offset_to_idx = UnorderedHashMap::<usize, usize>::default();
+    let mut code_size = 0;
+    for (idx, instruction) in instructions.iter().enumerate() {
+        offsets.push(code_size);
+        offset_to_idx.insert(code_size, idx);
+        code_size += instruction.body.op_size();
+    }
+    let statement_offset = |statement_idx: StatementIdx| {
+        sierra_statement_info.get(statement_idx.0).map_or(code_size, |info| info.start_offset)
+    };
+    let mut relocations: UnorderedHashMap<usize, &Relocation> =
+        relocations.iter().map(|entry| (entry.instruction_idx, &entry.relocation)).collect();
+
+    let mut nodes = Vec::with_capacity(instructions.len());
+    for (idx, instruction) in instructions.iter().enumerate() {
+        let mut instruction = instruction.clone();
+        let relocation = relocations.remove(&idx);
+        let jump_offset = match &instruction.body {
+            InstructionBody::Call(CallInstruction { target, relative })
+            | InstructionBody::Jump(JumpInstruction { target, relative }) => {
+                match (target, relative) {
+                    (DerefOrImmediate::Immediate(value), true) => Some(value.value.clone()),
+                    _ => return None,
+                }
+            }
+            InstructionBody::Jnz(JnzInstruction { jump_offset, .. }) => match jump_offset {
+                DerefOrImmediate::Immediate(value) => Some(value.value.clone()),
+                DerefOrImmediate::Deref(_) => return None,
+            },
+            InstructionBody::AddAp(_) | InstructionBody::AssertEq(_) | InstructionBody::Ret(_) => {
+                None
+            }
+        };
+        let (target, pinned_relocation) = match (relocation, jump_offset) {
+            (Some(Relocation::RelativeStatementId(statement_idx)), Some(jump_offset)) => {
+                let target_offset =
+                    statement_offset(*statement_idx) as i128 + jump_offset.to_i128()?;
+                (Some(*offset_to_idx.get(&target_offset.try_into().ok()?)?), None)
+            }
+            // Jumps out of the code, into the const segments.
+            (Some(relocation), _) => (None, Some(relocation.clone())),
+            (None, Some(jump_offset)) => {
+                let target_offset = offsets[idx] as i128 + jump_offset.to_i128()?;
+                (Some(*offset_to_idx.get(&target_offset.try_into().ok()?)?), None)
+            }
+            (None, None) => (None, None),
+        };
+        if target.is_some() {
+            // The offset is recomputed when the optimization is done.
+            *jump_offset_mut(&mut instruction).unwrap() = BigIntAsHex { value: 0.into() };
+        }
+        nodes.push(Some(Node { instruction, target, pinned_relocation }));
+    }
+    Some(nodes)
+}
+
+/// Runs a single round of optimizations. Returns whether anything changed.
+fn optimize_nodes(nodes: &mut [Option<Node>], entry_instructions: &[usize]) -> bool {
+    let mut changed = thread_jumps(nodes);
+
+    // Remove jumps to the next instruction.
+    for idx in 0..nodes.len() {
+        let Some(node) = &nodes[idx] else { continue };
+        if node.target.is_some_and(|target| resolve(nodes, target) == resolve(nodes, idx + 1))
+            && !node.instruction.inc_ap
+            && node.instruction.hints.is_empty()
+            && matches!(node.instruction.body, InstructionBody::Jump(_) | InstructionBody::Jnz(_))
+        {
+            nodes[idx] = None;
+            changed = true;
+        }
+    }
+
+    // Instructions that may be reached other than from the previous instruction.
+    let mut targets = UnorderedHashSet::<usize>::default();
+    for idx in entry_instructions
+        .iter()
+        .copied()
+        .chain(nodes.iter().flatten().filter_map(|node| node.target))
+    {
+        targets.insert(resolve(nodes, idx));
+    }
+
+    let mut prev_idx = None;
+    for idx in 0..nodes.len() {
+        if nodes[idx].is_none() {
+            continue;
+        }
+        let Some(prev) = prev_idx else {
+            prev_idx = Some(idx);
+            continue;
+        };
+        let next_node = nodes[idx].as_ref().unwrap();
+        let removable = !targets.contains(&idx)
+            && next_node.instruction.hints.is_empty()
+            && next_node.pinned_relocation.is_none();
+        let prev_node = nodes[prev].as_ref().unwrap();
+        if removable && prev_node.pinned_relocation.is_none() {
+            if let Some(merged) = merge_instructions(&prev_node.instruction, &next_node.instruction)
+            {
+                nodes[prev].as_mut().unwrap().instruction = merged;
+                nodes[idx] = None;
+                changed = true;
+                continue;
+            }
+        }
+        prev_idx = Some(idx);
+    }
+    changed
+}
+
+/// Threads jumps and calls to unconditional jumps to the final target, and replaces jumps to `ret`
+/// with `ret`. Returns whether anything changed.
+fn thread_jumps(nodes: &mut [Option<Node>]) -> bool {
+    let mut changed = false;
+    for idx in 0..nodes.len() {
+        let Some(target) = nodes[idx].as_ref().and_then(|node| node.target) else { continue };
+        let mut final_target = resolve(nodes, target);
+        let mut visited = UnorderedHashSet::<usize>::default();
+        while let Some(Node { instruction, target: Some(next_target), .. }) =
+            nodes.get(final_target).and_then(|node| node.as_ref())
+        {
+            if !matches!(instruction.body, InstructionBody::Jump(_))
+                || instruction.inc_ap
+                || !instruction.hints.is_empty()
+                || !visited.insert(final_target)
+            {
+                break;
+            }
+            final_target = resolve(nodes, *next_target);
+        }
+        let target_is_ret =
+            nodes.get(final_target).and_then(|node| node.as_ref()).is_some_and(|node| {
+                matches!(node.instruction.body, InstructionBody::Ret(_))
+                    && node.instruction.hints.is_empty()
+            });
+        let node = nodes[idx].as_mut().unwrap();
+        if target_is_ret
+            && matches!(node.instruction.body, InstructionBody::Jump(_))
+            && !node.instruction.inc_ap
+        {
+            node.instruction.body = InstructionBody::Ret(RetInstruction {});
+            node.target = None;
+            changed = true;
+        } else if final_target != target {
+            node.target = Some(final_target);
+            changed = true;
+        }
+    }
+    changed
+}
+
+/// Returns the index of the first remaining instruction at or after `idx`, which is where
+/// execution continues when reaching `idx`.
+fn resolve(nodes: &[Option<Node>], idx: usize) -> usize {
+    (idx..nodes.len()).find(|idx| nodes[*idx].is_some()).unwrap_or(nodes.len())
+}
+
+/// Returns a single instruction equivalent to running `first` and then `second`, if there is one.
+fn merge_instructions(first: &Instruction, second: &Instruction) -> Option<Instruction> {
+    match (&first.body, &second.body) {
+        // `[ap + 0] = x; ap += 1;` => `[ap + 0] = x, ap++;`
+        (InstructionBody::AssertEq(_), InstructionBody::AddAp(add_ap))
+            if !first.inc_ap && is_add_ap_one(add_ap) =>
+        {
+            let mut merged = first.clone();
+            merged.inc_ap = true;
+            Some(merged)
+        }
+        // `ap += 1; [ap - 1] = x;` => `[ap + 0] = x, ap++;`
+        (InstructionBody::AddAp(add_ap), InstructionBody::AssertEq(assert_eq))
+            if !first.inc_ap && !second.inc_ap && is_add_ap_one(add_ap) =>
+        {
+            let mut merged =
+                Instruction::new(InstructionBody::AssertEq(shift_assert_eq(assert_eq, 1)?), true);
+            merged.hints = first.hints.clone();
+            Some(merged)
+        }
+        // Asserting again what the previous assertion asserted.
+        (InstructionBody::AssertEq(first_assert), InstructionBody::AssertEq(second_assert))
+            if !second.inc_ap =>
+        {
+            let second_assert = shift_assert_eq(second_assert, if first.inc_ap { 1 } else { 0 })?;
+            let is_same = second_assert == *first_assert
+                || (second_assert.b == ResOperand::Deref(first_assert.a)
+                    && first_assert.b == ResOperand::Deref(second_assert.a));
+            is_same.then(|| first.clone())
+        }
+        _ => None,
+    }
+}
+
+/// Returns whether the instruction is `ap += 1`.
+fn is_add_ap_one(add_ap: &AddApInstruction) -> bool {
+    matches!(&add_ap.operand, ResOperand::Immediate(value) if value.value.is_one())
+}
+
+/// Returns the assertion with all `ap` based cells shifted by `delta`, as if it ran when `ap` was
+/// smaller by `delta`.
+fn shift_assert_eq(assert_eq: &AssertEqInstruction, delta: i16) -> Option<AssertEqInstruction> {
+    let shift = |cell: &CellRef| -> Option<CellRef> {
+        Some(match cell.register {
+            Register::AP => {
+                CellRef { register: Register::AP, offset: cell.offset.checked_add(delta)? }
+            }
+            Register::FP => *cell,
+        })
+    };
+    let shift_deref_or_imm = |operand: &DerefOrImmediate| -> Option<DerefOrImmediate> {
+        Some(match operand {
+            DerefOrImmediate::Deref(cell) => DerefOrImmediate::Deref(shift(cell)?),
+            DerefOrImmediate::Immediate(_) => operand.clone(),
+        })
+    };
+    let b = match &assert_eq.b {
+        ResOperand::Deref(cell) => ResOperand::Deref(shift(cell)?),
+        ResOperand::DoubleDeref(cell, offset) => ResOperand::DoubleDeref(shift(cell)?, *offset),
+        ResOperand::Immediate(value) => ResOperand::Immediate(value.clone()),
+        ResOperand::BinOp(bin_op) => {
+            let mut bin_op = bin_op.clone();
+            bin_op.a = shift(&bin_op.a)?;
+            bin_op.b = shift_deref_or_imm(&bin_op.b)?;
+            ResOperand::BinOp(bin_op)
+        }
+    };
+    Some(AssertEqInstruction { a: shift(&assert_eq.a)?, b })
+}
+
+/// Returns the immediate jump offset of a relative jump or call instruction.
+fn jump_offset_mut(instruction: &mut Instruction) -> Option<&mut BigIntAsHex> {
+    match &mut instruction.body {
+        InstructionBody::Call(CallInstruction {
+            target: DerefOrImmediate::Immediate(value),
+            ..
+        })
+        | InstructionBody::Jump(JumpInstruction {
+            target: DerefOrImmediate::Immediate(value),
+            ..
+        })
+        | InstructionBody::Jnz(JnzInstruction {
+            jump_offset: DerefOrImmediate::Immediate(value),
+            ..
+        }) => Some(value),
+        _ => None,
+    }
+}
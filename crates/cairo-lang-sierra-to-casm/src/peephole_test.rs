@@ -0,0 +1,219 @@
+use cairo_lang_casm::casm;
+use cairo_lang_casm::hints::{CoreHint, Hint};
+use cairo_lang_casm::inline::CasmContext;
+use cairo_lang_casm::instructions::{Instruction, InstructionBody, JumpInstruction};
+use cairo_lang_casm::operand::{CellRef, DerefOrImmediate, Register};
+use cairo_lang_sierra::program::StatementIdx;
+use indoc::indoc;
+use itertools::Itertools;
+use pretty_assertions::assert_eq;
+use test_case::test_case;
+
+use super::optimize_instructions;
+use crate::compiler::{ReturnStatementDebugInfo, SierraStatementDebugInfo, StatementKindDebugInfo};
+use crate::relocations::{Relocation, RelocationEntry};
+
+/// Optimizes the instructions, where the statements start at the instructions at
+/// `statement_starts`. Returns the optimized code, the new instruction index of each statement
+/// start, and the remaining relocations.
+fn optimize(
+    instructions: Vec<Instruction>,
+    statement_starts: &[usize],
+    relocations: Vec<RelocationEntry>,
+    entry_points: &[usize],
+) -> (String, Vec<usize>, Vec<RelocationEntry>) {
+    let offset_of = |instructions: &[Instruction], idx: usize| -> usize {
+        instructions[..idx].iter().map(|instruction| instruction.body.op_size()).sum()
+    };
+    let mut sierra_statement_info = statement_starts
+        .iter()
+        .enumerate()
+        .map(|(i, start)| SierraStatementDebugInfo {
+            start_offset: offset_of(&instructions, *start),
+            end_offset: offset_of(
+                &instructions,
+                statement_starts.get(i + 1).copied().unwrap_or(instructions.len()),
+            ),
+            instruction_idx: *start,
+            additional_kind_info: StatementKindDebugInfo::Return(ReturnStatementDebugInfo {
+                ref_values: vec![],
+            }),
+        })
+        .collect_vec();
+    let mut instructions = instructions;
+    let mut relocations = relocations;
+    optimize_instructions(
+        &mut instructions,
+        &mut relocations,
+        &mut sierra_statement_info,
+        &entry_points.iter().map(|idx| StatementIdx(*idx)).collect_vec(),
+    );
+    for (i, info) in sierra_statement_info.iter().enumerate() {
+        assert_eq!(info.start_offset, offset_of(&instructions, info.instruction_idx));
+        let next_start = sierra_statement_info.get(i + 1).map(|next| next.instruction_idx);
+        assert_eq!(
+            info.end_offset,
+            offset_of(&instructions, next_start.unwrap_or(instructions.len()))
+        );
+    }
+    (
+        instructions.iter().map(|instruction| format!("{instruction};\n")).join(""),
+        sierra_statement_info.iter().map(|info| info.instruction_idx).collect(),
+        relocations,
+    )
+}
+
+#[test_case(
+    casm! {
+        [ap + 0] = 1;
+        ap += 1;
+        ret;
+    },
+    indoc! {"
+        [ap + 0] = 1, ap++;
+        ret;
+    "};
+    "merge ap increment after assertion"
+)]
+#[test_case(
+    casm! {
+        ap += 1;
+        [ap - 1] = [fp - 3];
+        ret;
+    },
+    indoc! {"
+        [ap + 0] = [fp + -3], ap++;
+        ret;
+    "};
+    "merge ap increment before assertion"
+)]
+#[test_case(
+    casm! {
+        [ap + 0] = [fp - 3], ap++;
+        [fp - 3] = [ap - 1];
+        [ap + 0] = [ap - 1] + 2;
+        [ap + 0] = [ap - 1] + 2;
+        ret;
+    },
+    indoc! {"
+        [ap + 0] = [fp + -3], ap++;
+        [ap + 0] = [ap + -1] + 2;
+        ret;
+    "};
+    "repeated assertions"
+)]
+#[test_case(
+    casm! {
+        jmp rel 2;
+        jmp rel 4 if [ap - 1] != 0;
+        [ap + 0] = 1, ap++;
+        jmp rel 2;
+        jmp rel 4;
+        [ap + 0] = 2, ap++;
+        ret;
+    },
+    indoc! {"
+        jmp rel 8 if [ap + -1] != 0;
+        [ap + 0] = 1, ap++;
+        ret;
+        ret;
+        [ap + 0] = 2, ap++;
+        ret;
+    "};
+    "jumps"
+)]
+fn test_optimize(casm: CasmContext, expected: &str) {
+    assert_eq!(optimize(casm.instructions, &[0], vec![], &[0]).0, expected);
+}
+
+#[test]
+fn test_unknown_jump_target() {
+    let mut casm = casm! {
+        [ap + 0] = 1;
+        ap += 1;
+    };
+    casm.instructions.push(Instruction::new(
+        InstructionBody::Jump(JumpInstruction {
+            target: DerefOrImmediate::Deref(CellRef { register: Register::AP, offset: -1 }),
+            relative: true,
+        }),
+        false,
+    ));
+    let expected = indoc! {"
+        [ap + 0] = 1;
+        ap += 1;
+        jmp rel [ap + -1];
+    "};
+    assert_eq!(optimize(casm.instructions, &[0], vec![], &[0]).0, expected);
+}
+
+#[test]
+fn test_statements_and_relocations() {
+    let casm = casm! {
+        // Statement #0.
+        jmp rel 0;
+        // Statement #1.
+        call rel 0;
+        [ap + 0] = [ap - 1] + 0, ap++;
+        ret;
+        // Statement #2.
+        [ap + 0] = 2;
+        ap += 1;
+        ret;
+    };
+    let relocations = vec![
+        RelocationEntry {
+            instruction_idx: 0,
+            relocation: Relocation::RelativeStatementId(StatementIdx(2)),
+        },
+        RelocationEntry { instruction_idx: 1, relocation: Relocation::EndOfProgram },
+        RelocationEntry { instruction_idx: 2, relocation: Relocation::EndOfProgram },
+    ];
+    assert_eq!(
+        optimize(casm.instructions, &[0, 1, 4], relocations, &[0]),
+        (
+            indoc! {"
+                jmp rel 7;
+                call rel 0;
+                [ap + 0] = [ap + -1] + 0, ap++;
+                ret;
+                [ap + 0] = 2, ap++;
+                ret;
+            "}
+            .to_string(),
+            vec![0, 1, 4],
+            vec![
+                RelocationEntry { instruction_idx: 1, relocation: Relocation::EndOfProgram },
+                RelocationEntry { instruction_idx: 2, relocation: Relocation::EndOfProgram },
+            ]
+        )
+    );
+}
+
+#[test]
+fn test_no_merge_into_reachable_instructions() {
+    // The assertion starts an entry point, so it may be reached without the `ap` increment.
+    let casm = casm! {
+        ap += 1;
+        [ap - 1] = 1;
+        ret;
+    };
+    let expected = indoc! {"
+        ap += 1;
+        [ap + -1] = 1;
+        ret;
+    "};
+    assert_eq!(optimize(casm.instructions, &[0, 1], vec![], &[0, 1]).0, expected);
+
+    // The hint of the `ap` increment must run after the assertion.
+    let mut casm = casm! {
+        [ap + 0] = 1;
+        ap += 1;
+        ret;
+    };
+    let hint: Hint =
+        CoreHint::AllocSegment { dst: CellRef { register: Register::AP, offset: 0 } }.into();
+    casm.instructions[1].hints.push(hint);
+    let expected = casm.instructions.iter().map(|instruction| format!("{instruction};\n")).join("");
+    assert_eq!(optimize(casm.instructions, &[0], vec![], &[0]).0, expected);
+}
@@ -11,7 +11,7 @@ use crate::compiler::ConstsInfo;
 
 pub type CodeOffset = usize;
 
-#[derive(Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, PartialEq)]
 pub enum Relocation {
     /// Adds program_offset(StatementIdx) and subtracts the program offset of the casm instruction
     /// that is being relocated.
@@ -1,33 +1,79 @@
 use std::collections::HashMap;
 
 use cairo_lang_utils::extract_matches;
-use num_bigint::BigInt;
+use itertools::{chain, repeat_n};
+use num_bigint::{BigInt, BigUint};
 use num_integer::Integer;
-use num_traits::{One, ToPrimitive, Zero};
-use starknet_types_core::felt::{Felt as Felt252, NonZeroFelt as NonZeroFelt252};
+use num_traits::{One, Signed, ToPrimitive, Zero};
+use starknet_types_core::curve::ProjectivePoint;
+use starknet_types_core::felt::{
+    CAIRO_PRIME_BIGINT, Felt as Felt252, NonZeroFelt as NonZeroFelt252,
+};
+use starknet_types_core::hash::{Pedersen, Poseidon, StarkHash};
 
 use super::LibfuncSimulationError;
+use super::syscalls::SyscallHandler;
 use super::value::CoreValue;
-use crate::extensions::array::ArrayConcreteLibfunc;
+use crate::extensions::array::{ArrayConcreteLibfunc, ConcreteMultiPopLibfunc};
 use crate::extensions::boolean::BoolConcreteLibfunc;
-use crate::extensions::core::CoreConcreteLibfunc;
+use crate::extensions::bounded_int::{
+    BoundedIntConcreteLibfunc, BoundedIntConstrainConcreteLibfunc, BoundedIntType,
+};
+use crate::extensions::boxing::BoxConcreteLibfunc;
+use crate::extensions::bytes31::{Bytes31ConcreteLibfunc, Bytes31Type};
+use crate::extensions::casts::{CastConcreteLibfunc, DowncastConcreteLibfunc};
+use crate::extensions::circuit::{
+    CircuitConcreteLibfunc, CircuitInfo, CircuitTypeConcrete, ConcreteCircuit,
+    ConcreteGetOutputLibFunc, GateOffsets,
+};
+use crate::extensions::const_type::{
+    ConstAsBoxConcreteLibfunc, ConstAsImmediateConcreteLibfunc, ConstConcreteLibfunc,
+    ConstConcreteType,
+};
+use crate::extensions::consts::SignatureAndConstConcreteLibfunc;
+use crate::extensions::core::{CoreConcreteLibfunc, CoreTypeConcrete};
+use crate::extensions::coupon::CouponConcreteLibfunc;
 use crate::extensions::ec::EcConcreteLibfunc;
 use crate::extensions::enm::{EnumConcreteLibfunc, EnumInitConcreteLibfunc};
 use crate::extensions::felt252::{
     Felt252BinaryOpConcreteLibfunc, Felt252BinaryOperationConcrete, Felt252BinaryOperator,
     Felt252Concrete, Felt252ConstConcreteLibfunc, Felt252OperationWithConstConcreteLibfunc,
+    Felt252Type,
+};
+use crate::extensions::felt252_dict::{
+    Felt252DictConcreteLibfunc, Felt252DictEntryConcreteLibfunc,
 };
-use crate::extensions::felt252_dict::Felt252DictConcreteLibfunc;
 use crate::extensions::function_call::SignatureAndFunctionConcreteLibfunc;
 use crate::extensions::gas::GasConcreteLibfunc;
+use crate::extensions::int::signed::{
+    Sint8Type, Sint16Type, Sint32Type, Sint64Type, SintConcrete, SintTraits,
+};
+use crate::extensions::int::signed128::{Sint128Concrete, Sint128Type};
 use crate::extensions::int::unsigned::{
-    Uint8Concrete, Uint16Concrete, Uint32Concrete, Uint64Concrete,
+    Uint8Concrete, Uint8Type, Uint16Concrete, Uint16Type, Uint32Concrete, Uint32Type,
+    Uint64Concrete, Uint64Type,
 };
-use crate::extensions::int::unsigned128::Uint128Concrete;
-use crate::extensions::int::{IntConstConcreteLibfunc, IntOperator};
+use crate::extensions::int::unsigned128::{Uint128Concrete, Uint128Type};
+use crate::extensions::int::unsigned256::Uint256Concrete;
+use crate::extensions::int::unsigned512::Uint512Concrete;
+use crate::extensions::int::{IntConstConcreteLibfunc, IntMulTraits, IntOperator, IntTraits};
+use crate::extensions::is_zero::IsZeroTraits;
+use crate::extensions::lib_func::SignatureAndTypeConcreteLibfunc;
 use crate::extensions::mem::MemConcreteLibfunc;
-use crate::extensions::structure::StructConcreteLibfunc;
-use crate::ids::FunctionId;
+use crate::extensions::nullable::NullableConcreteLibfunc;
+use crate::extensions::pedersen::PedersenConcreteLibfunc;
+use crate::extensions::poseidon::PoseidonConcreteLibfunc;
+use crate::extensions::range::IntRangeConcreteLibfunc;
+use crate::extensions::starknet::StarkNetConcreteLibfunc;
+use crate::extensions::starknet::interoperability::{ClassHashType, ContractAddressType};
+use crate::extensions::starknet::secp256::{Secp256ConcreteLibfunc, Secp256OpConcreteLibfunc};
+use crate::extensions::starknet::storage::{StorageAddressType, StorageBaseAddressType};
+use crate::extensions::starknet::testing::{CheatcodeConcreteLibfunc, TestingConcreteLibfunc};
+use crate::extensions::structure::{StructConcreteLibfunc, StructConcreteType};
+use crate::extensions::types::InfoAndTypeConcreteType;
+use crate::extensions::{ConcreteLibfunc, ConcreteType, NamedType};
+use crate::ids::{ConcreteTypeId, FunctionId, GenericTypeId};
+use crate::program::GenericArg;
 
 /// Helper macro to take the inputs and return an error if the number of inputs is wrong, or the
 /// type of the expected inputs is wrong. Usage:
@@ -46,16 +92,26 @@ macro_rules! take_inputs {
 /// Simulates the run of a single libfunc. Returns the value representations of the outputs, and
 /// the chosen branch given the inputs.
 ///
+/// `get_type` resolves the concrete types the libfuncs are specialized with.
 /// `simulate_function` is a function that simulates running of a user function. It is provided here
 /// for the case where the extensions need to use it.
+/// `syscall_handler` handles the Starknet syscalls and cheatcodes.
 pub fn simulate<
+    'a,
+    GetType: Fn(&ConcreteTypeId) -> Option<&'a CoreTypeConcrete>,
     GetStatementGasInfo: Fn() -> Option<i64>,
-    SimulateFunction: Fn(&FunctionId, Vec<CoreValue>) -> Result<Vec<CoreValue>, LibfuncSimulationError>,
+    SimulateFunction: Fn(
+        &FunctionId,
+        Vec<CoreValue>,
+        &mut dyn SyscallHandler,
+    ) -> Result<Vec<CoreValue>, LibfuncSimulationError>,
 >(
     libfunc: &CoreConcreteLibfunc,
     inputs: Vec<CoreValue>,
+    get_type: GetType,
     get_statement_gas_info: GetStatementGasInfo,
     simulate_function: SimulateFunction,
+    syscall_handler: &mut dyn SyscallHandler,
 ) -> Result<(Vec<CoreValue>, usize), LibfuncSimulationError> {
     Ok(match libfunc {
         CoreConcreteLibfunc::Drop(_) => {
@@ -66,45 +122,37 @@ pub fn simulate<
             let [value] = take_inputs(inputs)?;
             (vec![value.clone(), value], 0)
         }
-        CoreConcreteLibfunc::Ec(libfunc) => {
-            match libfunc {
-                EcConcreteLibfunc::TryNew(_) => {
-                    take_inputs!(let [CoreValue::Felt252(x), CoreValue::Felt252(y)] = inputs);
-                    const BETA: Felt252 = Felt252::from_hex_unchecked(
-                        "0x6f21413efbe40de150e596d72f7a8c5609ad26c15c915c1f4cdfcb99cee9e89",
-                    );
-                    // If the point is on the curve use the fallthrough branch and return the
-                    // point.
-                    if y * y == x * x * x + x + BETA {
-                        (vec![CoreValue::EcPoint(x, y)], 0)
-                    } else {
-                        (vec![], 1)
-                    }
-                }
-                EcConcreteLibfunc::UnwrapPoint(_) => {
-                    take_inputs!(let [CoreValue::EcPoint(x, y)] = inputs);
-                    (vec![CoreValue::Felt252(x), CoreValue::Felt252(y)], 0)
-                }
-                _ => unimplemented!(),
-            }
-        }
+        CoreConcreteLibfunc::Ec(libfunc) => simulate_ec_libfunc(libfunc, inputs)?,
         CoreConcreteLibfunc::FunctionCall(SignatureAndFunctionConcreteLibfunc {
             function, ..
-        })
-        | CoreConcreteLibfunc::CouponCall(SignatureAndFunctionConcreteLibfunc {
+        }) => (simulate_function(&function.id, inputs, syscall_handler)?, 0),
+        CoreConcreteLibfunc::CouponCall(SignatureAndFunctionConcreteLibfunc {
             function, ..
-        }) => (simulate_function(&function.id, inputs)?, 0),
+        }) => {
+            let mut inputs = inputs;
+            // The coupon is the last argument, and is consumed by the call.
+            match inputs.pop() {
+                Some(CoreValue::Coupon) => {}
+                Some(_) => return Err(LibfuncSimulationError::WrongArgType),
+                None => return Err(LibfuncSimulationError::WrongNumberOfArgs),
+            }
+            (simulate_function(&function.id, inputs, syscall_handler)?, 0)
+        }
         CoreConcreteLibfunc::Gas(GasConcreteLibfunc::WithdrawGas(_)) => {
             let count = get_statement_gas_info()
                 .ok_or(LibfuncSimulationError::UnresolvedStatementGasInfo)?;
             take_inputs!(let [CoreValue::RangeCheck, CoreValue::GasBuiltin(gas_counter)] = inputs);
-            if gas_counter >= count {
-                // Have enough gas - return reduced counter and jump to success branch.
-                (vec![CoreValue::RangeCheck, CoreValue::GasBuiltin(gas_counter - count)], 0)
-            } else {
-                // Don't have enough gas - return the same counter and jump to failure branch.
-                (vec![CoreValue::RangeCheck, CoreValue::GasBuiltin(gas_counter)], 1)
-            }
+            simulate_withdraw_gas(gas_counter, count)
+        }
+        CoreConcreteLibfunc::Gas(GasConcreteLibfunc::BuiltinWithdrawGas(_)) => {
+            let count = get_statement_gas_info()
+                .ok_or(LibfuncSimulationError::UnresolvedStatementGasInfo)?;
+            take_inputs!(let [
+                CoreValue::RangeCheck,
+                CoreValue::GasBuiltin(gas_counter),
+                CoreValue::BuiltinCosts,
+            ] = inputs);
+            simulate_withdraw_gas(gas_counter, count)
         }
         CoreConcreteLibfunc::Gas(GasConcreteLibfunc::RedepositGas(_)) => {
             let count = get_statement_gas_info()
@@ -116,96 +164,62 @@ pub fn simulate<
             take_inputs!(let [CoreValue::GasBuiltin(gas_counter)] = inputs);
             (vec![CoreValue::GasBuiltin(gas_counter), CoreValue::Uint128(gas_counter as u128)], 0)
         }
-        CoreConcreteLibfunc::Gas(
-            GasConcreteLibfunc::BuiltinWithdrawGas(_) | GasConcreteLibfunc::GetBuiltinCosts(_),
-        ) => {
-            unimplemented!("Simulation of the builtin cost functionality is not implemented yet.")
+        CoreConcreteLibfunc::Gas(GasConcreteLibfunc::GetBuiltinCosts(_)) => {
+            let [] = take_inputs(inputs)?;
+            (vec![CoreValue::BuiltinCosts], 0)
         }
         CoreConcreteLibfunc::BranchAlign(_) => {
             let [] = take_inputs(inputs)?;
             get_statement_gas_info().ok_or(LibfuncSimulationError::UnresolvedStatementGasInfo)?;
             (vec![], 0)
         }
-        CoreConcreteLibfunc::Array(ArrayConcreteLibfunc::New(_)) => {
-            let [] = take_inputs(inputs)?;
-            (vec![CoreValue::Array(vec![])], 0)
-        }
-        CoreConcreteLibfunc::Array(ArrayConcreteLibfunc::SpanFromTuple(_)) => {
-            take_inputs!(let [CoreValue::Struct(members)] = inputs);
-            (vec![CoreValue::Array(members)], 0)
-        }
-        CoreConcreteLibfunc::Array(ArrayConcreteLibfunc::TupleFromSpan(_)) => todo!(),
-        CoreConcreteLibfunc::Array(ArrayConcreteLibfunc::Append(_)) => {
-            take_inputs!(let [CoreValue::Array(mut arr), element] = inputs);
-            arr.push(element);
-            (vec![CoreValue::Array(arr)], 0)
-        }
-        CoreConcreteLibfunc::Array(ArrayConcreteLibfunc::PopFront(_)) => {
-            take_inputs!(let [CoreValue::Array(mut arr)] = inputs);
-            if arr.is_empty() {
-                (vec![CoreValue::Array(arr)], 1)
-            } else {
-                let front = arr.remove(0);
-                (vec![CoreValue::Array(arr), front], 0)
-            }
-        }
-        CoreConcreteLibfunc::Array(ArrayConcreteLibfunc::PopFrontConsume(_)) => {
-            take_inputs!(let [CoreValue::Array(mut arr)] = inputs);
-            if arr.is_empty() { (vec![CoreValue::Array(arr)], 1) } else { (vec![arr.remove(0)], 0) }
-        }
-        CoreConcreteLibfunc::Array(ArrayConcreteLibfunc::Get(_)) => {
-            take_inputs!(
-                let [CoreValue::RangeCheck, CoreValue::Array(arr), CoreValue::Uint32(idx)] = inputs
-            );
-            match arr.get(idx as usize).cloned() {
-                Some(element) => (vec![CoreValue::RangeCheck, element], 0),
-                None => (vec![CoreValue::RangeCheck], 1),
-            }
-        }
-        CoreConcreteLibfunc::Array(ArrayConcreteLibfunc::Slice(_)) => {
-            take_inputs!(let [
-                CoreValue::RangeCheck,
-                CoreValue::Array(arr),
-                CoreValue::Uint32(start),
-                CoreValue::Uint32(length),
-            ] = inputs);
-            match arr.get(start as usize..(start + length) as usize) {
-                Some(elements) => {
-                    (vec![CoreValue::RangeCheck, CoreValue::Array(elements.to_vec())], 0)
-                }
-                None => (vec![CoreValue::RangeCheck], 1),
-            }
-        }
-        CoreConcreteLibfunc::Array(ArrayConcreteLibfunc::Len(_)) => {
-            take_inputs!(let [CoreValue::Array(arr)] = inputs);
-            (vec![CoreValue::Uint32(arr.len() as u32)], 0)
-        }
-        CoreConcreteLibfunc::Array(ArrayConcreteLibfunc::SnapshotPopFront(_)) => todo!(),
-        CoreConcreteLibfunc::Array(ArrayConcreteLibfunc::SnapshotPopBack(_)) => todo!(),
-        CoreConcreteLibfunc::Array(ArrayConcreteLibfunc::SnapshotMultiPopFront(_)) => todo!(),
-        CoreConcreteLibfunc::Array(ArrayConcreteLibfunc::SnapshotMultiPopBack(_)) => todo!(),
+        CoreConcreteLibfunc::Array(libfunc) => simulate_array_libfunc(libfunc, inputs, &get_type)?,
         CoreConcreteLibfunc::Uint8(libfunc) => simulate_u8_libfunc(libfunc, inputs)?,
         CoreConcreteLibfunc::Uint16(libfunc) => simulate_u16_libfunc(libfunc, inputs)?,
         CoreConcreteLibfunc::Uint32(libfunc) => simulate_u32_libfunc(libfunc, inputs)?,
         CoreConcreteLibfunc::Uint64(libfunc) => simulate_u64_libfunc(libfunc, inputs)?,
         CoreConcreteLibfunc::Uint128(libfunc) => simulate_u128_libfunc(libfunc, inputs)?,
-        CoreConcreteLibfunc::Sint8(_)
-        | CoreConcreteLibfunc::Sint16(_)
-        | CoreConcreteLibfunc::Sint32(_)
-        | CoreConcreteLibfunc::Sint64(_)
-        | CoreConcreteLibfunc::Sint128(_) => {
-            unimplemented!("Simulation of signed integer libfuncs is not implemented yet.")
-        }
+        CoreConcreteLibfunc::Uint256(libfunc) => simulate_u256_libfunc(libfunc, inputs)?,
+        CoreConcreteLibfunc::Uint512(libfunc) => simulate_u512_libfunc(libfunc, inputs)?,
+        CoreConcreteLibfunc::Sint8(libfunc) => simulate_sint_libfunc(libfunc, inputs)?,
+        CoreConcreteLibfunc::Sint16(libfunc) => simulate_sint_libfunc(libfunc, inputs)?,
+        CoreConcreteLibfunc::Sint32(libfunc) => simulate_sint_libfunc(libfunc, inputs)?,
+        CoreConcreteLibfunc::Sint64(libfunc) => simulate_sint_libfunc(libfunc, inputs)?,
+        CoreConcreteLibfunc::Sint128(libfunc) => simulate_i128_libfunc(libfunc, inputs)?,
         CoreConcreteLibfunc::Bool(libfunc) => simulate_bool_libfunc(libfunc, inputs)?,
         CoreConcreteLibfunc::Felt252(libfunc) => simulate_felt252_libfunc(libfunc, inputs)?,
         CoreConcreteLibfunc::UnwrapNonZero(_) => (inputs, 0),
         CoreConcreteLibfunc::Mem(
             MemConcreteLibfunc::Rename(_) | MemConcreteLibfunc::StoreTemp(_),
         )
-        | CoreConcreteLibfunc::Box(_) => {
+        | CoreConcreteLibfunc::Box(BoxConcreteLibfunc::ForwardSnapshot(_))
+        | CoreConcreteLibfunc::Nullable(NullableConcreteLibfunc::ForwardSnapshot(_)) => {
             let [value] = take_inputs(inputs)?;
             (vec![value], 0)
         }
+        CoreConcreteLibfunc::Box(BoxConcreteLibfunc::Into(_)) => {
+            let [value] = take_inputs(inputs)?;
+            (vec![CoreValue::Box(Box::new(value))], 0)
+        }
+        CoreConcreteLibfunc::Box(BoxConcreteLibfunc::Unbox(_)) => {
+            take_inputs!(let [CoreValue::Box(value)] = inputs);
+            (vec![*value], 0)
+        }
+        CoreConcreteLibfunc::Nullable(NullableConcreteLibfunc::Null(_)) => {
+            let [] = take_inputs(inputs)?;
+            (vec![CoreValue::Nullable(None)], 0)
+        }
+        CoreConcreteLibfunc::Nullable(NullableConcreteLibfunc::NullableFromBox(_)) => {
+            take_inputs!(let [CoreValue::Box(value)] = inputs);
+            (vec![CoreValue::Nullable(Some(value))], 0)
+        }
+        CoreConcreteLibfunc::Nullable(NullableConcreteLibfunc::MatchNullable(_)) => {
+            take_inputs!(let [CoreValue::Nullable(value)] = inputs);
+            match value {
+                None => (vec![], 0),
+                Some(value) => (vec![CoreValue::Box(value)], 1),
+            }
+        }
         CoreConcreteLibfunc::Mem(MemConcreteLibfunc::FinalizeLocals(_))
         | CoreConcreteLibfunc::UnconditionalJump(_)
         | CoreConcreteLibfunc::ApTracking(_) => {
@@ -234,7 +248,12 @@ pub fn simulate<
             take_inputs!(let [CoreValue::Enum { value, index }] = inputs);
             (vec![*value], index)
         }
-        CoreConcreteLibfunc::Enum(EnumConcreteLibfunc::FromBoundedInt(_)) => todo!(),
+        CoreConcreteLibfunc::Enum(EnumConcreteLibfunc::FromBoundedInt(_)) => {
+            let [value] = take_inputs(inputs)?;
+            let index =
+                int_input(&value)?.to_usize().ok_or(LibfuncSimulationError::WrongArgType)?;
+            (vec![CoreValue::Enum { value: Box::new(CoreValue::Struct(vec![])), index }], 0)
+        }
         CoreConcreteLibfunc::Struct(StructConcreteLibfunc::Construct(_)) => {
             (vec![CoreValue::Struct(inputs)], 0)
         }
@@ -245,25 +264,68 @@ pub fn simulate<
             (members, 0)
         }
         CoreConcreteLibfunc::Felt252Dict(Felt252DictConcreteLibfunc::New(_)) => {
-            let [] = take_inputs(inputs)?;
-            (vec![CoreValue::Dict(HashMap::new())], 0)
+            take_inputs!(let [CoreValue::SegmentArena] = inputs);
+            (vec![CoreValue::SegmentArena, CoreValue::Dict(HashMap::new())], 0)
         }
         CoreConcreteLibfunc::Felt252Dict(Felt252DictConcreteLibfunc::Squash(_)) => {
-            take_inputs!(let [CoreValue::RangeCheck, CoreValue::Dict(dict)] = inputs);
+            take_inputs!(let [
+                CoreValue::RangeCheck,
+                CoreValue::GasBuiltin(gas_counter),
+                CoreValue::SegmentArena,
+                CoreValue::Dict(dict),
+            ] = inputs);
             // Returning the same dict since it is exactly the same as the squashed one.
-            (vec![CoreValue::RangeCheck, CoreValue::Dict(dict)], 0)
+            (
+                vec![
+                    CoreValue::RangeCheck,
+                    CoreValue::GasBuiltin(gas_counter),
+                    CoreValue::SegmentArena,
+                    CoreValue::Dict(dict),
+                ],
+                0,
+            )
+        }
+        CoreConcreteLibfunc::Felt252DictEntry(Felt252DictEntryConcreteLibfunc::Get(
+            SignatureAndTypeConcreteLibfunc { ty, .. },
+        )) => {
+            take_inputs!(let [CoreValue::Dict(dict), CoreValue::Felt252(key)] = inputs);
+            let value = match dict.get(&key) {
+                Some(value) => value.clone(),
+                None => default_value(&get_type, ty)?,
+            };
+            (vec![CoreValue::DictEntry { dict, key }, value], 0)
         }
-        CoreConcreteLibfunc::Pedersen(_) => {
-            unimplemented!("Simulation of the Pedersen hash function is not implemented yet.");
+        CoreConcreteLibfunc::Felt252DictEntry(Felt252DictEntryConcreteLibfunc::Finalize(_)) => {
+            take_inputs!(let [CoreValue::DictEntry { mut dict, key }, value] = inputs);
+            dict.insert(key, value);
+            (vec![CoreValue::Dict(dict)], 0)
         }
-        CoreConcreteLibfunc::Poseidon(_) => {
-            unimplemented!("Simulation of the Poseidon hash function is not implemented yet.");
+        CoreConcreteLibfunc::Pedersen(PedersenConcreteLibfunc::PedersenHash(_)) => {
+            take_inputs!(let [CoreValue::Pedersen, CoreValue::Felt252(a), CoreValue::Felt252(b)] = inputs);
+            (vec![CoreValue::Pedersen, CoreValue::Felt252(Pedersen::hash(&a, &b))], 0)
         }
-        CoreConcreteLibfunc::StarkNet(_) => {
-            unimplemented!("Simulation of the StarkNet functionalities is not implemented yet.")
+        CoreConcreteLibfunc::Poseidon(PoseidonConcreteLibfunc::HadesPermutation(_)) => {
+            take_inputs!(let [
+                CoreValue::Poseidon,
+                CoreValue::Felt252(s0),
+                CoreValue::Felt252(s1),
+                CoreValue::Felt252(s2),
+            ] = inputs);
+            let mut state = [s0, s1, s2];
+            Poseidon::hades_permutation(&mut state);
+            let [s0, s1, s2] = state;
+            (
+                vec![
+                    CoreValue::Poseidon,
+                    CoreValue::Felt252(s0),
+                    CoreValue::Felt252(s1),
+                    CoreValue::Felt252(s2),
+                ],
+                0,
+            )
         }
-        CoreConcreteLibfunc::Nullable(_) => {
-            unimplemented!("Simulation of nullable is not implemented yet.")
+        CoreConcreteLibfunc::StarkNet(libfunc) => {
+            simulate_starknet_libfunc(libfunc, inputs, syscall_handler)?
         }
         CoreConcreteLibfunc::Debug(_) => {
             take_inputs!(let [CoreValue::Array(arr)] = inputs);
@@ -283,19 +345,266 @@ pub fn simulate<
             let [value] = take_inputs(inputs)?;
             (vec![value.clone(), value], 0)
         }
-        CoreConcreteLibfunc::Cast(_) => unimplemented!(),
-        CoreConcreteLibfunc::Felt252DictEntry(_) => unimplemented!(),
-        CoreConcreteLibfunc::Uint256(_) => unimplemented!(),
-        CoreConcreteLibfunc::Uint512(_) => unimplemented!(),
-        CoreConcreteLibfunc::Bytes31(_) => unimplemented!(),
-        CoreConcreteLibfunc::Const(_) => unimplemented!(),
-        CoreConcreteLibfunc::Coupon(_) => unimplemented!(),
-        CoreConcreteLibfunc::BoundedInt(_) => unimplemented!(),
-        CoreConcreteLibfunc::Circuit(_) => unimplemented!(),
-        CoreConcreteLibfunc::IntRange(_) => unimplemented!(),
+        CoreConcreteLibfunc::Cast(libfunc) => simulate_cast_libfunc(libfunc, inputs, &get_type)?,
+        CoreConcreteLibfunc::Bytes31(libfunc) => simulate_bytes31_libfunc(libfunc, inputs)?,
+        CoreConcreteLibfunc::Const(ConstConcreteLibfunc::AsImmediate(
+            ConstAsImmediateConcreteLibfunc { const_type, .. },
+        )) => {
+            let [] = take_inputs(inputs)?;
+            (vec![const_value(&get_type, const_type)?], 0)
+        }
+        CoreConcreteLibfunc::Const(ConstConcreteLibfunc::AsBox(ConstAsBoxConcreteLibfunc {
+            const_type,
+            ..
+        })) => {
+            let [] = take_inputs(inputs)?;
+            (vec![CoreValue::Box(Box::new(const_value(&get_type, const_type)?))], 0)
+        }
+        CoreConcreteLibfunc::Coupon(CouponConcreteLibfunc::Buy(_)) => {
+            let [] = take_inputs(inputs)?;
+            (vec![CoreValue::Coupon], 0)
+        }
+        CoreConcreteLibfunc::Coupon(CouponConcreteLibfunc::Refund(_)) => {
+            take_inputs!(let [CoreValue::Coupon] = inputs);
+            (vec![], 0)
+        }
+        CoreConcreteLibfunc::BoundedInt(libfunc) => simulate_bounded_int_libfunc(libfunc, inputs)?,
+        CoreConcreteLibfunc::Circuit(libfunc) => {
+            simulate_circuit_libfunc(libfunc, inputs, &get_type)?
+        }
+        CoreConcreteLibfunc::IntRange(libfunc) => simulate_int_range_libfunc(libfunc, inputs)?,
+    })
+}
+
+/// Simulates withdrawing `count` gas from `gas_counter`.
+fn simulate_withdraw_gas(gas_counter: i64, count: i64) -> (Vec<CoreValue>, usize) {
+    if gas_counter >= count {
+        // Have enough gas - return reduced counter and jump to success branch.
+        (vec![CoreValue::RangeCheck, CoreValue::GasBuiltin(gas_counter - count)], 0)
+    } else {
+        // Don't have enough gas - return the same counter and jump to failure branch.
+        (vec![CoreValue::RangeCheck, CoreValue::GasBuiltin(gas_counter)], 1)
+    }
+}
+
+/// Simulate array library functions.
+fn simulate_array_libfunc<'a>(
+    libfunc: &ArrayConcreteLibfunc,
+    inputs: Vec<CoreValue>,
+    get_type: &dyn Fn(&ConcreteTypeId) -> Option<&'a CoreTypeConcrete>,
+) -> Result<(Vec<CoreValue>, usize), LibfuncSimulationError> {
+    Ok(match libfunc {
+        ArrayConcreteLibfunc::New(_) => {
+            let [] = take_inputs(inputs)?;
+            (vec![CoreValue::Array(vec![])], 0)
+        }
+        ArrayConcreteLibfunc::SpanFromTuple(_) => {
+            take_inputs!(let [CoreValue::Box(tuple)] = inputs);
+            let CoreValue::Struct(members) = *tuple else {
+                return Err(LibfuncSimulationError::WrongArgType);
+            };
+            (vec![CoreValue::Array(members)], 0)
+        }
+        ArrayConcreteLibfunc::TupleFromSpan(SignatureAndTypeConcreteLibfunc { ty, .. }) => {
+            take_inputs!(let [CoreValue::Array(arr)] = inputs);
+            if arr.len() == tuple_size(get_type, ty)? {
+                (vec![CoreValue::Box(Box::new(CoreValue::Struct(arr)))], 0)
+            } else {
+                (vec![], 1)
+            }
+        }
+        ArrayConcreteLibfunc::Append(_) => {
+            take_inputs!(let [CoreValue::Array(mut arr), element] = inputs);
+            arr.push(element);
+            (vec![CoreValue::Array(arr)], 0)
+        }
+        ArrayConcreteLibfunc::PopFront(_) | ArrayConcreteLibfunc::SnapshotPopFront(_) => {
+            take_inputs!(let [CoreValue::Array(mut arr)] = inputs);
+            if arr.is_empty() {
+                (vec![CoreValue::Array(arr)], 1)
+            } else {
+                let front = arr.remove(0);
+                (vec![CoreValue::Array(arr), CoreValue::Box(Box::new(front))], 0)
+            }
+        }
+        ArrayConcreteLibfunc::PopFrontConsume(_) => {
+            take_inputs!(let [CoreValue::Array(mut arr)] = inputs);
+            if arr.is_empty() {
+                (vec![], 1)
+            } else {
+                let front = arr.remove(0);
+                (vec![CoreValue::Array(arr), CoreValue::Box(Box::new(front))], 0)
+            }
+        }
+        ArrayConcreteLibfunc::SnapshotPopBack(_) => {
+            take_inputs!(let [CoreValue::Array(mut arr)] = inputs);
+            match arr.pop() {
+                Some(back) => (vec![CoreValue::Array(arr), CoreValue::Box(Box::new(back))], 0),
+                None => (vec![CoreValue::Array(arr)], 1),
+            }
+        }
+        ArrayConcreteLibfunc::SnapshotMultiPopFront(ConcreteMultiPopLibfunc {
+            popped_ty, ..
+        }) => {
+            take_inputs!(let [CoreValue::RangeCheck, CoreValue::Array(mut arr)] = inputs);
+            let count = tuple_size(get_type, popped_ty)?;
+            if arr.len() < count {
+                (vec![CoreValue::RangeCheck, CoreValue::Array(arr)], 1)
+            } else {
+                let popped = arr.drain(..count).collect();
+                (
+                    vec![
+                        CoreValue::RangeCheck,
+                        CoreValue::Array(arr),
+                        CoreValue::Box(Box::new(CoreValue::Struct(popped))),
+                    ],
+                    0,
+                )
+            }
+        }
+        ArrayConcreteLibfunc::SnapshotMultiPopBack(ConcreteMultiPopLibfunc {
+            popped_ty, ..
+        }) => {
+            take_inputs!(let [CoreValue::RangeCheck, CoreValue::Array(mut arr)] = inputs);
+            let count = tuple_size(get_type, popped_ty)?;
+            if arr.len() < count {
+                (vec![CoreValue::RangeCheck, CoreValue::Array(arr)], 1)
+            } else {
+                let popped = arr.split_off(arr.len() - count);
+                (
+                    vec![
+                        CoreValue::RangeCheck,
+                        CoreValue::Array(arr),
+                        CoreValue::Box(Box::new(CoreValue::Struct(popped))),
+                    ],
+                    0,
+                )
+            }
+        }
+        ArrayConcreteLibfunc::Get(_) => {
+            take_inputs!(
+                let [CoreValue::RangeCheck, CoreValue::Array(arr), CoreValue::Uint32(idx)] = inputs
+            );
+            match arr.get(idx as usize).cloned() {
+                Some(element) => {
+                    (vec![CoreValue::RangeCheck, CoreValue::Box(Box::new(element))], 0)
+                }
+                None => (vec![CoreValue::RangeCheck], 1),
+            }
+        }
+        ArrayConcreteLibfunc::Slice(_) => {
+            take_inputs!(let [
+                CoreValue::RangeCheck,
+                CoreValue::Array(arr),
+                CoreValue::Uint32(start),
+                CoreValue::Uint32(length),
+            ] = inputs);
+            match start.checked_add(length).and_then(|end| arr.get(start as usize..end as usize)) {
+                Some(elements) => {
+                    (vec![CoreValue::RangeCheck, CoreValue::Array(elements.to_vec())], 0)
+                }
+                None => (vec![CoreValue::RangeCheck], 1),
+            }
+        }
+        ArrayConcreteLibfunc::Len(_) => {
+            take_inputs!(let [CoreValue::Array(arr)] = inputs);
+            (vec![CoreValue::Uint32(arr.len() as u32)], 0)
+        }
+    })
+}
+
+/// Simulate EC library functions.
+fn simulate_ec_libfunc(
+    libfunc: &EcConcreteLibfunc,
+    inputs: Vec<CoreValue>,
+) -> Result<(Vec<CoreValue>, usize), LibfuncSimulationError> {
+    Ok(match libfunc {
+        EcConcreteLibfunc::TryNew(_) => {
+            take_inputs!(let [CoreValue::Felt252(x), CoreValue::Felt252(y)] = inputs);
+            // If the point is on the curve use the fallthrough branch and return the point.
+            if y * y == x * x * x + x + BETA {
+                (vec![CoreValue::EcPoint(x, y)], 0)
+            } else {
+                (vec![], 1)
+            }
+        }
+        EcConcreteLibfunc::UnwrapPoint(_) => {
+            take_inputs!(let [CoreValue::EcPoint(x, y)] = inputs);
+            (vec![CoreValue::Felt252(x), CoreValue::Felt252(y)], 0)
+        }
+        EcConcreteLibfunc::Zero(_) => {
+            let [] = take_inputs(inputs)?;
+            (vec![CoreValue::EcPoint(Felt252::ZERO, Felt252::ZERO)], 0)
+        }
+        EcConcreteLibfunc::Neg(_) => {
+            take_inputs!(let [CoreValue::EcPoint(x, y)] = inputs);
+            (vec![CoreValue::EcPoint(x, -y)], 0)
+        }
+        EcConcreteLibfunc::IsZero(_) => {
+            take_inputs!(let [CoreValue::EcPoint(x, y)] = inputs);
+            // The point at infinity is the only point with `y = 0`.
+            if y.is_zero() { (vec![], 0) } else { (vec![CoreValue::EcPoint(x, y)], 1) }
+        }
+        EcConcreteLibfunc::PointFromX(_) => {
+            take_inputs!(let [CoreValue::RangeCheck, CoreValue::Felt252(x)] = inputs);
+            match (x * x * x + x + BETA).sqrt() {
+                Some(y) => {
+                    // Choosing the smaller of the two possible roots.
+                    let y = if y.to_biguint() <= (-y).to_biguint() { y } else { -y };
+                    (vec![CoreValue::RangeCheck, CoreValue::EcPoint(x, y)], 0)
+                }
+                None => (vec![CoreValue::RangeCheck], 1),
+            }
+        }
+        EcConcreteLibfunc::StateInit(_) => {
+            let [] = take_inputs(inputs)?;
+            (vec![CoreValue::EcState(Felt252::ZERO, Felt252::ZERO)], 0)
+        }
+        EcConcreteLibfunc::StateAdd(_) => {
+            take_inputs!(let [CoreValue::EcState(sx, sy), CoreValue::EcPoint(x, y)] = inputs);
+            let (x, y) = ec_to_affine(&(ec_to_projective(sx, sy) + ec_to_projective(x, y)));
+            (vec![CoreValue::EcState(x, y)], 0)
+        }
+        EcConcreteLibfunc::StateAddMul(_) => {
+            take_inputs!(let [
+                CoreValue::EcOp,
+                CoreValue::EcState(sx, sy),
+                CoreValue::Felt252(m),
+                CoreValue::EcPoint(x, y),
+            ] = inputs);
+            let (x, y) = ec_to_affine(&(ec_to_projective(sx, sy) + &ec_to_projective(x, y) * m));
+            (vec![CoreValue::EcOp, CoreValue::EcState(x, y)], 0)
+        }
+        EcConcreteLibfunc::StateFinalize(_) => {
+            take_inputs!(let [CoreValue::EcState(x, y)] = inputs);
+            if x.is_zero() && y.is_zero() {
+                (vec![], 1)
+            } else {
+                (vec![CoreValue::EcPoint(x, y)], 0)
+            }
+        }
     })
 }
 
+/// Converts the coordinates of an EC point to a projective point, where `(0, 0)` is the point at
+/// infinity.
+fn ec_to_projective(x: Felt252, y: Felt252) -> ProjectivePoint {
+    if x.is_zero() && y.is_zero() {
+        ProjectivePoint::identity()
+    } else {
+        ProjectivePoint::from_affine_unchecked(x, y)
+    }
+}
+
+/// Converts a projective point to the coordinates of an EC point, where `(0, 0)` is the point at
+/// infinity.
+fn ec_to_affine(point: &ProjectivePoint) -> (Felt252, Felt252) {
+    match point.to_affine() {
+        Ok(point) if !point.is_identity() => (point.x(), point.y()),
+        _ => (Felt252::ZERO, Felt252::ZERO),
+    }
+}
+
 /// Simulate boolean library functions.
 fn simulate_bool_libfunc(
     libfunc: &BoolConcreteLibfunc,
@@ -370,9 +679,18 @@ fn simulate_u128_libfunc(
         }
         Uint128Concrete::FromFelt252(_) => {
             take_inputs!(let [CoreValue::RangeCheck, CoreValue::Felt252(value)] = inputs);
-            match value.to_u128() {
-                Some(value) => (vec![CoreValue::RangeCheck, CoreValue::Uint128(value)], 0),
-                None => (vec![CoreValue::RangeCheck], 1),
+            let (high, low) = value.to_biguint().div_rem(&(BigUint::one() << 128));
+            if high.is_zero() {
+                (vec![CoreValue::RangeCheck, CoreValue::Uint128(low.to_u128().unwrap())], 0)
+            } else {
+                (
+                    vec![
+                        CoreValue::RangeCheck,
+                        CoreValue::Uint128(high.to_u128().unwrap()),
+                        CoreValue::Uint128(low.to_u128().unwrap()),
+                    ],
+                    1,
+                )
             }
         }
         Uint128Concrete::ToFelt252(_) => {
@@ -404,11 +722,11 @@ fn simulate_u128_libfunc(
         }
         Uint128Concrete::GuaranteeMul(_) => {
             take_inputs!(let [CoreValue::Uint128(lhs), CoreValue::Uint128(rhs)] = inputs);
-            let (limb1, limb0) = (BigInt::from(lhs) * rhs).div_rem(&BigInt::one().pow(128));
+            let (high, low) = (BigInt::from(lhs) * rhs).div_rem(&(BigInt::one() << 128));
             (
                 vec![
-                    CoreValue::Uint128(limb0.to_u128().unwrap()),
-                    CoreValue::Uint128(limb1.to_u128().unwrap()),
+                    CoreValue::Uint128(high.to_u128().unwrap()),
+                    CoreValue::Uint128(low.to_u128().unwrap()),
                     CoreValue::U128MulGuarantee,
                 ],
                 0,
@@ -433,7 +751,10 @@ fn simulate_u128_libfunc(
             // "True" branch (branch 1) is the case a == b.
             (vec![], usize::from(lhs == rhs))
         }
-        Uint128Concrete::ByteReverse(_) => todo!("ByteReverse"),
+        Uint128Concrete::ByteReverse(_) => {
+            take_inputs!(let [CoreValue::Bitwise, CoreValue::Uint128(value)] = inputs);
+            (vec![CoreValue::Bitwise, CoreValue::Uint128(value.swap_bytes())], 0)
+        }
         Uint128Concrete::Bitwise(_) => {
             take_inputs!(let [
                 CoreValue::Bitwise, CoreValue::Uint128(lhs), CoreValue::Uint128(rhs)
@@ -442,8 +763,8 @@ fn simulate_u128_libfunc(
                 vec![
                     CoreValue::Bitwise,
                     CoreValue::Uint128(lhs & rhs),
-                    CoreValue::Uint128(lhs | rhs),
                     CoreValue::Uint128(lhs ^ rhs),
+                    CoreValue::Uint128(lhs | rhs),
                 ],
                 0,
             )
@@ -487,15 +808,43 @@ fn simulate_u8_libfunc(
             (vec![CoreValue::Felt252(Felt252::from(value))], 0)
         }
         Uint8Concrete::FromFelt252(_) => {
-            take_inputs!(let [CoreValue::RangeCheck, CoreValue::Uint8(value)] = inputs);
+            take_inputs!(let [CoreValue::RangeCheck, CoreValue::Felt252(value)] = inputs);
             match value.to_u8() {
                 Some(value) => (vec![CoreValue::RangeCheck, CoreValue::Uint8(value)], 0),
                 None => (vec![CoreValue::RangeCheck], 1),
             }
         }
-        Uint8Concrete::IsZero(_) => unimplemented!(),
-        Uint8Concrete::Divmod(_) => unimplemented!(),
-        Uint8Concrete::Bitwise(_) => unimplemented!(),
+        Uint8Concrete::IsZero(_) => {
+            take_inputs!(let [CoreValue::Uint8(value)] = inputs);
+            if value.is_zero() { (vec![], 0) } else { (vec![CoreValue::Uint8(value)], 1) }
+        }
+        Uint8Concrete::Divmod(_) => {
+            take_inputs!(let [
+                CoreValue::RangeCheck, CoreValue::Uint8(lhs), CoreValue::Uint8(rhs)
+            ] = inputs);
+            (
+                vec![
+                    CoreValue::RangeCheck,
+                    CoreValue::Uint8(lhs / rhs),
+                    CoreValue::Uint8(lhs % rhs),
+                ],
+                0,
+            )
+        }
+        Uint8Concrete::Bitwise(_) => {
+            take_inputs!(let [
+                CoreValue::Bitwise, CoreValue::Uint8(lhs), CoreValue::Uint8(rhs)
+            ] = inputs);
+            (
+                vec![
+                    CoreValue::Bitwise,
+                    CoreValue::Uint8(lhs & rhs),
+                    CoreValue::Uint8(lhs ^ rhs),
+                    CoreValue::Uint8(lhs | rhs),
+                ],
+                0,
+            )
+        }
         Uint8Concrete::WideMul(_) => {
             take_inputs!(let [CoreValue::Uint8(lhs), CoreValue::Uint8(rhs)] = inputs);
             (vec![CoreValue::Uint16(u16::from(lhs) * u16::from(rhs))], 0)
@@ -539,15 +888,43 @@ fn simulate_u16_libfunc(
             (vec![CoreValue::Felt252(Felt252::from(value))], 0)
         }
         Uint16Concrete::FromFelt252(_) => {
-            take_inputs!(let [CoreValue::RangeCheck, CoreValue::Uint16(value)] = inputs);
+            take_inputs!(let [CoreValue::RangeCheck, CoreValue::Felt252(value)] = inputs);
             match value.to_u16() {
                 Some(value) => (vec![CoreValue::RangeCheck, CoreValue::Uint16(value)], 0),
                 None => (vec![CoreValue::RangeCheck], 1),
             }
         }
-        Uint16Concrete::IsZero(_) => unimplemented!(),
-        Uint16Concrete::Divmod(_) => unimplemented!(),
-        Uint16Concrete::Bitwise(_) => unimplemented!(),
+        Uint16Concrete::IsZero(_) => {
+            take_inputs!(let [CoreValue::Uint16(value)] = inputs);
+            if value.is_zero() { (vec![], 0) } else { (vec![CoreValue::Uint16(value)], 1) }
+        }
+        Uint16Concrete::Divmod(_) => {
+            take_inputs!(let [
+                CoreValue::RangeCheck, CoreValue::Uint16(lhs), CoreValue::Uint16(rhs)
+            ] = inputs);
+            (
+                vec![
+                    CoreValue::RangeCheck,
+                    CoreValue::Uint16(lhs / rhs),
+                    CoreValue::Uint16(lhs % rhs),
+                ],
+                0,
+            )
+        }
+        Uint16Concrete::Bitwise(_) => {
+            take_inputs!(let [
+                CoreValue::Bitwise, CoreValue::Uint16(lhs), CoreValue::Uint16(rhs)
+            ] = inputs);
+            (
+                vec![
+                    CoreValue::Bitwise,
+                    CoreValue::Uint16(lhs & rhs),
+                    CoreValue::Uint16(lhs ^ rhs),
+                    CoreValue::Uint16(lhs | rhs),
+                ],
+                0,
+            )
+        }
         Uint16Concrete::WideMul(_) => {
             take_inputs!(let [CoreValue::Uint16(lhs), CoreValue::Uint16(rhs)] = inputs);
             (vec![CoreValue::Uint32(u32::from(lhs) * u32::from(rhs))], 0)
@@ -597,12 +974,40 @@ fn simulate_u32_libfunc(
                 None => (vec![CoreValue::RangeCheck], 1),
             }
         }
-        Uint32Concrete::IsZero(_) => unimplemented!(),
-        Uint32Concrete::Divmod(_) => unimplemented!(),
-        Uint32Concrete::Bitwise(_) => unimplemented!(),
-        Uint32Concrete::WideMul(_) => {
-            take_inputs!(let [CoreValue::Uint32(lhs), CoreValue::Uint32(rhs)] = inputs);
-            (vec![CoreValue::Uint64(u64::from(lhs) * u64::from(rhs))], 0)
+        Uint32Concrete::IsZero(_) => {
+            take_inputs!(let [CoreValue::Uint32(value)] = inputs);
+            if value.is_zero() { (vec![], 0) } else { (vec![CoreValue::Uint32(value)], 1) }
+        }
+        Uint32Concrete::Divmod(_) => {
+            take_inputs!(let [
+                CoreValue::RangeCheck, CoreValue::Uint32(lhs), CoreValue::Uint32(rhs)
+            ] = inputs);
+            (
+                vec![
+                    CoreValue::RangeCheck,
+                    CoreValue::Uint32(lhs / rhs),
+                    CoreValue::Uint32(lhs % rhs),
+                ],
+                0,
+            )
+        }
+        Uint32Concrete::Bitwise(_) => {
+            take_inputs!(let [
+                CoreValue::Bitwise, CoreValue::Uint32(lhs), CoreValue::Uint32(rhs)
+            ] = inputs);
+            (
+                vec![
+                    CoreValue::Bitwise,
+                    CoreValue::Uint32(lhs & rhs),
+                    CoreValue::Uint32(lhs ^ rhs),
+                    CoreValue::Uint32(lhs | rhs),
+                ],
+                0,
+            )
+        }
+        Uint32Concrete::WideMul(_) => {
+            take_inputs!(let [CoreValue::Uint32(lhs), CoreValue::Uint32(rhs)] = inputs);
+            (vec![CoreValue::Uint64(u64::from(lhs) * u64::from(rhs))], 0)
         }
     })
 }
@@ -637,7 +1042,7 @@ fn simulate_u64_libfunc(
             (vec![], usize::from(lhs == rhs))
         }
         Uint64Concrete::ToFelt252(_) => {
-            take_inputs!(let [CoreValue::RangeCheck, CoreValue::Uint64(value)] = inputs);
+            take_inputs!(let [CoreValue::Uint64(value)] = inputs);
             (vec![CoreValue::Felt252(Felt252::from(value))], 0)
         }
         Uint64Concrete::FromFelt252(_) => {
@@ -647,9 +1052,37 @@ fn simulate_u64_libfunc(
                 None => (vec![CoreValue::RangeCheck], 1),
             }
         }
-        Uint64Concrete::IsZero(_) => unimplemented!(),
-        Uint64Concrete::Divmod(_) => unimplemented!(),
-        Uint64Concrete::Bitwise(_) => unimplemented!(),
+        Uint64Concrete::IsZero(_) => {
+            take_inputs!(let [CoreValue::Uint64(value)] = inputs);
+            if value.is_zero() { (vec![], 0) } else { (vec![CoreValue::Uint64(value)], 1) }
+        }
+        Uint64Concrete::Divmod(_) => {
+            take_inputs!(let [
+                CoreValue::RangeCheck, CoreValue::Uint64(lhs), CoreValue::Uint64(rhs)
+            ] = inputs);
+            (
+                vec![
+                    CoreValue::RangeCheck,
+                    CoreValue::Uint64(lhs / rhs),
+                    CoreValue::Uint64(lhs % rhs),
+                ],
+                0,
+            )
+        }
+        Uint64Concrete::Bitwise(_) => {
+            take_inputs!(let [
+                CoreValue::Bitwise, CoreValue::Uint64(lhs), CoreValue::Uint64(rhs)
+            ] = inputs);
+            (
+                vec![
+                    CoreValue::Bitwise,
+                    CoreValue::Uint64(lhs & rhs),
+                    CoreValue::Uint64(lhs ^ rhs),
+                    CoreValue::Uint64(lhs | rhs),
+                ],
+                0,
+            )
+        }
         Uint64Concrete::WideMul(_) => {
             take_inputs!(let [CoreValue::Uint64(lhs), CoreValue::Uint64(rhs)] = inputs);
             (vec![CoreValue::Uint128(u128::from(lhs) * u128::from(rhs))], 0)
@@ -713,6 +1146,950 @@ fn simulate_felt252_libfunc(
     })
 }
 
+/// Simulate u256 library functions.
+fn simulate_u256_libfunc(
+    libfunc: &Uint256Concrete,
+    inputs: Vec<CoreValue>,
+) -> Result<(Vec<CoreValue>, usize), LibfuncSimulationError> {
+    Ok(match libfunc {
+        Uint256Concrete::IsZero(_) => {
+            let [value] = take_inputs(inputs)?;
+            if u128_limbs_value(&value)?.is_zero() { (vec![], 0) } else { (vec![value], 1) }
+        }
+        Uint256Concrete::Divmod(_) => {
+            take_inputs!(let [CoreValue::RangeCheck, lhs, rhs] = inputs);
+            let (lhs, rhs) = (u128_limbs_value(&lhs)?, u128_limbs_value(&rhs)?);
+            if rhs.is_zero() {
+                return Err(LibfuncSimulationError::WrongArgType);
+            }
+            let (quotient, remainder) = lhs.div_rem(&rhs);
+            (
+                vec![
+                    CoreValue::RangeCheck,
+                    u128_limbs(&quotient, 2),
+                    u128_limbs(&remainder, 2),
+                    CoreValue::U128MulGuarantee,
+                ],
+                0,
+            )
+        }
+        Uint256Concrete::SquareRoot(_) => {
+            take_inputs!(let [CoreValue::RangeCheck, value] = inputs);
+            let root = u128_limbs_value(&value)?.sqrt();
+            (vec![CoreValue::RangeCheck, CoreValue::Uint128(root.to_u128().unwrap())], 0)
+        }
+        Uint256Concrete::InvModN(_) => {
+            take_inputs!(let [CoreValue::RangeCheck, value, modulus] = inputs);
+            let (value, modulus) = (u128_limbs_value(&value)?, u128_limbs_value(&modulus)?);
+            let gcd = value.extended_gcd(&modulus);
+            if !modulus.is_one() && gcd.gcd.is_one() {
+                (
+                    chain!(
+                        [CoreValue::RangeCheck, u128_limbs(&gcd.x.mod_floor(&modulus), 2)],
+                        repeat_n(CoreValue::U128MulGuarantee, 8)
+                    )
+                    .collect(),
+                    0,
+                )
+            } else {
+                (
+                    vec![
+                        CoreValue::RangeCheck,
+                        CoreValue::U128MulGuarantee,
+                        CoreValue::U128MulGuarantee,
+                    ],
+                    1,
+                )
+            }
+        }
+    })
+}
+
+/// Simulate u512 library functions.
+fn simulate_u512_libfunc(
+    libfunc: &Uint512Concrete,
+    inputs: Vec<CoreValue>,
+) -> Result<(Vec<CoreValue>, usize), LibfuncSimulationError> {
+    Ok(match libfunc {
+        Uint512Concrete::DivModU256(_) => {
+            take_inputs!(let [CoreValue::RangeCheck, lhs, rhs] = inputs);
+            let (lhs, rhs) = (u128_limbs_value(&lhs)?, u128_limbs_value(&rhs)?);
+            if rhs.is_zero() {
+                return Err(LibfuncSimulationError::WrongArgType);
+            }
+            let (quotient, remainder) = lhs.div_rem(&rhs);
+            (
+                chain!(
+                    [CoreValue::RangeCheck, u128_limbs(&quotient, 4), u128_limbs(&remainder, 2)],
+                    repeat_n(CoreValue::U128MulGuarantee, 5)
+                )
+                .collect(),
+                0,
+            )
+        }
+    })
+}
+
+/// Simulate signed integer library functions, for the signed types of less than 128 bits.
+fn simulate_sint_libfunc<TSintTraits: SintTraits + IntMulTraits + IsZeroTraits>(
+    libfunc: &SintConcrete<TSintTraits>,
+    inputs: Vec<CoreValue>,
+) -> Result<(Vec<CoreValue>, usize), LibfuncSimulationError> {
+    let ty = <TSintTraits as IntTraits>::GENERIC_TYPE_ID;
+    let bits = std::mem::size_of::<TSintTraits::IntType>() * 8;
+    Ok(match libfunc {
+        SintConcrete::Const(IntConstConcreteLibfunc { c, .. }) => {
+            let [] = take_inputs(inputs)?;
+            (vec![typed_int(&ty, (*c).into())?], 0)
+        }
+        SintConcrete::Equal(_) => simulate_int_equal(inputs)?,
+        SintConcrete::ToFelt252(_) => simulate_int_to_felt252(inputs)?,
+        SintConcrete::FromFelt252(_) => simulate_int_from_felt252(&ty, inputs)?,
+        SintConcrete::Operation(libfunc) => {
+            simulate_sint_operation(&ty, bits, &libfunc.operator, inputs)?
+        }
+        SintConcrete::Diff(_) => simulate_sint_diff(&TSintTraits::UNSIGNED_INT_TYPE, bits, inputs)?,
+        SintConcrete::IsZero(_) => simulate_int_is_zero(inputs)?,
+        SintConcrete::WideMul(_) => {
+            let [lhs, rhs] = take_inputs(inputs)?;
+            let value = int_input(&lhs)? * int_input(&rhs)?;
+            (vec![typed_int(&TSintTraits::WIDE_MUL_RES_TYPE_ID, value)?], 0)
+        }
+    })
+}
+
+/// Simulate i128 library functions.
+fn simulate_i128_libfunc(
+    libfunc: &Sint128Concrete,
+    inputs: Vec<CoreValue>,
+) -> Result<(Vec<CoreValue>, usize), LibfuncSimulationError> {
+    let ty = Sint128Type::id();
+    Ok(match libfunc {
+        Sint128Concrete::Const(IntConstConcreteLibfunc { c, .. }) => {
+            let [] = take_inputs(inputs)?;
+            (vec![CoreValue::Sint128(*c)], 0)
+        }
+        Sint128Concrete::Equal(_) => simulate_int_equal(inputs)?,
+        Sint128Concrete::ToFelt252(_) => simulate_int_to_felt252(inputs)?,
+        Sint128Concrete::FromFelt252(_) => simulate_int_from_felt252(&ty, inputs)?,
+        Sint128Concrete::Operation(libfunc) => {
+            simulate_sint_operation(&ty, 128, &libfunc.operator, inputs)?
+        }
+        Sint128Concrete::Diff(_) => simulate_sint_diff(&Uint128Type::id(), 128, inputs)?,
+        Sint128Concrete::IsZero(_) => simulate_int_is_zero(inputs)?,
+    })
+}
+
+/// Simulates an equality check of two integers.
+fn simulate_int_equal(
+    inputs: Vec<CoreValue>,
+) -> Result<(Vec<CoreValue>, usize), LibfuncSimulationError> {
+    let [lhs, rhs] = take_inputs(inputs)?;
+    // "False" branch (branch 0) is the case a != b.
+    // "True" branch (branch 1) is the case a == b.
+    Ok((vec![], usize::from(int_input(&lhs)? == int_input(&rhs)?)))
+}
+
+/// Simulates the conversion of an integer to a felt252.
+fn simulate_int_to_felt252(
+    inputs: Vec<CoreValue>,
+) -> Result<(Vec<CoreValue>, usize), LibfuncSimulationError> {
+    let [value] = take_inputs(inputs)?;
+    Ok((vec![CoreValue::Felt252(Felt252::from(&int_input(&value)?))], 0))
+}
+
+/// Simulates the conversion of a felt252 to an integer of the given type, where the felt252 is
+/// interpreted as a signed value.
+fn simulate_int_from_felt252(
+    ty: &GenericTypeId,
+    inputs: Vec<CoreValue>,
+) -> Result<(Vec<CoreValue>, usize), LibfuncSimulationError> {
+    take_inputs!(let [CoreValue::RangeCheck, CoreValue::Felt252(value)] = inputs);
+    Ok(match int_of_generic_type(ty, felt252_to_signed(&value)) {
+        Some(value) => (vec![CoreValue::RangeCheck, value], 0),
+        None => (vec![CoreValue::RangeCheck], 1),
+    })
+}
+
+/// Simulates checking whether an integer is zero.
+fn simulate_int_is_zero(
+    inputs: Vec<CoreValue>,
+) -> Result<(Vec<CoreValue>, usize), LibfuncSimulationError> {
+    let [value] = take_inputs(inputs)?;
+    Ok(if int_input(&value)?.is_zero() { (vec![], 0) } else { (vec![value], 1) })
+}
+
+/// Simulates an overflowing operation of signed integers of the given type with `bits` bits.
+/// Branch 0 is the in-range case, branch 1 is the underflow case, and branch 2 is the overflow
+/// case, where the wrapped result is returned.
+fn simulate_sint_operation(
+    ty: &GenericTypeId,
+    bits: usize,
+    operator: &IntOperator,
+    inputs: Vec<CoreValue>,
+) -> Result<(Vec<CoreValue>, usize), LibfuncSimulationError> {
+    take_inputs!(let [CoreValue::RangeCheck, lhs, rhs] = inputs);
+    let (lhs, rhs) = (int_input(&lhs)?, int_input(&rhs)?);
+    let value = match operator {
+        IntOperator::OverflowingAdd => lhs + rhs,
+        IntOperator::OverflowingSub => lhs - rhs,
+    };
+    let modulus = BigInt::one() << bits;
+    let half = BigInt::one() << (bits - 1);
+    let (value, branch) = if value < -&half {
+        (value + modulus, 1)
+    } else if value >= half {
+        (value - modulus, 2)
+    } else {
+        (value, 0)
+    };
+    Ok((vec![CoreValue::RangeCheck, typed_int(ty, value)?], branch))
+}
+
+/// Simulates the difference of signed integers with `bits` bits, returned as the matching unsigned
+/// type - wrapped in case of a negative difference.
+fn simulate_sint_diff(
+    unsigned_ty: &GenericTypeId,
+    bits: usize,
+    inputs: Vec<CoreValue>,
+) -> Result<(Vec<CoreValue>, usize), LibfuncSimulationError> {
+    take_inputs!(let [CoreValue::RangeCheck, lhs, rhs] = inputs);
+    let value = int_input(&lhs)? - int_input(&rhs)?;
+    Ok(if value.is_negative() {
+        (vec![CoreValue::RangeCheck, typed_int(unsigned_ty, value + (BigInt::one() << bits))?], 1)
+    } else {
+        (vec![CoreValue::RangeCheck, typed_int(unsigned_ty, value)?], 0)
+    })
+}
+
+/// Simulate bounded int library functions.
+fn simulate_bounded_int_libfunc(
+    libfunc: &BoundedIntConcreteLibfunc,
+    inputs: Vec<CoreValue>,
+) -> Result<(Vec<CoreValue>, usize), LibfuncSimulationError> {
+    Ok(match libfunc {
+        BoundedIntConcreteLibfunc::Add(_) => {
+            let [lhs, rhs] = take_inputs(inputs)?;
+            (vec![CoreValue::BoundedInt(int_input(&lhs)? + int_input(&rhs)?)], 0)
+        }
+        BoundedIntConcreteLibfunc::Sub(_) => {
+            let [lhs, rhs] = take_inputs(inputs)?;
+            (vec![CoreValue::BoundedInt(int_input(&lhs)? - int_input(&rhs)?)], 0)
+        }
+        BoundedIntConcreteLibfunc::Mul(_) => {
+            let [lhs, rhs] = take_inputs(inputs)?;
+            (vec![CoreValue::BoundedInt(int_input(&lhs)? * int_input(&rhs)?)], 0)
+        }
+        BoundedIntConcreteLibfunc::DivRem(_) => {
+            take_inputs!(let [CoreValue::RangeCheck, lhs, rhs] = inputs);
+            let (lhs, rhs) = (int_input(&lhs)?, int_input(&rhs)?);
+            if rhs.is_zero() {
+                return Err(LibfuncSimulationError::WrongArgType);
+            }
+            let (quotient, remainder) = lhs.div_rem(&rhs);
+            (
+                vec![
+                    CoreValue::RangeCheck,
+                    CoreValue::BoundedInt(quotient),
+                    CoreValue::BoundedInt(remainder),
+                ],
+                0,
+            )
+        }
+        BoundedIntConcreteLibfunc::Constrain(BoundedIntConstrainConcreteLibfunc {
+            boundary,
+            ..
+        }) => {
+            take_inputs!(let [CoreValue::RangeCheck, value] = inputs);
+            let value = int_input(&value)?;
+            let branch = usize::from(&value >= boundary);
+            (vec![CoreValue::RangeCheck, CoreValue::BoundedInt(value)], branch)
+        }
+        BoundedIntConcreteLibfunc::IsZero(_) => simulate_int_is_zero(inputs)?,
+        BoundedIntConcreteLibfunc::WrapNonZero(_) => {
+            let [value] = take_inputs(inputs)?;
+            (vec![value], 0)
+        }
+    })
+}
+
+/// Simulate int range library functions.
+fn simulate_int_range_libfunc(
+    libfunc: &IntRangeConcreteLibfunc,
+    inputs: Vec<CoreValue>,
+) -> Result<(Vec<CoreValue>, usize), LibfuncSimulationError> {
+    Ok(match libfunc {
+        IntRangeConcreteLibfunc::TryNew(_) => {
+            take_inputs!(let [CoreValue::RangeCheck, start, end] = inputs);
+            if int_input(&start)? <= int_input(&end)? {
+                let range = CoreValue::IntRange { start: Box::new(start), end: Box::new(end) };
+                (vec![CoreValue::RangeCheck, range], 0)
+            } else {
+                // An invalid range is replaced with the empty range `[end, end)`.
+                let range =
+                    CoreValue::IntRange { start: Box::new(end.clone()), end: Box::new(end) };
+                (vec![CoreValue::RangeCheck, range], 1)
+            }
+        }
+        IntRangeConcreteLibfunc::PopFront(_) => {
+            take_inputs!(let [CoreValue::IntRange { start, end }] = inputs);
+            let start_value = int_input(&start)?;
+            if start_value >= int_input(&end)? {
+                (vec![], 0)
+            } else {
+                let next = int_like(&start, start_value + 1)?;
+                (vec![CoreValue::IntRange { start: Box::new(next), end }, *start], 1)
+            }
+        }
+    })
+}
+
+/// Simulate cast library functions.
+fn simulate_cast_libfunc<'a>(
+    libfunc: &CastConcreteLibfunc,
+    inputs: Vec<CoreValue>,
+    get_type: &dyn Fn(&ConcreteTypeId) -> Option<&'a CoreTypeConcrete>,
+) -> Result<(Vec<CoreValue>, usize), LibfuncSimulationError> {
+    Ok(match libfunc {
+        CastConcreteLibfunc::Downcast(DowncastConcreteLibfunc { to_ty, to_range, .. }) => {
+            take_inputs!(let [CoreValue::RangeCheck, value] = inputs);
+            let value = int_input(&value)?;
+            if to_range.lower <= value && value < to_range.upper {
+                (vec![CoreValue::RangeCheck, int_of_type(get_type, to_ty, value)?], 0)
+            } else {
+                (vec![CoreValue::RangeCheck], 1)
+            }
+        }
+        CastConcreteLibfunc::Upcast(libfunc) => {
+            let [value] = take_inputs(inputs)?;
+            let to_ty = &libfunc.branch_signatures()[0].vars[0].ty;
+            (vec![int_of_type(get_type, to_ty, int_input(&value)?)?], 0)
+        }
+    })
+}
+
+/// Simulate bytes31 library functions.
+fn simulate_bytes31_libfunc(
+    libfunc: &Bytes31ConcreteLibfunc,
+    inputs: Vec<CoreValue>,
+) -> Result<(Vec<CoreValue>, usize), LibfuncSimulationError> {
+    Ok(match libfunc {
+        Bytes31ConcreteLibfunc::Const(SignatureAndConstConcreteLibfunc { c, .. }) => {
+            let [] = take_inputs(inputs)?;
+            (vec![CoreValue::Felt252(c.into())], 0)
+        }
+        Bytes31ConcreteLibfunc::ToFelt252(_) => {
+            take_inputs!(let [CoreValue::Felt252(value)] = inputs);
+            (vec![CoreValue::Felt252(value)], 0)
+        }
+        Bytes31ConcreteLibfunc::TryFromFelt252(_) => {
+            simulate_felt252_bounded_try_from(inputs, 248)?
+        }
+    })
+}
+
+/// Simulates the conversion of a felt252 to a felt252 based type, with values in `[0, 2^bits)`.
+fn simulate_felt252_bounded_try_from(
+    inputs: Vec<CoreValue>,
+    bits: usize,
+) -> Result<(Vec<CoreValue>, usize), LibfuncSimulationError> {
+    take_inputs!(let [CoreValue::RangeCheck, CoreValue::Felt252(value)] = inputs);
+    Ok(if value.to_bigint() < BigInt::one() << bits {
+        (vec![CoreValue::RangeCheck, CoreValue::Felt252(value)], 0)
+    } else {
+        (vec![CoreValue::RangeCheck], 1)
+    })
+}
+
+/// Simulate Starknet library functions.
+fn simulate_starknet_libfunc(
+    libfunc: &StarkNetConcreteLibfunc,
+    inputs: Vec<CoreValue>,
+    syscall_handler: &mut dyn SyscallHandler,
+) -> Result<(Vec<CoreValue>, usize), LibfuncSimulationError> {
+    Ok(match libfunc {
+        StarkNetConcreteLibfunc::ClassHashConst(SignatureAndConstConcreteLibfunc { c, .. })
+        | StarkNetConcreteLibfunc::ContractAddressConst(SignatureAndConstConcreteLibfunc {
+            c,
+            ..
+        })
+        | StarkNetConcreteLibfunc::StorageBaseAddressConst(SignatureAndConstConcreteLibfunc {
+            c,
+            ..
+        }) => {
+            let [] = take_inputs(inputs)?;
+            (vec![CoreValue::Felt252(c.into())], 0)
+        }
+        StarkNetConcreteLibfunc::ClassHashTryFromFelt252(_)
+        | StarkNetConcreteLibfunc::ContractAddressTryFromFelt252(_)
+        | StarkNetConcreteLibfunc::StorageAddressTryFromFelt252(_) => {
+            simulate_felt252_bounded_try_from(inputs, 251)?
+        }
+        StarkNetConcreteLibfunc::ClassHashToFelt252(_)
+        | StarkNetConcreteLibfunc::ContractAddressToFelt252(_)
+        | StarkNetConcreteLibfunc::StorageAddressToFelt252(_)
+        | StarkNetConcreteLibfunc::StorageAddressFromBase(_) => {
+            take_inputs!(let [CoreValue::Felt252(value)] = inputs);
+            (vec![CoreValue::Felt252(value)], 0)
+        }
+        StarkNetConcreteLibfunc::StorageBaseAddressFromFelt252(_) => {
+            take_inputs!(let [CoreValue::RangeCheck, CoreValue::Felt252(value)] = inputs);
+            let bound = (BigInt::one() << 251) - 256;
+            let value = value.to_bigint().mod_floor(&bound);
+            (vec![CoreValue::RangeCheck, CoreValue::Felt252(Felt252::from(&value))], 0)
+        }
+        StarkNetConcreteLibfunc::StorageAddressFromBaseAndOffset(_) => {
+            take_inputs!(let [CoreValue::Felt252(base), CoreValue::Uint8(offset)] = inputs);
+            (vec![CoreValue::Felt252(base + Felt252::from(offset))], 0)
+        }
+        StarkNetConcreteLibfunc::Sha256StateHandleInit(_)
+        | StarkNetConcreteLibfunc::Sha256StateHandleDigest(_) => {
+            let [value] = take_inputs(inputs)?;
+            (vec![value], 0)
+        }
+        StarkNetConcreteLibfunc::Testing(TestingConcreteLibfunc::Cheatcode(
+            CheatcodeConcreteLibfunc { selector, .. },
+        )) => {
+            let [span] = take_inputs(inputs)?;
+            let outputs = syscall_handler.cheatcode(selector, span_felts(span)?)?;
+            (vec![felts_span(outputs)], 0)
+        }
+        StarkNetConcreteLibfunc::CallContract(_) => {
+            simulate_syscall("CallContract", inputs, syscall_handler)?
+        }
+        StarkNetConcreteLibfunc::StorageRead(_) => {
+            simulate_syscall("StorageRead", inputs, syscall_handler)?
+        }
+        StarkNetConcreteLibfunc::StorageWrite(_) => {
+            simulate_syscall("StorageWrite", inputs, syscall_handler)?
+        }
+        StarkNetConcreteLibfunc::EmitEvent(_) => {
+            simulate_syscall("EmitEvent", inputs, syscall_handler)?
+        }
+        StarkNetConcreteLibfunc::GetBlockHash(_) => {
+            simulate_syscall("GetBlockHash", inputs, syscall_handler)?
+        }
+        StarkNetConcreteLibfunc::GetExecutionInfo(_) => {
+            simulate_syscall("GetExecutionInfo", inputs, syscall_handler)?
+        }
+        StarkNetConcreteLibfunc::GetExecutionInfoV2(_) => {
+            simulate_syscall("GetExecutionInfoV2", inputs, syscall_handler)?
+        }
+        StarkNetConcreteLibfunc::Deploy(_) => simulate_syscall("Deploy", inputs, syscall_handler)?,
+        StarkNetConcreteLibfunc::Keccak(_) => simulate_syscall("Keccak", inputs, syscall_handler)?,
+        StarkNetConcreteLibfunc::Sha256ProcessBlock(_) => {
+            simulate_syscall("Sha256ProcessBlock", inputs, syscall_handler)?
+        }
+        StarkNetConcreteLibfunc::LibraryCall(_) => {
+            simulate_syscall("LibraryCall", inputs, syscall_handler)?
+        }
+        StarkNetConcreteLibfunc::ReplaceClass(_) => {
+            simulate_syscall("ReplaceClass", inputs, syscall_handler)?
+        }
+        StarkNetConcreteLibfunc::GetClassHashAt(_) => {
+            simulate_syscall("GetClassHashAt", inputs, syscall_handler)?
+        }
+        StarkNetConcreteLibfunc::SendMessageToL1(_) => {
+            simulate_syscall("SendMessageToL1", inputs, syscall_handler)?
+        }
+        StarkNetConcreteLibfunc::Secp256(libfunc) => {
+            let selector = match libfunc {
+                Secp256ConcreteLibfunc::K1(libfunc) => match libfunc {
+                    Secp256OpConcreteLibfunc::New(_) => "Secp256k1New",
+                    Secp256OpConcreteLibfunc::Add(_) => "Secp256k1Add",
+                    Secp256OpConcreteLibfunc::Mul(_) => "Secp256k1Mul",
+                    Secp256OpConcreteLibfunc::GetPointFromX(_) => "Secp256k1GetPointFromX",
+                    Secp256OpConcreteLibfunc::GetXy(_) => "Secp256k1GetXy",
+                },
+                Secp256ConcreteLibfunc::R1(libfunc) => match libfunc {
+                    Secp256OpConcreteLibfunc::New(_) => "Secp256r1New",
+                    Secp256OpConcreteLibfunc::Add(_) => "Secp256r1Add",
+                    Secp256OpConcreteLibfunc::Mul(_) => "Secp256r1Mul",
+                    Secp256OpConcreteLibfunc::GetPointFromX(_) => "Secp256r1GetPointFromX",
+                    Secp256OpConcreteLibfunc::GetXy(_) => "Secp256r1GetXy",
+                },
+            };
+            simulate_syscall(selector, inputs, syscall_handler)?
+        }
+    })
+}
+
+/// Simulates a syscall, given the gas builtin and the system followed by the syscall's inputs.
+/// The success branch returns the syscall's outputs, and the failure branch returns the revert
+/// reason.
+fn simulate_syscall(
+    selector: &str,
+    inputs: Vec<CoreValue>,
+    syscall_handler: &mut dyn SyscallHandler,
+) -> Result<(Vec<CoreValue>, usize), LibfuncSimulationError> {
+    let mut inputs = inputs;
+    if inputs.len() < 2 {
+        return Err(LibfuncSimulationError::WrongNumberOfArgs);
+    }
+    let syscall_inputs = inputs.split_off(2);
+    take_inputs!(let [CoreValue::GasBuiltin(mut gas_counter), CoreValue::System] = inputs);
+    Ok(match syscall_handler.syscall(selector, syscall_inputs, &mut gas_counter)? {
+        Ok(outputs) => {
+            (chain!([CoreValue::GasBuiltin(gas_counter), CoreValue::System], outputs).collect(), 0)
+        }
+        Err(revert_reason) => (
+            vec![
+                CoreValue::GasBuiltin(gas_counter),
+                CoreValue::System,
+                CoreValue::Array(revert_reason.into_iter().map(CoreValue::Felt252).collect()),
+            ],
+            1,
+        ),
+    })
+}
+
+/// Returns the felts of a span of felt252s.
+fn span_felts(span: CoreValue) -> Result<Vec<Felt252>, LibfuncSimulationError> {
+    let CoreValue::Struct(members) = span else {
+        return Err(LibfuncSimulationError::WrongArgType);
+    };
+    take_inputs!(let [CoreValue::Array(felts)] = members);
+    felts
+        .into_iter()
+        .map(|felt| match felt {
+            CoreValue::Felt252(felt) => Ok(felt),
+            _ => Err(LibfuncSimulationError::WrongArgType),
+        })
+        .collect()
+}
+
+/// Returns a span of felt252s with the given felts.
+fn felts_span(felts: Vec<Felt252>) -> CoreValue {
+    CoreValue::Struct(vec![CoreValue::Array(felts.into_iter().map(CoreValue::Felt252).collect())])
+}
+
+/// Simulate circuit library functions.
+fn simulate_circuit_libfunc<'a>(
+    libfunc: &CircuitConcreteLibfunc,
+    inputs: Vec<CoreValue>,
+    get_type: &dyn Fn(&ConcreteTypeId) -> Option<&'a CoreTypeConcrete>,
+) -> Result<(Vec<CoreValue>, usize), LibfuncSimulationError> {
+    Ok(match libfunc {
+        CircuitConcreteLibfunc::InitCircuitData(_) => {
+            take_inputs!(let [CoreValue::RangeCheck96] = inputs);
+            (vec![CoreValue::RangeCheck96, CoreValue::CircuitInputAccumulator(vec![])], 0)
+        }
+        CircuitConcreteLibfunc::AddInput(SignatureAndTypeConcreteLibfunc { ty, .. }) => {
+            take_inputs!(let [CoreValue::CircuitInputAccumulator(mut values), value] = inputs);
+            values.push(u96_limbs_value(&value)?);
+            if values.len() == circuit_info(get_type, ty)?.n_inputs {
+                (vec![CoreValue::CircuitData(values)], 0)
+            } else {
+                (vec![CoreValue::CircuitInputAccumulator(values)], 1)
+            }
+        }
+        CircuitConcreteLibfunc::GetDescriptor(_) => {
+            let [] = take_inputs(inputs)?;
+            (vec![CoreValue::CircuitDescriptor], 0)
+        }
+        CircuitConcreteLibfunc::Eval(SignatureAndTypeConcreteLibfunc { ty, .. }) => {
+            take_inputs!(let [
+                CoreValue::AddMod,
+                CoreValue::MulMod,
+                CoreValue::CircuitDescriptor,
+                CoreValue::CircuitData(circuit_inputs),
+                CoreValue::CircuitModulus(modulus),
+                _,
+                _,
+            ] = inputs);
+            match evaluate_circuit(circuit_info(get_type, ty)?, &circuit_inputs, &modulus) {
+                Ok(values) => (
+                    vec![
+                        CoreValue::AddMod,
+                        CoreValue::MulMod,
+                        CoreValue::CircuitOutputs { values, modulus },
+                    ],
+                    0,
+                ),
+                Err((values, nullifier)) => (
+                    vec![
+                        CoreValue::AddMod,
+                        CoreValue::MulMod,
+                        CoreValue::CircuitOutputs { values, modulus: modulus.clone() },
+                        CoreValue::CircuitFailureGuarantee { nullifier, modulus },
+                    ],
+                    1,
+                ),
+            }
+        }
+        CircuitConcreteLibfunc::GetOutput(ConcreteGetOutputLibFunc {
+            circuit_ty,
+            output_ty,
+            ..
+        }) => {
+            take_inputs!(let [CoreValue::CircuitOutputs { values, modulus }] = inputs);
+            let offset = *circuit_info(get_type, circuit_ty)?
+                .values
+                .get(output_ty)
+                .ok_or(LibfuncSimulationError::WrongArgType)?;
+            let value = values.get(offset).ok_or(LibfuncSimulationError::WrongArgType)?;
+            let limbs = split_limbs(value, 96, 4);
+            (
+                vec![
+                    CoreValue::Struct(limbs.iter().cloned().map(CoreValue::BoundedInt).collect()),
+                    CoreValue::U96LimbsLessThanGuarantee {
+                        value: limbs,
+                        bound: split_limbs(&modulus, 96, 4),
+                    },
+                ],
+                0,
+            )
+        }
+        CircuitConcreteLibfunc::TryIntoCircuitModulus(_) => {
+            let [value] = take_inputs(inputs)?;
+            let modulus = u96_limbs_value(&value)?;
+            if modulus >= BigInt::from(2) {
+                (vec![CoreValue::CircuitModulus(modulus)], 0)
+            } else {
+                (vec![], 1)
+            }
+        }
+        CircuitConcreteLibfunc::FailureGuaranteeVerify(_) => {
+            take_inputs!(let [
+                CoreValue::RangeCheck96,
+                CoreValue::MulMod,
+                CoreValue::CircuitFailureGuarantee { nullifier, modulus },
+                _,
+                _,
+            ] = inputs);
+            (
+                vec![
+                    CoreValue::RangeCheck96,
+                    CoreValue::MulMod,
+                    CoreValue::U96LimbsLessThanGuarantee {
+                        value: split_limbs(&nullifier, 96, 4),
+                        bound: split_limbs(&modulus, 96, 4),
+                    },
+                ],
+                0,
+            )
+        }
+        CircuitConcreteLibfunc::IntoU96Guarantee(_) => {
+            let [value] = take_inputs(inputs)?;
+            (vec![CoreValue::U96Guarantee(int_input(&value)?)], 0)
+        }
+        CircuitConcreteLibfunc::U96GuaranteeVerify(_) => {
+            take_inputs!(let [CoreValue::RangeCheck96, CoreValue::U96Guarantee(_)] = inputs);
+            (vec![CoreValue::RangeCheck96], 0)
+        }
+        CircuitConcreteLibfunc::U96LimbsLessThanGuaranteeVerify(_) => {
+            take_inputs!(
+                let [CoreValue::U96LimbsLessThanGuarantee { mut value, mut bound }] = inputs
+            );
+            let (Some(value_top), Some(bound_top)) = (value.pop(), bound.pop()) else {
+                return Err(LibfuncSimulationError::WrongArgType);
+            };
+            if value_top == bound_top {
+                (vec![CoreValue::U96LimbsLessThanGuarantee { value, bound }], 0)
+            } else {
+                (vec![CoreValue::U96Guarantee(bound_top - value_top - 1)], 1)
+            }
+        }
+        CircuitConcreteLibfunc::U96SingleLimbLessThanGuaranteeVerify(_) => {
+            take_inputs!(let [CoreValue::U96LimbsLessThanGuarantee { value, bound }] = inputs);
+            let (Some(value), Some(bound)) = (value.first(), bound.first()) else {
+                return Err(LibfuncSimulationError::WrongArgType);
+            };
+            (vec![CoreValue::U96Guarantee(bound - value - 1)], 0)
+        }
+    })
+}
+
+/// Returns the info of the given circuit type.
+fn circuit_info<'a>(
+    get_type: &dyn Fn(&ConcreteTypeId) -> Option<&'a CoreTypeConcrete>,
+    ty: &ConcreteTypeId,
+) -> Result<&'a CircuitInfo, LibfuncSimulationError> {
+    match resolve_type(get_type, ty)? {
+        CoreTypeConcrete::Circuit(CircuitTypeConcrete::Circuit(ConcreteCircuit {
+            circuit_info,
+            ..
+        })) => Ok(circuit_info),
+        _ => Err(LibfuncSimulationError::WrongArgType),
+    }
+}
+
+/// Evaluates a circuit modulo `modulus` given its inputs, and returns the values array of the
+/// circuit - the constant 1, the inputs, and then the values of the gates.
+/// On failure to invert a value, returns the partially computed values (with unknown values as 0)
+/// and a nullifier of the non-invertible value.
+fn evaluate_circuit(
+    info: &CircuitInfo,
+    inputs: &[BigInt],
+    modulus: &BigInt,
+) -> Result<Vec<BigInt>, (Vec<BigInt>, BigInt)> {
+    let mut values: Vec<Option<BigInt>> = vec![None; 1 + info.n_inputs + info.values.len()];
+    values[0] = Some(BigInt::one());
+    for (value, input) in values[1..].iter_mut().zip(inputs) {
+        *value = Some(input.clone());
+    }
+    let known_values = |values: Vec<Option<BigInt>>| -> Vec<BigInt> {
+        values.into_iter().map(Option::unwrap_or_default).collect()
+    };
+    // Evaluating the gates until no more values can be deduced, as sub and inverse gates are
+    // encoded with their output as an operand.
+    loop {
+        let mut progress = false;
+        for GateOffsets { lhs, rhs, output } in &info.add_offsets {
+            match (values[*lhs].clone(), values[*rhs].clone(), values[*output].clone()) {
+                (Some(lhs_value), Some(rhs_value), None) => {
+                    values[*output] = Some((lhs_value + rhs_value).mod_floor(modulus));
+                    progress = true;
+                }
+                (None, Some(rhs_value), Some(output_value)) => {
+                    values[*lhs] = Some((output_value - rhs_value).mod_floor(modulus));
+                    progress = true;
+                }
+                _ => {}
+            }
+        }
+        for GateOffsets { lhs, rhs, output } in &info.mul_offsets {
+            match (values[*lhs].clone(), values[*rhs].clone(), values[*output].clone()) {
+                (Some(lhs_value), Some(rhs_value), None) => {
+                    values[*output] = Some((lhs_value * rhs_value).mod_floor(modulus));
+                    progress = true;
+                }
+                (None, Some(rhs_value), Some(output_value)) => {
+                    let gcd = rhs_value.extended_gcd(modulus);
+                    if !gcd.gcd.is_one() {
+                        return Err((known_values(values), modulus / gcd.gcd));
+                    }
+                    values[*lhs] = Some((output_value * gcd.x).mod_floor(modulus));
+                    progress = true;
+                }
+                _ => {}
+            }
+        }
+        if !progress {
+            return Ok(known_values(values));
+        }
+    }
+}
+
+/// The BETA constant of the STARK curve.
+const BETA: Felt252 = Felt252::from_hex_unchecked(
+    "0x6f21413efbe40de150e596d72f7a8c5609ad26c15c915c1f4cdfcb99cee9e89",
+);
+
+/// Returns the concrete type of the given type id.
+fn resolve_type<'a>(
+    get_type: &dyn Fn(&ConcreteTypeId) -> Option<&'a CoreTypeConcrete>,
+    ty: &ConcreteTypeId,
+) -> Result<&'a CoreTypeConcrete, LibfuncSimulationError> {
+    get_type(ty).ok_or_else(|| LibfuncSimulationError::UnresolvedType(ty.clone()))
+}
+
+/// Returns the number of members of the given tuple type.
+fn tuple_size<'a>(
+    get_type: &dyn Fn(&ConcreteTypeId) -> Option<&'a CoreTypeConcrete>,
+    ty: &ConcreteTypeId,
+) -> Result<usize, LibfuncSimulationError> {
+    match resolve_type(get_type, ty)? {
+        CoreTypeConcrete::Struct(StructConcreteType { members, .. }) => Ok(members.len()),
+        _ => Err(LibfuncSimulationError::WrongArgType),
+    }
+}
+
+/// Returns the value of the given const type.
+fn const_value<'a>(
+    get_type: &dyn Fn(&ConcreteTypeId) -> Option<&'a CoreTypeConcrete>,
+    const_ty: &ConcreteTypeId,
+) -> Result<CoreValue, LibfuncSimulationError> {
+    let CoreTypeConcrete::Const(ConstConcreteType { inner_ty, inner_data, .. }) =
+        resolve_type(get_type, const_ty)?
+    else {
+        return Err(LibfuncSimulationError::WrongArgType);
+    };
+    Ok(match (resolve_type(get_type, inner_ty)?, inner_data.as_slice()) {
+        (CoreTypeConcrete::Struct(_), members) => CoreValue::Struct(
+            members
+                .iter()
+                .map(|member| match member {
+                    GenericArg::Type(member_ty) => const_value(get_type, member_ty),
+                    _ => Err(LibfuncSimulationError::WrongArgType),
+                })
+                .collect::<Result<_, _>>()?,
+        ),
+        (CoreTypeConcrete::Enum(_), [GenericArg::Value(index), GenericArg::Type(variant_ty)]) => {
+            CoreValue::Enum {
+                value: Box::new(const_value(get_type, variant_ty)?),
+                index: index.to_usize().ok_or(LibfuncSimulationError::WrongArgType)?,
+            }
+        }
+        (CoreTypeConcrete::NonZero(_), [GenericArg::Type(inner_const_ty)]) => {
+            const_value(get_type, inner_const_ty)?
+        }
+        (_, [GenericArg::Value(value)]) => int_of_type(get_type, inner_ty, value.clone())?,
+        _ => return Err(LibfuncSimulationError::WrongArgType),
+    })
+}
+
+/// Returns the value of a missing dict entry of the given type.
+fn default_value<'a>(
+    get_type: &dyn Fn(&ConcreteTypeId) -> Option<&'a CoreTypeConcrete>,
+    ty: &ConcreteTypeId,
+) -> Result<CoreValue, LibfuncSimulationError> {
+    match resolve_type(get_type, ty)? {
+        CoreTypeConcrete::Nullable(_) => Ok(CoreValue::Nullable(None)),
+        _ => int_of_type(get_type, ty, BigInt::zero()),
+    }
+}
+
+/// Returns the value of the given integer type (or a non-zero or snapshot of one) holding `value`.
+fn int_of_type<'a>(
+    get_type: &dyn Fn(&ConcreteTypeId) -> Option<&'a CoreTypeConcrete>,
+    ty: &ConcreteTypeId,
+    value: BigInt,
+) -> Result<CoreValue, LibfuncSimulationError> {
+    match resolve_type(get_type, ty)? {
+        CoreTypeConcrete::NonZero(InfoAndTypeConcreteType { ty, .. })
+        | CoreTypeConcrete::Snapshot(InfoAndTypeConcreteType { ty, .. }) => {
+            int_of_type(get_type, ty, value)
+        }
+        concrete => typed_int(&concrete.info().long_id.generic_id, value),
+    }
+}
+
+/// Returns the value of the integer type with the given generic id holding `value`, or an error if
+/// it is out of the type's range.
+fn typed_int(
+    generic_id: &GenericTypeId,
+    value: BigInt,
+) -> Result<CoreValue, LibfuncSimulationError> {
+    int_of_generic_type(generic_id, value).ok_or(LibfuncSimulationError::WrongArgType)
+}
+
+/// Returns the value of the integer type with the given generic id holding `value`, or `None` if
+/// it is out of the type's range.
+fn int_of_generic_type(generic_id: &GenericTypeId, value: BigInt) -> Option<CoreValue> {
+    Some(if *generic_id == Uint8Type::id() {
+        CoreValue::Uint8(value.to_u8()?)
+    } else if *generic_id == Uint16Type::id() {
+        CoreValue::Uint16(value.to_u16()?)
+    } else if *generic_id == Uint32Type::id() {
+        CoreValue::Uint32(value.to_u32()?)
+    } else if *generic_id == Uint64Type::id() {
+        CoreValue::Uint64(value.to_u64()?)
+    } else if *generic_id == Uint128Type::id() {
+        CoreValue::Uint128(value.to_u128()?)
+    } else if *generic_id == Sint8Type::id() {
+        CoreValue::Sint8(value.to_i8()?)
+    } else if *generic_id == Sint16Type::id() {
+        CoreValue::Sint16(value.to_i16()?)
+    } else if *generic_id == Sint32Type::id() {
+        CoreValue::Sint32(value.to_i32()?)
+    } else if *generic_id == Sint64Type::id() {
+        CoreValue::Sint64(value.to_i64()?)
+    } else if *generic_id == Sint128Type::id() {
+        CoreValue::Sint128(value.to_i128()?)
+    } else if *generic_id == BoundedIntType::id() {
+        CoreValue::BoundedInt(value)
+    } else if [
+        Felt252Type::id(),
+        Bytes31Type::id(),
+        ClassHashType::id(),
+        ContractAddressType::id(),
+        StorageAddressType::id(),
+        StorageBaseAddressType::id(),
+    ]
+    .contains(generic_id)
+    {
+        CoreValue::Felt252(Felt252::from(&value))
+    } else {
+        return None;
+    })
+}
+
+/// Returns a value of the same integer type as `like` holding `value`.
+fn int_like(like: &CoreValue, value: BigInt) -> Result<CoreValue, LibfuncSimulationError> {
+    let value = match like {
+        CoreValue::Felt252(_) => Some(CoreValue::Felt252(Felt252::from(&value))),
+        CoreValue::Uint8(_) => value.to_u8().map(CoreValue::Uint8),
+        CoreValue::Uint16(_) => value.to_u16().map(CoreValue::Uint16),
+        CoreValue::Uint32(_) => value.to_u32().map(CoreValue::Uint32),
+        CoreValue::Uint64(_) => value.to_u64().map(CoreValue::Uint64),
+        CoreValue::Uint128(_) => value.to_u128().map(CoreValue::Uint128),
+        CoreValue::Sint8(_) => value.to_i8().map(CoreValue::Sint8),
+        CoreValue::Sint16(_) => value.to_i16().map(CoreValue::Sint16),
+        CoreValue::Sint32(_) => value.to_i32().map(CoreValue::Sint32),
+        CoreValue::Sint64(_) => value.to_i64().map(CoreValue::Sint64),
+        CoreValue::Sint128(_) => value.to_i128().map(CoreValue::Sint128),
+        CoreValue::BoundedInt(_) => Some(CoreValue::BoundedInt(value)),
+        _ => None,
+    };
+    value.ok_or(LibfuncSimulationError::WrongArgType)
+}
+
+/// Returns the value of an integer-like value, or an error if it is not one.
+/// Felt252 values are interpreted as signed values.
+fn int_input(value: &CoreValue) -> Result<BigInt, LibfuncSimulationError> {
+    Ok(match value {
+        CoreValue::Felt252(value) => felt252_to_signed(value),
+        CoreValue::Uint8(value) => (*value).into(),
+        CoreValue::Uint16(value) => (*value).into(),
+        CoreValue::Uint32(value) => (*value).into(),
+        CoreValue::Uint64(value) => (*value).into(),
+        CoreValue::Uint128(value) => (*value).into(),
+        CoreValue::Sint8(value) => (*value).into(),
+        CoreValue::Sint16(value) => (*value).into(),
+        CoreValue::Sint32(value) => (*value).into(),
+        CoreValue::Sint64(value) => (*value).into(),
+        CoreValue::Sint128(value) => (*value).into(),
+        CoreValue::BoundedInt(value) | CoreValue::U96Guarantee(value) => value.clone(),
+        _ => return Err(LibfuncSimulationError::WrongArgType),
+    })
+}
+
+/// Returns the value of a felt252 as a signed value in `(-P/2, P/2]`.
+fn felt252_to_signed(value: &Felt252) -> BigInt {
+    let value = value.to_bigint();
+    let prime: &BigInt = &CAIRO_PRIME_BIGINT;
+    if value > prime >> 1 { value - prime } else { value }
+}
+
+/// Returns the value of a number given as a struct of limbs of `limb_bits` bits, least significant
+/// first.
+fn limbs_value(value: &CoreValue, limb_bits: usize) -> Result<BigInt, LibfuncSimulationError> {
+    let CoreValue::Struct(limbs) = value else {
+        return Err(LibfuncSimulationError::WrongArgType);
+    };
+    limbs
+        .iter()
+        .rev()
+        .try_fold(BigInt::zero(), |acc, limb| Ok((acc << limb_bits) + int_input(limb)?))
+}
+
+/// Returns the value of a number given as a struct of u128 limbs, such as u256 and u512.
+fn u128_limbs_value(value: &CoreValue) -> Result<BigInt, LibfuncSimulationError> {
+    limbs_value(value, 128)
+}
+
+/// Returns the value of a number given as a struct of u96 limbs, such as u384.
+fn u96_limbs_value(value: &CoreValue) -> Result<BigInt, LibfuncSimulationError> {
+    limbs_value(value, 96)
+}
+
+/// Splits a non-negative value into `count` limbs of `limb_bits` bits, least significant first.
+fn split_limbs(value: &BigInt, limb_bits: usize, count: usize) -> Vec<BigInt> {
+    let mask = (BigInt::one() << limb_bits) - 1;
+    (0..count).map(|i| (value >> (limb_bits * i)) & &mask).collect()
+}
+
+/// Returns a number as a struct of `count` u128 limbs, least significant first.
+fn u128_limbs(value: &BigInt, count: usize) -> CoreValue {
+    CoreValue::Struct(
+        split_limbs(value, 128, count)
+            .into_iter()
+            .map(|limb| CoreValue::Uint128(limb.to_u128().unwrap()))
+            .collect(),
+    )
+}
+
 /// Takes the inputs and returns an array of the correct size, or an error if the number of inputs
 /// is wrong.
 fn take_inputs<const COUNT: usize>(
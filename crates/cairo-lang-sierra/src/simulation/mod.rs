@@ -1,17 +1,19 @@
 use std::collections::HashMap;
 
 use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
-use itertools::izip;
+use itertools::{izip, zip_eq};
 use thiserror::Error;
 
+use self::syscalls::{SyscallHandler, UnsupportedSyscallHandler};
 use self::value::CoreValue;
 use crate::edit_state::{EditStateError, put_results, take_args};
 use crate::extensions::core::{CoreConcreteLibfunc, CoreLibfunc, CoreType};
-use crate::ids::{FunctionId, VarId};
+use crate::ids::{ConcreteTypeId, FunctionId, VarId};
 use crate::program::{Program, Statement, StatementIdx};
 use crate::program_registry::{ProgramRegistry, ProgramRegistryError};
 
 pub mod core;
+pub mod syscalls;
 #[cfg(test)]
 mod test;
 pub mod value;
@@ -25,6 +27,10 @@ pub enum LibfuncSimulationError {
     WrongArgType,
     #[error("Could not resolve requested symbol value")]
     UnresolvedStatementGasInfo,
+    #[error("Could not resolve a required type")]
+    UnresolvedType(ConcreteTypeId),
+    #[error("Syscall is not supported by the syscall handler")]
+    UnsupportedSyscall(String),
     #[error("Error occurred during user function call")]
     FunctionSimulationError(FunctionId, Box<SimulationError>),
 }
@@ -52,13 +58,31 @@ pub fn run(
     statement_gas_info: &HashMap<StatementIdx, i64>,
     function_id: &FunctionId,
     inputs: Vec<CoreValue>,
+) -> Result<Vec<CoreValue>, SimulationError> {
+    run_with_syscall_handler(
+        program,
+        statement_gas_info,
+        function_id,
+        inputs,
+        &mut UnsupportedSyscallHandler,
+    )
+}
+
+/// Runs a function from the program with the given inputs, using the given handler for Starknet
+/// syscalls and cheatcodes.
+pub fn run_with_syscall_handler(
+    program: &Program,
+    statement_gas_info: &HashMap<StatementIdx, i64>,
+    function_id: &FunctionId,
+    inputs: Vec<CoreValue>,
+    syscall_handler: &mut dyn SyscallHandler,
 ) -> Result<Vec<CoreValue>, SimulationError> {
     let context = SimulationContext {
         program,
         statement_gas_info,
         registry: &ProgramRegistry::new(program)?,
     };
-    context.simulate_function(function_id, inputs)
+    context.simulate_function(function_id, inputs, syscall_handler)
 }
 
 /// Helper class for running the simulation.
@@ -73,6 +97,7 @@ impl SimulationContext<'_> {
         &self,
         function_id: &FunctionId,
         inputs: Vec<CoreValue>,
+        syscall_handler: &mut dyn SyscallHandler,
     ) -> Result<Vec<CoreValue>, SimulationError> {
         let func = self.registry.get_function(function_id)?;
         let mut current_statement_id = func.entry_point;
@@ -116,9 +141,10 @@ impl SimulationContext<'_> {
                         libfunc,
                         inputs,
                         current_statement_id,
+                        syscall_handler,
                     )?;
                     let branch_info = &invocation.branches[chosen_branch];
-                    state = put_results(remaining, izip!(branch_info.results.iter(), outputs))
+                    state = put_results(remaining, zip_eq(branch_info.results.iter(), outputs))
                         .map_err(|error| {
                             SimulationError::EditStateError(error, current_statement_id)
                         })?;
@@ -135,19 +161,22 @@ impl SimulationContext<'_> {
         libfunc: &CoreConcreteLibfunc,
         inputs: Vec<CoreValue>,
         current_statement_id: StatementIdx,
+        syscall_handler: &mut dyn SyscallHandler,
     ) -> Result<(Vec<CoreValue>, usize), SimulationError> {
         core::simulate(
             libfunc,
             inputs,
+            |id| self.registry.get_type(id).ok(),
             || self.statement_gas_info.get(idx).copied(),
-            |function_id, inputs| {
-                self.simulate_function(function_id, inputs).map_err(|error| {
+            |function_id, inputs, syscall_handler| {
+                self.simulate_function(function_id, inputs, syscall_handler).map_err(|error| {
                     LibfuncSimulationError::FunctionSimulationError(
                         function_id.clone(),
                         Box::new(error),
                     )
                 })
             },
+            syscall_handler,
         )
         .map_err(|error| SimulationError::LibfuncSimulationError(error, current_statement_id))
     }
@@ -0,0 +1,50 @@
+use num_bigint::BigInt;
+use starknet_types_core::felt::Felt as Felt252;
+
+use super::LibfuncSimulationError;
+use super::value::CoreValue;
+
+/// The result of a syscall - the outputs of the syscall on success, or the revert reason on
+/// failure.
+pub type SyscallResult = Result<Vec<CoreValue>, Vec<Felt252>>;
+
+/// Handler for the Starknet syscalls and cheatcodes invoked during a simulation.
+pub trait SyscallHandler {
+    /// Handles the syscall named `selector` (e.g. `StorageRead`), given its inputs excluding the
+    /// gas builtin and the system. May charge the syscall's cost from `gas_counter`.
+    fn syscall(
+        &mut self,
+        selector: &str,
+        inputs: Vec<CoreValue>,
+        gas_counter: &mut i64,
+    ) -> Result<SyscallResult, LibfuncSimulationError>;
+
+    /// Handles the cheatcode with the given selector, given the felts of its input span.
+    /// Returns the felts of its output span.
+    fn cheatcode(
+        &mut self,
+        selector: &BigInt,
+        inputs: Vec<Felt252>,
+    ) -> Result<Vec<Felt252>, LibfuncSimulationError>;
+}
+
+/// A syscall handler that fails on every syscall and cheatcode.
+pub struct UnsupportedSyscallHandler;
+impl SyscallHandler for UnsupportedSyscallHandler {
+    fn syscall(
+        &mut self,
+        selector: &str,
+        _inputs: Vec<CoreValue>,
+        _gas_counter: &mut i64,
+    ) -> Result<SyscallResult, LibfuncSimulationError> {
+        Err(LibfuncSimulationError::UnsupportedSyscall(selector.into()))
+    }
+
+    fn cheatcode(
+        &mut self,
+        selector: &BigInt,
+        _inputs: Vec<Felt252>,
+    ) -> Result<Vec<Felt252>, LibfuncSimulationError> {
+        Err(LibfuncSimulationError::UnsupportedSyscall(format!("cheatcode {selector}")))
+    }
+}
@@ -1,23 +1,30 @@
+use std::collections::HashMap;
+
 use bimap::BiMap;
 use num_bigint::BigInt;
+use starknet_types_core::felt::Felt;
 use test_case::test_case;
 
 use super::LibfuncSimulationError::{
-    self, FunctionSimulationError, WrongArgType, WrongNumberOfArgs,
+    self, FunctionSimulationError, UnsupportedSyscall, WrongArgType, WrongNumberOfArgs,
 };
+use super::syscalls::UnsupportedSyscallHandler;
 use super::value::CoreValue::{
-    self, Array, GasBuiltin, RangeCheck, Uint32, Uint128, Uninitialized,
+    self, Array, Bitwise, Dict, DictEntry, Felt252, GasBuiltin, RangeCheck, SegmentArena, Sint8,
+    System, U128MulGuarantee, Uint8, Uint32, Uint64, Uint128, Uninitialized,
 };
 use super::{SimulationError, core};
-use crate::extensions::GenericLibfunc;
-use crate::extensions::core::CoreLibfunc;
+use crate::extensions::core::{CoreLibfunc, CoreType};
 use crate::extensions::lib_func::{
     SierraApChange, SignatureSpecializationContext, SpecializationContext,
 };
 use crate::extensions::type_specialization_context::TypeSpecializationContext;
 use crate::extensions::types::TypeInfo;
+use crate::extensions::{ConcreteLibfunc, ConcreteType, GenericLibfunc, GenericType};
 use crate::ids::{ConcreteTypeId, FunctionId, GenericTypeId};
-use crate::program::{ConcreteTypeLongId, Function, FunctionSignature, GenericArg, StatementIdx};
+use crate::program::{
+    ConcreteTypeLongId, Function, FunctionSignature, GenericArg, Param, StatementIdx,
+};
 use crate::test_utils::build_bijective_mapping;
 
 fn type_arg(name: &str) -> GenericArg {
@@ -39,6 +46,19 @@ impl MockSpecializationContext {
     pub fn new() -> Self {
         Self { mapping: build_bijective_mapping() }
     }
+
+    /// Returns the concrete types of all the mapped types that can be specialized.
+    fn concrete_types(&self) -> HashMap<ConcreteTypeId, <CoreType as GenericType>::Concrete> {
+        self.mapping
+            .iter()
+            .filter_map(|(id, long_id)| {
+                let concrete = CoreType::by_id(&long_id.generic_id)?
+                    .specialize(self, &long_id.generic_args)
+                    .ok()?;
+                Some((id.clone(), concrete))
+            })
+            .collect()
+    }
 }
 
 impl SpecializationContext for MockSpecializationContext {
@@ -47,11 +67,14 @@ impl SpecializationContext for MockSpecializationContext {
     }
 
     fn try_get_function(&self, function_id: &FunctionId) -> Option<Function> {
-        ["drop_all_inputs", "identity", "unimplemented"]
-            .into_iter()
-            .map(|name| -> FunctionId { name.into() })
-            .find(|id: &FunctionId| id == function_id)
-            .map(|_| Function::new(function_id.clone(), vec![], vec![], StatementIdx(0)))
+        let params = ["a", "b"].map(|id| Param { id: id.into(), ty: "u128".into() }).to_vec();
+        let ret_types = match function_id.debug_name.as_deref()? {
+            "drop_all_inputs" => vec![],
+            "identity" => vec!["u128".into(), "u128".into()],
+            "unimplemented" => vec![],
+            _ => return None,
+        };
+        Some(Function::new(function_id.clone(), params, ret_types, StatementIdx(0)))
     }
 }
 impl TypeSpecializationContext for MockSpecializationContext {
@@ -81,7 +104,14 @@ impl TypeSpecializationContext for MockSpecializationContext {
                 zero_sized: false,
             })
         } else {
-            None
+            let long_id = self.mapping.get_by_left(&id)?;
+            Some(
+                CoreType::by_id(&long_id.generic_id)?
+                    .specialize(self, &long_id.generic_args)
+                    .ok()?
+                    .info()
+                    .clone(),
+            )
         }
     }
 }
@@ -112,20 +142,23 @@ impl SignatureSpecializationContext for MockSpecializationContext {
     }
 }
 
-/// Expects to find a libfunc and simulate it.
+/// Expects to find a libfunc and simulate it. Also checks that the outputs match the signature of
+/// the chosen branch.
 fn simulate(
     id: &str,
     generic_args: Vec<GenericArg>,
     inputs: Vec<CoreValue>,
 ) -> Result<(Vec<CoreValue>, usize), LibfuncSimulationError> {
-    core::simulate(
-        &CoreLibfunc::by_id(&id.into())
-            .unwrap()
-            .specialize(&MockSpecializationContext::new(), &generic_args)
-            .unwrap(),
+    let context = MockSpecializationContext::new();
+    let types = context.concrete_types();
+    let libfunc =
+        CoreLibfunc::by_id(&id.into()).unwrap().specialize(&context, &generic_args).unwrap();
+    let result = core::simulate(
+        &libfunc,
         inputs,
+        |id| types.get(id),
         || Some(4),
-        |id, inputs| {
+        |id, inputs, _| {
             if id == &"drop_all_inputs".into() {
                 Ok(vec![])
             } else if id == &"identity".into() {
@@ -137,7 +170,28 @@ fn simulate(
                 ))
             }
         },
-    )
+        &mut UnsupportedSyscallHandler,
+    );
+    if let Ok((outputs, chosen_branch)) = &result {
+        assert_eq!(outputs.len(), libfunc.branch_signatures()[*chosen_branch].vars.len());
+    }
+    result
+}
+
+fn boxed(value: CoreValue) -> CoreValue {
+    CoreValue::Box(Box::new(value))
+}
+
+fn felt(value: impl Into<Felt>) -> CoreValue {
+    Felt252(value.into())
+}
+
+fn felt_from_bigint(value: BigInt) -> CoreValue {
+    Felt252((&value).into())
+}
+
+fn dict<const N: usize>(entries: [(u64, CoreValue); N]) -> HashMap<Felt, CoreValue> {
+    entries.into_iter().map(|(key, value)| (key.into(), value)).collect()
 }
 
 #[test_case("withdraw_gas", vec![], vec![RangeCheck, GasBuiltin(5)]
@@ -157,6 +211,58 @@ fn simulate(
 #[test_case("u128_overflowing_sub", vec![], vec![RangeCheck, Uint128(3), Uint128(5)]
              => Ok((vec![RangeCheck, Uint128(u128::MAX - 1)], 1));
             "u128_overflowing_sub(3, 5)")]
+#[test_case("i8_overflowing_add_impl", vec![], vec![RangeCheck, Sint8(100), Sint8(27)]
+             => Ok((vec![RangeCheck, Sint8(127)], 0));
+            "i8_overflowing_add_impl(100, 27)")]
+#[test_case("i8_overflowing_add_impl", vec![], vec![RangeCheck, Sint8(-100), Sint8(-29)]
+             => Ok((vec![RangeCheck, Sint8(127)], 1));
+            "i8_overflowing_add_impl(-100, -29)")]
+#[test_case("i8_overflowing_add_impl", vec![], vec![RangeCheck, Sint8(100), Sint8(28)]
+             => Ok((vec![RangeCheck, Sint8(-128)], 2));
+            "i8_overflowing_add_impl(100, 28)")]
+#[test_case("i8_diff", vec![], vec![RangeCheck, Sint8(3), Sint8(5)]
+             => Ok((vec![RangeCheck, Uint8(254)], 1)); "i8_diff(3, 5)")]
+#[test_case("array_pop_front", vec![type_arg("u128")], vec![Array(vec![Uint128(1), Uint128(2)])]
+             => Ok((vec![Array(vec![Uint128(2)]), boxed(Uint128(1))], 0));
+            "array_pop_front([1, 2])")]
+#[test_case("array_pop_front", vec![type_arg("u128")], vec![Array(vec![])]
+             => Ok((vec![Array(vec![])], 1)); "array_pop_front([])")]
+#[test_case("array_pop_front_consume", vec![type_arg("u128")], vec![Array(vec![Uint128(1), Uint128(2)])]
+             => Ok((vec![Array(vec![Uint128(2)]), boxed(Uint128(1))], 0));
+            "array_pop_front_consume([1, 2])")]
+#[test_case("array_pop_front_consume", vec![type_arg("u128")], vec![Array(vec![])]
+             => Ok((vec![], 1)); "array_pop_front_consume([])")]
+#[test_case("array_snapshot_pop_front", vec![type_arg("u128")], vec![Array(vec![Uint128(1), Uint128(2)])]
+             => Ok((vec![Array(vec![Uint128(2)]), boxed(Uint128(1))], 0));
+            "array_snapshot_pop_front([1, 2])")]
+#[test_case("array_snapshot_pop_back", vec![type_arg("u128")], vec![Array(vec![Uint128(1), Uint128(2)])]
+             => Ok((vec![Array(vec![Uint128(1)]), boxed(Uint128(2))], 0));
+            "array_snapshot_pop_back([1, 2])")]
+#[test_case("array_snapshot_pop_back", vec![type_arg("u128")], vec![Array(vec![])]
+             => Ok((vec![Array(vec![])], 1)); "array_snapshot_pop_back([])")]
+#[test_case("array_get", vec![type_arg("u128")], vec![RangeCheck, Array(vec![Uint128(5)]), Uint32(1)]
+             => Ok((vec![RangeCheck], 1)); "array_get([5], 1)")]
+#[test_case("array_slice", vec![type_arg("u128")],
+            vec![RangeCheck, Array(vec![Uint128(1), Uint128(2), Uint128(3)]), Uint32(1), Uint32(2)]
+             => Ok((vec![RangeCheck, Array(vec![Uint128(2), Uint128(3)])], 0));
+            "array_slice([1, 2, 3], 1, 2)")]
+#[test_case("array_slice", vec![type_arg("u128")],
+            vec![RangeCheck, Array(vec![Uint128(1)]), Uint32(1), Uint32(1)]
+             => Ok((vec![RangeCheck], 1)); "array_slice([1], 1, 1)")]
+#[test_case("u128s_from_felt252", vec![], vec![RangeCheck, felt(5)]
+             => Ok((vec![RangeCheck, Uint128(5)], 0)); "u128s_from_felt252(5)")]
+#[test_case("u128s_from_felt252", vec![], vec![RangeCheck, felt_from_bigint((BigInt::from(1) << 128) + 3)]
+             => Ok((vec![RangeCheck, Uint128(1), Uint128(3)], 1));
+            "u128s_from_felt252(2**128 + 3)")]
+#[test_case("u8_try_from_felt252", vec![], vec![RangeCheck, felt(256)]
+             => Ok((vec![RangeCheck], 1)); "u8_try_from_felt252(256)")]
+#[test_case("u128_eq", vec![], vec![Uint128(3), Uint128(3)] => Ok((vec![], 1)); "u128_eq(3, 3)")]
+#[test_case("u32_is_zero", vec![], vec![Uint32(0)] => Ok((vec![], 0)); "u32_is_zero(0)")]
+#[test_case("u64_is_zero", vec![], vec![Uint64(7)] => Ok((vec![Uint64(7)], 1)); "u64_is_zero(7)")]
+#[test_case("bytes31_try_from_felt252", vec![], vec![RangeCheck, felt(5)]
+             => Ok((vec![RangeCheck, felt(5)], 0)); "bytes31_try_from_felt252(5)")]
+#[test_case("bytes31_try_from_felt252", vec![], vec![RangeCheck, felt_from_bigint(BigInt::from(1) << 248)]
+             => Ok((vec![RangeCheck], 1)); "bytes31_try_from_felt252(2**248)")]
 fn simulate_branch(
     id: &str,
     generic_args: Vec<GenericArg>,
@@ -171,13 +277,21 @@ fn simulate_branch(
 #[test_case("array_append", vec![type_arg("u128")], vec![Array(vec![]), Uint128(4)] =>
             Ok(vec![Array(vec![Uint128(4)])]); "array_append([], 4)")]
 #[test_case("array_get", vec![type_arg("u128")], vec![RangeCheck, Array(vec![Uint128(5)]), Uint32(0)]
-             => Ok(vec![RangeCheck, Uint128(5)]); "array_get([5], 0)")]
+             => Ok(vec![RangeCheck, boxed(Uint128(5))]); "array_get([5], 0)")]
 #[test_case("array_len", vec![type_arg("u128")], vec![Array(vec![])] =>
             Ok(vec![Uint32(0)]); "array_len([])")]
 #[test_case("u128_safe_divmod", vec![], vec![RangeCheck, Uint128(32), Uint128(5)]
              => Ok(vec![RangeCheck, Uint128(6), Uint128(2)]); "u128_safe_divmod(32, 5)")]
 #[test_case("u128_const", vec![value_arg(3)], vec![] => Ok(vec![Uint128(3)]);
             "u128_const<3>()")]
+#[test_case("u128_byte_reverse", vec![], vec![Bitwise, Uint128(1)]
+             => Ok(vec![Bitwise, Uint128(1 << 120)]); "u128_byte_reverse(1)")]
+#[test_case("u8_bitwise", vec![], vec![Bitwise, Uint8(12), Uint8(10)]
+             => Ok(vec![Bitwise, Uint8(8), Uint8(6), Uint8(14)]); "u8_bitwise(12, 10)")]
+#[test_case("into_box", vec![type_arg("u128")], vec![Uint128(6)] => Ok(vec![boxed(Uint128(6))]);
+            "into_box<u128>(6)")]
+#[test_case("unbox", vec![type_arg("u128")], vec![boxed(Uint128(6))] => Ok(vec![Uint128(6)]);
+            "unbox<u128>(6)")]
 #[test_case("dup", vec![type_arg("u128")], vec![Uint128(24)]
              => Ok(vec![Uint128(24), Uint128(24)]); "dup<u128>(24)")]
 #[test_case("drop", vec![type_arg("u128")], vec![Uint128(2)] => Ok(vec![]); "drop<u128>(2)")]
@@ -194,6 +308,37 @@ fn simulate_branch(
              => Ok(vec![]); "function_call<drop_all_inputs>()")]
 #[test_case("function_call", vec![user_func_arg("identity")], vec![Uint128(3), Uint128(5)]
              => Ok(vec![Uint128(3), Uint128(5)]); "function_call<identity>()")]
+#[test_case("u128_guarantee_mul", vec![], vec![Uint128(u128::MAX), Uint128(2)]
+             => Ok(vec![Uint128(1), Uint128(u128::MAX - 1), U128MulGuarantee]);
+            "u128_guarantee_mul(2**128 - 1, 2)")]
+#[test_case("u128_mul_guarantee_verify", vec![], vec![RangeCheck, U128MulGuarantee]
+             => Ok(vec![RangeCheck]); "u128_mul_guarantee_verify()")]
+#[test_case("u32_wide_mul", vec![], vec![Uint32(u32::MAX), Uint32(2)]
+             => Ok(vec![Uint64(2 * u64::from(u32::MAX))]); "u32_wide_mul(2**32 - 1, 2)")]
+#[test_case("u64_sqrt", vec![], vec![RangeCheck, Uint64(17)]
+             => Ok(vec![RangeCheck, Uint32(4)]); "u64_sqrt(17)")]
+#[test_case("u8_safe_divmod", vec![], vec![RangeCheck, Uint8(7), Uint8(2)]
+             => Ok(vec![RangeCheck, Uint8(3), Uint8(1)]); "u8_safe_divmod(7, 2)")]
+#[test_case("felt252_dict_new", vec![type_arg("felt252")], vec![SegmentArena]
+             => Ok(vec![SegmentArena, Dict(dict([]))]); "felt252_dict_new<felt252>()")]
+#[test_case("felt252_dict_entry_get", vec![type_arg("felt252")], vec![Dict(dict([])), felt(1)]
+             => Ok(vec![DictEntry { dict: dict([]), key: 1.into() }, felt(0)]);
+            "felt252_dict_entry_get<felt252>({}, 1)")]
+#[test_case("felt252_dict_entry_get", vec![type_arg("felt252")],
+            vec![Dict(dict([(1, felt(5))])), felt(1)]
+             => Ok(vec![DictEntry { dict: dict([(1, felt(5))]), key: 1.into() }, felt(5)]);
+            "felt252_dict_entry_get<felt252>({1: 5}, 1)")]
+#[test_case("felt252_dict_entry_finalize", vec![type_arg("felt252")],
+            vec![DictEntry { dict: dict([(1, felt(5))]), key: 1.into() }, felt(7)]
+             => Ok(vec![Dict(dict([(1, felt(7))]))]);
+            "felt252_dict_entry_finalize<felt252>({1: 5}[1], 7)")]
+#[test_case("felt252_dict_squash", vec![type_arg("felt252")],
+            vec![RangeCheck, GasBuiltin(5), SegmentArena, Dict(dict([(1, felt(7))]))]
+             => Ok(vec![RangeCheck, GasBuiltin(5), SegmentArena, Dict(dict([(1, felt(7))]))]);
+            "felt252_dict_squash<felt252>({1: 7})")]
+#[test_case("bytes31_const", vec![value_arg(5)], vec![] => Ok(vec![felt(5)]); "bytes31_const<5>()")]
+#[test_case("bytes31_to_felt252", vec![], vec![felt(5)] => Ok(vec![felt(5)]);
+            "bytes31_to_felt252(5)")]
 fn simulate_none_branch(
     id: &str,
     generic_args: Vec<GenericArg>,
@@ -229,6 +374,8 @@ fn simulate_none_branch(
 #[test_case("finalize_locals", vec![], vec![Uint128(4)] => WrongNumberOfArgs; "finalize_locals(4)")]
 #[test_case("rename", vec![type_arg("u128")], vec![] => WrongNumberOfArgs; "rename<u128>()")]
 #[test_case("jump", vec![], vec![Uint128(4)] => WrongNumberOfArgs; "jump(4)")]
+#[test_case("storage_read_syscall", vec![], vec![GasBuiltin(10), System, Uint32(0), Felt252(5.into())]
+             => UnsupportedSyscall("StorageRead".into()); "storage_read_syscall(0, 5)")]
 #[test_case("function_call", vec![user_func_arg("unimplemented")], vec![] =>
             FunctionSimulationError(
                 "unimplemented".into(),
@@ -1,11 +1,14 @@
 use std::collections::HashMap;
 
+use num_bigint::BigInt;
 use starknet_types_core::felt::Felt as Felt252;
 
 /// The logical value of a variable for Sierra simulation.
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum CoreValue {
     EcPoint(Felt252, Felt252),
+    /// An EC state - the accumulated point, where `(0, 0)` represents the point at infinity.
+    EcState(Felt252, Felt252),
     Felt252(Felt252),
     GasBuiltin(i64),
     Uint8(u8),
@@ -13,17 +16,73 @@ pub enum CoreValue {
     Uint32(u32),
     Uint64(u64),
     Uint128(u128),
+    Sint8(i8),
+    Sint16(i16),
+    Sint32(i32),
+    Sint64(i64),
+    Sint128(i128),
+    BoundedInt(BigInt),
     Array(Vec<CoreValue>),
     Dict(HashMap<Felt252, CoreValue>),
+    /// An entry taken from a dict - the dict, and the key of the entry.
+    DictEntry {
+        dict: HashMap<Felt252, CoreValue>,
+        key: Felt252,
+    },
+    Box(Box<CoreValue>),
+    /// A nullable value - `None` represents null.
+    Nullable(Option<Box<CoreValue>>),
+    /// An integer range `[start, end)`.
+    IntRange {
+        start: Box<CoreValue>,
+        end: Box<CoreValue>,
+    },
     Enum {
         value: Box<CoreValue>,
         /// The index of the relevant variant.
         index: usize,
     },
     Struct(Vec<CoreValue>),
+    /// The circuit inputs added so far.
+    CircuitInputAccumulator(Vec<BigInt>),
+    /// The full inputs of a circuit.
+    CircuitData(Vec<BigInt>),
+    /// The values of a circuit evaluation, where the value at offset 0 is the constant 1, followed
+    /// by the inputs and then the gate outputs. Also used for the partial outputs of a failed
+    /// evaluation, where unknown values are 0.
+    CircuitOutputs {
+        values: Vec<BigInt>,
+        modulus: BigInt,
+    },
+    CircuitModulus(BigInt),
+    /// A guarantee that the circuit evaluation failed - `nullifier` is a non-zero value smaller
+    /// than `modulus` that nullifies the non-invertible value.
+    CircuitFailureGuarantee {
+        nullifier: BigInt,
+        modulus: BigInt,
+    },
+    /// A guarantee that the value is a u96.
+    U96Guarantee(BigInt),
+    /// A guarantee that `value` is smaller than `bound`, where both are u96 limbs, least
+    /// significant first.
+    U96LimbsLessThanGuarantee {
+        value: Vec<BigInt>,
+        bound: Vec<BigInt>,
+    },
     // The untracked types - do not carry any value.
     Uninitialized,
     RangeCheck,
     Bitwise,
     U128MulGuarantee,
+    CircuitDescriptor,
+    Pedersen,
+    Poseidon,
+    EcOp,
+    SegmentArena,
+    System,
+    RangeCheck96,
+    AddMod,
+    MulMod,
+    BuiltinCosts,
+    Coupon,
 }
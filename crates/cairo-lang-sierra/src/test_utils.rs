@@ -9,6 +9,8 @@ use crate::program::{ConcreteTypeLongId, GenericArg};
 pub fn build_bijective_mapping() -> BiMap<ConcreteTypeId, ConcreteTypeLongId> {
     let mut elements = BiMap::new();
     elements.insert("T".into(), as_type_long_id("T", &[]));
+    elements.insert("u8".into(), as_type_long_id("u8", &[]));
+    elements.insert("i8".into(), as_type_long_id("i8", &[]));
     elements.insert("u32".into(), as_type_long_id("u32", &[]));
     elements.insert("u64".into(), as_type_long_id("u64", &[]));
     elements.insert("u128".into(), as_type_long_id("u128", &[]));
@@ -22,7 +24,11 @@ pub fn build_bijective_mapping() -> BiMap<ConcreteTypeId, ConcreteTypeLongId> {
     elements
         .insert("Option".into(), as_named_type_long_id("Enum", "Option", &["felt252", "Tuple<>"]));
     elements.insert("NonZeroFelt252".into(), as_type_long_id("NonZero", &["felt252"]));
+    elements.insert("NonZeroU8".into(), as_type_long_id("NonZero", &["u8"]));
+    elements.insert("NonZeroU32".into(), as_type_long_id("NonZero", &["u32"]));
+    elements.insert("NonZeroU64".into(), as_type_long_id("NonZero", &["u64"]));
     elements.insert("NonZeroU128".into(), as_type_long_id("NonZero", &["u128"]));
+    elements.insert("U128MulGuarantee".into(), as_type_long_id("U128MulGuarantee", &[]));
     elements.insert("ArrayFelt252".into(), as_type_long_id("Array", &["felt252"]));
     elements.insert("ArrayFelt252".into(), as_type_long_id("Array", &["felt252"]));
     elements.insert(
@@ -51,7 +57,18 @@ pub fn build_bijective_mapping() -> BiMap<ConcreteTypeId, ConcreteTypeLongId> {
     elements.insert("Uninitializedu128".into(), as_type_long_id("Uninitialized", &["u128"]));
     elements.insert("GasBuiltin".into(), as_type_long_id("GasBuiltin", &[]));
     elements.insert("RangeCheck".into(), as_type_long_id("RangeCheck", &[]));
+    elements.insert("Bitwise".into(), as_type_long_id("Bitwise", &[]));
     elements.insert("System".into(), as_type_long_id("System", &[]));
+    elements.insert("SegmentArena".into(), as_type_long_id("SegmentArena", &[]));
+    elements.insert("Felt252DictFelt252".into(), as_type_long_id("Felt252Dict", &["felt252"]));
+    elements.insert(
+        "Felt252DictEntryFelt252".into(),
+        as_type_long_id("Felt252DictEntry", &["felt252"]),
+    );
+    elements.insert(
+        "SquashedFelt252DictFelt252".into(),
+        as_type_long_id("SquashedFelt252Dict", &["felt252"]),
+    );
     elements.insert("StorageBaseAddress".into(), as_type_long_id("StorageBaseAddress", &[]));
     elements.insert("StorageAddress".into(), as_type_long_id("StorageAddress", &[]));
     elements.insert("ContractAddress".into(), as_type_long_id("ContractAddress", &[]));
@@ -0,0 +1,367 @@
+//! Lossless text representation of a [ProgramArtifact].
+//!
+//! The text is the regular Sierra program text, which uses the debug names of the ids where
+//! available, followed by a metadata comment block holding the numeric ids behind the names and
+//! the [DebugInfo] of the artifact. The text is therefore still parsable by [ProgramParser].
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use itertools::zip_eq;
+use num_bigint::BigUint;
+use serde::{Deserialize, Serialize};
+use smol_str::SmolStr;
+use thiserror::Error;
+
+use crate::ProgramParser;
+use crate::debug_info::DebugInfo;
+use crate::ids::{ConcreteLibfuncId, ConcreteTypeId, FunctionId, UserTypeId, VarId};
+use crate::program::{GenericArg, Program, ProgramArtifact, Statement};
+
+#[cfg(test)]
+#[path = "text_test.rs"]
+mod test;
+
+/// The line opening the metadata comment block.
+const METADATA_MARKER: &str = "// @metadata";
+
+#[derive(Debug, Error)]
+pub enum TextFormatError {
+    #[error("Failed to parse Sierra program: {0}")]
+    ParseError(String),
+    #[error("Invalid metadata: {0}")]
+    InvalidMetadata(#[from] serde_json::Error),
+    #[error("The program can not be represented losslessly in the text format.")]
+    NotRepresentable,
+}
+
+/// Returns the text representation of the artifact.
+///
+/// Ids are printed by their debug names when these are restored exactly by parsing, and by their
+/// numeric ids otherwise. Fails if the artifact can not be restored exactly from the text, e.g. if
+/// an id has different debug names in different places.
+pub fn artifact_to_text(artifact: &ProgramArtifact) -> Result<String, TextFormatError> {
+    let original_ids = program_ids(&artifact.program);
+    let mut numeric_ids = HashSet::new();
+    loop {
+        let metadata =
+            TextMetadata::new(&artifact.program, artifact.debug_info.clone(), &numeric_ids);
+        let mut program = artifact.program.clone();
+        visit_program_ids(&mut program, &mut |id| {
+            if numeric_ids.contains(&id.key()) {
+                id.strip_debug_name();
+            }
+        });
+        let text = render(&program, &metadata)?;
+        let parsed_ids = match artifact_from_text(&text) {
+            Ok(parsed) if serde_json::to_value(&parsed)? == serde_json::to_value(artifact)? => {
+                return Ok(text);
+            }
+            Ok(parsed) => program_ids(&parsed.program),
+            // Some name breaks the parsing, so print all the ids by number.
+            Err(_) => vec![],
+        };
+        let prev_numeric_count = numeric_ids.len();
+        if parsed_ids.len() == original_ids.len() {
+            // Print the ids that were not restored exactly by number.
+            numeric_ids.extend(
+                zip_eq(&original_ids, &parsed_ids)
+                    .filter(|(original, parsed)| original != parsed)
+                    .map(|((key, _), _)| key.clone()),
+            );
+        } else {
+            numeric_ids.extend(original_ids.iter().map(|(key, _)| key.clone()));
+        }
+        if numeric_ids.len() == prev_numeric_count {
+            return Err(TextFormatError::NotRepresentable);
+        }
+    }
+}
+
+/// Parses an artifact from its text representation.
+///
+/// Text without a metadata block is parsed as a regular Sierra program without debug info.
+pub fn artifact_from_text(text: &str) -> Result<ProgramArtifact, TextFormatError> {
+    let mut program = ProgramParser::new()
+        .parse(text)
+        .map_err(|err| TextFormatError::ParseError(err.to_string()))?;
+    let Some(metadata_start) = text.lines().position(|line| line == METADATA_MARKER) else {
+        return Ok(ProgramArtifact::stripped(program));
+    };
+    let metadata_json = text
+        .lines()
+        .skip(metadata_start + 1)
+        .map(|line| line.strip_prefix("//").unwrap_or(line))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let metadata: TextMetadata = serde_json::from_str(&metadata_json)?;
+    metadata.apply(&mut program);
+    Ok(ProgramArtifact { program, debug_info: metadata.debug_info })
+}
+
+/// Renders the program text followed by the metadata block, if there is any metadata.
+fn render(program: &Program, metadata: &TextMetadata) -> Result<String, TextFormatError> {
+    let mut text = program.to_string();
+    if !metadata.is_empty() {
+        text.push('\n');
+        text.push_str(METADATA_MARKER);
+        text.push('\n');
+        for line in serde_json::to_string_pretty(metadata)?.lines() {
+            text.push_str("// ");
+            text.push_str(line);
+            text.push('\n');
+        }
+    }
+    Ok(text)
+}
+
+/// Returns all the ids in the program with their debug names, in a fixed order.
+fn program_ids(program: &Program) -> Vec<(IdKey, Option<SmolStr>)> {
+    let mut ids = vec![];
+    visit_program_ids(&mut program.clone(), &mut |mut id| {
+        let debug_name = id.debug_name().clone();
+        ids.push((id.key(), debug_name));
+    });
+    ids
+}
+
+/// The metadata of the text representation.
+///
+/// Holds the numeric ids of all the debug names in the program that differ from the id the parser
+/// would derive from the name itself or are printed as numeric ids, and the debug info of the
+/// artifact.
+#[derive(Default, Serialize, Deserialize)]
+struct TextMetadata {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    type_ids: Vec<(u64, SmolStr)>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    libfunc_ids: Vec<(u64, SmolStr)>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    function_ids: Vec<(u64, SmolStr)>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    var_ids: Vec<(u64, SmolStr)>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    user_type_ids: Vec<(BigUint, SmolStr)>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    debug_info: Option<DebugInfo>,
+}
+impl TextMetadata {
+    /// Collects the metadata of the given program, where `numeric_ids` are the ids printed as
+    /// numeric ids.
+    fn new(program: &Program, debug_info: Option<DebugInfo>, numeric_ids: &HashSet<IdKey>) -> Self {
+        let mut type_ids = BTreeMap::new();
+        let mut libfunc_ids = BTreeMap::new();
+        let mut function_ids = BTreeMap::new();
+        let mut var_ids = BTreeMap::new();
+        let mut user_type_ids = BTreeMap::new();
+        visit_program_ids(&mut program.clone(), &mut |id| {
+            let numeric = numeric_ids.contains(&id.key());
+            match id {
+                IdMut::Type(id) => {
+                    collect_name(&mut type_ids, id.id, &id.debug_name, numeric, || {
+                        ConcreteTypeId::from_string(id.debug_name.clone().unwrap()).id
+                    })
+                }
+                IdMut::Libfunc(id) => {
+                    collect_name(&mut libfunc_ids, id.id, &id.debug_name, numeric, || {
+                        ConcreteLibfuncId::from_string(id.debug_name.clone().unwrap()).id
+                    })
+                }
+                IdMut::Function(id) => {
+                    collect_name(&mut function_ids, id.id, &id.debug_name, numeric, || {
+                        FunctionId::from_string(id.debug_name.clone().unwrap()).id
+                    })
+                }
+                IdMut::Var(id) => {
+                    collect_name(&mut var_ids, id.id, &id.debug_name, numeric, || {
+                        VarId::from_string(id.debug_name.clone().unwrap()).id
+                    })
+                }
+                IdMut::UserType(id) => {
+                    collect_name(&mut user_type_ids, id.id.clone(), &id.debug_name, numeric, || {
+                        UserTypeId::from_string(id.debug_name.clone().unwrap()).id
+                    })
+                }
+            }
+        });
+        Self {
+            type_ids: type_ids.into_iter().collect(),
+            libfunc_ids: libfunc_ids.into_iter().collect(),
+            function_ids: function_ids.into_iter().collect(),
+            var_ids: var_ids.into_iter().collect(),
+            user_type_ids: user_type_ids.into_iter().collect(),
+            debug_info,
+        }
+    }
+
+    /// Returns whether there is no metadata to keep.
+    fn is_empty(&self) -> bool {
+        self.type_ids.is_empty()
+            && self.libfunc_ids.is_empty()
+            && self.function_ids.is_empty()
+            && self.var_ids.is_empty()
+            && self.user_type_ids.is_empty()
+            && self.debug_info.is_none()
+    }
+
+    /// Restores the ids of the parsed program: ids printed by name get their numeric id back, and
+    /// ids printed by numeric id get their name back.
+    fn apply(&self, program: &mut Program) {
+        let type_ids = IdNames::new(&self.type_ids);
+        let libfunc_ids = IdNames::new(&self.libfunc_ids);
+        let function_ids = IdNames::new(&self.function_ids);
+        let var_ids = IdNames::new(&self.var_ids);
+        let user_type_ids = IdNames::new(&self.user_type_ids);
+        visit_program_ids(program, &mut |id| match id {
+            IdMut::Type(id) => type_ids.restore(&mut id.id, &mut id.debug_name),
+            IdMut::Libfunc(id) => libfunc_ids.restore(&mut id.id, &mut id.debug_name),
+            IdMut::Function(id) => function_ids.restore(&mut id.id, &mut id.debug_name),
+            IdMut::Var(id) => var_ids.restore(&mut id.id, &mut id.debug_name),
+            IdMut::UserType(id) => user_type_ids.restore(&mut id.id, &mut id.debug_name),
+        });
+    }
+}
+
+/// Records the name of the id, unless it is printed by name and the parser would derive the same id
+/// from the name.
+fn collect_name<Id: Ord>(
+    names: &mut BTreeMap<Id, SmolStr>,
+    id: Id,
+    debug_name: &Option<SmolStr>,
+    numeric: bool,
+    parsed_id: impl FnOnce() -> Id,
+) {
+    if let Some(name) = debug_name {
+        if numeric || parsed_id() != id {
+            names.entry(id).or_insert_with(|| name.clone());
+        }
+    }
+}
+
+/// Lookups between the numeric ids and the names of a single kind of id.
+struct IdNames<Id> {
+    /// The ids of the names, or `None` for names shared by several ids.
+    name_to_id: HashMap<SmolStr, Option<Id>>,
+    id_to_name: HashMap<Id, SmolStr>,
+}
+impl<Id: Clone + Eq + std::hash::Hash> IdNames<Id> {
+    fn new(names: &[(Id, SmolStr)]) -> Self {
+        let mut name_to_id = HashMap::new();
+        for (id, name) in names {
+            name_to_id
+                .entry(name.clone())
+                .and_modify(|existing: &mut Option<Id>| *existing = None)
+                .or_insert_with(|| Some(id.clone()));
+        }
+        Self { name_to_id, id_to_name: names.iter().cloned().collect() }
+    }
+
+    fn restore(&self, id: &mut Id, debug_name: &mut Option<SmolStr>) {
+        match debug_name {
+            Some(name) => {
+                if let Some(Some(original_id)) = self.name_to_id.get(name) {
+                    *id = original_id.clone();
+                }
+            }
+            None => *debug_name = self.id_to_name.get(id).cloned(),
+        }
+    }
+}
+
+/// A mutable reference to an id in a program.
+enum IdMut<'a> {
+    Type(&'a mut ConcreteTypeId),
+    Libfunc(&'a mut ConcreteLibfuncId),
+    Function(&'a mut FunctionId),
+    Var(&'a mut VarId),
+    UserType(&'a mut UserTypeId),
+}
+impl IdMut<'_> {
+    fn key(&self) -> IdKey {
+        match self {
+            IdMut::Type(id) => IdKey::Type(id.id),
+            IdMut::Libfunc(id) => IdKey::Libfunc(id.id),
+            IdMut::Function(id) => IdKey::Function(id.id),
+            IdMut::Var(id) => IdKey::Var(id.id),
+            IdMut::UserType(id) => IdKey::UserType(id.id.clone()),
+        }
+    }
+
+    fn debug_name(&mut self) -> &mut Option<SmolStr> {
+        match self {
+            IdMut::Type(id) => &mut id.debug_name,
+            IdMut::Libfunc(id) => &mut id.debug_name,
+            IdMut::Function(id) => &mut id.debug_name,
+            IdMut::Var(id) => &mut id.debug_name,
+            IdMut::UserType(id) => &mut id.debug_name,
+        }
+    }
+
+    fn strip_debug_name(mut self) {
+        *self.debug_name() = None;
+    }
+}
+
+/// The numeric id of an id in a program, along with its kind.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+enum IdKey {
+    Type(u64),
+    Libfunc(u64),
+    Function(u64),
+    Var(u64),
+    UserType(BigUint),
+}
+
+/// Calls `f` on every id in the program.
+fn visit_program_ids(program: &mut Program, f: &mut dyn FnMut(IdMut<'_>)) {
+    for declaration in &mut program.type_declarations {
+        f(IdMut::Type(&mut declaration.id));
+        visit_generic_args_ids(&mut declaration.long_id.generic_args, f);
+    }
+    for declaration in &mut program.libfunc_declarations {
+        f(IdMut::Libfunc(&mut declaration.id));
+        visit_generic_args_ids(&mut declaration.long_id.generic_args, f);
+    }
+    for statement in &mut program.statements {
+        match statement {
+            Statement::Invocation(invocation) => {
+                f(IdMut::Libfunc(&mut invocation.libfunc_id));
+                for var in &mut invocation.args {
+                    f(IdMut::Var(var));
+                }
+                for branch in &mut invocation.branches {
+                    for var in &mut branch.results {
+                        f(IdMut::Var(var));
+                    }
+                }
+            }
+            Statement::Return(vars) => {
+                for var in vars {
+                    f(IdMut::Var(var));
+                }
+            }
+        }
+    }
+    for func in &mut program.funcs {
+        f(IdMut::Function(&mut func.id));
+        for ty in func.signature.param_types.iter_mut().chain(&mut func.signature.ret_types) {
+            f(IdMut::Type(ty));
+        }
+        for param in &mut func.params {
+            f(IdMut::Var(&mut param.id));
+            f(IdMut::Type(&mut param.ty));
+        }
+    }
+}
+
+/// Calls `f` on every id in the generic args.
+fn visit_generic_args_ids(generic_args: &mut [GenericArg], f: &mut dyn FnMut(IdMut<'_>)) {
+    for generic_arg in generic_args {
+        match generic_arg {
+            GenericArg::Type(id) => f(IdMut::Type(id)),
+            GenericArg::UserType(id) => f(IdMut::UserType(id)),
+            GenericArg::UserFunc(id) => f(IdMut::Function(id)),
+            GenericArg::Libfunc(id) => f(IdMut::Libfunc(id)),
+            GenericArg::Value(_) => {}
+        }
+    }
+}
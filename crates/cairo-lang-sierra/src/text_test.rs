@@ -0,0 +1,179 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use indoc::indoc;
+use pretty_assertions::assert_eq;
+
+use super::{IdMut, TextFormatError, artifact_from_text, artifact_to_text, visit_program_ids};
+use crate::ProgramParser;
+use crate::debug_info::DebugInfo;
+use crate::ids::ConcreteTypeId;
+use crate::program::{Program, ProgramArtifact, Statement};
+
+/// Returns the paths of all the example Sierra programs.
+fn example_paths() -> Vec<PathBuf> {
+    let manifest_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let dirs = [manifest_dir.join("examples"), manifest_dir.join("../../tests/test_data")];
+    let mut paths: Vec<_> = dirs
+        .iter()
+        .flat_map(|dir| std::fs::read_dir(dir).expect("Could not read examples directory."))
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "sierra"))
+        .collect();
+    paths.sort();
+    assert!(!paths.is_empty());
+    paths
+}
+
+fn parse_example(path: &PathBuf) -> Program {
+    ProgramParser::new()
+        .parse(&std::fs::read_to_string(path).expect("Could not read example program."))
+        .unwrap_or_else(|err| panic!("Could not parse {}: {err}", path.display()))
+}
+
+/// Replaces all the declaration ids of the program with their index, keeping their debug names,
+/// similarly to the ids of a compiled contract.
+fn make_ids_canonical(program: &mut Program) {
+    let type_ids: HashMap<_, _> = program
+        .type_declarations
+        .iter()
+        .enumerate()
+        .map(|(i, declaration)| (declaration.id.id, i as u64))
+        .collect();
+    let libfunc_ids: HashMap<_, _> = program
+        .libfunc_declarations
+        .iter()
+        .enumerate()
+        .map(|(i, declaration)| (declaration.id.id, i as u64))
+        .collect();
+    let function_ids: HashMap<_, _> =
+        program.funcs.iter().enumerate().map(|(i, func)| (func.id.id, i as u64)).collect();
+    visit_program_ids(program, &mut |id| match id {
+        IdMut::Type(id) => id.id = type_ids[&id.id],
+        IdMut::Libfunc(id) => id.id = libfunc_ids[&id.id],
+        IdMut::Function(id) => id.id = function_ids[&id.id],
+        IdMut::Var(_) | IdMut::UserType(_) => {}
+    });
+}
+
+fn assert_round_trip(artifact: &ProgramArtifact) -> String {
+    let text = artifact_to_text(artifact).unwrap();
+    let parsed = artifact_from_text(&text).unwrap();
+    assert_eq!(serde_json::to_value(&parsed).unwrap(), serde_json::to_value(artifact).unwrap());
+    text
+}
+
+#[test]
+fn test_examples_round_trip() {
+    for path in example_paths() {
+        let program = parse_example(&path);
+
+        // Ids derived from their names need no metadata.
+        let text = assert_round_trip(&ProgramArtifact::stripped(program.clone()));
+        assert_eq!(text, program.to_string(), "{}", path.display());
+
+        // Canonical ids are kept alongside their names, with the debug info and annotations.
+        let mut canonical = program.clone();
+        make_ids_canonical(&mut canonical);
+        let mut debug_info = DebugInfo::extract(&canonical);
+        debug_info
+            .annotations
+            .insert("github.com/starkware-libs/cairo".into(), serde_json::json!({"a": [1, 2]}));
+        let text = assert_round_trip(
+            &ProgramArtifact::stripped(canonical.clone()).with_debug_info(debug_info.clone()),
+        );
+        assert!(text.starts_with(&program.to_string()), "{}", path.display());
+
+        // Ids without names are kept as numeric ids.
+        let mut stripped = canonical;
+        visit_program_ids(&mut stripped, &mut |id| id.strip_debug_name());
+        assert_round_trip(&ProgramArtifact::stripped(stripped).with_debug_info(debug_info));
+    }
+}
+
+#[test]
+fn test_ambiguous_names() {
+    let mut program = ProgramParser::new()
+        .parse(indoc! {"
+            type felt252 = felt252;
+            type NonZeroFelt252 = NonZero<felt252>;
+
+            libfunc felt252_is_zero = felt252_is_zero;
+
+            felt252_is_zero([0]) { fallthrough() 2([1]) };
+            return ();
+            return ([1]);
+
+            Foo@0([0]: felt252) -> (NonZeroFelt252);
+        "})
+        .unwrap();
+    // Two different types named `felt252`.
+    program.type_declarations[1].id.debug_name = Some("felt252".into());
+    program.funcs[0].signature.ret_types[0].debug_name = Some("felt252".into());
+    let artifact = ProgramArtifact::stripped(program);
+    let text = assert_round_trip(&artifact);
+    assert!(text.contains(&format!("type [{}] = ", artifact.program.type_declarations[1].id.id)));
+}
+
+#[test]
+fn test_inconsistent_names() {
+    let mut program = ProgramParser::new()
+        .parse(indoc! {"
+            type felt252 = felt252;
+
+            return (a);
+
+            Foo@0(a: felt252) -> (felt252);
+        "})
+        .unwrap();
+    program.type_declarations[0].id.id = 0;
+    program.funcs[0].params[0].ty.id = 0;
+    program.funcs[0].signature.param_types[0].id = 0;
+    program.funcs[0].signature.ret_types[0] =
+        ConcreteTypeId { id: 0, debug_name: Some("other".into()) };
+    assert!(matches!(
+        artifact_to_text(&ProgramArtifact::stripped(program)),
+        Err(TextFormatError::NotRepresentable)
+    ));
+}
+
+#[test]
+fn test_text_without_metadata() {
+    let text = indoc! {"
+        type felt252 = felt252;
+
+        return (a);
+
+        Foo@0(a: felt252) -> (felt252);
+    "};
+    let artifact = artifact_from_text(text).unwrap();
+    assert_eq!(artifact.debug_info, None);
+    assert_eq!(artifact.program, ProgramParser::new().parse(text).unwrap());
+    assert!(matches!(artifact_from_text("type = ;\n"), Err(TextFormatError::ParseError(_))));
+}
+
+#[test]
+fn test_unparsable_names() {
+    let mut program = ProgramParser::new()
+        .parse(indoc! {"
+            type felt252 = felt252;
+            type NonZeroFelt252 = NonZero<felt252>;
+
+            return (a);
+
+            Foo@0(a: felt252) -> (felt252);
+        "})
+        .unwrap();
+    // A name the parser normalizes is printed by numeric id, other names are kept.
+    program.type_declarations[1].id.debug_name = Some("NonZero::<(felt252,)>".into());
+    let text = assert_round_trip(&ProgramArtifact::stripped(program.clone()));
+    assert!(text.starts_with(&format!(
+        "type felt252 = felt252;\ntype [{}] = NonZero<felt252>;",
+        program.type_declarations[1].id.id
+    )));
+
+    // A name that breaks the parsing.
+    program.funcs[0].params[0].id.debug_name = Some("a b".into());
+    program.statements[0] = Statement::Return(vec![program.funcs[0].params[0].id.clone()]);
+    assert_round_trip(&ProgramArtifact::stripped(program));
+}
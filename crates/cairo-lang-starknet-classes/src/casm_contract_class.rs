@@ -327,11 +327,13 @@ impl CasmContractClass {
         contract_class: ContractClass,
         add_pythonic_hints: bool,
         max_bytecode_size: usize,
+        peephole_optimizations: bool,
     ) -> Result<Self, StarknetSierraCompilationError> {
         Ok(Self::from_contract_class_with_debug_info(
             contract_class,
             add_pythonic_hints,
             max_bytecode_size,
+            peephole_optimizations,
         )?
         .0)
     }
@@ -340,6 +342,7 @@ impl CasmContractClass {
         contract_class: ContractClass,
         add_pythonic_hints: bool,
         max_bytecode_size: usize,
+        peephole_optimizations: bool,
     ) -> Result<(Self, CairoProgramDebugInfo), StarknetSierraCompilationError> {
         let prime = Felt252::prime();
         for felt252 in &contract_class.sierra_program {
@@ -425,7 +428,7 @@ impl CasmContractClass {
         let cairo_program = cairo_lang_sierra_to_casm::compiler::compile(
             &program,
             &metadata,
-            SierraToCasmConfig { gas_usage_check: true, max_bytecode_size },
+            SierraToCasmConfig { gas_usage_check: true, max_bytecode_size, peephole_optimizations },
         )?;
 
         let AssembledCairoProgram { bytecode, hints } = cairo_program.assemble();
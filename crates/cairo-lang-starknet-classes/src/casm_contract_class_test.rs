@@ -24,8 +24,14 @@ fn test_casm_contract_from_contract_class_failure(name: &str) {
     contract_class.sierra_program[17] = BigUintAsHex { value: Felt252::prime() };
 
     let add_pythonic_hints = false;
+    let peephole_optimizations = false;
     assert_eq!(
-        CasmContractClass::from_contract_class(contract_class, add_pythonic_hints, usize::MAX),
+        CasmContractClass::from_contract_class(
+            contract_class,
+            add_pythonic_hints,
+            usize::MAX,
+            peephole_optimizations,
+        ),
         Err(StarknetSierraCompilationError::ValueOutOfRange)
     );
 }
@@ -53,12 +59,23 @@ fn test_casm_contract_from_contract_class_from_contracts_crate(name: &str) {
         serde_json::from_reader(BufReader::new(std::fs::File::open(contract_path).unwrap()))
             .unwrap();
     let add_pythonic_hints = true;
-    let casm_contract =
-        CasmContractClass::from_contract_class(contract, add_pythonic_hints, usize::MAX).unwrap();
+    let peephole_optimizations = false;
+    let casm_contract = CasmContractClass::from_contract_class(
+        contract.clone(),
+        add_pythonic_hints,
+        usize::MAX,
+        peephole_optimizations,
+    )
+    .unwrap();
     compare_contents_or_fix_with_path(
         &get_example_file_path(&format!("{name}.compiled_contract_class.json")),
         serde_json::to_string_pretty(&casm_contract).unwrap() + "\n",
     );
+
+    let optimized_casm_contract =
+        CasmContractClass::from_contract_class(contract, add_pythonic_hints, usize::MAX, true)
+            .unwrap();
+    assert!(optimized_casm_contract.bytecode.len() <= casm_contract.bytecode.len());
 }
 
 // TODO(Tomer-C): Check for more concrete types per libfunc.
@@ -0,0 +1,86 @@
+use cairo_lang_sierra_to_casm::compiler::{SierraToCasmConfig, compile};
+use cairo_lang_sierra_to_casm::cost_report::{CostReport, FunctionCostReport, TokenCosts};
+use cairo_lang_sierra_to_casm::metadata::{MetadataComputationConfig, calc_metadata};
+use num_bigint::BigUint;
+use serde::{Deserialize, Serialize};
+
+use crate::casm_contract_class::StarknetSierraCompilationError;
+use crate::contract_class::{ContractClass, ContractEntryPoint};
+
+#[cfg(test)]
+#[path = "contract_cost_report_test.rs"]
+mod test;
+
+/// A static cost report of a contract class, for comparing the costs of versions of a contract.
+///
+/// The costs include the runtime cost token types (steps, holes and range checks), and are computed
+/// without the fixed entry point cost enforced when compiling the class for the network.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContractCostReport {
+    /// The costs of all the functions of the contract.
+    pub functions: Vec<FunctionCostReport>,
+    pub entry_points_by_type: EntryPointsCostReport,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EntryPointsCostReport {
+    #[serde(rename = "EXTERNAL")]
+    pub external: Vec<EntryPointCostReport>,
+    #[serde(rename = "L1_HANDLER")]
+    pub l1_handler: Vec<EntryPointCostReport>,
+    #[serde(rename = "CONSTRUCTOR")]
+    pub constructor: Vec<EntryPointCostReport>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EntryPointCostReport {
+    /// A field element that encodes the signature of the called function.
+    #[serde(
+        serialize_with = "cairo_lang_utils::bigint::serialize_big_uint",
+        deserialize_with = "cairo_lang_utils::bigint::deserialize_big_uint"
+    )]
+    pub selector: BigUint,
+    /// The name of the function of the entry point.
+    pub function: String,
+    /// The gas required for calling the entry point.
+    pub entry_cost: Option<TokenCosts>,
+}
+
+impl ContractCostReport {
+    /// Computes the report of the given contract class.
+    pub fn new(contract_class: &ContractClass) -> Result<Self, StarknetSierraCompilationError> {
+        let program = contract_class.extract_sierra_program()?;
+        let metadata = calc_metadata(&program, MetadataComputationConfig {
+            compute_runtime_costs: true,
+            ..Default::default()
+        })?;
+        let cairo_program = compile(&program, &metadata, SierraToCasmConfig {
+            gas_usage_check: true,
+            max_bytecode_size: usize::MAX,
+            peephole_optimizations: false,
+        })?;
+        let CostReport { functions } = CostReport::new(&program, &metadata, &cairo_program);
+        let entry_points_report = |entry_points: &[ContractEntryPoint]| {
+            entry_points
+                .iter()
+                .map(|entry_point| {
+                    let function = functions
+                        .get(entry_point.function_idx)
+                        .ok_or(StarknetSierraCompilationError::EntryPointError)?;
+                    Ok(EntryPointCostReport {
+                        selector: entry_point.selector.clone(),
+                        function: function.name.clone(),
+                        entry_cost: function.entry_cost.clone(),
+                    })
+                })
+                .collect::<Result<Vec<_>, StarknetSierraCompilationError>>()
+        };
+        let entry_points = &contract_class.entry_points_by_type;
+        let entry_points_by_type = EntryPointsCostReport {
+            external: entry_points_report(&entry_points.external)?,
+            l1_handler: entry_points_report(&entry_points.l1_handler)?,
+            constructor: entry_points_report(&entry_points.constructor)?,
+        };
+        Ok(Self { functions, entry_points_by_type })
+    }
+}
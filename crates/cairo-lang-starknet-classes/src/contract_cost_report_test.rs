@@ -0,0 +1,31 @@
+use std::io::BufReader;
+
+use cairo_lang_test_utils::compare_contents_or_fix_with_path;
+use test_case::test_case;
+
+use super::ContractCostReport;
+use crate::contract_class::ContractClass;
+use crate::test_utils::get_example_file_path;
+
+/// Tests that the cost report of a contract is the same as in <test_case>.cost_report.json.
+#[test_case("minimal_contract__minimal_contract")]
+#[test_case("hello_starknet__hello_starknet")]
+fn test_contract_cost_report(name: &str) {
+    let contract_path = get_example_file_path(&format!("{name}.contract_class.json"));
+    let contract: ContractClass =
+        serde_json::from_reader(BufReader::new(std::fs::File::open(contract_path).unwrap()))
+            .unwrap();
+    let report = ContractCostReport::new(&contract).unwrap();
+    for function in &report.functions {
+        // The worst-case cost includes the cost of entering the function.
+        if let (Some(entry_cost), Some(max_cost)) = (&function.entry_cost, &function.max_cost) {
+            for (token, cost) in entry_cost {
+                assert!(max_cost[token] >= *cost, "{}: {token}", function.name);
+            }
+        }
+    }
+    compare_contents_or_fix_with_path(
+        &get_example_file_path(&format!("{name}.cost_report.json")),
+        serde_json::to_string_pretty(&report).unwrap() + "\n",
+    );
+}
@@ -10,10 +10,12 @@ pub mod allowed_libfuncs;
 pub mod casm_contract_class;
 pub mod compiler_version;
 pub mod contract_class;
+pub mod contract_cost_report;
 mod contract_segmentation;
 mod felt252_serde;
 mod felt252_vec_compression;
 pub mod keccak;
+pub mod sierra_convert;
 
 pub use contract_segmentation::NestedIntList;
 
@@ -0,0 +1,147 @@
+//! Conversions of Sierra programs between their text, JSON and contract class felt252 forms.
+
+use cairo_lang_sierra::debug_info::DebugInfo;
+use cairo_lang_sierra::program::{ProgramArtifact, VersionedProgram};
+use cairo_lang_sierra::text::{TextFormatError, artifact_from_text, artifact_to_text};
+use cairo_lang_utils::bigint::BigUintAsHex;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::compiler_version::{VersionId, current_compiler_version_id, current_sierra_version_id};
+use crate::felt252_serde::{Felt252SerdeError, sierra_from_felt252s, sierra_to_felt252s};
+
+#[cfg(test)]
+#[path = "sierra_convert_test.rs"]
+mod test;
+
+/// The debug info annotation holding the versions of a program read from its felt252 form, so that
+/// converting it back to felt252s keeps them.
+pub const SIERRA_VERSIONS_ANNOTATION: &str = "github.com/starkware-libs/cairo/sierra-versions";
+
+#[derive(Error, Debug)]
+pub enum SierraConvertError {
+    #[error(transparent)]
+    TextFormatError(#[from] TextFormatError),
+    #[error(transparent)]
+    JsonError(#[from] serde_json::Error),
+    #[error(transparent)]
+    Felt252SerdeError(#[from] Felt252SerdeError),
+}
+
+/// The forms of a Sierra program.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SierraFormat {
+    /// The Sierra text format, see [cairo_lang_sierra::text].
+    Text,
+    /// A JSON serialized [VersionedProgram].
+    Json,
+    /// A JSON serialized [Felt252Program], or a full contract class.
+    Felts,
+}
+impl SierraFormat {
+    /// Detects the format of the given content.
+    pub fn detect(content: &str) -> Self {
+        if !content.trim_start().starts_with('{') {
+            return SierraFormat::Text;
+        }
+        match serde_json::from_str::<serde_json::Value>(content) {
+            Ok(value) if value.get("sierra_program").is_some() => SierraFormat::Felts,
+            _ => SierraFormat::Json,
+        }
+    }
+}
+
+/// A Sierra program in its contract class felt252 form.
+///
+/// Has the same fields as [crate::contract_class::ContractClass], so a contract class can be
+/// deserialized as a [Felt252Program] as well.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Felt252Program {
+    pub sierra_program: Vec<BigUintAsHex>,
+    pub sierra_program_debug_info: Option<DebugInfo>,
+}
+
+/// The versions of a program in its felt252 form.
+#[derive(Serialize, Deserialize)]
+struct SierraVersions {
+    sierra_version: VersionId,
+    compiler_version: VersionId,
+}
+
+impl Felt252Program {
+    /// Converts the program into an artifact, with the names of the debug info populated into the
+    /// program. The versions of the program are kept in the [SIERRA_VERSIONS_ANNOTATION]
+    /// annotation of the debug info.
+    pub fn into_artifact(self) -> Result<ProgramArtifact, SierraConvertError> {
+        let (sierra_version, compiler_version, mut program) =
+            sierra_from_felt252s(&self.sierra_program)?;
+        let mut debug_info = self.sierra_program_debug_info.unwrap_or_default();
+        debug_info.populate(&mut program);
+        debug_info.annotations.insert(
+            SIERRA_VERSIONS_ANNOTATION.into(),
+            serde_json::to_value(SierraVersions { sierra_version, compiler_version })?,
+        );
+        Ok(ProgramArtifact { program, debug_info: Some(debug_info) })
+    }
+
+    /// Converts an artifact into its felt252 form.
+    ///
+    /// The ids of the declarations in the program must be their indices, as in compiled contracts.
+    /// The debug info of the artifact is extended with the debug names of the program, except for
+    /// the names of variables and user types, which the felt252 form can not hold. The versions are
+    /// taken from the [SIERRA_VERSIONS_ANNOTATION] annotation if present, and are the current
+    /// versions otherwise. Empty debug info is omitted.
+    pub fn from_artifact(artifact: &ProgramArtifact) -> Result<Self, SierraConvertError> {
+        let mut debug_info = artifact.debug_info.clone().unwrap_or_default();
+        let versions = match debug_info.annotations.shift_remove(SIERRA_VERSIONS_ANNOTATION) {
+            Some(versions) => serde_json::from_value(versions)?,
+            None => SierraVersions {
+                sierra_version: current_sierra_version_id(),
+                compiler_version: current_compiler_version_id(),
+            },
+        };
+        let names = DebugInfo::extract(&artifact.program);
+        debug_info.type_names.extend(names.type_names);
+        debug_info.libfunc_names.extend(names.libfunc_names);
+        debug_info.user_func_names.extend(names.user_func_names);
+        Ok(Self {
+            sierra_program: sierra_to_felt252s(
+                versions.sierra_version,
+                versions.compiler_version,
+                &artifact.program,
+            )?,
+            sierra_program_debug_info: (debug_info != DebugInfo::default()).then_some(debug_info),
+        })
+    }
+}
+
+/// Parses a Sierra program in the given format.
+pub fn parse_sierra(
+    content: &str,
+    format: SierraFormat,
+) -> Result<ProgramArtifact, SierraConvertError> {
+    match format {
+        SierraFormat::Text => Ok(artifact_from_text(content)?),
+        SierraFormat::Json => {
+            let VersionedProgram::V1 { program, .. } = serde_json::from_str(content)?;
+            Ok(program)
+        }
+        SierraFormat::Felts => serde_json::from_str::<Felt252Program>(content)?.into_artifact(),
+    }
+}
+
+/// Formats a Sierra program in the given format.
+pub fn format_sierra(
+    artifact: &ProgramArtifact,
+    format: SierraFormat,
+) -> Result<String, SierraConvertError> {
+    match format {
+        SierraFormat::Text => Ok(artifact_to_text(artifact)?),
+        SierraFormat::Json => {
+            Ok(serde_json::to_string_pretty(&VersionedProgram::v1(artifact.clone()))?)
+        }
+        SierraFormat::Felts => {
+            Ok(serde_json::to_string_pretty(&Felt252Program::from_artifact(artifact)?)?)
+        }
+    }
+}
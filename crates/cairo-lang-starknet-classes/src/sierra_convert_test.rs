@@ -0,0 +1,71 @@
+use std::fs::read_to_string;
+use std::path::PathBuf;
+
+use cairo_lang_sierra::ProgramParser;
+use cairo_lang_sierra::debug_info::DebugInfo;
+use cairo_lang_sierra::program::ProgramArtifact;
+use cairo_lang_sierra_generator::canonical_id_replacer::CanonicalReplacer;
+use cairo_lang_sierra_generator::replace_ids::SierraIdReplacer;
+use pretty_assertions::assert_eq;
+
+use super::{Felt252Program, SierraFormat, format_sierra, parse_sierra};
+use crate::test_utils::get_example_file_path;
+
+/// Returns the paths of all the example files with the given suffix.
+fn example_paths(suffix: &str) -> Vec<PathBuf> {
+    let mut paths: Vec<_> = std::fs::read_dir(get_example_file_path(""))
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.to_str().unwrap().ends_with(suffix))
+        .collect();
+    paths.sort();
+    assert!(!paths.is_empty());
+    paths
+}
+
+/// Converts the artifact through all the formats, checking the format detection and that each
+/// conversion keeps the artifact as is.
+fn convert_through_all_formats(artifact: &ProgramArtifact) -> ProgramArtifact {
+    let mut artifact = artifact.clone();
+    for format in [SierraFormat::Text, SierraFormat::Json, SierraFormat::Felts] {
+        let content = format_sierra(&artifact, format).unwrap();
+        assert_eq!(SierraFormat::detect(&content), format);
+        let parsed = parse_sierra(&content, format).unwrap();
+        if format != SierraFormat::Felts {
+            assert_eq!(parsed, artifact);
+        }
+        artifact = parsed;
+    }
+    artifact
+}
+
+#[test]
+fn test_contract_classes_round_trip() {
+    for path in example_paths(".contract_class.json") {
+        let content = read_to_string(&path).unwrap();
+        assert_eq!(SierraFormat::detect(&content), SierraFormat::Felts);
+        let felts: Felt252Program = serde_json::from_str(&content).unwrap();
+        let artifact = parse_sierra(&content, SierraFormat::Felts).unwrap();
+        assert_eq!(convert_through_all_formats(&artifact), artifact, "{}", path.display());
+        assert_eq!(Felt252Program::from_artifact(&artifact).unwrap(), felts, "{}", path.display());
+    }
+}
+
+#[test]
+fn test_sierra_programs_round_trip() {
+    for path in example_paths(".sierra") {
+        let program = ProgramParser::new().parse(&read_to_string(&path).unwrap()).unwrap();
+        let program = CanonicalReplacer::from_program(&program).apply(&program);
+        let artifact = ProgramArtifact::stripped(program.clone())
+            .with_debug_info(DebugInfo::extract(&program));
+        let converted = convert_through_all_formats(&artifact);
+        // Only the versions annotation is added by the felt252 form.
+        assert_eq!(converted.program, artifact.program, "{}", path.display());
+        assert_eq!(
+            Felt252Program::from_artifact(&converted).unwrap(),
+            Felt252Program::from_artifact(&artifact).unwrap(),
+            "{}",
+            path.display()
+        );
+    }
+}
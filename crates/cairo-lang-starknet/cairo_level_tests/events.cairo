@@ -131,3 +131,69 @@ fn test_pop_log() {
     assert_eq!(starknet::testing::pop_log_raw(contract_address), Option::Some((keys, data)));
     assert_eq!(starknet::testing::pop_log_raw(contract_address), Option::Some((keys, data)));
 }
+
+#[test]
+fn test_get_logs() {
+    let (contract_address, _) = deploy_syscall(
+        contract_with_event::TEST_CLASS_HASH.try_into().unwrap(), 0, [].span(), false
+    )
+        .unwrap();
+    let mut contract = IContractWithEventDispatcher { contract_address };
+    contract.emit_event(true);
+    contract.emit_event(false);
+    contract.emit_event(true);
+
+    let incremental_events = array![
+        Event::IncrementalEvent(IncrementalEvent { value: 0 }),
+        Event::IncrementalEvent(IncrementalEvent { value: 1 }),
+    ];
+    assert_eq!(starknet::testing::count_logs(contract_address), 3);
+    assert_eq!(
+        starknet::testing::get_logs_with_key(contract_address, selector!("IncrementalEvent")),
+        incremental_events
+    );
+    // Getting the events does not pop them.
+    assert_eq!(
+        starknet::testing::get_logs(contract_address),
+        array![
+            Event::IncrementalEvent(IncrementalEvent { value: 0 }),
+            Event::StaticEvent(StaticEvent {}),
+            Event::IncrementalEvent(IncrementalEvent { value: 1 }),
+        ]
+    );
+    assert_eq!(
+        starknet::testing::pop_log(contract_address),
+        Option::Some(Event::IncrementalEvent(IncrementalEvent { value: 0 }))
+    );
+    assert_eq!(starknet::testing::count_logs(contract_address), 2);
+
+    starknet::testing::clear_logs(contract_address);
+    assert_eq!(starknet::testing::count_logs(contract_address), 0);
+    assert!(starknet::testing::pop_log_raw(contract_address).is_none());
+}
+
+#[test]
+fn test_get_all_logs() {
+    let first = starknet::contract_address_const::<0x1234>();
+    let second = starknet::contract_address_const::<0x2345>();
+    starknet::testing::set_contract_address(first);
+    starknet::syscalls::emit_event_syscall([1].span(), [10].span()).unwrap_syscall();
+    starknet::testing::set_contract_address(second);
+    starknet::syscalls::emit_event_syscall([2].span(), [20].span()).unwrap_syscall();
+    starknet::testing::set_contract_address(first);
+    starknet::syscalls::emit_event_syscall([3].span(), [30].span()).unwrap_syscall();
+
+    assert_eq!(
+        starknet::testing::get_all_logs_raw(),
+        array![
+            (first, [1].span(), [10].span()),
+            (second, [2].span(), [20].span()),
+            (first, [3].span(), [30].span()),
+        ]
+    );
+    starknet::testing::pop_log_raw(first).unwrap();
+    assert_eq!(
+        starknet::testing::get_all_logs_raw(),
+        array![(second, [2].span(), [20].span()), (first, [3].span(), [30].span())]
+    );
+}
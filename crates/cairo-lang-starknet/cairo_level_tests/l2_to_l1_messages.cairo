@@ -100,3 +100,21 @@ fn test_pop_l2_to_l1_message() {
         testing::pop_l2_to_l1_message(contract_address), Option::Some((to_address, payload))
     );
 }
+
+#[test]
+fn test_get_l2_to_l1_messages() {
+    let contract_address = starknet::contract_address_const::<0x42>();
+    testing::set_contract_address(contract_address);
+
+    starknet::syscalls::send_message_to_l1_syscall(1, [10].span()).unwrap_syscall();
+    starknet::syscalls::send_message_to_l1_syscall(2, [20, 21].span()).unwrap_syscall();
+
+    let messages = array![(1, [10].span()), (2, [20, 21].span())];
+    assert_eq!(testing::get_l2_to_l1_messages(contract_address), messages);
+    // Getting the messages does not pop them.
+    assert_eq!(testing::get_l2_to_l1_messages(contract_address), messages);
+
+    testing::clear_l2_to_l1_messages(contract_address);
+    assert_eq!(testing::get_l2_to_l1_messages(contract_address), array![]);
+    assert!(testing::pop_l2_to_l1_message(contract_address).is_none());
+}
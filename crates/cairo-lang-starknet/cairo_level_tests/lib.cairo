@@ -17,10 +17,14 @@ mod keccak;
 #[cfg(test)]
 mod l2_to_l1_messages;
 #[cfg(test)]
+mod mocks;
+#[cfg(test)]
 mod multi_component_test;
 #[cfg(test)]
 mod replace_class_test;
 #[cfg(test)]
+mod state_snapshots;
+#[cfg(test)]
 mod storage_access;
 #[cfg(test)]
 mod contract_address_test;
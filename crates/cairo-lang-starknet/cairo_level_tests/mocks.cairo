@@ -0,0 +1,101 @@
+use starknet::syscalls::{
+    call_contract_syscall, deploy_syscall, get_class_hash_at_syscall, library_call_syscall,
+    storage_read_syscall, storage_write_syscall
+};
+use starknet::SyscallResultTrait;
+use starknet::testing::{
+    clear_failing_syscall, clear_mock_call, clear_mock_library_call, fail_syscall, mock_call,
+    mock_library_call
+};
+
+#[starknet::interface]
+trait IValue<T> {
+    fn get(self: @T) -> u128;
+}
+
+#[starknet::contract]
+mod value_contract {
+    #[storage]
+    struct Storage {}
+
+    #[external(v0)]
+    fn get(self: @ContractState) -> u128 {
+        7
+    }
+}
+
+fn deploy_value_contract() -> IValueDispatcher {
+    let (contract_address, _) = deploy_syscall(
+        value_contract::TEST_CLASS_HASH.try_into().unwrap(), 0, [].span(), false
+    )
+        .unwrap_syscall();
+    IValueDispatcher { contract_address }
+}
+
+#[test]
+fn test_mock_call_of_undeployed_contract() {
+    let contract_address = 0x1234.try_into().unwrap();
+    mock_call(contract_address, selector!("get"), Result::Ok([5].span()));
+    assert_eq!(IValueDispatcher { contract_address }.get(), 5);
+}
+
+#[test]
+fn test_mock_call_revert() {
+    let contract_address = 0x1234.try_into().unwrap();
+    mock_call(contract_address, selector!("get"), Result::Err(array!['mocked failure']));
+    assert_eq!(
+        call_contract_syscall(contract_address, selector!("get"), [].span()),
+        Result::Err(array!['mocked failure', 'ENTRYPOINT_FAILED'])
+    );
+}
+
+#[test]
+fn test_mock_call_of_deployed_contract() {
+    let contract = deploy_value_contract();
+    mock_call(contract.contract_address, selector!("get"), Result::Ok([5].span()));
+    assert_eq!(contract.get(), 5);
+    clear_mock_call(contract.contract_address, selector!("get"));
+    assert_eq!(contract.get(), 7);
+}
+
+#[test]
+fn test_mock_library_call() {
+    let class_hash = value_contract::TEST_CLASS_HASH.try_into().unwrap();
+    mock_library_call(class_hash, selector!("get"), Result::Ok([5].span()));
+    assert_eq!(
+        library_call_syscall(class_hash, selector!("get"), [].span()), Result::Ok([5].span())
+    );
+    clear_mock_library_call(class_hash, selector!("get"));
+    assert_eq!(
+        library_call_syscall(class_hash, selector!("get"), [].span()), Result::Ok([7].span())
+    );
+}
+
+#[test]
+fn test_fail_storage_read() {
+    let address = 0x10.try_into().unwrap();
+    storage_write_syscall(0, address, 100).unwrap_syscall();
+    fail_syscall('StorageRead', ['forced failure'].span());
+    assert_eq!(storage_read_syscall(0, address), Result::Err(array!['forced failure']));
+    clear_failing_syscall('StorageRead');
+    assert_eq!(storage_read_syscall(0, address), Result::Ok(100));
+}
+
+#[test]
+fn test_fail_deploy() {
+    fail_syscall('Deploy', ['forced failure'].span());
+    let class_hash = value_contract::TEST_CLASS_HASH.try_into().unwrap();
+    let result = deploy_syscall(class_hash, 0, [].span(), false);
+    assert_eq!(result, Result::Err(array!['forced failure']));
+    clear_failing_syscall('Deploy');
+    // The failed deployment had no effect, so the contract may still be deployed.
+    let (contract_address, _) = deploy_syscall(class_hash, 0, [].span(), false).unwrap_syscall();
+    assert_eq!(get_class_hash_at_syscall(contract_address), Result::Ok(class_hash));
+}
+
+#[test]
+fn test_fail_call_contract() {
+    let contract = deploy_value_contract();
+    fail_syscall('CallContract', [].span());
+    assert!(call_contract_syscall(contract.contract_address, selector!("get"), [].span()).is_err());
+}
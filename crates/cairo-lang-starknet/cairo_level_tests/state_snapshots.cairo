@@ -0,0 +1,112 @@
+use starknet::syscalls::deploy_syscall;
+use starknet::SyscallResultTrait;
+use starknet::testing::{revert_to_snapshot, snapshot_state};
+
+#[starknet::interface]
+trait ICounter<T> {
+    fn increment(ref self: T);
+    fn get(self: @T) -> u128;
+}
+
+#[starknet::contract]
+mod counter {
+    use starknet::storage::{StoragePointerReadAccess, StoragePointerWriteAccess};
+
+    #[storage]
+    struct Storage {
+        value: u128,
+    }
+
+    #[derive(Copy, Drop, Debug, PartialEq, starknet::Event)]
+    pub struct Incremented {
+        pub value: u128,
+    }
+
+    #[event]
+    #[derive(Copy, Drop, Debug, PartialEq, starknet::Event)]
+    pub enum Event {
+        Incremented: Incremented,
+    }
+
+    #[external(v0)]
+    fn increment(ref self: ContractState) {
+        let value = self.value.read() + 1;
+        self.value.write(value);
+        self.emit(Incremented { value });
+    }
+
+    #[external(v0)]
+    fn get(self: @ContractState) -> u128 {
+        self.value.read()
+    }
+}
+
+fn deploy_counter() -> ICounterDispatcher {
+    let (contract_address, _) = deploy_syscall(
+        counter::TEST_CLASS_HASH.try_into().unwrap(), 0, [].span(), false
+    )
+        .unwrap_syscall();
+    ICounterDispatcher { contract_address }
+}
+
+#[test]
+fn test_revert_storage() {
+    let contract = deploy_counter();
+    contract.increment();
+    let snapshot_id = snapshot_state();
+    contract.increment();
+    contract.increment();
+    assert_eq!(contract.get(), 3);
+    revert_to_snapshot(snapshot_id);
+    assert_eq!(contract.get(), 1);
+    contract.increment();
+    assert_eq!(contract.get(), 2);
+    // A snapshot may be reverted to multiple times.
+    revert_to_snapshot(snapshot_id);
+    assert_eq!(contract.get(), 1);
+}
+
+#[test]
+fn test_revert_deployments() {
+    let snapshot_id = snapshot_state();
+    let contract = deploy_counter();
+    revert_to_snapshot(snapshot_id);
+    let (contract_address, _) = deploy_syscall(
+        counter::TEST_CLASS_HASH.try_into().unwrap(), 0, [].span(), false
+    )
+        .unwrap_syscall();
+    // The address is available again, as the deployment was reverted.
+    assert_eq!(contract_address, contract.contract_address);
+}
+
+#[test]
+fn test_revert_logs_and_execution_info() {
+    let contract = deploy_counter();
+    starknet::testing::set_block_number(10);
+    let snapshot_id = snapshot_state();
+    contract.increment();
+    starknet::testing::set_block_number(20);
+    revert_to_snapshot(snapshot_id);
+    assert_eq!(starknet::get_block_info().block_number, 10);
+    assert_eq!(starknet::testing::pop_log_raw(contract.contract_address), Option::None);
+}
+
+#[test]
+fn test_multiple_snapshots() {
+    let contract = deploy_counter();
+    let first = snapshot_state();
+    contract.increment();
+    let second = snapshot_state();
+    contract.increment();
+    revert_to_snapshot(first);
+    assert_eq!(contract.get(), 0);
+    revert_to_snapshot(second);
+    assert_eq!(contract.get(), 1);
+}
+
+#[test]
+#[should_panic(expected: ('Invalid snapshot id',))]
+fn test_revert_to_invalid_snapshot() {
+    let snapshot_id = snapshot_state();
+    revert_to_snapshot(snapshot_id + 1);
+}
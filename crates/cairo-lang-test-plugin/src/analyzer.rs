@@ -0,0 +1,112 @@
+use cairo_lang_defs::ids::{FunctionWithBodyId, ModuleId, NamedLanguageElementId};
+use cairo_lang_defs::plugin::PluginDiagnostic;
+use cairo_lang_semantic::db::SemanticGroup;
+use cairo_lang_semantic::items::enm::SemanticEnumEx;
+use cairo_lang_semantic::plugin::AnalyzerPlugin;
+use cairo_lang_semantic::{ConcreteTypeId, GenericArgumentId, TypeId, TypeLongId};
+use cairo_lang_syntax::node::TypedStablePtr;
+use cairo_lang_utils::LookupIntern;
+
+use crate::test_config::{TestConfig, try_extract_test_config};
+
+/// The extern types of numeric values, which the fuzzer generates as such.
+const NUMERIC_EXTERN_TYPES: [&str; 17] = [
+    "felt252",
+    "u8",
+    "u16",
+    "u32",
+    "u64",
+    "u128",
+    "i8",
+    "i16",
+    "i32",
+    "i64",
+    "i128",
+    "bytes31",
+    "BoundedInt",
+    "ContractAddress",
+    "ClassHash",
+    "StorageAddress",
+    "StorageBaseAddress",
+];
+
+/// Plugin to add diagnostics for fuzz tests with parameters the fuzzer cannot generate arguments
+/// for.
+#[derive(Default, Debug)]
+pub struct FuzzAnalyzer;
+
+impl AnalyzerPlugin for FuzzAnalyzer {
+    fn diagnostics(&self, db: &dyn SemanticGroup, module_id: ModuleId) -> Vec<PluginDiagnostic> {
+        let mut diagnostics = vec![];
+        let Ok(free_functions) = db.module_free_functions_ids(module_id) else {
+            return diagnostics;
+        };
+        for free_function_id in free_functions.iter().copied() {
+            let Ok(attrs) =
+                db.function_with_body_attributes(FunctionWithBodyId::Free(free_function_id))
+            else {
+                continue;
+            };
+            // Invalid configurations are reported by the `TestPlugin`.
+            let Ok(Some(TestConfig { fuzz: Some(_), .. })) =
+                try_extract_test_config(db.upcast(), attrs)
+            else {
+                continue;
+            };
+            let Ok(signature) = db.free_function_signature(free_function_id) else { continue };
+            for param in &signature.params {
+                if !is_fuzzable(db, param.ty, &mut vec![]) {
+                    diagnostics.push(PluginDiagnostic::error(
+                        param.stable_ptr(db.upcast()).untyped(),
+                        format!("Arguments of type `{}` cannot be fuzzed.", param.ty.format(db)),
+                    ));
+                }
+            }
+        }
+        diagnostics
+    }
+}
+
+/// Returns whether the fuzzer can generate arguments of the given type: numbers, `NonZero`s,
+/// arrays and snapshots, and structs, tuples and enums made of them.
+///
+/// `visited` holds the types being checked, as a type may contain itself through an array.
+fn is_fuzzable(db: &dyn SemanticGroup, ty: TypeId, visited: &mut Vec<TypeId>) -> bool {
+    if visited.contains(&ty) {
+        return true;
+    }
+    visited.push(ty);
+    let fuzzable = match ty.lookup_intern(db) {
+        TypeLongId::Concrete(ConcreteTypeId::Struct(concrete_struct_id)) => {
+            db.concrete_struct_members(concrete_struct_id).is_ok_and(|members| {
+                members.values().all(|member| is_fuzzable(db, member.ty, visited))
+            })
+        }
+        TypeLongId::Concrete(ConcreteTypeId::Enum(concrete_enum_id)) => {
+            db.concrete_enum_variants(concrete_enum_id).is_ok_and(|variants| {
+                !variants.is_empty()
+                    && variants.iter().all(|variant| is_fuzzable(db, variant.ty, visited))
+            })
+        }
+        TypeLongId::Concrete(ConcreteTypeId::Extern(concrete_extern_type_id)) => {
+            let name = concrete_extern_type_id.extern_type_id(db).name(db.upcast());
+            match concrete_extern_type_id.lookup_intern(db).generic_args[..] {
+                [GenericArgumentId::Type(inner)]
+                    if ["Array", "NonZero"].contains(&name.as_str()) =>
+                {
+                    is_fuzzable(db, inner, visited)
+                }
+                _ => NUMERIC_EXTERN_TYPES.contains(&name.as_str()),
+            }
+        }
+        TypeLongId::Tuple(types) => types.into_iter().all(|ty| is_fuzzable(db, ty, visited)),
+        TypeLongId::Snapshot(inner) | TypeLongId::FixedSizeArray { type_id: inner, .. } => {
+            is_fuzzable(db, inner, visited)
+        }
+        // Missing types are already reported.
+        TypeLongId::Missing(_) => true,
+        _ => false,
+    };
+    visited.pop();
+    fuzzable
+}
@@ -1,12 +1,14 @@
 use std::default::Default;
 use std::sync::Arc;
 
-use anyhow::{Result, ensure};
+use anyhow::{Result, bail, ensure};
 use cairo_lang_compiler::db::RootDatabase;
 use cairo_lang_compiler::diagnostics::DiagnosticsReporter;
 use cairo_lang_compiler::get_sierra_program_for_functions;
 use cairo_lang_debug::DebugWithDb;
-use cairo_lang_defs::ids::{FreeFunctionId, FunctionWithBodyId, ModuleItemId};
+use cairo_lang_defs::ids::{
+    FreeFunctionId, FunctionWithBodyId, ModuleItemId, TopLevelLanguageElementId,
+};
 use cairo_lang_filesystem::db::FilesGroup;
 use cairo_lang_filesystem::ids::CrateId;
 use cairo_lang_lowering::ids::ConcreteFunctionWithBodyId;
@@ -38,14 +40,19 @@ use serde::{Deserialize, Serialize};
 use starknet_types_core::felt::Felt as Felt252;
 pub use test_config::{TestConfig, try_extract_test_config};
 
+mod analyzer;
 mod inline_macros;
 pub mod plugin;
 pub mod test_config;
 
-const TEST_ATTR: &str = "test";
+#[cfg(test)]
+mod test;
+
+pub const TEST_ATTR: &str = "test";
 const SHOULD_PANIC_ATTR: &str = "should_panic";
 const IGNORE_ATTR: &str = "ignore";
 const AVAILABLE_GAS_ATTR: &str = "available_gas";
+const FUZZ_ATTR: &str = "fuzz";
 const STATIC_GAS_ARG: &str = "static";
 
 /// Configuration for test compilation.
@@ -130,7 +137,7 @@ pub fn compile_test_prepared_db(
         db,
         tests_compilation_config.executable_crate_ids.unwrap_or_else(|| test_crate_ids.clone()),
     );
-    let all_tests = find_all_tests(db, test_crate_ids.clone());
+    let all_tests = find_all_tests(db, test_crate_ids.clone())?;
 
     let func_ids = chain!(
         executable_functions.clone().into_keys(),
@@ -245,10 +252,12 @@ pub struct TestCompilationMetadata {
 }
 
 /// Finds the tests in the requested crates.
+///
+/// Returns an error if the configuration of a test is invalid.
 fn find_all_tests(
     db: &dyn SemanticGroup,
     main_crates: Vec<CrateId>,
-) -> Vec<(FreeFunctionId, TestConfig)> {
+) -> Result<Vec<(FreeFunctionId, TestConfig)>> {
     let mut tests = vec![];
     for crate_id in main_crates {
         let modules = db.crate_modules(crate_id);
@@ -256,18 +265,26 @@ fn find_all_tests(
             let Ok(module_items) = db.module_items(*module_id) else {
                 continue;
             };
-            tests.extend(module_items.iter().filter_map(|item| {
-                let ModuleItemId::FreeFunction(func_id) = item else { return None };
+            for item in module_items.iter() {
+                let ModuleItemId::FreeFunction(func_id) = item else { continue };
                 let Ok(attrs) =
                     db.function_with_body_attributes(FunctionWithBodyId::Free(*func_id))
                 else {
-                    return None;
+                    continue;
                 };
-                Some((*func_id, try_extract_test_config(db.upcast(), attrs).unwrap()?))
-            }));
+                match try_extract_test_config(db.upcast(), attrs) {
+                    Ok(Some(test)) => tests.push((*func_id, test)),
+                    Ok(None) => {}
+                    Err(diagnostics) => bail!(
+                        "Invalid test configuration of `{}`: {}",
+                        func_id.full_path(db.upcast()),
+                        diagnostics.iter().map(|diagnostic| &diagnostic.message).join(" ")
+                    ),
+                }
+            }
         }
     }
-    tests
+    Ok(tests)
 }
 
 /// The suite of plugins that implements assert macros for tests.
@@ -286,6 +303,9 @@ pub fn test_assert_suite() -> PluginSuite {
 /// The suite of plugins for compilation for testing.
 pub fn test_plugin_suite() -> PluginSuite {
     let mut suite = PluginSuite::default();
-    suite.add_plugin::<TestPlugin>().add(test_assert_suite());
+    suite
+        .add_plugin::<TestPlugin>()
+        .add_analyzer_plugin::<analyzer::FuzzAnalyzer>()
+        .add(test_assert_suite());
     suite
 }
@@ -1,9 +1,9 @@
-use cairo_lang_defs::plugin::{MacroPlugin, MacroPluginMetadata, PluginResult};
+use cairo_lang_defs::plugin::{MacroPlugin, MacroPluginMetadata, PluginDiagnostic, PluginResult};
 use cairo_lang_syntax::attribute::structured::AttributeListStructurize;
-use cairo_lang_syntax::node::ast;
 use cairo_lang_syntax::node::db::SyntaxGroup;
+use cairo_lang_syntax::node::{TypedStablePtr, TypedSyntaxNode, ast};
 
-use super::{AVAILABLE_GAS_ATTR, IGNORE_ATTR, SHOULD_PANIC_ATTR, TEST_ATTR};
+use super::{AVAILABLE_GAS_ATTR, FUZZ_ATTR, IGNORE_ATTR, SHOULD_PANIC_ATTR, TEST_ATTR};
 use crate::test_config::try_extract_test_config;
 
 /// Plugin to create diagnostics for tests attributes.
@@ -21,11 +21,16 @@ impl MacroPlugin for TestPlugin {
         PluginResult {
             code: None,
             diagnostics: if let ast::ModuleItem::FreeFunction(free_func_ast) = item_ast {
-                try_extract_test_config(db, free_func_ast.attributes(db).structurize(db)).err()
+                match try_extract_test_config(db, free_func_ast.attributes(db).structurize(db)) {
+                    Ok(Some(config)) => {
+                        check_test_params(db, &free_func_ast, config.fuzz.is_some())
+                    }
+                    Ok(None) => vec![],
+                    Err(diagnostics) => diagnostics,
+                }
             } else {
-                None
-            }
-            .unwrap_or_default(),
+                vec![]
+            },
             remove_original_item: false,
         }
     }
@@ -36,6 +41,31 @@ impl MacroPlugin for TestPlugin {
             AVAILABLE_GAS_ATTR.to_string(),
             SHOULD_PANIC_ATTR.to_string(),
             IGNORE_ATTR.to_string(),
+            FUZZ_ATTR.to_string(),
         ]
     }
 }
+
+/// Checks that a test has parameters if and only if it is a fuzz test, as the arguments of a test
+/// can only be generated by the fuzzer.
+fn check_test_params(
+    db: &dyn SyntaxGroup,
+    free_func_ast: &ast::FunctionWithBody,
+    is_fuzz: bool,
+) -> Vec<PluginDiagnostic> {
+    let signature = free_func_ast.declaration(db).signature(db);
+    let has_params = !signature.parameters(db).elements(db).is_empty();
+    if has_params && !is_fuzz {
+        vec![PluginDiagnostic::error(
+            signature.stable_ptr().untyped(),
+            format!("Tests with parameters must be marked with `#[{FUZZ_ATTR}]`."),
+        )]
+    } else if !has_params && is_fuzz {
+        vec![PluginDiagnostic::error(
+            signature.stable_ptr().untyped(),
+            "Fuzz tests must have parameters.".into(),
+        )]
+    } else {
+        vec![]
+    }
+}
@@ -0,0 +1,45 @@
+use std::sync::{LazyLock, Mutex};
+
+use cairo_lang_compiler::db::RootDatabase;
+use cairo_lang_semantic::test_utils::setup_test_module;
+use cairo_lang_test_utils::parse_test_file::TestRunnerResult;
+use cairo_lang_test_utils::{test_lock, verify_diagnostics_expectation};
+use cairo_lang_utils::ordered_hash_map::OrderedHashMap;
+
+use crate::test_plugin_suite;
+
+/// Salsa database configured to find the corelib, with the test plugin suite. Reused by the tests
+/// to share the cached queries of the corelib.
+static SHARED_DB: LazyLock<Mutex<RootDatabase>> = LazyLock::new(|| {
+    Mutex::new(
+        RootDatabase::builder()
+            .detect_corelib()
+            .with_plugin_suite(test_plugin_suite())
+            .build()
+            .unwrap(),
+    )
+});
+
+cairo_lang_test_utils::test_file_test!(
+    diagnostics,
+    "src/test_data",
+    {
+        fuzz: "fuzz",
+    },
+    test_diagnostics
+);
+
+/// Returns the diagnostics of the given module code, with the test plugin suite.
+fn test_diagnostics(
+    inputs: &OrderedHashMap<String, String>,
+    args: &OrderedHashMap<String, String>,
+) -> TestRunnerResult {
+    let db = test_lock(&SHARED_DB).snapshot();
+    let (_, diagnostics) = setup_test_module(&db, &inputs["cairo_code"]).split();
+    let error = verify_diagnostics_expectation(args, &diagnostics);
+
+    TestRunnerResult {
+        outputs: OrderedHashMap::from([("expected_diagnostics".into(), diagnostics)]),
+        error,
+    }
+}
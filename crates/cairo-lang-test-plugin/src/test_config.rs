@@ -10,7 +10,9 @@ use num_traits::ToPrimitive;
 use serde::{Deserialize, Serialize};
 use starknet_types_core::felt::Felt as Felt252;
 
-use super::{AVAILABLE_GAS_ATTR, IGNORE_ATTR, SHOULD_PANIC_ATTR, STATIC_GAS_ARG, TEST_ATTR};
+use super::{
+    AVAILABLE_GAS_ATTR, FUZZ_ATTR, IGNORE_ATTR, SHOULD_PANIC_ATTR, STATIC_GAS_ARG, TEST_ATTR,
+};
 
 /// Expectation for a panic case.
 #[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
@@ -30,6 +32,15 @@ pub enum TestExpectation {
     Panics(PanicExpectation),
 }
 
+/// The configuration of a fuzz test, which is run with randomly generated arguments.
+#[derive(Clone, Serialize, Deserialize, Debug, Default, PartialEq)]
+pub struct FuzzConfig {
+    /// The number of runs of the test, if set by the test.
+    pub runs: Option<u32>,
+    /// The seed for generating the arguments, if set by the test.
+    pub seed: Option<u64>,
+}
+
 /// The configuration for running a single test.
 #[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
 pub struct TestConfig {
@@ -39,6 +50,9 @@ pub struct TestConfig {
     pub expectation: TestExpectation,
     /// Should the test be ignored.
     pub ignored: bool,
+    /// The fuzzing configuration, if the test is a fuzz test.
+    #[serde(default)]
+    pub fuzz: Option<FuzzConfig>,
 }
 
 /// Extracts the configuration of a tests from attributes, or returns the diagnostics if the
@@ -51,6 +65,7 @@ pub fn try_extract_test_config(
     let ignore_attr = attrs.iter().find(|attr| attr.id.as_str() == IGNORE_ATTR);
     let available_gas_attr = attrs.iter().find(|attr| attr.id.as_str() == AVAILABLE_GAS_ATTR);
     let should_panic_attr = attrs.iter().find(|attr| attr.id.as_str() == SHOULD_PANIC_ATTR);
+    let fuzz_attr = attrs.iter().find(|attr| attr.id.as_str() == FUZZ_ATTR);
     let mut diagnostics = vec![];
     if let Some(attr) = test_attr {
         if !attr.args.is_empty() {
@@ -60,7 +75,9 @@ pub fn try_extract_test_config(
             ));
         }
     } else {
-        for attr in [ignore_attr, available_gas_attr, should_panic_attr].into_iter().flatten() {
+        for attr in
+            [ignore_attr, available_gas_attr, should_panic_attr, fuzz_attr].into_iter().flatten()
+        {
             diagnostics.push(PluginDiagnostic::error(
                 attr.id_stable_ptr.untyped(),
                 "Attribute should only appear on tests.".into(),
@@ -79,6 +96,7 @@ pub fn try_extract_test_config(
         false
     };
     let available_gas = extract_available_gas(available_gas_attr, db, &mut diagnostics);
+    let fuzz = fuzz_attr.map(|attr| extract_fuzz_config(attr, db, &mut diagnostics));
     let (should_panic, expected_panic_felts) = if let Some(attr) = should_panic_attr {
         if attr.args.is_empty() {
             (true, None)
@@ -116,6 +134,7 @@ pub fn try_extract_test_config(
                 TestExpectation::Success
             },
             ignored,
+            fuzz,
         })
     })
 }
@@ -158,6 +177,54 @@ fn extract_available_gas(
     })
 }
 
+/// Extracts the fuzzing configuration from the `fuzz` attribute, of the form
+/// `#[fuzz(runs: <number>, seed: <number>)]` where both arguments are optional.
+/// Adds a diagnostic for every malformed argument.
+fn extract_fuzz_config(
+    attr: &Attribute,
+    db: &dyn SyntaxGroup,
+    diagnostics: &mut Vec<PluginDiagnostic>,
+) -> FuzzConfig {
+    let mut config = FuzzConfig::default();
+    for arg in &attr.args {
+        let AttributeArgVariant::Named { name, value, .. } = &arg.variant else {
+            diagnostics.push(PluginDiagnostic::error(
+                arg.arg.stable_ptr().untyped(),
+                "Expected a named argument: `runs` or `seed`.".into(),
+            ));
+            continue;
+        };
+        let value = match value {
+            ast::Expr::Literal(literal) => literal.numeric_value(db),
+            _ => None,
+        };
+        match name.text.as_str() {
+            "runs" => {
+                config.runs =
+                    value.and_then(|v| v.to_u32()).filter(|runs| *runs != 0).on_none(|| {
+                        diagnostics.push(PluginDiagnostic::error(
+                            arg.arg.stable_ptr().untyped(),
+                            "`runs` should be a positive literal in `u32` range.".into(),
+                        ))
+                    });
+            }
+            "seed" => {
+                config.seed = value.and_then(|v| v.to_u64()).on_none(|| {
+                    diagnostics.push(PluginDiagnostic::error(
+                        arg.arg.stable_ptr().untyped(),
+                        "`seed` should be a non-negative literal in `u64` range.".into(),
+                    ))
+                });
+            }
+            _ => diagnostics.push(PluginDiagnostic::error(
+                arg.arg.stable_ptr().untyped(),
+                "Expected a named argument: `runs` or `seed`.".into(),
+            )),
+        }
+    }
+    config
+}
+
 /// Tries to extract the expected panic bytes out of the given `should_panic` attribute.
 /// Assumes the attribute is `should_panic`.
 fn extract_panic_bytes(db: &dyn SyntaxGroup, attr: &Attribute) -> Option<Vec<Felt252>> {
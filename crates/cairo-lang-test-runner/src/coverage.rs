@@ -0,0 +1,146 @@
+//! Line coverage of test runs, reported in the LCOV format and as a JSON summary.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use cairo_lang_defs::db::DefsGroup;
+use cairo_lang_diagnostics::ToOption;
+use cairo_lang_filesystem::ids::{CrateId, FileId, FileLongId};
+use cairo_lang_sierra::program::StatementIdx;
+use cairo_lang_sierra_generator::statements_locations::StatementsLocations;
+use cairo_lang_utils::LookupIntern;
+use cairo_lang_utils::unordered_hash_map::UnorderedHashMap;
+use serde_json::{Value, json};
+
+/// The name of the LCOV report file in the coverage directory.
+const LCOV_FILE_NAME: &str = "lcov.info";
+/// The name of the JSON summary file in the coverage directory.
+const SUMMARY_FILE_NAME: &str = "coverage.json";
+
+/// The number of executions of each executable Sierra statement, aggregated over test runs.
+#[derive(Debug, Default)]
+pub struct StatementsCoverage {
+    /// The number of executions of each executable statement, including the unexecuted ones.
+    pub hits: UnorderedHashMap<StatementIdx, usize>,
+}
+impl StatementsCoverage {
+    /// Creates the coverage of the given executable statements, before any run.
+    pub fn new(executable_statements: impl Iterator<Item = StatementIdx>) -> Self {
+        Self { hits: executable_statements.map(|idx| (idx, 0)).collect() }
+    }
+
+    /// Adds the number of executions of each statement in a run.
+    pub fn add_run(&mut self, run_hits: &UnorderedHashMap<StatementIdx, usize>) {
+        self.hits.merge(run_hits, |mut e, hits| *e.get_mut() += hits);
+    }
+}
+
+/// The number of executions of each line of the source files.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct LinesCoverage {
+    /// The number of executions of each line, by file path and 1 based line number.
+    /// Only lines of code that Sierra statements were generated from are included.
+    pub files: BTreeMap<PathBuf, BTreeMap<usize, usize>>,
+}
+impl LinesCoverage {
+    /// Maps the coverage of Sierra statements to the lines of code they were generated from,
+    /// where the number of executions of a line is the maximal number of executions of its
+    /// statements. A statement is mapped to all the lines in its locations, so code inlined from a
+    /// function is also counted in the line of the call.
+    /// Files of the core library are excluded.
+    pub fn new(
+        db: &dyn DefsGroup,
+        statements_locations: &StatementsLocations,
+        coverage: &StatementsCoverage,
+    ) -> Self {
+        let core_crate = CrateId::core(db);
+        let mut file_paths = HashMap::<FileId, Option<PathBuf>>::new();
+        let mut files = BTreeMap::<PathBuf, BTreeMap<usize, usize>>::new();
+        for (idx, hits) in coverage.hits.iter_sorted() {
+            let Some(locations) = statements_locations.locations.get(idx) else { continue };
+            let mut lines = BTreeSet::new();
+            for location in locations {
+                let location = location.diagnostic_location(db).user_location(db.upcast());
+                let file_id = location.file_id;
+                let path = file_paths
+                    .entry(file_id)
+                    .or_insert_with(|| user_file_path(db, file_id, core_crate));
+                let Some(path) = path else { continue };
+                let Some(position) = location.span.position_in_file(db.upcast(), file_id) else {
+                    continue;
+                };
+                lines.insert((path.clone(), position.start.line + 1));
+            }
+            for (path, line) in lines {
+                let line_hits = files.entry(path).or_default().entry(line).or_default();
+                *line_hits = (*line_hits).max(*hits);
+            }
+        }
+        Self { files }
+    }
+
+    /// Returns the coverage in the LCOV format.
+    pub fn to_lcov(&self) -> String {
+        let mut lcov = String::new();
+        for (path, lines) in &self.files {
+            lcov.push_str("TN:\n");
+            lcov.push_str(&format!("SF:{}\n", path.display()));
+            for (line, hits) in lines {
+                lcov.push_str(&format!("DA:{line},{hits}\n"));
+            }
+            let (found, hit) = lines_found_and_hit(lines);
+            lcov.push_str(&format!("LF:{found}\nLH:{hit}\nend_of_record\n"));
+        }
+        lcov
+    }
+
+    /// Returns a JSON summary of the coverage, with the number of lines found and hit, per file
+    /// and in total.
+    pub fn to_json_summary(&self) -> Value {
+        let (mut total_found, mut total_hit) = (0, 0);
+        let mut files = serde_json::Map::new();
+        for (path, lines) in &self.files {
+            let (found, hit) = lines_found_and_hit(lines);
+            total_found += found;
+            total_hit += hit;
+            files.insert(path.display().to_string(), summary_entry(found, hit));
+        }
+        json!({ "total": summary_entry(total_found, total_hit), "files": files })
+    }
+
+    /// Writes the LCOV report and the JSON summary into the given directory.
+    pub fn write_reports(&self, dir: &Path) -> Result<()> {
+        fs::create_dir_all(dir).with_context(|| {
+            format!("Failed creating the coverage directory `{}`.", dir.display())
+        })?;
+        let lcov_path = dir.join(LCOV_FILE_NAME);
+        fs::write(&lcov_path, self.to_lcov())
+            .with_context(|| format!("Failed writing `{}`.", lcov_path.display()))?;
+        let summary_path = dir.join(SUMMARY_FILE_NAME);
+        fs::write(&summary_path, format!("{:#}\n", self.to_json_summary()))
+            .with_context(|| format!("Failed writing `{}`.", summary_path.display()))?;
+        Ok(())
+    }
+}
+
+/// Returns the path of the given file if it is an on disk file that is not part of the core
+/// library.
+fn user_file_path(db: &dyn DefsGroup, file_id: FileId, core_crate: CrateId) -> Option<PathBuf> {
+    let FileLongId::OnDisk(path) = file_id.lookup_intern(db) else { return None };
+    let file_modules = db.file_modules(file_id).to_option()?;
+    let module_id = file_modules.first()?;
+    (module_id.owning_crate(db) != core_crate).then_some(path)
+}
+
+/// Returns the number of lines found and the number of lines hit.
+fn lines_found_and_hit(lines: &BTreeMap<usize, usize>) -> (usize, usize) {
+    (lines.len(), lines.values().filter(|hits| **hits > 0).count())
+}
+
+/// Returns a JSON summary entry of the given number of lines found and hit.
+fn summary_entry(found: usize, hit: usize) -> Value {
+    let percent = if found == 0 { 100.0 } else { 100.0 * hit as f64 / found as f64 };
+    json!({ "lines_found": found, "lines_hit": hit, "percent": percent })
+}
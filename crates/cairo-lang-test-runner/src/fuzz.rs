@@ -0,0 +1,148 @@
+//! Running of fuzz tests, with randomly generated arguments.
+
+use anyhow::{Context, Result, ensure};
+use cairo_lang_runner::args::UserArgs;
+use cairo_lang_runner::{SierraCasmRunner, StarknetState};
+use cairo_lang_sierra::program::{Function, StatementIdx};
+use cairo_lang_test_plugin::TestConfig;
+use cairo_lang_test_plugin::test_config::FuzzConfig;
+use cairo_lang_utils::unordered_hash_map::UnorderedHashMap;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde_json::Value;
+
+use crate::{TestRun, TestStatus, run_test_function};
+
+/// The maximal number of runs done while shrinking the arguments of a failing fuzz test.
+const MAX_SHRINK_RUNS: usize = 1000;
+
+/// The report of the fuzzing of a test.
+#[derive(Debug)]
+pub struct FuzzingReport {
+    /// The number of runs with random arguments, including runs whose arguments were discarded.
+    pub runs: u32,
+    /// The seed used for generating the arguments.
+    pub seed: u64,
+    /// The minimal arguments found to fail the test, if any, in the JSON form of `cairo-run
+    /// --args`.
+    pub failing_args: Option<Value>,
+}
+
+/// Runs a fuzz test with random arguments, until it fails or all the runs are done. On failure,
+/// the failing arguments are shrunk to minimal ones that still fail the test.
+///
+/// Returns the report of the last run (the run with the minimal failing arguments on failure) and
+/// the report of the fuzzing. When profiling, the Sierra statement hits of the returned run are of
+/// all the runs, so that coverage includes all the generated arguments.
+pub(crate) fn run_fuzz_test(
+    runner: &SierraCasmRunner,
+    func: &Function,
+    test: &TestConfig,
+    initial_state: &StarknetState,
+    fuzz_config: &FuzzConfig,
+    default_runs: u32,
+    default_seed: Option<u64>,
+) -> Result<(TestRun, FuzzingReport)> {
+    let runs = fuzz_config.runs.unwrap_or(default_runs);
+    ensure!(runs != 0, "Fuzz tests must have at least one run.");
+    let seed = fuzz_config.seed.or(default_seed).unwrap_or_else(|| rand::thread_rng().gen());
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut statement_hits = UnorderedHashMap::default();
+    let mut last_run = None;
+    for i in 1..=runs {
+        let args = runner
+            .generate_args(func, &mut rng)
+            .with_context(|| "Failed generating arguments for a fuzz test.")?;
+        let Some(run) =
+            run_with_args(runner, func, test, initial_state, &args, &mut statement_hits)?
+        else {
+            continue;
+        };
+        if matches!(run.status, TestStatus::Fail(_)) {
+            let (failing_args, run) =
+                shrink(runner, func, test, initial_state, args, run, &mut statement_hits)?;
+            return Ok((with_statement_hits(run, statement_hits), FuzzingReport {
+                runs: i,
+                seed,
+                failing_args: Some(failing_args),
+            }));
+        }
+        last_run = Some(run);
+    }
+    let last_run =
+        last_run.with_context(|| "All the generated arguments of the fuzz test were discarded.")?;
+    Ok((with_statement_hits(last_run, statement_hits), FuzzingReport {
+        runs,
+        seed,
+        failing_args: None,
+    }))
+}
+
+/// Replaces the Sierra statement hits of the profiling info of the run, if any, with the given
+/// hits.
+fn with_statement_hits(
+    mut run: TestRun,
+    statement_hits: UnorderedHashMap<StatementIdx, usize>,
+) -> TestRun {
+    if let Some(profiling_info) = &mut run.profiling_info {
+        profiling_info.sierra_statement_hits = statement_hits;
+    }
+    run
+}
+
+/// Shrinks the failing arguments `args` of a test, whose run is `run`, by repeatedly replacing
+/// them with the first simpler variation that still fails the test.
+fn shrink(
+    runner: &SierraCasmRunner,
+    func: &Function,
+    test: &TestConfig,
+    initial_state: &StarknetState,
+    mut args: Value,
+    mut run: TestRun,
+    statement_hits: &mut UnorderedHashMap<StatementIdx, usize>,
+) -> Result<(Value, TestRun)> {
+    let mut shrink_runs = 0;
+    'shrinking: while shrink_runs < MAX_SHRINK_RUNS {
+        for candidate in runner.shrink_args(func, &args) {
+            if shrink_runs == MAX_SHRINK_RUNS {
+                break 'shrinking;
+            }
+            shrink_runs += 1;
+            let candidate_run =
+                run_with_args(runner, func, test, initial_state, &candidate, statement_hits)?;
+            if let Some(candidate_run) =
+                candidate_run.filter(|run| matches!(run.status, TestStatus::Fail(_)))
+            {
+                args = candidate;
+                run = candidate_run;
+                continue 'shrinking;
+            }
+        }
+        break;
+    }
+    Ok((args, run))
+}
+
+/// Runs the test function with the given JSON arguments, adding the Sierra statement hits of the
+/// run to `statement_hits` when profiling.
+///
+/// Returns `None` if the arguments are rejected when decoded (e.g. a zero value generated for a
+/// `NonZero` parameter), in which case they are discarded rather than failing the test.
+fn run_with_args(
+    runner: &SierraCasmRunner,
+    func: &Function,
+    test: &TestConfig,
+    initial_state: &StarknetState,
+    args: &Value,
+    statement_hits: &mut UnorderedHashMap<StatementIdx, usize>,
+) -> Result<Option<TestRun>> {
+    let Ok(args) = runner.decode_args(func, &UserArgs::Json(args.clone())) else {
+        return Ok(None);
+    };
+    let run = run_test_function(runner, func, test, initial_state, &args)?;
+    if let Some(profiling_info) = &run.profiling_info {
+        statement_hits
+            .merge(&profiling_info.sierra_statement_hits, |mut e, hits| *e.get_mut() += hits);
+    }
+    Ok(Some(run))
+}
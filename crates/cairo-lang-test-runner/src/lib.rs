@@ -1,8 +1,11 @@
-use std::path::Path;
-use std::sync::Mutex;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{OnceLock, mpsc};
+use std::time::{Duration, Instant};
 use std::vec::IntoIter;
 
-use anyhow::{Context, Result, bail};
+use anyhow::{Context, Result, bail, ensure};
 use cairo_lang_compiler::db::RootDatabase;
 use cairo_lang_compiler::diagnostics::DiagnosticsReporter;
 use cairo_lang_compiler::project::setup_project;
@@ -10,14 +13,15 @@ use cairo_lang_filesystem::cfg::{Cfg, CfgSet};
 use cairo_lang_filesystem::ids::CrateId;
 use cairo_lang_runner::casm_run::format_next_item;
 use cairo_lang_runner::profiling::{
-    ProfilingInfo, ProfilingInfoProcessor, ProfilingInfoProcessorParams,
+    ProfilerOutputFormat, ProfilingInfo, ProfilingInfoProcessor, ProfilingInfoProcessorParams,
 };
 use cairo_lang_runner::{
-    ProfilingInfoCollectionConfig, RunResultValue, SierraCasmRunner, StarknetExecutionResources,
+    Arg, EmittedEvent, ProfilingInfoCollectionConfig, RunResultValue, SierraCasmRunner,
+    StarknetExecutionResources, StarknetState,
 };
 use cairo_lang_sierra::extensions::gas::CostTokenType;
 use cairo_lang_sierra::ids::FunctionId;
-use cairo_lang_sierra::program::{Program, StatementIdx};
+use cairo_lang_sierra::program::{Function, Program, StatementIdx};
 use cairo_lang_sierra_generator::db::SierraGenGroup;
 use cairo_lang_sierra_to_casm::metadata::MetadataComputationConfig;
 use cairo_lang_starknet::contract::ContractInfo;
@@ -33,9 +37,17 @@ use cairo_lang_utils::unordered_hash_map::UnorderedHashMap;
 use colored::Colorize;
 use itertools::Itertools;
 use num_traits::ToPrimitive;
-use rayon::prelude::{IntoParallelIterator, ParallelIterator};
+use rayon::ThreadPoolBuilder;
+use rayon::iter::{IndexedParallelIterator, IntoParallelIterator, ParallelIterator};
 use starknet_types_core::felt::Felt as Felt252;
 
+use crate::coverage::{LinesCoverage, StatementsCoverage};
+use crate::fuzz::{FuzzingReport, run_fuzz_test};
+use crate::reporter::{TestOutputFormat, TestReporter};
+
+pub mod coverage;
+pub mod fuzz;
+pub mod reporter;
 #[cfg(test)]
 mod test;
 
@@ -102,6 +114,30 @@ impl CompiledTestRunner {
 
     /// Execute preconfigured test execution.
     pub fn run(self, db: Option<&RootDatabase>) -> Result<Option<TestsSummary>> {
+        let mut reporter = self.config.format.reporter(self.config.print_resource_usage);
+        let summary = self.run_with_reporter(db, reporter.as_mut())?;
+
+        let n_failed = summary.failed().count();
+        if n_failed == 0 {
+            Ok(None)
+        } else {
+            bail!(
+                "test result: {}. {} passed; {} failed; {} ignored",
+                "FAILED".bright_red(),
+                summary.passed().count(),
+                n_failed,
+                summary.ignored().count()
+            );
+        }
+    }
+
+    /// Execute preconfigured test execution, reporting the results to the given reporter instead
+    /// of the one of the configured format.
+    pub fn run_with_reporter(
+        self,
+        db: Option<&RootDatabase>,
+        reporter: &mut dyn TestReporter,
+    ) -> Result<TestsSummary> {
         let (compiled, filtered_out) = filter_test_cases(
             self.compiled,
             self.config.include_ignored,
@@ -109,12 +145,24 @@ impl CompiledTestRunner {
             &self.config.filter,
         );
 
-        let TestsSummary { passed, failed, ignored, failed_run_results } = run_tests(
+        let start = Instant::now();
+        let statements_locations = compiled.metadata.statements_locations;
+        // Checked before running, as the coverage reports are only written after the run.
+        let coverage_reports = match &self.config.coverage {
+            Some(dir) => Some((
+                dir,
+                db.context("The db must be passed when collecting coverage.")?,
+                statements_locations
+                    .as_ref()
+                    .context("Statements locations must be present when collecting coverage.")?,
+            )),
+            None => None,
+        };
+        let summary = run_tests(
             if self.config.run_profiler == RunProfilerConfig::Cairo {
                 let db = db.expect("db must be passed when profiling.");
-                let statements_locations = compiled
-                    .metadata
-                    .statements_locations
+                let statements_locations = statements_locations
+                    .as_ref()
                     .expect("statements locations must be present when profiling.");
                 Some(PorfilingAuxData {
                     db,
@@ -129,51 +177,35 @@ impl CompiledTestRunner {
             compiled.metadata.function_set_costs,
             compiled.metadata.contracts_info,
             &self.config,
+            reporter,
         )?;
+        reporter.on_run_finished(&summary, filtered_out, start.elapsed());
 
-        if failed.is_empty() {
-            println!(
-                "test result: {}. {} passed; {} failed; {} ignored; {filtered_out} filtered out;",
-                "ok".bright_green(),
-                passed.len(),
-                failed.len(),
-                ignored.len()
-            );
-            Ok(None)
-        } else {
-            println!("failures:");
-            for (failure, run_result) in failed.iter().zip_eq(failed_run_results) {
-                print!("   {failure} - ");
-                match run_result {
-                    RunResultValue::Success(_) => {
-                        println!("expected panic but finished successfully.");
-                    }
-                    RunResultValue::Panic(values) => {
-                        println!("{}", format_for_panic(values.into_iter()));
-                    }
-                }
-            }
-            println!();
-            bail!(
-                "test result: {}. {} passed; {} failed; {} ignored",
-                "FAILED".bright_red(),
-                passed.len(),
-                failed.len(),
-                ignored.len()
-            );
+        if let (Some((dir, db, statements_locations)), Some(coverage)) =
+            (coverage_reports, &summary.coverage)
+        {
+            LinesCoverage::new(db, statements_locations, coverage).write_reports(dir)?;
         }
+
+        Ok(summary)
     }
 }
 
 /// Formats the given felts as a panic string.
-fn format_for_panic(mut felts: IntoIter<Felt252>) -> String {
+fn format_for_panic(felts: IntoIter<Felt252>) -> String {
+    let items = format_items(felts);
+    let panic_values_string =
+        if let [item] = &items[..] { item.clone() } else { format!("({})", items.join(", ")) };
+    format!("Panicked with {panic_values_string}.")
+}
+
+/// Formats the given felts as items, where strings and short strings are also shown as such.
+fn format_items(mut felts: IntoIter<Felt252>) -> Vec<String> {
     let mut items = Vec::new();
     while let Some(item) = format_next_item(&mut felts) {
         items.push(item.quote_if_string());
     }
-    let panic_values_string =
-        if let [item] = &items[..] { item.clone() } else { format!("({})", items.join(", ")) };
-    format!("Panicked with {panic_values_string}.")
+    items
 }
 
 /// Whether to run the profiler, and what results to produce.
@@ -197,10 +229,33 @@ pub struct TestRunConfig {
     pub ignored: bool,
     /// Whether to run the profiler and how.
     pub run_profiler: RunProfilerConfig,
+    /// The format of the profiling info of each test. Profiling info in formats other than text is
+    /// written into `profiler_output_dir` instead of being reported.
+    pub profiler_format: ProfilerOutputFormat,
+    /// The directory into which the profiling info of each test is written, as
+    /// `<test name>.<extension of the format>`, for formats other than text.
+    pub profiler_output_dir: PathBuf,
     /// Whether to enable gas calculation.
     pub gas_enabled: bool,
     /// Whether to print used resources after each test.
     pub print_resource_usage: bool,
+    /// Whether to capture the debug prints of each test into its report, instead of printing them
+    /// to the standard output.
+    pub capture_output: bool,
+    /// The number of tests to run in parallel. Uses the number of available CPUs if `None`.
+    pub jobs: Option<usize>,
+    /// The format in which the results are reported.
+    pub format: TestOutputFormat,
+    /// The number of runs of fuzz tests that don't set it.
+    pub fuzzer_runs: u32,
+    /// The seed for generating the arguments of fuzz tests that don't set it. Random if `None`.
+    pub fuzzer_seed: Option<u64>,
+    /// A JSON fixture file of the Starknet state every test starts from. The default state is used
+    /// if `None`.
+    pub starknet_state_fixture: Option<PathBuf>,
+    /// The directory into which the line coverage reports of the run are written. Coverage is not
+    /// collected if `None`.
+    pub coverage: Option<PathBuf>,
 }
 
 /// The test cases compiler.
@@ -307,30 +362,78 @@ pub fn filter_test_cases(
     (tests, filtered_out)
 }
 
-/// The status of a ran test.
-enum TestStatus {
+/// The status of a test.
+#[derive(Debug)]
+pub enum TestStatus {
     Success,
     Fail(RunResultValue),
+    Ignored,
+}
+
+/// The report of a single test.
+#[derive(Debug)]
+pub struct TestReport {
+    /// The full path of the test function.
+    pub name: String,
+    /// The status of the test.
+    pub status: TestStatus,
+    /// The duration of the run, zero for ignored tests. For fuzz tests, of all the runs.
+    pub duration: Duration,
+    /// The gas usage of the run if relevant. For fuzz tests, of the last run, which is the run
+    /// with the minimal failing arguments if the test failed.
+    pub gas_usage: Option<i64>,
+    /// The used resources of the run, `None` for ignored tests. For fuzz tests, of the last run.
+    pub used_resources: Option<StarknetExecutionResources>,
+    /// The processed profiling info of the run, if requested.
+    pub profiling_info: Option<String>,
+    /// The report of the fuzzing, for fuzz tests.
+    pub fuzzing: Option<FuzzingReport>,
+    /// The events emitted and not popped by the run, in the order of their emission. For fuzz
+    /// tests, of the last run.
+    pub unpopped_events: Vec<EmittedEvent>,
+    /// The output of the debug prints of the run, if captured. For fuzz tests, of the last run.
+    pub output: Option<String>,
 }
 
-/// The result of a ran test.
+/// The result of a ran test, before processing its profiling info.
 struct TestResult {
-    /// The status of the run.
-    status: TestStatus,
-    /// The gas usage of the run if relevant.
-    gas_usage: Option<i64>,
-    /// The used resources of the run.
-    used_resources: StarknetExecutionResources,
+    /// The report of the test.
+    report: TestReport,
     /// The profiling info of the run, if requested.
     profiling_info: Option<ProfilingInfo>,
+    /// The number of executions of each Sierra statement in the run, if coverage is collected.
+    sierra_statement_hits: Option<UnorderedHashMap<StatementIdx, usize>>,
 }
 
 /// Summary data of the ran tests.
+#[derive(Debug, Default)]
 pub struct TestsSummary {
-    passed: Vec<String>,
-    failed: Vec<String>,
-    ignored: Vec<String>,
-    failed_run_results: Vec<RunResultValue>,
+    /// The reports of the tests, in the order of the tests.
+    pub reports: Vec<TestReport>,
+    /// The coverage of the Sierra statements by all the tests, if collected.
+    pub coverage: Option<StatementsCoverage>,
+}
+impl TestsSummary {
+    /// Returns the names of the passed tests.
+    pub fn passed(&self) -> impl Iterator<Item = &String> {
+        self.reports
+            .iter()
+            .filter(|report| matches!(report.status, TestStatus::Success))
+            .map(|report| &report.name)
+    }
+
+    /// Returns the reports of the failed tests.
+    pub fn failed(&self) -> impl Iterator<Item = &TestReport> {
+        self.reports.iter().filter(|report| matches!(report.status, TestStatus::Fail(_)))
+    }
+
+    /// Returns the names of the ignored tests.
+    pub fn ignored(&self) -> impl Iterator<Item = &String> {
+        self.reports
+            .iter()
+            .filter(|report| matches!(report.status, TestStatus::Ignored))
+            .map(|report| &report.name)
+    }
 }
 
 /// Auxiliary data that is required when running tests with profiling.
@@ -347,12 +450,127 @@ pub fn run_tests(
     function_set_costs: OrderedHashMap<FunctionId, OrderedHashMap<CostTokenType, i32>>,
     contracts_info: OrderedHashMap<Felt252, ContractInfo>,
     config: &TestRunConfig,
+    reporter: &mut dyn TestReporter,
 ) -> Result<TestsSummary> {
+    let initial_state = match &config.starknet_state_fixture {
+        Some(path) => load_starknet_state(path)?,
+        None => StarknetState::default(),
+    };
+    let pool = ThreadPoolBuilder::new()
+        .num_threads(config.jobs.unwrap_or_default())
+        .build()
+        .with_context(|| "Failed creating the tests thread pool.")?;
+    // Each worker of the pool runs its tests with its own runner, created when the worker gets
+    // its first test. The runner of the first worker is created in advance, for reporting setup
+    // failures before running any test.
+    let runner = create_runner(&sierra_program, &function_set_costs, &contracts_info, config)?;
+    reporter.on_run_started(named_tests.len());
+    let mut summary = TestsSummary {
+        coverage: config
+            .coverage
+            .is_some()
+            .then(|| StatementsCoverage::new(runner.executable_sierra_statements())),
+        ..TestsSummary::default()
+    };
+    let profiling_params = if profiler_data.is_some() {
+        ProfilingInfoProcessorParams::default()
+    } else {
+        // Processing that requires the db is disabled.
+        ProfilingInfoProcessorParams {
+            process_by_generic_libfunc: false,
+            process_by_original_user_function: false,
+            process_by_cairo_function: false,
+            process_by_cairo_stack_trace: false,
+            ..ProfilingInfoProcessorParams::default()
+        }
+    };
+    if config.run_profiler != RunProfilerConfig::None
+        && config.profiler_format != ProfilerOutputFormat::Text
+    {
+        fs::create_dir_all(&config.profiler_output_dir).with_context(|| {
+            format!(
+                "Failed creating the profiler output directory `{}`.",
+                config.profiler_output_dir.display()
+            )
+        })?;
+    }
+
+    // The tests are run by the workers of the pool, while their results are processed by the
+    // current thread in the original order of the tests, so the output is deterministic.
+    // Processing on the current thread also allows running in parallel when profiling, as the
+    // db is only used for processing the results.
+    let runners = (0..pool.current_num_threads()).map(|_| OnceLock::new()).collect_vec();
+    runners[0].set(runner).ok();
+    let worker_runner = |worker: usize| -> Result<&SierraCasmRunner> {
+        if let Some(runner) = runners[worker].get() {
+            return Ok(runner);
+        }
+        let runner = create_runner(&sierra_program, &function_set_costs, &contracts_info, config)?;
+        Ok(runners[worker].get_or_init(|| runner))
+    };
+    let n_tests = named_tests.len();
+    pool.in_place_scope(|scope| {
+        let (sender, receiver) = mpsc::channel();
+        let worker_runner = &worker_runner;
+        let initial_state = &initial_state;
+        scope.spawn(move |_| {
+            // No new tests are started once a test fails to run, or once the receiver is dropped
+            // on a failure of processing the results.
+            named_tests
+                .into_par_iter()
+                .enumerate()
+                .try_for_each(|(index, (name, test))| {
+                    let worker =
+                        rayon::current_thread_index().expect("Tests run on the pool workers.");
+                    let test_result = worker_runner(worker).and_then(|runner| {
+                        run_single_test(test, name, runner, initial_state, config)
+                    });
+                    let failed = test_result.is_err();
+                    sender.send((index, test_result)).map_err(|_| ())?;
+                    if failed { Err(()) } else { Ok(()) }
+                })
+                .ok();
+        });
+
+        let mut pending_results = BTreeMap::new();
+        let mut next_index = 0;
+        for (index, test_result) in receiver {
+            pending_results.insert(index, test_result);
+            while let Some(test_result) = pending_results.remove(&next_index) {
+                next_index += 1;
+                update_summary(
+                    &mut summary,
+                    test_result?,
+                    &profiler_data,
+                    &sierra_program,
+                    &profiling_params,
+                    config,
+                    reporter,
+                )?;
+            }
+        }
+        // The run stops early on a failure, in which case results of following tests may have
+        // arrived while the results of some preceding tests never will.
+        if let Some(err) = pending_results.into_values().find_map(Result::err) {
+            return Err(err);
+        }
+        ensure!(next_index == n_tests, "Only {next_index} of the {n_tests} tests were run.");
+        Ok(summary)
+    })
+}
+
+/// Creates a runner for the tests of `sierra_program`.
+fn create_runner(
+    sierra_program: &Program,
+    function_set_costs: &OrderedHashMap<FunctionId, OrderedHashMap<CostTokenType, i32>>,
+    contracts_info: &OrderedHashMap<Felt252, ContractInfo>,
+    config: &TestRunConfig,
+) -> Result<SierraCasmRunner> {
     let runner = SierraCasmRunner::new(
         sierra_program.clone(),
         if config.gas_enabled {
             Some(MetadataComputationConfig {
-                function_set_costs,
+                function_set_costs: function_set_costs.clone(),
                 linear_gas_solver: true,
                 linear_ap_change_solver: true,
                 skip_non_linear_solver_comparisons: false,
@@ -361,203 +579,198 @@ pub fn run_tests(
         } else {
             None
         },
-        contracts_info,
+        contracts_info.clone(),
         match config.run_profiler {
-            RunProfilerConfig::None => None,
-            RunProfilerConfig::Cairo | RunProfilerConfig::Sierra => {
-                Some(ProfilingInfoCollectionConfig::default())
-            }
+            RunProfilerConfig::None if config.coverage.is_none() => None,
+            _ => Some(ProfilingInfoCollectionConfig::default()),
         },
     )
     .with_context(|| "Failed setting up runner.")?;
-    let suffix = if named_tests.len() != 1 { "s" } else { "" };
-    println!("running {} test{}", named_tests.len(), suffix);
-    let wrapped_summary = Mutex::new(Ok(TestsSummary {
-        passed: vec![],
-        failed: vec![],
-        ignored: vec![],
-        failed_run_results: vec![],
-    }));
-
-    // Run in parallel if possible. If running with db, parallelism is impossible.
-    if profiler_data.is_none() {
-        named_tests
-            .into_par_iter()
-            .map(|(name, test)| run_single_test(test, name, &runner))
-            .for_each(|res| {
-                update_summary(
-                    &wrapped_summary,
-                    res,
-                    &None,
-                    &sierra_program,
-                    &ProfilingInfoProcessorParams {
-                        process_by_original_user_function: false,
-                        process_by_cairo_function: false,
-                        ..ProfilingInfoProcessorParams::default()
-                    },
-                    config.print_resource_usage,
-                );
-            });
-    } else {
-        eprintln!("Note: Tests don't run in parallel when running with profiling.");
-        named_tests
-            .into_iter()
-            .map(move |(name, test)| run_single_test(test, name, &runner))
-            .for_each(|test_result| {
-                update_summary(
-                    &wrapped_summary,
-                    test_result,
-                    &profiler_data,
-                    &sierra_program,
-                    &ProfilingInfoProcessorParams::default(),
-                    config.print_resource_usage,
-                );
-            });
-    }
+    Ok(if config.capture_output { runner.with_captured_debug_output() } else { runner })
+}
 
-    wrapped_summary.into_inner().unwrap()
+/// Loads the Starknet state from a JSON fixture file.
+fn load_starknet_state(path: &Path) -> Result<StarknetState> {
+    let content = fs::read_to_string(path).with_context(|| {
+        format!("Failed reading the Starknet state fixture `{}`.", path.display())
+    })?;
+    serde_json::from_str(&content)
+        .with_context(|| format!("Failed parsing the Starknet state fixture `{}`.", path.display()))
 }
 
-/// Runs a single test and returns a tuple of its name and result.
+/// Runs a single test, starting from the given Starknet state, and returns its result.
 fn run_single_test(
     test: TestConfig,
     name: String,
     runner: &SierraCasmRunner,
-) -> anyhow::Result<(String, Option<TestResult>)> {
+    initial_state: &StarknetState,
+    config: &TestRunConfig,
+) -> anyhow::Result<TestResult> {
     if test.ignored {
-        return Ok((name, None));
+        return Ok(TestResult {
+            report: TestReport {
+                name,
+                status: TestStatus::Ignored,
+                duration: Duration::ZERO,
+                gas_usage: None,
+                used_resources: None,
+                profiling_info: None,
+                fuzzing: None,
+                unpopped_events: vec![],
+                output: None,
+            },
+            profiling_info: None,
+            sierra_statement_hits: None,
+        });
     }
     let func = runner.find_function(name.as_str())?;
-    let result = runner
-        .run_function_with_starknet_context(func, &[], test.available_gas, Default::default())
-        .with_context(|| format!("Failed to run the function `{}`.", name.as_str()))?;
-    Ok((
-        name,
-        Some(TestResult {
-            status: match &result.value {
-                RunResultValue::Success(_) => match test.expectation {
-                    TestExpectation::Success => TestStatus::Success,
-                    TestExpectation::Panics(_) => TestStatus::Fail(result.value),
-                },
-                RunResultValue::Panic(value) => match test.expectation {
-                    TestExpectation::Success => TestStatus::Fail(result.value),
-                    TestExpectation::Panics(panic_expectation) => match panic_expectation {
-                        PanicExpectation::Exact(expected) if value != &expected => {
-                            TestStatus::Fail(result.value)
-                        }
-                        _ => TestStatus::Success,
-                    },
+    let start = Instant::now();
+    let (run, fuzzing) = if let Some(fuzz_config) = &test.fuzz {
+        let (run, fuzzing) = run_fuzz_test(
+            runner,
+            func,
+            &test,
+            initial_state,
+            fuzz_config,
+            config.fuzzer_runs,
+            config.fuzzer_seed,
+        )
+        .with_context(|| format!("Failed to fuzz the function `{}`.", name.as_str()))?;
+        (run, Some(fuzzing))
+    } else {
+        let run = run_test_function(runner, func, &test, initial_state, &[])
+            .with_context(|| format!("Failed to run the function `{}`.", name.as_str()))?;
+        (run, None)
+    };
+    let mut profiling_info = run.profiling_info;
+    let sierra_statement_hits = if config.coverage.is_some() {
+        profiling_info.as_mut().map(|info| std::mem::take(&mut info.sierra_statement_hits))
+    } else {
+        None
+    };
+    if config.run_profiler == RunProfilerConfig::None {
+        profiling_info = None;
+    }
+    Ok(TestResult {
+        report: TestReport {
+            name,
+            status: run.status,
+            duration: start.elapsed(),
+            gas_usage: run.gas_usage,
+            used_resources: Some(run.used_resources),
+            profiling_info: None,
+            fuzzing,
+            unpopped_events: run.unpopped_events,
+            output: run.output,
+        },
+        profiling_info,
+        sierra_statement_hits,
+    })
+}
+
+/// The outcome of a single run of a test function.
+struct TestRun {
+    /// The status of the test according to the run.
+    status: TestStatus,
+    /// The gas usage of the run if relevant.
+    gas_usage: Option<i64>,
+    /// The used resources of the run.
+    used_resources: StarknetExecutionResources,
+    /// The profiling info of the run, if requested.
+    profiling_info: Option<ProfilingInfo>,
+    /// The events emitted and not popped by the run.
+    unpopped_events: Vec<EmittedEvent>,
+    /// The output of the debug prints of the run, if captured.
+    output: Option<String>,
+}
+
+/// Runs the function of a test once, with the given arguments, starting from the given Starknet
+/// state.
+fn run_test_function(
+    runner: &SierraCasmRunner,
+    func: &Function,
+    test: &TestConfig,
+    initial_state: &StarknetState,
+    args: &[Arg],
+) -> anyhow::Result<TestRun> {
+    let result = runner.run_function_with_starknet_context(
+        func,
+        args,
+        test.available_gas,
+        initial_state.clone(),
+    )?;
+    Ok(TestRun {
+        status: match &result.value {
+            RunResultValue::Success(_) => match &test.expectation {
+                TestExpectation::Success => TestStatus::Success,
+                TestExpectation::Panics(_) => TestStatus::Fail(result.value),
+            },
+            RunResultValue::Panic(value) => match &test.expectation {
+                TestExpectation::Success => TestStatus::Fail(result.value),
+                TestExpectation::Panics(panic_expectation) => match panic_expectation {
+                    PanicExpectation::Exact(expected) if value != expected => {
+                        TestStatus::Fail(result.value)
+                    }
+                    _ => TestStatus::Success,
                 },
             },
-            gas_usage: test
-                .available_gas
-                .zip(result.gas_counter)
-                .map(|(before, after)| {
-                    before.into_or_panic::<i64>() - after.to_bigint().to_i64().unwrap()
-                })
-                .or_else(|| {
-                    runner.initial_required_gas(func).map(|gas| gas.into_or_panic::<i64>())
-                }),
-            used_resources: result.used_resources,
-            profiling_info: result.profiling_info,
-        }),
-    ))
+        },
+        gas_usage: test
+            .available_gas
+            .zip(result.gas_counter)
+            .map(|(before, after)| {
+                before.into_or_panic::<i64>() - after.to_bigint().to_i64().unwrap()
+            })
+            .or_else(|| runner.initial_required_gas(func).map(|gas| gas.into_or_panic::<i64>())),
+        used_resources: result.used_resources,
+        profiling_info: result.profiling_info,
+        unpopped_events: result.starknet_state.unpopped_events(),
+        output: result.debug_output,
+    })
 }
 
-/// Updates the test summary with the given test result.
+/// Updates the test summary with the given test result, and reports it. Profiling info in formats
+/// other than text is written to its file.
 fn update_summary(
-    wrapped_summary: &Mutex<std::prelude::v1::Result<TestsSummary, anyhow::Error>>,
-    test_result: std::prelude::v1::Result<(String, Option<TestResult>), anyhow::Error>,
+    summary: &mut TestsSummary,
+    TestResult { mut report, profiling_info, sierra_statement_hits }: TestResult,
     profiler_data: &Option<PorfilingAuxData<'_>>,
     sierra_program: &Program,
     profiling_params: &ProfilingInfoProcessorParams,
-    print_resource_usage: bool,
-) {
-    let mut wrapped_summary = wrapped_summary.lock().unwrap();
-    if wrapped_summary.is_err() {
-        return;
-    }
-    let (name, opt_result) = match test_result {
-        Ok((name, opt_result)) => (name, opt_result),
-        Err(err) => {
-            *wrapped_summary = Err(err);
-            return;
-        }
-    };
-    let summary = wrapped_summary.as_mut().unwrap();
-    let (res_type, status_str, gas_usage, used_resources, profiling_info) =
-        if let Some(result) = opt_result {
-            let (res_type, status_str) = match result.status {
-                TestStatus::Success => (&mut summary.passed, "ok".bright_green()),
-                TestStatus::Fail(run_result) => {
-                    summary.failed_run_results.push(run_result);
-                    (&mut summary.failed, "fail".bright_red())
-                }
-            };
-            (
-                res_type,
-                status_str,
-                result.gas_usage,
-                print_resource_usage.then_some(result.used_resources),
-                result.profiling_info,
-            )
-        } else {
-            (&mut summary.ignored, "ignored".bright_yellow(), None, None, None)
-        };
-    if let Some(gas_usage) = gas_usage {
-        println!("test {name} ... {status_str} (gas usage est.: {gas_usage})");
-    } else {
-        println!("test {name} ... {status_str}");
-    }
-    if let Some(used_resources) = used_resources {
-        let filtered = used_resources.basic_resources.filter_unused_builtins();
-        // Prints the used resources per test. E.g.:
-        // ```ignore
-        // test cairo_level_tests::interoperability::test_contract_not_deployed ... ok (gas usage est.: 77320)
-        //     steps: 42
-        //     memory holes: 20
-        //     builtins: ("range_check_builtin": 3)
-        //     syscalls: ("CallContract": 1)
-        // test cairo_level_tests::events::test_pop_log ... ok (gas usage est.: 55440)
-        //     steps: 306
-        //     memory holes: 35
-        //     builtins: ("range_check_builtin": 24)
-        //     syscalls: ("EmitEvent": 2)
-        // ```
-        println!("    steps: {}", filtered.n_steps);
-        println!("    memory holes: {}", filtered.n_memory_holes);
-
-        print_resource_map(
-            filtered.builtin_instance_counter.into_iter().map(|(k, v)| (k.to_string(), v)),
-            "builtins",
-        );
-        print_resource_map(used_resources.syscalls.into_iter(), "syscalls");
-    }
+    config: &TestRunConfig,
+    reporter: &mut dyn TestReporter,
+) -> Result<()> {
     if let Some(profiling_info) = profiling_info {
-        let Some(PorfilingAuxData { db, statements_functions }) = profiler_data else {
-            panic!("profiler_data is None");
+        let (db, statements_functions) = match profiler_data {
+            Some(PorfilingAuxData { db, statements_functions }) => {
+                (Some(*db), statements_functions.clone())
+            }
+            None => (None, Default::default()),
         };
         let profiling_processor = ProfilingInfoProcessor::new(
-            Some(*db),
+            db,
             sierra_program.clone(),
-            statements_functions.clone(),
+            statements_functions,
             Default::default(),
         );
         let processed_profiling_info =
             profiling_processor.process_ex(&profiling_info, profiling_params);
-        println!("Profiling info:\n{processed_profiling_info}");
+        if config.profiler_format == ProfilerOutputFormat::Text {
+            report.profiling_info = Some(processed_profiling_info.to_string());
+        } else {
+            let path = config.profiler_output_dir.join(format!(
+                "{}.{}",
+                report.name.replace("::", "."),
+                config.profiler_format.extension()
+            ));
+            fs::write(&path, processed_profiling_info.export(config.profiler_format))
+                .with_context(|| format!("Failed writing `{}`.", path.display()))?;
+        }
     }
-    res_type.push(name);
-}
-
-/// Given an iterator of (String, usize) pairs, prints a usage map. E.g.:
-///     syscalls: ("EmitEvent": 2)
-///     syscalls: ("CallContract": 1)
-fn print_resource_map(m: impl ExactSizeIterator<Item = (String, usize)>, resource_type: &str) {
-    if m.len() != 0 {
-        println!(
-            "    {resource_type}: ({})",
-            m.into_iter().sorted().map(|(k, v)| format!(r#""{k}": {v}"#)).join(", ")
-        );
+    if let (Some(coverage), Some(hits)) = (&mut summary.coverage, &sierra_statement_hits) {
+        coverage.add_run(hits);
     }
+    reporter.on_test_finished(&report);
+    summary.reports.push(report);
+    Ok(())
 }
@@ -0,0 +1,439 @@
+//! Reporters of test runs, printing the progress and results of the tests in different formats.
+
+use std::fmt::Write;
+use std::time::Duration;
+
+use cairo_lang_runner::json_output::{panic_data_to_json, resources_to_json};
+use cairo_lang_runner::{EmittedEvent, RunResultValue, StarknetExecutionResources};
+use colored::Colorize;
+use itertools::{Itertools, chain};
+use serde_json::{Map, Value, json};
+
+use crate::fuzz::FuzzingReport;
+use crate::{TestReport, TestStatus, TestsSummary, format_for_panic, format_items};
+
+#[cfg(test)]
+#[path = "reporter_test.rs"]
+mod test;
+
+/// The format in which the results of the tests are reported.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TestOutputFormat {
+    /// Human readable colored text.
+    #[default]
+    Pretty,
+    /// A JSON object per line, compatible with the libtest JSON format, with the additional
+    /// `gas_usage`, `resources`, `panic_data`, `unpopped_events`, `fuzzing` and `profiling_info`
+    /// fields per test. Failure messages are in `message`, and captured debug prints in `stdout`.
+    Json,
+    /// A JUnit XML report, printed once all the tests are done. Captured debug prints are in
+    /// `system-out`.
+    Junit,
+}
+impl TestOutputFormat {
+    /// Returns a reporter for this format.
+    pub fn reporter(self, print_resource_usage: bool) -> Box<dyn TestReporter> {
+        match self {
+            TestOutputFormat::Pretty => Box::new(PrettyReporter { print_resource_usage }),
+            TestOutputFormat::Json => Box::new(JsonReporter),
+            TestOutputFormat::Junit => Box::new(JunitReporter::default()),
+        }
+    }
+}
+
+/// Reports the progress and results of a test run.
+pub trait TestReporter {
+    /// Called before running the tests, with the number of tests to run.
+    fn on_run_started(&mut self, n_tests: usize);
+    /// Called once per test, in the order of the tests, when its result is available.
+    fn on_test_finished(&mut self, report: &TestReport);
+    /// Called once all the tests are done.
+    fn on_run_finished(&mut self, summary: &TestsSummary, filtered_out: usize, duration: Duration);
+}
+
+/// Reports the results as human readable colored text.
+pub struct PrettyReporter {
+    /// Whether to print the used resources of each test.
+    pub print_resource_usage: bool,
+}
+impl TestReporter for PrettyReporter {
+    fn on_run_started(&mut self, n_tests: usize) {
+        let suffix = if n_tests != 1 { "s" } else { "" };
+        println!("running {n_tests} test{suffix}");
+    }
+
+    fn on_test_finished(&mut self, report: &TestReport) {
+        let name = &report.name;
+        let status_str = match report.status {
+            TestStatus::Success => "ok".bright_green(),
+            TestStatus::Fail(_) => "fail".bright_red(),
+            TestStatus::Ignored => "ignored".bright_yellow(),
+        };
+        let fuzzing_str = report
+            .fuzzing
+            .as_ref()
+            .map(|fuzzing| format!(" (fuzzer runs: {}, seed: {})", fuzzing.runs, fuzzing.seed))
+            .unwrap_or_default();
+        if let Some(gas_usage) = report.gas_usage {
+            println!("test {name} ... {status_str} (gas usage est.: {gas_usage}){fuzzing_str}");
+        } else {
+            println!("test {name} ... {status_str}{fuzzing_str}");
+        }
+        if let Some(used_resources) =
+            report.used_resources.as_ref().filter(|_| self.print_resource_usage)
+        {
+            let filtered = used_resources.basic_resources.filter_unused_builtins();
+            // Prints the used resources per test. E.g.:
+            // ```ignore
+            // test cairo_level_tests::interoperability::test_contract_not_deployed ... ok (gas usage est.: 77320)
+            //     steps: 42
+            //     memory holes: 20
+            //     builtins: ("range_check_builtin": 3)
+            //     syscalls: ("CallContract": 1)
+            // test cairo_level_tests::events::test_pop_log ... ok (gas usage est.: 55440)
+            //     steps: 306
+            //     memory holes: 35
+            //     builtins: ("range_check_builtin": 24)
+            //     syscalls: ("EmitEvent": 2)
+            // ```
+            println!("    steps: {}", filtered.n_steps);
+            println!("    memory holes: {}", filtered.n_memory_holes);
+
+            print_resource_map(
+                filtered.builtin_instance_counter.into_iter().map(|(k, v)| (k.to_string(), v)),
+                "builtins",
+            );
+            print_resource_map(used_resources.syscalls.clone().into_iter(), "syscalls");
+        }
+        if let Some(profiling_info) = &report.profiling_info {
+            println!("Profiling info:\n{profiling_info}");
+        }
+    }
+
+    fn on_run_finished(
+        &mut self,
+        summary: &TestsSummary,
+        filtered_out: usize,
+        _duration: Duration,
+    ) {
+        let failed = summary.failed().collect_vec();
+        if failed.is_empty() {
+            println!(
+                "test result: {}. {} passed; {} failed; {} ignored; {filtered_out} filtered out;",
+                "ok".bright_green(),
+                summary.passed().count(),
+                failed.len(),
+                summary.ignored().count()
+            );
+        } else {
+            println!("failures:");
+            for report in failed {
+                println!("   {} - {}", report.name, failure_message(report));
+                if !report.unpopped_events.is_empty() {
+                    println!("      unpopped events:");
+                    for event in &report.unpopped_events {
+                        println!("        {}", format_event(event));
+                    }
+                }
+            }
+            println!();
+        }
+    }
+}
+
+/// Given an iterator of (String, usize) pairs, prints a usage map. E.g.:
+///     syscalls: ("EmitEvent": 2)
+///     syscalls: ("CallContract": 1)
+fn print_resource_map(m: impl ExactSizeIterator<Item = (String, usize)>, resource_type: &str) {
+    if m.len() != 0 {
+        println!(
+            "    {resource_type}: ({})",
+            m.into_iter().sorted().map(|(k, v)| format!(r#""{k}": {v}"#)).join(", ")
+        );
+    }
+}
+
+/// Returns the message describing the failure of a failed test.
+pub fn failure_message(report: &TestReport) -> String {
+    let TestStatus::Fail(run_result) = &report.status else { return String::new() };
+    let message = match run_result {
+        RunResultValue::Success(_) => "expected panic but finished successfully.".into(),
+        RunResultValue::Panic(values) => format_for_panic(values.clone().into_iter()),
+    };
+    match &report.fuzzing {
+        Some(FuzzingReport { seed, failing_args: Some(args), .. }) => {
+            format!("{message} Minimal failing input: {args} (seed: {seed}).")
+        }
+        _ => message,
+    }
+}
+
+/// Formats an emitted event, showing strings and short strings in its keys and data as such. E.g.:
+///     from 0x1234: keys: [0x5472616e73666572 ('Transfer')], data: [0x1, 0x2]
+fn format_event(event: &EmittedEvent) -> String {
+    format!(
+        "from {:#x}: keys: [{}], data: [{}]",
+        event.from_address.to_biguint(),
+        format_items(event.keys.clone().into_iter()).join(", "),
+        format_items(event.data.clone().into_iter()).join(", ")
+    )
+}
+
+/// Reports the results as JSON lines, in the libtest JSON format.
+pub struct JsonReporter;
+impl TestReporter for JsonReporter {
+    fn on_run_started(&mut self, n_tests: usize) {
+        println!("{}", json!({ "type": "suite", "event": "started", "test_count": n_tests }));
+    }
+
+    fn on_test_finished(&mut self, report: &TestReport) {
+        for event in json_test_events(report) {
+            println!("{event}");
+        }
+    }
+
+    fn on_run_finished(&mut self, summary: &TestsSummary, filtered_out: usize, duration: Duration) {
+        println!("{}", json_suite_finished_event(summary, filtered_out, duration));
+    }
+}
+
+/// Returns the JSON events of a finished test: its start and its result.
+fn json_test_events(report: &TestReport) -> [Value; 2] {
+    let name = &report.name;
+    let started = json!({ "type": "test", "event": "started", "name": name });
+    let mut event = Map::new();
+    event.insert("type".into(), "test".into());
+    event.insert("name".into(), name.as_str().into());
+    match &report.status {
+        TestStatus::Success => {
+            event.insert("event".into(), "ok".into());
+        }
+        TestStatus::Fail(run_result) => {
+            event.insert("event".into(), "failed".into());
+            event.insert("message".into(), failure_message(report).into());
+            if let RunResultValue::Panic(values) = run_result {
+                event.insert("panic_data".into(), panic_data_to_json(values));
+            }
+            event.insert(
+                "unpopped_events".into(),
+                report
+                    .unpopped_events
+                    .iter()
+                    .map(|event| {
+                        json!({
+                            "from_address": event.from_address,
+                            "keys": event.keys,
+                            "data": event.data,
+                        })
+                    })
+                    .collect(),
+            );
+        }
+        TestStatus::Ignored => {
+            event.insert("event".into(), "ignored".into());
+            return [started, Value::Object(event)];
+        }
+    }
+    if let Some(output) = captured_output(report) {
+        event.insert("stdout".into(), output.into());
+    }
+    event.insert("exec_time".into(), report.duration.as_secs_f64().into());
+    if let Some(gas_usage) = report.gas_usage {
+        event.insert("gas_usage".into(), gas_usage.into());
+    }
+    if let Some(used_resources) = &report.used_resources {
+        event.insert("resources".into(), resources_to_json(used_resources));
+    }
+    if let Some(fuzzing) = &report.fuzzing {
+        event.insert(
+            "fuzzing".into(),
+            json!({
+                "runs": fuzzing.runs,
+                "seed": fuzzing.seed,
+                "failing_args": fuzzing.failing_args,
+            }),
+        );
+    }
+    if let Some(profiling_info) = &report.profiling_info {
+        event.insert("profiling_info".into(), profiling_info.as_str().into());
+    }
+    [started, Value::Object(event)]
+}
+
+/// Returns the JSON event of the end of the test run.
+fn json_suite_finished_event(
+    summary: &TestsSummary,
+    filtered_out: usize,
+    duration: Duration,
+) -> Value {
+    let n_failed = summary.failed().count();
+    json!({
+        "type": "suite",
+        "event": if n_failed == 0 { "ok" } else { "failed" },
+        "passed": summary.passed().count(),
+        "failed": n_failed,
+        "ignored": summary.ignored().count(),
+        "measured": 0,
+        "filtered_out": filtered_out,
+        "exec_time": duration.as_secs_f64(),
+    })
+}
+
+/// Reports the results as a JUnit XML document, printed once all the tests are done.
+#[derive(Default)]
+pub struct JunitReporter {
+    /// The `testcase` elements of the finished tests.
+    test_cases: String,
+}
+impl JunitReporter {
+    /// Returns the JUnit XML document of the run, with the test cases finished so far.
+    fn document(&self, summary: &TestsSummary, duration: Duration) -> String {
+        let mut document = String::new();
+        writeln!(document, r#"<?xml version="1.0" encoding="UTF-8"?>"#).unwrap();
+        writeln!(document, "<testsuites>").unwrap();
+        writeln!(
+            document,
+            r#"  <testsuite name="cairo-test" tests="{}" failures="{}" errors="0" skipped="{}" time="{:.6}">"#,
+            summary.reports.len(),
+            summary.failed().count(),
+            summary.ignored().count(),
+            duration.as_secs_f64()
+        )
+        .unwrap();
+        document.push_str(&self.test_cases);
+        writeln!(document, "  </testsuite>").unwrap();
+        writeln!(document, "</testsuites>").unwrap();
+        document
+    }
+}
+impl TestReporter for JunitReporter {
+    fn on_run_started(&mut self, _n_tests: usize) {}
+
+    fn on_test_finished(&mut self, report: &TestReport) {
+        self.test_cases.push_str(&junit_test_case(report));
+    }
+
+    fn on_run_finished(
+        &mut self,
+        summary: &TestsSummary,
+        _filtered_out: usize,
+        duration: Duration,
+    ) {
+        print!("{}", self.document(summary, duration));
+    }
+}
+
+/// Returns the JUnit `testcase` element of a finished test.
+fn junit_test_case(report: &TestReport) -> String {
+    let (classname, name) = report.name.rsplit_once("::").unwrap_or(("", &report.name));
+    let mut case = String::new();
+    write!(
+        case,
+        r#"    <testcase name="{}" classname="{}" time="{:.6}">"#,
+        xml_escape(name),
+        xml_escape(classname),
+        report.duration.as_secs_f64()
+    )
+    .unwrap();
+    case.push('\n');
+    let properties = report
+        .gas_usage
+        .map(|gas_usage| ("gas_usage".to_string(), gas_usage.to_string()))
+        .into_iter()
+        .chain(report.fuzzing.iter().flat_map(|fuzzing| {
+            [
+                ("fuzzer_runs".to_string(), fuzzing.runs.to_string()),
+                ("fuzzer_seed".to_string(), fuzzing.seed.to_string()),
+            ]
+        }))
+        .chain(report.used_resources.iter().flat_map(resource_properties))
+        .collect_vec();
+    if !properties.is_empty() {
+        case.push_str("      <properties>\n");
+        for (name, value) in properties {
+            writeln!(
+                case,
+                r#"        <property name="{}" value="{}"/>"#,
+                xml_escape(&name),
+                xml_escape(&value)
+            )
+            .unwrap();
+        }
+        case.push_str("      </properties>\n");
+    }
+    match &report.status {
+        TestStatus::Success => {}
+        TestStatus::Fail(run_result) => {
+            let message = xml_escape(&failure_message(report));
+            let (failure_type, body) = match run_result {
+                RunResultValue::Success(_) => ("missing_panic", String::new()),
+                RunResultValue::Panic(values) => {
+                    ("panic", xml_escape(&panic_data_to_json(values).to_string()))
+                }
+            };
+            writeln!(
+                case,
+                r#"      <failure message="{message}" type="{failure_type}">{body}</failure>"#
+            )
+            .unwrap();
+        }
+        TestStatus::Ignored => case.push_str("      <skipped/>\n"),
+    }
+    let system_out = chain!(captured_output(report), report.profiling_info.as_deref()).join("\n");
+    if !system_out.is_empty() {
+        writeln!(case, "      <system-out>{}</system-out>", xml_escape(&system_out)).unwrap();
+    }
+    if matches!(report.status, TestStatus::Fail(_)) && !report.unpopped_events.is_empty() {
+        let events = report.unpopped_events.iter().map(format_event).join("\n");
+        writeln!(case, "      <system-err>unpopped events:\n{}</system-err>", xml_escape(&events))
+            .unwrap();
+    }
+    case.push_str("    </testcase>\n");
+    case
+}
+
+/// Returns the used resources as a list of JUnit properties.
+fn resource_properties(resources: &StarknetExecutionResources) -> Vec<(String, String)> {
+    let basic_resources = resources.basic_resources.filter_unused_builtins();
+    [
+        ("steps".to_string(), basic_resources.n_steps.to_string()),
+        ("memory_holes".to_string(), basic_resources.n_memory_holes.to_string()),
+    ]
+    .into_iter()
+    .chain(
+        basic_resources
+            .builtin_instance_counter
+            .iter()
+            .map(|(name, count)| (format!("builtins.{name}"), count.to_string()))
+            .sorted(),
+    )
+    .chain(
+        resources
+            .syscalls
+            .iter()
+            .map(|(name, count)| (format!("syscalls.{name}"), count.to_string()))
+            .sorted(),
+    )
+    .collect()
+}
+
+/// Returns the captured output of the debug prints of a test, if any.
+fn captured_output(report: &TestReport) -> Option<&str> {
+    report.output.as_deref().filter(|output| !output.is_empty())
+}
+
+/// Escapes the given text for usage in XML attributes and text.
+fn xml_escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
@@ -0,0 +1,268 @@
+use std::time::Duration;
+
+use cairo_lang_runner::{EmittedEvent, RunResultValue};
+use serde_json::json;
+use starknet_types_core::felt::Felt as Felt252;
+
+use super::{
+    JunitReporter, TestReporter, json_suite_finished_event, json_test_events, junit_test_case,
+    xml_escape,
+};
+use crate::fuzz::FuzzingReport;
+use crate::{TestReport, TestStatus, TestsSummary};
+
+/// Returns a report of a test with the given name and status, and no optional info.
+fn report(name: &str, status: TestStatus) -> TestReport {
+    TestReport {
+        name: name.into(),
+        status,
+        duration: Duration::from_millis(1500),
+        gas_usage: None,
+        used_resources: None,
+        profiling_info: None,
+        fuzzing: None,
+        unpopped_events: vec![],
+        output: None,
+    }
+}
+
+/// Returns a report of a failed test, panicking with a short string that requires XML escaping.
+fn panic_report() -> TestReport {
+    TestReport {
+        gas_usage: Some(1200),
+        unpopped_events: vec![EmittedEvent {
+            from_address: Felt252::from(0x1234),
+            keys: vec![Felt252::from_bytes_be_slice(b"Transfer")],
+            data: vec![Felt252::from(1)],
+        }],
+        ..report(
+            "tests::test_<&>\"'",
+            TestStatus::Fail(RunResultValue::Panic(vec![Felt252::from_bytes_be_slice(b"<&>\"'")])),
+        )
+    }
+}
+
+#[test]
+fn test_xml_escape() {
+    assert_eq!(xml_escape("plain text"), "plain text");
+    assert_eq!(
+        xml_escape("a<b & c>d \"e\" 'f'"),
+        "a&lt;b &amp; c&gt;d &quot;e&quot; &apos;f&apos;"
+    );
+    assert_eq!(xml_escape("&amp;"), "&amp;amp;");
+}
+
+#[test]
+fn test_json_passed() {
+    let report = TestReport {
+        gas_usage: Some(100),
+        fuzzing: Some(FuzzingReport { runs: 20, seed: 3, failing_args: None }),
+        ..report("tests::test_ok", TestStatus::Success)
+    };
+    assert_eq!(json_test_events(&report), [
+        json!({ "type": "test", "event": "started", "name": "tests::test_ok" }),
+        json!({
+            "type": "test",
+            "name": "tests::test_ok",
+            "event": "ok",
+            "exec_time": 1.5,
+            "gas_usage": 100,
+            "fuzzing": { "runs": 20, "seed": 3, "failing_args": null },
+        }),
+    ]);
+}
+
+#[test]
+fn test_json_panicked() {
+    assert_eq!(json_test_events(&panic_report()), [
+        json!({ "type": "test", "event": "started", "name": "tests::test_<&>\"'" }),
+        json!({
+            "type": "test",
+            "name": "tests::test_<&>\"'",
+            "event": "failed",
+            "message": "Panicked with 0x3c263e2227 ('<&>\"'').",
+            "panic_data": [{ "felt252": "0x3c263e2227", "short_string": "<&>\"'" }],
+            "unpopped_events": [{
+                "from_address": "0x1234",
+                "keys": ["0x5472616e73666572"],
+                "data": ["0x1"],
+            }],
+            "exec_time": 1.5,
+            "gas_usage": 1200,
+        }),
+    ]);
+}
+
+#[test]
+fn test_json_missing_panic() {
+    let report = report(
+        "tests::test_should_panic",
+        TestStatus::Fail(RunResultValue::Success(vec![])),
+    );
+    assert_eq!(json_test_events(&report)[1], json!({
+        "type": "test",
+        "name": "tests::test_should_panic",
+        "event": "failed",
+        "message": "expected panic but finished successfully.",
+        "unpopped_events": [],
+        "exec_time": 1.5,
+    }));
+}
+
+#[test]
+fn test_json_printed_output() {
+    let report = TestReport {
+        output: Some("printed <line>\n".into()),
+        ..report("tests::test_print", TestStatus::Fail(RunResultValue::Success(vec![])))
+    };
+    assert_eq!(json_test_events(&report)[1], json!({
+        "type": "test",
+        "name": "tests::test_print",
+        "event": "failed",
+        "message": "expected panic but finished successfully.",
+        "unpopped_events": [],
+        "stdout": "printed <line>\n",
+        "exec_time": 1.5,
+    }));
+}
+
+#[test]
+fn test_json_ignored() {
+    let report = report("tests::test_ignored", TestStatus::Ignored);
+    assert_eq!(json_test_events(&report)[1], json!({
+        "type": "test",
+        "name": "tests::test_ignored",
+        "event": "ignored",
+    }));
+}
+
+#[test]
+fn test_json_suite_finished() {
+    let summary = TestsSummary {
+        reports: vec![
+            report("tests::test_ok", TestStatus::Success),
+            panic_report(),
+            report("tests::test_ignored", TestStatus::Ignored),
+        ],
+        coverage: None,
+    };
+    assert_eq!(json_suite_finished_event(&summary, 2, Duration::from_millis(250)), json!({
+        "type": "suite",
+        "event": "failed",
+        "passed": 1,
+        "failed": 1,
+        "ignored": 1,
+        "measured": 0,
+        "filtered_out": 2,
+        "exec_time": 0.25,
+    }));
+}
+
+#[test]
+fn test_junit_passed() {
+    let report = TestReport {
+        gas_usage: Some(100),
+        fuzzing: Some(FuzzingReport { runs: 20, seed: 3, failing_args: None }),
+        ..report("tests::test_ok", TestStatus::Success)
+    };
+    assert_eq!(
+        junit_test_case(&report),
+        r#"    <testcase name="test_ok" classname="tests" time="1.500000">
+      <properties>
+        <property name="gas_usage" value="100"/>
+        <property name="fuzzer_runs" value="20"/>
+        <property name="fuzzer_seed" value="3"/>
+      </properties>
+    </testcase>
+"#
+    );
+}
+
+#[test]
+fn test_junit_panicked() {
+    assert_eq!(
+        junit_test_case(&panic_report()),
+        r#"    <testcase name="test_&lt;&amp;&gt;&quot;&apos;" classname="tests" time="1.500000">
+      <properties>
+        <property name="gas_usage" value="1200"/>
+      </properties>
+      <failure message="Panicked with 0x3c263e2227 (&apos;&lt;&amp;&gt;&quot;&apos;&apos;)." type="panic">[{&quot;felt252&quot;:&quot;0x3c263e2227&quot;,&quot;short_string&quot;:&quot;&lt;&amp;&gt;\&quot;&apos;&quot;}]</failure>
+      <system-err>unpopped events:
+from 0x1234: keys: [0x5472616e73666572 (&apos;Transfer&apos;)], data: [0x1]</system-err>
+    </testcase>
+"#
+    );
+}
+
+#[test]
+fn test_junit_missing_panic() {
+    let report = report("test_should_panic", TestStatus::Fail(RunResultValue::Success(vec![])));
+    assert_eq!(
+        junit_test_case(&report),
+        r#"    <testcase name="test_should_panic" classname="" time="1.500000">
+      <failure message="expected panic but finished successfully." type="missing_panic"></failure>
+    </testcase>
+"#
+    );
+}
+
+#[test]
+fn test_junit_printed_output() {
+    let report = TestReport {
+        output: Some("printed <line>\n".into()),
+        profiling_info: Some("profile".into()),
+        ..report("tests::test_print", TestStatus::Fail(RunResultValue::Success(vec![])))
+    };
+    assert_eq!(
+        junit_test_case(&report),
+        r#"    <testcase name="test_print" classname="tests" time="1.500000">
+      <failure message="expected panic but finished successfully." type="missing_panic"></failure>
+      <system-out>printed &lt;line&gt;
+
+profile</system-out>
+    </testcase>
+"#
+    );
+}
+
+#[test]
+fn test_junit_ignored() {
+    let report = report("tests::test_ignored", TestStatus::Ignored);
+    assert_eq!(
+        junit_test_case(&report),
+        r#"    <testcase name="test_ignored" classname="tests" time="1.500000">
+      <skipped/>
+    </testcase>
+"#
+    );
+}
+
+#[test]
+fn test_junit_document() {
+    let mut reporter = JunitReporter::default();
+    let summary = TestsSummary {
+        reports: vec![
+            report("tests::test_ok", TestStatus::Success),
+            report("tests::test_ignored", TestStatus::Ignored),
+        ],
+        coverage: None,
+    };
+    reporter.on_run_started(summary.reports.len());
+    for report in &summary.reports {
+        reporter.on_test_finished(report);
+    }
+    assert_eq!(
+        reporter.document(&summary, Duration::from_millis(250)),
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<testsuites>
+  <testsuite name="cairo-test" tests="2" failures="0" errors="0" skipped="1" time="0.250000">
+    <testcase name="test_ok" classname="tests" time="1.500000">
+    </testcase>
+    <testcase name="test_ignored" classname="tests" time="1.500000">
+      <skipped/>
+    </testcase>
+  </testsuite>
+</testsuites>
+"#
+    );
+}
@@ -1,18 +1,26 @@
+use std::path::PathBuf;
+
+use cairo_lang_runner::EmittedEvent;
+use cairo_lang_runner::profiling::ProfilerOutputFormat;
 use cairo_lang_sierra::program::{Program, ProgramArtifact};
 use cairo_lang_test_plugin::test_config::TestExpectation;
 use cairo_lang_test_plugin::{TestCompilationMetadata, TestConfig, TestsCompilationConfig};
 use cairo_lang_utils::byte_array::BYTE_ARRAY_MAGIC;
-use itertools::Itertools;
+use itertools::{Itertools, chain};
 use starknet_types_core::felt::Felt as Felt252;
 
-use crate::{TestCompilation, TestCompiler, filter_test_cases, format_for_panic};
+use crate::coverage::LinesCoverage;
+use crate::reporter::TestOutputFormat;
+use crate::{
+    CompiledTestRunner, RunProfilerConfig, TestCompilation, TestCompiler, TestRunConfig,
+    TestsSummary, filter_test_cases, format_for_panic, run_tests,
+};
 
-#[test]
-fn test_compiled_serialization() {
-    use std::path::PathBuf;
+/// Returns the compiler of the tests of the `test_data` crate.
+fn test_data_compiler(gas_enabled: bool) -> TestCompiler {
     let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("test_data");
 
-    let compiler = TestCompiler::try_new(&path, true, false, TestsCompilationConfig {
+    TestCompiler::try_new(&path, true, gas_enabled, TestsCompilationConfig {
         starknet: true,
         add_statements_functions: false,
         add_statements_code_locations: false,
@@ -20,8 +28,17 @@ fn test_compiled_serialization() {
         contract_crate_ids: None,
         executable_crate_ids: None,
     })
-    .unwrap();
-    let compiled = compiler.build().unwrap();
+    .unwrap()
+}
+
+/// Compiles the tests of the `test_data` crate.
+fn compile_test_data(gas_enabled: bool) -> TestCompilation {
+    test_data_compiler(gas_enabled).build().unwrap()
+}
+
+#[test]
+fn test_compiled_serialization() {
+    let compiled = compile_test_data(false);
     let serialized = serde_json::to_string_pretty(&compiled).unwrap();
     let deserialized: TestCompilation = serde_json::from_str(&serialized).unwrap();
 
@@ -34,6 +51,250 @@ fn test_compiled_serialization() {
     );
 }
 
+/// Returns the configuration for running the tests containing `filter` with the given number of
+/// jobs.
+fn test_run_config(filter: &str, jobs: usize) -> TestRunConfig {
+    TestRunConfig {
+        filter: filter.into(),
+        include_ignored: false,
+        ignored: false,
+        run_profiler: RunProfilerConfig::None,
+        profiler_format: ProfilerOutputFormat::Text,
+        profiler_output_dir: PathBuf::from("profiles"),
+        gas_enabled: true,
+        print_resource_usage: false,
+        capture_output: false,
+        jobs: Some(jobs),
+        format: TestOutputFormat::Pretty,
+        fuzzer_runs: 256,
+        fuzzer_seed: None,
+        starknet_state_fixture: None,
+        coverage: None,
+    }
+}
+
+/// Runs the given compiled tests with the given configuration.
+fn run_compiled_tests(compiled: &TestCompilation, config: &TestRunConfig) -> TestsSummary {
+    let (compiled, _) =
+        filter_test_cases(compiled.clone(), config.include_ignored, config.ignored, &config.filter);
+    run_tests(
+        None,
+        compiled.metadata.named_tests,
+        compiled.sierra_program.program,
+        compiled.metadata.function_set_costs,
+        compiled.metadata.contracts_info,
+        config,
+        config.format.reporter(config.print_resource_usage).as_mut(),
+    )
+    .unwrap()
+}
+
+#[test]
+fn test_parallel_run_is_deterministic() {
+    let compiled = compile_test_data(true);
+    let run_with_jobs = |jobs| {
+        let summary = run_compiled_tests(&compiled, &test_run_config("contracts::tests::", jobs));
+        (
+            summary.passed().cloned().collect_vec(),
+            summary
+                .failed()
+                .map(|report| {
+                    let failing_args = report.fuzzing.as_ref().map(|f| f.failing_args.clone());
+                    (report.name.clone(), format!("{:?}", report.status), failing_args)
+                })
+                .collect_vec(),
+            summary.ignored().cloned().collect_vec(),
+        )
+    };
+    let sequential = run_with_jobs(1);
+    assert_eq!(sequential.0, [
+        "contracts::tests::test_flow",
+        "contracts::tests::test_add",
+        "contracts::tests::test_expected_panic",
+        "contracts::tests::test_fuzz_add",
+        "contracts::tests::test_fuzz_non_zero",
+    ]);
+    assert_eq!(sequential.1.iter().map(|(name, ..)| name).collect_vec(), [
+        "contracts::tests::test_unexpected_panic",
+        "contracts::tests::test_unpopped_events",
+        "contracts::tests::test_fuzz_bounded",
+    ]);
+    assert_eq!(sequential.2, ["contracts::tests::test_ignored"]);
+    for jobs in [2, 4] {
+        assert_eq!(run_with_jobs(jobs), sequential);
+    }
+}
+
+#[test]
+fn test_run_error_of_late_test() {
+    let (compiled, _) =
+        filter_test_cases(compile_test_data(true), false, false, "contracts::tests::test_fuzz_add");
+    let slow_test = compiled.metadata.named_tests[0].clone();
+    // With 2 jobs, the second half of the tests is run by the second worker, which fails on its
+    // first test while most of the tests of the first worker were not started.
+    let named_tests = chain!(
+        std::iter::repeat_n(slow_test.clone(), 10),
+        [to_named_test(&("contracts::tests::test_missing", false))],
+        std::iter::repeat_n(slow_test, 9),
+    )
+    .collect_vec();
+    for jobs in [1, 2] {
+        let config = test_run_config("", jobs);
+        let err = run_tests(
+            None,
+            named_tests.clone(),
+            compiled.sierra_program.program.clone(),
+            compiled.metadata.function_set_costs.clone(),
+            compiled.metadata.contracts_info.clone(),
+            &config,
+            config.format.reporter(config.print_resource_usage).as_mut(),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Function with suffix `contracts::tests::test_missing` to run not found."
+        );
+    }
+}
+
+#[test]
+fn test_fuzzing() {
+    let compiled = compile_test_data(true);
+    let summary = run_compiled_tests(&compiled, &test_run_config("contracts::tests::", 2));
+    let fuzzing = |name: &str| {
+        let report = summary.reports.iter().find(|report| report.name == name).unwrap();
+        report.fuzzing.as_ref().unwrap()
+    };
+
+    let passed = fuzzing("contracts::tests::test_fuzz_add");
+    assert_eq!((passed.runs, passed.seed, &passed.failing_args), (20, 3, &None));
+
+    // `NonZero` parameters may get values whose low cells are zero.
+    let non_zero = fuzzing("contracts::tests::test_fuzz_non_zero");
+    assert_eq!((non_zero.runs, non_zero.seed, &non_zero.failing_args), (50, 7, &None));
+
+    // The failing arguments are shrunk to the minimal failing arguments.
+    let failed = fuzzing("contracts::tests::test_fuzz_bounded");
+    assert_eq!(failed.seed, 5);
+    assert_eq!(failed.failing_args, Some(serde_json::json!([[0, 0, 0], 100])));
+}
+
+#[test]
+fn test_starknet_state_fixture() {
+    let compiled = compile_test_data(true);
+    let config = test_run_config("contracts::fixture_tests::", 1);
+    let summary = run_compiled_tests(&compiled, &config);
+    assert_eq!(summary.failed().count(), 1);
+
+    let config = TestRunConfig {
+        starknet_state_fixture: Some(
+            PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("test_data/starknet_state.json"),
+        ),
+        ..config
+    };
+    let summary = run_compiled_tests(&compiled, &config);
+    assert_eq!(summary.passed().collect_vec(), [
+        "contracts::fixture_tests::test_state_from_fixture"
+    ]);
+}
+
+#[test]
+fn test_unpopped_events() {
+    let compiled = compile_test_data(true);
+    let config = test_run_config("contracts::tests::test_unpopped_events", 1);
+    let summary = run_compiled_tests(&compiled, &config);
+    let [report] = &summary.reports[..] else { panic!("Expected a single test report.") };
+    assert_eq!(report.unpopped_events, [EmittedEvent {
+        from_address: Felt252::from(0x1234),
+        keys: vec![Felt252::from_hex_unchecked("756e706f70706564")], // 'unpopped'
+        data: vec![Felt252::from(1)],
+    }]);
+}
+
+#[test]
+fn test_captured_output() {
+    let compiled = compile_test_data(true);
+    let config = TestRunConfig {
+        capture_output: true,
+        ..test_run_config("contracts::output_tests::test_print", 1)
+    };
+    let summary = run_compiled_tests(&compiled, &config);
+    let [report] = &summary.reports[..] else { panic!("Expected a single test report.") };
+    assert_eq!(report.output.as_deref(), Some("captured 1\n"));
+}
+
+#[test]
+fn test_captured_output_of_contract_call() {
+    let compiled = compile_test_data(true);
+    let config = TestRunConfig {
+        capture_output: true,
+        ..test_run_config("contracts::output_tests::test_nested_print", 1)
+    };
+    let summary = run_compiled_tests(&compiled, &config);
+    let [report] = &summary.reports[..] else { panic!("Expected a single test report.") };
+    assert_eq!(report.output.as_deref(), Some("before call\nin contract 2\nafter call\n"));
+}
+
+#[test]
+fn test_coverage() {
+    let compiler = test_data_compiler(true);
+    let compiled = compiler.build().unwrap();
+    let config = TestRunConfig {
+        coverage: Some(PathBuf::from("coverage")),
+        ..test_run_config("contracts::tests::test_flow", 1)
+    };
+    let summary = run_compiled_tests(&compiled, &config);
+    let coverage = LinesCoverage::new(
+        &compiler.db,
+        compiled.metadata.statements_locations.as_ref().unwrap(),
+        summary.coverage.as_ref().unwrap(),
+    );
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("test_data/lib.cairo");
+    let lines = &coverage.files[&path];
+    // The body of `increase`, called by the test through a contract call.
+    assert!(lines[&27] > 0);
+    // The body of `test_flow`.
+    assert!(lines[&52] > 0);
+    // The body of `test_add`, which is not run.
+    assert_eq!(lines[&61], 0);
+    assert!(coverage.files.keys().all(|path| !path.ends_with("corelib/src/lib.cairo")));
+
+    let lcov = coverage.to_lcov();
+    assert!(lcov.starts_with(&format!("TN:\nSF:{}\n", path.display())));
+    assert!(lcov.contains("DA:61,0\n"));
+    let summary = coverage.to_json_summary();
+    assert_eq!(summary["total"]["lines_found"], lines.len());
+}
+
+#[test]
+fn test_coverage_without_db() {
+    let config = TestRunConfig {
+        coverage: Some(PathBuf::from("coverage")),
+        ..test_run_config("contracts::tests::test_flow", 1)
+    };
+    let err = CompiledTestRunner::new(compile_test_data(true), config).run(None).unwrap_err();
+    assert_eq!(err.to_string(), "The db must be passed when collecting coverage.");
+}
+
+#[test]
+fn test_profiler_output() {
+    let compiled = compile_test_data(true);
+    let profiler_output_dir = std::env::temp_dir().join("cairo-test-runner-profiles");
+    let config = TestRunConfig {
+        run_profiler: RunProfilerConfig::Sierra,
+        profiler_format: ProfilerOutputFormat::Folded,
+        profiler_output_dir: profiler_output_dir.clone(),
+        ..test_run_config("contracts::tests::test_add", 1)
+    };
+    let summary = run_compiled_tests(&compiled, &config);
+    let [report] = &summary.reports[..] else { panic!("Expected a single test report.") };
+    assert_eq!(report.profiling_info, None);
+    let folded =
+        std::fs::read_to_string(profiler_output_dir.join("contracts.tests.test_add.folded"))
+            .unwrap();
+    assert!(folded.starts_with("contracts::tests::test_add "));
+}
+
 #[test]
 fn test_format_for_panic() {
     // Valid short string.
@@ -230,6 +491,7 @@ fn to_named_test(test: &(&str, bool)) -> (String, TestConfig) {
         available_gas: None,
         expectation: TestExpectation::Success,
         ignored: test.1,
+        fuzz: None,
     })
 }
 
@@ -55,4 +55,101 @@ mod tests {
         assert_eq!(contract0.get(), 100);
         assert_eq!(contract1.get(), 400);
     }
+
+    #[test]
+    fn test_add() {
+        assert_eq!(1_u128 + 2, 3);
+    }
+
+    #[test]
+    #[should_panic(expected: ('wrong',))]
+    fn test_expected_panic() {
+        core::panic_with_felt252('wrong');
+    }
+
+    #[test]
+    fn test_unexpected_panic() {
+        core::panic_with_felt252('unexpected');
+    }
+
+    #[test]
+    #[ignore]
+    fn test_ignored() {}
+
+    #[test]
+    fn test_unpopped_events() {
+        let contract_address = starknet::contract_address_const::<0x1234>();
+        starknet::testing::set_contract_address(contract_address);
+        starknet::syscalls::emit_event_syscall(['popped'].span(), [].span()).unwrap();
+        starknet::syscalls::emit_event_syscall(['unpopped'].span(), [1].span()).unwrap();
+        starknet::testing::pop_log_raw(contract_address).unwrap();
+        core::panic_with_felt252('failing');
+    }
+
+    #[test]
+    #[fuzz(runs: 20, seed: 3)]
+    fn test_fuzz_add(a: u64, b: u64) {
+        let (a, b): (u128, u128) = (a.into(), b.into());
+        assert_eq!(a + b, b + a);
+    }
+
+    #[test]
+    #[fuzz(seed: 5)]
+    fn test_fuzz_bounded(values: Array<u8>, x: u8) {
+        assert!(values.len() < 3 || x < 100);
+    }
+
+    #[test]
+    #[fuzz(runs: 50, seed: 7)]
+    fn test_fuzz_non_zero(a: u256, b: NonZero<u256>) {
+        let (q, r) = core::traits::DivRem::div_rem(a, b);
+        let b: u256 = b.into();
+        assert!(r < b);
+        assert_eq!(q * b + r, a);
+    }
+}
+
+/// Tests expecting the Starknet state of the `starknet_state.json` fixture.
+#[cfg(test)]
+mod fixture_tests {
+    use starknet::syscalls::storage_read_syscall;
+
+    #[test]
+    fn test_state_from_fixture() {
+        assert_eq!(starknet::get_contract_address(), 0x1234.try_into().unwrap());
+        assert_eq!(starknet::get_block_info().block_number, 1000);
+        assert_eq!(storage_read_syscall(0, 0x10.try_into().unwrap()).unwrap(), 100);
+    }
+}
+
+/// Tests printing debug output.
+#[cfg(test)]
+mod output_tests {
+    #[test]
+    fn test_print() {
+        println!("captured {}", 1);
+    }
+
+    #[test]
+    fn test_nested_print() {
+        println!("before call");
+        let (address, _) = starknet::syscalls::deploy_syscall(
+            super::Printer::TEST_CLASS_HASH.try_into().unwrap(), 0, [].span(), false
+        )
+            .unwrap();
+        starknet::syscalls::call_contract_syscall(address, selector!("print"), [2].span()).unwrap();
+        println!("after call");
+    }
+}
+
+/// A contract printing from its entry point, for testing output of contract calls.
+#[starknet::contract]
+mod Printer {
+    #[storage]
+    struct Storage {}
+
+    #[external(v0)]
+    fn print(self: @ContractState, value: felt252) {
+        println!("in contract {}", value);
+    }
 }
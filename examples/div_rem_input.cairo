@@ -0,0 +1,3 @@
+fn div_rem_input(a: u256, b: NonZero<u256>) -> (u256, u256) {
+    core::traits::DivRem::div_rem(a, b)
+}
@@ -1,5 +1,6 @@
 mod complex_input;
 mod corelib_usage;
+mod div_rem_input;
 mod enum_flow;
 mod fib;
 mod fib_array;
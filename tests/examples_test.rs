@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::path::PathBuf;
 use std::sync::{Arc, Mutex};
 
@@ -10,17 +11,26 @@ use cairo_lang_filesystem::db::FilesGroupEx;
 use cairo_lang_filesystem::flag::Flag;
 use cairo_lang_filesystem::ids::{CrateId, FlagId};
 use cairo_lang_lowering::ids::ConcreteFunctionWithBodyId;
-use cairo_lang_runner::{Arg, RunResultValue, SierraCasmRunner, token_gas_cost};
+use cairo_lang_runner::json_output::ValueDecoder;
+use cairo_lang_runner::{Arg, RunResultStarknet, RunResultValue, SierraCasmRunner, token_gas_cost};
+use cairo_lang_sierra::extensions::core::{CoreLibfunc, CoreType, CoreTypeConcrete};
 use cairo_lang_sierra::extensions::gas::CostTokenType;
+use cairo_lang_sierra::ids::ConcreteTypeId;
+use cairo_lang_sierra::program::{Function, Program, StatementIdx};
+use cairo_lang_sierra::program_registry::ProgramRegistry;
+use cairo_lang_sierra::simulation;
+use cairo_lang_sierra::simulation::value::CoreValue;
 use cairo_lang_sierra_generator::db::SierraGenGroup;
 use cairo_lang_sierra_generator::program_generator::SierraProgramWithDebug;
 use cairo_lang_sierra_generator::replace_ids::replace_sierra_ids_in_program;
 use cairo_lang_sierra_to_casm::compiler::SierraToCasmConfig;
 use cairo_lang_sierra_to_casm::metadata::{calc_metadata, calc_metadata_ap_change_only};
+use cairo_lang_sierra_type_size::{TypeSizeMap, get_type_size_map};
 use cairo_lang_test_utils::compare_contents_or_fix_with_path;
 use cairo_lang_utils::{Upcast, extract_matches};
-use itertools::Itertools;
+use itertools::{Itertools, chain, repeat_n, zip_eq};
 use rstest::{fixture, rstest};
+use serde_json::json;
 use starknet_types_core::felt::Felt as Felt252;
 
 type ExampleDirData = (Mutex<RootDatabase>, Vec<CrateId>);
@@ -60,7 +70,7 @@ fn checked_compile_to_sierra(
     name: &str,
     (db, crate_ids): &ExampleDirData,
     auto_add_withdraw_gas: bool,
-) -> cairo_lang_sierra::program::Program {
+) -> Program {
     let mut locked_db = db.lock().unwrap();
     let add_withdraw_gas_flag_id = FlagId::new(locked_db.snapshot().upcast(), "add_withdraw_gas");
     locked_db.set_flag(
@@ -161,7 +171,11 @@ fn cairo_to_casm(
             } else {
                 calc_metadata_ap_change_only(&program).unwrap()
             },
-            SierraToCasmConfig { gas_usage_check, max_bytecode_size: usize::MAX },
+            SierraToCasmConfig {
+                gas_usage_check,
+                max_bytecode_size: usize::MAX,
+                peephole_optimizations: false,
+            },
         )
         .unwrap()
         .to_string(),
@@ -179,13 +193,31 @@ fn cairo_to_casm_auto_gas(#[case] name: &str, example_dir_data: &ExampleDirData)
         cairo_lang_sierra_to_casm::compiler::compile(
             &program,
             &calc_metadata(&program, Default::default()).unwrap(),
-            SierraToCasmConfig { gas_usage_check: true, max_bytecode_size: usize::MAX },
+            SierraToCasmConfig {
+                gas_usage_check: true,
+                max_bytecode_size: usize::MAX,
+                peephole_optimizations: false,
+            },
         )
         .unwrap()
         .to_string(),
     );
 }
 
+/// Compiles the example `name` and returns a runner for it, along with its first function.
+fn example_runner(name: &str, example_dir_data: &ExampleDirData) -> (SierraCasmRunner, Function) {
+    let runner = SierraCasmRunner::new(
+        checked_compile_to_sierra(name, example_dir_data, false),
+        None,
+        Default::default(),
+        None,
+    )
+    .expect("Failed setting up runner.");
+    // find first
+    let func = runner.find_function("").expect("Failed finding the function.").clone();
+    (runner, func)
+}
+
 fn run_function(
     name: &str,
     params: &[Felt252],
@@ -194,22 +226,34 @@ fn run_function(
     example_dir_data: &ExampleDirData,
     auto_add_withdraw_gas: bool,
 ) -> RunResultValue {
+    let program = checked_compile_to_sierra(name, example_dir_data, auto_add_withdraw_gas);
     let runner = SierraCasmRunner::new(
-        checked_compile_to_sierra(name, example_dir_data, auto_add_withdraw_gas),
+        program.clone(),
         if available_gas.is_some() { Some(Default::default()) } else { None },
         Default::default(),
         None,
     )
     .expect("Failed setting up runner.");
-    let result = runner
-        .run_function_with_starknet_context(
-            // find first
-            runner.find_function("").expect("Failed finding the function."),
-            &params.iter().cloned().map(Arg::Value).collect_vec(),
-            available_gas,
-            Default::default(),
-        )
-        .expect("Failed running the function.");
+    let run = |runner: &SierraCasmRunner| {
+        runner
+            .run_function_with_starknet_context(
+                // find first
+                runner.find_function("").expect("Failed finding the function."),
+                &params.iter().cloned().map(Arg::Value).collect_vec(),
+                available_gas,
+                Default::default(),
+            )
+            .expect("Failed running the function.")
+    };
+    let result = run(&runner);
+    // The simulation of the Sierra program must agree with the CASM run.
+    assert_simulation_matches(&program, &runner, params, available_gas, &result);
+    // Peephole optimizations must not change the behavior of the program.
+    let optimized_result = run(&runner
+        .with_peephole_optimizations()
+        .expect("Failed compiling with peephole optimizations."));
+    assert_eq!(optimized_result.value, result.value);
+    assert_eq!(optimized_result.gas_counter, result.gas_counter);
     if let Some(expected_cost) = expected_cost {
         assert_eq!(
             Felt252::from(available_gas.unwrap()) - result.gas_counter.unwrap(),
@@ -219,6 +263,185 @@ fn run_function(
     result.value
 }
 
+/// The stack size of the thread running the simulation of a function.
+const SIMULATION_STACK_SIZE: usize = 64 * 1024 * 1024;
+
+/// Simulates the first function of `program` on `params`, and asserts that the returned value and
+/// gas counter match the ones of the CASM run `result`.
+fn assert_simulation_matches(
+    program: &Program,
+    runner: &SierraCasmRunner,
+    params: &[Felt252],
+    available_gas: Option<usize>,
+    result: &RunResultStarknet,
+) {
+    let func = runner.find_function("").expect("Failed finding the function.");
+    let registry = ProgramRegistry::<CoreType, CoreLibfunc>::new(program).unwrap();
+    let type_sizes = get_type_size_map(program, &registry).unwrap();
+    // Every statement is known to the simulation, with the gas withdrawn or redeposited by it.
+    let mut statement_gas_info: HashMap<StatementIdx, i64> =
+        (0..program.statements.len()).map(|idx| (StatementIdx(idx), 0)).collect();
+    if available_gas.is_some() {
+        let metadata = calc_metadata(program, Default::default()).unwrap();
+        for ((idx, token), value) in metadata.gas_info.variable_values {
+            *statement_gas_info.get_mut(&idx).unwrap() += value * token_gas_cost(token) as i64;
+        }
+    }
+    let initial_gas = runner.get_initial_available_gas(func, available_gas).unwrap() as i64;
+    let mut params = params.iter();
+    let inputs = func
+        .signature
+        .param_types
+        .iter()
+        .map(|ty| match registry.get_type(ty).unwrap() {
+            CoreTypeConcrete::GasBuiltin(_) => CoreValue::GasBuiltin(initial_gas),
+            CoreTypeConcrete::RangeCheck(_) => CoreValue::RangeCheck,
+            CoreTypeConcrete::Pedersen(_) => CoreValue::Pedersen,
+            CoreTypeConcrete::Bitwise(_) => CoreValue::Bitwise,
+            CoreTypeConcrete::Felt252(_) => CoreValue::Felt252(*params.next().unwrap()),
+            CoreTypeConcrete::Uint32(_) => {
+                CoreValue::Uint32(params.next().unwrap().to_biguint().try_into().unwrap())
+            }
+            CoreTypeConcrete::Uint128(_) => {
+                CoreValue::Uint128(params.next().unwrap().to_biguint().try_into().unwrap())
+            }
+            _ => panic!("Unsupported parameter type `{ty}` for simulation."),
+        })
+        .collect();
+    // The simulation recurses on every function call, so it requires a larger stack than the
+    // default test thread stack.
+    let outputs = std::thread::scope(|s| {
+        std::thread::Builder::new()
+            .stack_size(SIMULATION_STACK_SIZE)
+            .spawn_scoped(s, || simulation::run(program, &statement_gas_info, &func.id, inputs))
+            .unwrap()
+            .join()
+            .unwrap()
+    })
+    .expect("Failed simulating the function.");
+
+    let mut gas_counter = None;
+    let mut value = None;
+    for (ty, output) in zip_eq(&func.signature.ret_types, outputs) {
+        match output {
+            CoreValue::GasBuiltin(gas) => gas_counter = Some(Felt252::from(gas)),
+            CoreValue::RangeCheck | CoreValue::Pedersen | CoreValue::Bitwise => {}
+            output => {
+                assert!(value.is_none(), "Expected a single returned value.");
+                value = Some((ty, output));
+            }
+        }
+    }
+    assert_eq!(gas_counter, result.gas_counter);
+    let Some((ty, value)) = value else {
+        assert_eq!(result.value, RunResultValue::Success(vec![]));
+        return;
+    };
+    let value = match runner.return_value_type(func) {
+        // The function returns a panic result, its variant is the run result.
+        Some(inner_ty) if &inner_ty != ty => match value {
+            CoreValue::Enum { value, index: 0 } => (inner_ty, *value),
+            CoreValue::Enum { value, index: 1 } => {
+                let [_, CoreValue::Array(data)] = &extract_matches!(*value, CoreValue::Struct)[..]
+                else {
+                    panic!("Unexpected panic data.");
+                };
+                let data =
+                    data.iter().map(|felt| *extract_matches!(felt, CoreValue::Felt252)).collect();
+                assert_eq!(result.value, RunResultValue::Panic(data));
+                return;
+            }
+            value => panic!("Unexpected panic result {value:?}."),
+        },
+        _ => (ty.clone(), value),
+    };
+    let RunResultValue::Success(values) = &result.value else {
+        panic!("The simulation succeeded but the run panicked.");
+    };
+    let mut encoder =
+        SimulatedValueEncoder { registry: &registry, type_sizes: &type_sizes, memory: vec![None] };
+    let (ty, value) = value;
+    let cells = encoder.encode(&ty, &value);
+    let decoder = ValueDecoder {
+        sierra_program_registry: &registry,
+        type_sizes: &type_sizes,
+        memory: &encoder.memory,
+    };
+    assert_eq!(
+        decoder.decode(&ty, &cells).unwrap(),
+        runner.return_value_to_json(func, values, &result.memory).unwrap()
+    );
+}
+
+/// Encodes simulated values into their memory representation in a CASM run.
+struct SimulatedValueEncoder<'a> {
+    registry: &'a ProgramRegistry<CoreType, CoreLibfunc>,
+    type_sizes: &'a TypeSizeMap,
+    /// The memory holding the contents of arrays and boxes, where address 0 is left unused for
+    /// null pointers.
+    memory: Vec<Option<Felt252>>,
+}
+impl SimulatedValueEncoder<'_> {
+    /// Encodes `value` of type `ty` into cells.
+    fn encode(&mut self, ty: &ConcreteTypeId, value: &CoreValue) -> Vec<Felt252> {
+        match (self.registry.get_type(ty).unwrap(), value) {
+            (CoreTypeConcrete::Struct(info), CoreValue::Struct(members)) => {
+                zip_eq(&info.members, members)
+                    .flat_map(|(ty, value)| self.encode(ty, value))
+                    .collect()
+            }
+            (CoreTypeConcrete::Enum(info), CoreValue::Enum { value, index }) => {
+                let n_variants = info.variants.len();
+                let selector = if n_variants <= 2 { *index } else { 2 * (n_variants - index) - 1 };
+                let payload = self.encode(&info.variants[*index], value);
+                let padding = self.type_sizes[ty] as usize - 1 - payload.len();
+                chain!([Felt252::from(selector)], repeat_n(Felt252::ZERO, padding), payload)
+                    .collect()
+            }
+            (CoreTypeConcrete::Array(info), CoreValue::Array(elements)) => {
+                let cells = elements
+                    .iter()
+                    .flat_map(|element| self.encode(&info.ty, element))
+                    .collect_vec();
+                let len = cells.len();
+                let start = self.allocate(cells);
+                vec![start, start + Felt252::from(len)]
+            }
+            (CoreTypeConcrete::Snapshot(info), value) => self.encode(&info.ty, value),
+            (CoreTypeConcrete::NonZero(info), value) => self.encode(&info.ty, value),
+            (CoreTypeConcrete::Box(info), CoreValue::Box(inner)) => {
+                let cells = self.encode(&info.ty, inner);
+                vec![self.allocate(cells)]
+            }
+            (CoreTypeConcrete::Nullable(_), CoreValue::Nullable(None)) => vec![Felt252::ZERO],
+            (CoreTypeConcrete::Nullable(info), CoreValue::Nullable(Some(inner))) => {
+                let cells = self.encode(&info.ty, inner);
+                vec![self.allocate(cells)]
+            }
+            (_, CoreValue::Felt252(value)) => vec![*value],
+            (_, CoreValue::Uint8(value)) => vec![(*value).into()],
+            (_, CoreValue::Uint16(value)) => vec![(*value).into()],
+            (_, CoreValue::Uint32(value)) => vec![(*value).into()],
+            (_, CoreValue::Uint64(value)) => vec![(*value).into()],
+            (_, CoreValue::Uint128(value)) => vec![(*value).into()],
+            (_, CoreValue::Sint8(value)) => vec![(*value).into()],
+            (_, CoreValue::Sint16(value)) => vec![(*value).into()],
+            (_, CoreValue::Sint32(value)) => vec![(*value).into()],
+            (_, CoreValue::Sint64(value)) => vec![(*value).into()],
+            (_, CoreValue::Sint128(value)) => vec![(*value).into()],
+            (_, CoreValue::BoundedInt(value)) => vec![value.into()],
+            (_, value) => panic!("Unexpected simulated value {value:?} of type `{ty}`."),
+        }
+    }
+
+    /// Allocates `cells` in the memory, returning their address.
+    fn allocate(&mut self, cells: Vec<Felt252>) -> Felt252 {
+        let address = self.memory.len();
+        self.memory.extend(cells.into_iter().map(Some));
+        address.into()
+    }
+}
+
 #[rstest]
 #[case::fib(
     "fib",
@@ -285,6 +508,26 @@ fn run_function(
     &[3].map(Felt252::from), Some(100000), Some(9880 + 3 * token_gas_cost(CostTokenType::Pedersen)),
     RunResultValue::Success(vec![Felt252::from_hex_unchecked(
         "2dca1ad81a6107a9ef68c69f791bcdbda1df257aab76bd43ded73d96ed6227d")]))]
+#[case::enum_flow(
+    "enum_flow",
+    &[], None, None,
+    RunResultValue::Success(vec![Felt252::from(300)])
+)]
+#[case::match_or(
+    "match_or",
+    &[], None, None,
+    RunResultValue::Success(vec![])
+)]
+#[case::fib_caller(
+    "fib_caller",
+    &[], None, None,
+    RunResultValue::Success(vec![])
+)]
+#[case::pedersen_test(
+    "pedersen_test",
+    &[], None, None,
+    RunResultValue::Success(vec![Felt252::from_hex_unchecked(
+        "4c1cec8ca0d266e102559432703b9807b75dae05048908f6dedcb29f125e2da")]))]
 fn run_function_test(
     #[case] name: &str,
     #[case] params: &[Felt252],
@@ -346,17 +589,10 @@ fn run_fib_array_len(#[case] n: usize, #[case] last: usize, example_dir_data: &E
 
 #[rstest]
 fn complex_input_test(example_dir_data: &ExampleDirData) {
-    let runner = SierraCasmRunner::new(
-        checked_compile_to_sierra("complex_input", example_dir_data, false),
-        None,
-        Default::default(),
-        None,
-    )
-    .expect("Failed setting up runner.");
+    let (runner, func) = example_runner("complex_input", example_dir_data);
     let result = runner
         .run_function_with_starknet_context(
-            // find first
-            runner.find_function("").expect("Failed finding the function."),
+            &func,
             &[
                 // `felt_input`
                 Arg::Value(Felt252::from(1)),
@@ -421,3 +657,113 @@ fn complex_input_test(example_dir_data: &ExampleDirData) {
         ])
     );
 }
+
+#[rstest]
+#[case::json(
+    "[1, [2, 3], [[4, 5], [[6, 7]]], [[[8, 9], [[10, 11], [12, 13], [14, 15]]], [[16, 17], [[18, \
+     19]]]]]"
+)]
+#[case::json_u256_as_numbers(
+    "[\"1\", [\"0x2\", 3], [\"0x500000000000000000000000000000004\", [[6, 7]]], \
+     [[\"0x900000000000000000000000000000008\", [[10, 11], [12, 13], [14, 15]]], [[16, 17], [[18, \
+     19]]]]]"
+)]
+#[case::felts("1 2 2 3 4 5 1 6 7 2 8 9 3 10 11 12 13 14 15 16 17 1 18 19")]
+fn complex_input_decoded_args_test(#[case] args: &str, example_dir_data: &ExampleDirData) {
+    let (runner, func) = example_runner("complex_input", example_dir_data);
+    let args = runner
+        .decode_args(&func, &args.parse().expect("Failed parsing the arguments."))
+        .expect("Failed decoding the arguments.");
+    let result = runner
+        .run_function_with_starknet_context(&func, &args, None, Default::default())
+        .expect("Failed running the function.");
+    assert_eq!(
+        result.value,
+        RunResultValue::Success(vec![
+            Felt252::from(1 + 2 + 3 + 4 + 6 + 8 + 10 + 12 + 14 + 16 + 18),
+            Felt252::from(5 + 7 + 9 + 11 + 13 + 15 + 17 + 19)
+        ])
+    );
+}
+
+#[rstest]
+#[case::json("[7, 2]", [3, 0, 1, 0])]
+#[case::json_limbs("[7, [2, 0]]", [3, 0, 1, 0])]
+#[case::json_high_divisor(
+    "[\"0x300000000000000000000000000000001\", \"0x100000000000000000000000000000000\"]",
+    [3, 0, 1, 0]
+)]
+#[case::felts("7 0 2 0", [3, 0, 1, 0])]
+#[case::felts_high_divisor("1 3 0 1", [3, 0, 1, 0])]
+fn non_zero_input_decoded_args_test(
+    #[case] args: &str,
+    #[case] expected: [usize; 4],
+    example_dir_data: &ExampleDirData,
+) {
+    let (runner, func) = example_runner("div_rem_input", example_dir_data);
+    let args = runner
+        .decode_args(&func, &args.parse().expect("Failed parsing the arguments."))
+        .expect("Failed decoding the arguments.");
+    let result = runner
+        .run_function_with_starknet_context(&func, &args, None, Default::default())
+        .expect("Failed running the function.");
+    assert_eq!(result.value, RunResultValue::Success(expected.map(Felt252::from).to_vec()));
+}
+
+#[rstest]
+#[case::json_zero("[7, 0]", "Value `0` is out of range for type `NonZero<core::integer::u256>`.")]
+#[case::json_zero_limbs(
+    "[7, [0, 0]]",
+    "Value `0` is out of range for type `NonZero<core::integer::u256>`."
+)]
+#[case::felts_zero("7 0 0 0", "Value `0` is out of range for type `NonZero<core::integer::u256>`.")]
+#[case::json_out_of_range(
+    "[7, \"0x10000000000000000000000000000000000000000000000000000000000000000\"]",
+    "Value `115792089237316195423570985008687907853269984665640564039457584007913129639936` is \
+     out of range for type `core::integer::u256`."
+)]
+#[case::json_wrong_arity("[7]", "Expected 2 arguments, got 1.")]
+#[case::felts_missing("7 0 2", "Not enough felts to decode all the arguments.")]
+fn non_zero_input_decoding_error_test(
+    #[case] args: &str,
+    #[case] expected: &str,
+    example_dir_data: &ExampleDirData,
+) {
+    let (runner, func) = example_runner("div_rem_input", example_dir_data);
+    let err = runner
+        .decode_args(&func, &args.parse().expect("Failed parsing the arguments."))
+        .expect_err("Expected the arguments decoding to fail.");
+    assert_eq!(err.to_string(), expected);
+}
+
+#[rstest]
+#[case::fib_struct("fib_struct", &[1, 1, 9], json!(["0x37", "0x9", []]))]
+#[case::fib_u128_checked_pass(
+    "fib_u128_checked",
+    &[1, 1, 10],
+    json!({"variant": 0, "name": "Some", "value": 89})
+)]
+#[case::fib_u128_checked_fail(
+    "fib_u128_checked",
+    &[1, 1, 200],
+    json!({"variant": 1, "name": "None", "value": []})
+)]
+#[case::fib_array("fib_array", &[4], json!([["0x1", "0x1", "0x2", "0x3"], "0x3", 4]))]
+fn return_value_to_json_test(
+    #[case] name: &str,
+    #[case] params: &[usize],
+    #[case] expected: serde_json::Value,
+    example_dir_data: &ExampleDirData,
+) {
+    let (runner, func) = example_runner(name, example_dir_data);
+    let result = runner
+        .run_function_with_starknet_context(
+            &func,
+            &params.iter().map(|param| Arg::Value(Felt252::from(*param))).collect_vec(),
+            None,
+            Default::default(),
+        )
+        .expect("Failed running the function.");
+    let values = extract_matches!(result.value, RunResultValue::Success);
+    assert_eq!(runner.return_value_to_json(&func, &values, &result.memory).unwrap(), expected);
+}
@@ -6,6 +6,7 @@ import { Context } from "./context";
 import { Scarb } from "./scarb";
 import { isScarbProject } from "./scarbProject";
 import { StandaloneLS } from "./standalonels";
+import { registerTestRunner } from "./testRunner";
 import {
   registerMacroExpandProvider,
   registerVfsProvider,
@@ -65,6 +66,7 @@ export async function setupLanguageServer(ctx: Context): Promise<lc.LanguageClie
   registerVfsProvider(client, ctx);
   registerMacroExpandProvider(client, ctx);
   registerViewAnalyzedCratesProvider(client, ctx);
+  registerTestRunner(client, ctx);
 
   client.onNotification("scarb/could-not-find-scarb-executable", () => notifyScarbMissing(ctx));
 
@@ -25,3 +25,26 @@ export type ViewAnalyzedCratesResponse = string;
 export const viewAnalyzedCrates = new lc.RequestType0<ViewAnalyzedCratesResponse, void>(
   "cairo/viewAnalyzedCrates",
 );
+
+export type RunTestsRequest = {
+  textDocument: { uri: string };
+  path: string;
+  profiler: boolean;
+};
+export type RunTestsResponse = {
+  passed: number;
+  failed: number;
+  ignored: number;
+};
+export const runTests = new lc.RequestType<RunTestsRequest, RunTestsResponse, void>(
+  "cairo/runTests",
+);
+
+export type TestResultParams = {
+  name: string;
+  status: "passed" | "failed" | "ignored";
+  message: string | null;
+  output: string | null;
+  profilingInfo: string | null;
+};
+export const testResult = new lc.NotificationType<TestResultParams>("cairo/testResult");
@@ -0,0 +1,59 @@
+import * as lc from "vscode-languageclient/node";
+import * as vscode from "vscode";
+import { Context } from "./context";
+import { RunTestsRequest, runTests, testResult } from "./lspRequests";
+
+export const registerTestRunner = (client: lc.LanguageClient, ctx: Context) => {
+  const output = vscode.window.createOutputChannel("Cairo Tests");
+  ctx.extension.subscriptions.push(output);
+
+  ctx.extension.subscriptions.push(
+    client.onNotification(testResult, (result) => {
+      switch (result.status) {
+        case "passed":
+          output.appendLine(`test ${result.name} ... ok`);
+          break;
+        case "failed":
+          output.appendLine(`test ${result.name} ... fail: ${result.message ?? ""}`);
+          break;
+        case "ignored":
+          output.appendLine(`test ${result.name} ... ignored`);
+          break;
+      }
+      if (result.output) {
+        output.append(result.output.replace(/^/gm, "    "));
+        output.appendLine("");
+      }
+      if (result.profilingInfo) {
+        output.appendLine(`profiling info of ${result.name}:`);
+        output.appendLine(result.profilingInfo);
+      }
+    }),
+  );
+
+  ctx.extension.subscriptions.push(
+    vscode.commands.registerCommand("cairo.runTests", async (request: RunTestsRequest) => {
+      output.clear();
+      output.show(true);
+      output.appendLine(`running tests matching ${request.path}`);
+
+      await vscode.window.withProgress(
+        {
+          title: `Running ${request.path}...`,
+          location: vscode.ProgressLocation.Window,
+        },
+        async () => {
+          try {
+            const { passed, failed, ignored } = await client.sendRequest(runTests, request);
+            const result = failed == 0 ? "ok" : "FAILED";
+            output.appendLine(
+              `test result: ${result}. ${passed} passed; ${failed} failed; ${ignored} ignored`,
+            );
+          } catch (error) {
+            output.appendLine(`failed to run tests: ${error}`);
+          }
+        },
+      );
+    }),
+  );
+};